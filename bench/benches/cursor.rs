@@ -14,8 +14,8 @@ impl Restore {
     fn cursor_move(self) -> Option<CursorMove> {
         match self {
             Self::TopLeft => Some(CursorMove::Jump(0, 0)),
-            Self::BottomLeft => Some(CursorMove::Jump(u16::MAX, 0)),
-            Self::BottomRight => Some(CursorMove::Jump(u16::MAX, u16::MAX)),
+            Self::BottomLeft => Some(CursorMove::Jump(usize::MAX, 0)),
+            Self::BottomRight => Some(CursorMove::Jump(usize::MAX, usize::MAX)),
             Self::None => None,
         }
     }
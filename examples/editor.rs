@@ -15,7 +15,7 @@ use std::fs;
 use std::io;
 use std::io::{BufRead, Write};
 use std::path::PathBuf;
-use tui_textarea::{CursorMove, Input, Key, TextArea};
+use tui_textarea::{CursorMove, Input, Key, SearchResult, TextArea};
 
 macro_rules! error {
     ($fmt: expr $(, $args:tt)*) => {{
@@ -93,7 +93,6 @@ impl SearchBox<'_> {
 struct Buffer<'a> {
     textarea: TextArea<'a>,
     path: PathBuf,
-    modified: bool,
 }
 
 impl Buffer<'_> {
@@ -114,15 +113,11 @@ impl Buffer<'_> {
             TextArea::default() // File does not exist
         };
         textarea.set_line_number_style(Style::default().fg(Color::DarkGray));
-        Ok(Self {
-            textarea,
-            path,
-            modified: false,
-        })
+        Ok(Self { textarea, path })
     }
 
     fn save(&mut self) -> io::Result<()> {
-        if !self.modified {
+        if !self.textarea.is_modified() {
             return Ok(());
         }
         let mut f = io::BufWriter::new(fs::File::create(&self.path)?);
@@ -130,7 +125,7 @@ impl Buffer<'_> {
             f.write_all(line.as_bytes())?;
             f.write_all(b"\n")?;
         }
-        self.modified = false;
+        self.textarea.mark_saved();
         Ok(())
     }
 }
@@ -196,7 +191,11 @@ impl Editor<'_> {
                 f.render_widget(textarea, chunks[1]);
 
                 // Render status line
-                let modified = if buffer.modified { " [modified]" } else { "" };
+                let modified = if buffer.textarea.is_modified() {
+                    " [modified]"
+                } else {
+                    ""
+                };
                 let slot = format!("[{}/{}]", self.current + 1, self.buffers.len());
                 let path = format!(" {}{} ", buffer.path.display(), modified);
                 let (row, col) = textarea.cursor();
@@ -264,8 +263,14 @@ impl Editor<'_> {
                         ..
                     }
                     | Input { key: Key::Down, .. } => {
-                        if !textarea.search_forward(false) {
-                            self.search.set_error(Some("Pattern not found"));
+                        match textarea.search_forward_ex(false, true) {
+                            SearchResult::Found { wrapped: true } => {
+                                self.message = Some("Search hit BOTTOM, continuing at TOP".into());
+                            }
+                            SearchResult::Found { wrapped: false } => {}
+                            SearchResult::NotFound => {
+                                self.search.set_error(Some("Pattern not found"));
+                            }
                         }
                     }
                     Input {
@@ -280,17 +285,22 @@ impl Editor<'_> {
                         alt: false,
                         ..
                     }
-                    | Input { key: Key::Up, .. } => {
-                        if !textarea.search_back(false) {
+                    | Input { key: Key::Up, .. } => match textarea.search_back_ex(false, true) {
+                        SearchResult::Found { wrapped: true } => {
+                            self.message = Some("Search hit TOP, continuing at BOTTOM".into());
+                        }
+                        SearchResult::Found { wrapped: false } => {}
+                        SearchResult::NotFound => {
                             self.search.set_error(Some("Pattern not found"));
                         }
-                    }
+                    },
                     Input {
                         key: Key::Enter, ..
                     } => {
                         if !textarea.search_forward(true) {
                             self.message = Some("Pattern not found".into());
                         }
+                        textarea.commit_search_pattern();
                         self.search.close();
                         textarea.set_search_pattern("").unwrap();
                     }
@@ -337,8 +347,7 @@ impl Editor<'_> {
                         self.search.open();
                     }
                     input => {
-                        let buffer = &mut self.buffers[self.current];
-                        buffer.modified = buffer.textarea.input(input);
+                        self.buffers[self.current].textarea.input(input);
                     }
                 }
             }
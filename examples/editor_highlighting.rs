@@ -179,7 +179,6 @@ impl Buffer<'_> {
 
 struct State<'a> {
     textarea: TextArea<'a>,
-    highlighter: SyntaxHighlighter,
     syntaxes: Vec<(&'static str, &'static str)>, // (Name, Content)
     current_syntax_idx: usize,
     theme_name: String,
@@ -206,13 +205,12 @@ impl<'a> State<'a> {
                 .title("Syntax Highlighting Editor"),
         );
         textarea.set_line_number_style(Style::default().fg(Color::DarkGray));
-        textarea.set_syntax_highlighter(highlighter.clone()); // Clone the Arc-wrapped sets
+        textarea.set_syntax_highlighter(highlighter);
         textarea.set_syntax(Some(syntaxes[current_syntax_idx].0.to_string()));
         textarea.set_theme(Some(theme_name.clone()));
 
         State {
             textarea,
-            highlighter,
             syntaxes,
             current_syntax_idx,
             theme_name,
@@ -238,19 +236,9 @@ impl<'a> State<'a> {
 
     fn update_textarea_content_and_syntax(&mut self) {
         let (name, content) = self.syntaxes[self.current_syntax_idx];
-        // Replace content - create a new TextArea to easily replace lines
-        let mut new_textarea = TextArea::from(content.lines());
-        new_textarea.set_block(self.textarea.block().cloned().unwrap_or_default()); // Keep block
-        new_textarea.set_line_number_style(self.textarea.line_number_style().unwrap_or_default()); // Keep line numbers
-        new_textarea.set_syntax_highlighter(self.highlighter.clone());
-        new_textarea.set_syntax(Some(name.to_string()));
-        new_textarea.set_theme(Some(self.theme_name.clone()));
-        // Try to preserve cursor position roughly
-        let (r, c) = self.textarea.cursor();
-        let new_max_row = new_textarea.lines().len().saturating_sub(1);
-        let new_max_col = new_textarea.lines().get(r.min(new_max_row)).map_or(0, |l| l.chars().count());
-        new_textarea.move_cursor(tui_textarea::CursorMove::Jump(r.min(new_max_row) as u16, c.min(new_max_col) as u16));
-        self.textarea = new_textarea; // Replace the old textarea
+        // Replace content in place so that the settings, the yank buffer and the undo history are kept
+        self.textarea.set_lines(content.lines().map(String::from).collect());
+        self.textarea.set_syntax(Some(name.to_string()));
     }
 
     fn current_language_name(&self) -> &'static str {
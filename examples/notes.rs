@@ -0,0 +1,64 @@
+use crossterm::event::{DisableMouseCapture, EnableMouseCapture};
+use crossterm::terminal::{
+    disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
+};
+use ratatui::backend::CrosstermBackend;
+use ratatui::widgets::{Block, Borders};
+use ratatui::Terminal;
+use std::io;
+use tui_textarea::{CursorMove, Input, Key, ListContinuation, TextArea};
+
+fn main() -> io::Result<()> {
+    let stdout = io::stdout();
+    let mut stdout = stdout.lock();
+
+    enable_raw_mode()?;
+    crossterm::execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    let backend = CrosstermBackend::new(stdout);
+    let mut term = Terminal::new(backend)?;
+
+    let mut textarea = TextArea::from([
+        "Shopping",
+        "- milk",
+        "- eggs",
+        "",
+        "Steps",
+        "1. preheat the oven",
+        "",
+        "Todo",
+        "- [x] write notes",
+        "- [ ] ",
+    ]);
+    // Enter in a list item continues the list. Enter in an empty item outdents it or ends the list
+    textarea.set_list_continuation(Some(ListContinuation::default()));
+    textarea.move_cursor(CursorMove::Bottom);
+    textarea.move_cursor(CursorMove::End);
+    textarea.set_block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Notes (Esc to quit)"),
+    );
+
+    loop {
+        term.draw(|f| {
+            f.render_widget(&textarea, f.area());
+        })?;
+        match crossterm::event::read()?.into() {
+            Input { key: Key::Esc, .. } => break,
+            input => {
+                textarea.input(input);
+            }
+        }
+    }
+
+    disable_raw_mode()?;
+    crossterm::execute!(
+        term.backend_mut(),
+        LeaveAlternateScreen,
+        DisableMouseCapture
+    )?;
+    term.show_cursor()?;
+
+    println!("Lines: {:?}", textarea.lines());
+    Ok(())
+}
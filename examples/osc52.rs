@@ -0,0 +1,62 @@
+use crossterm::event::{DisableMouseCapture, EnableMouseCapture};
+use crossterm::terminal::{
+    disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
+};
+use ratatui::backend::CrosstermBackend;
+use ratatui::widgets::{Block, Borders};
+use ratatui::Terminal;
+use std::io::{self, Write};
+use tui_textarea::{Input, Key, TextArea};
+
+fn main() -> io::Result<()> {
+    let stdout = io::stdout();
+    let mut stdout = stdout.lock();
+
+    enable_raw_mode()?;
+    crossterm::execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    let backend = CrosstermBackend::new(stdout);
+    let mut term = Terminal::new(backend)?;
+
+    let mut textarea = TextArea::default();
+    textarea.set_block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Select text with Shift+Arrow and copy it with Ctrl+C"),
+    );
+
+    loop {
+        term.draw(|f| {
+            f.render_widget(&textarea, f.area());
+        })?;
+        match crossterm::event::read()?.into() {
+            Input { key: Key::Esc, .. } => break,
+            Input {
+                key: Key::Char('c'),
+                ctrl: true,
+                ..
+            } => {
+                // Send the selected text to the terminal's clipboard. This works even over SSH
+                if let Some(seq) = textarea.yank_as_osc52() {
+                    let backend = term.backend_mut();
+                    write!(backend, "{}", seq)?;
+                    backend.flush()?;
+                }
+                textarea.copy();
+            }
+            input => {
+                textarea.input(input);
+            }
+        }
+    }
+
+    disable_raw_mode()?;
+    crossterm::execute!(
+        term.backend_mut(),
+        LeaveAlternateScreen,
+        DisableMouseCapture
+    )?;
+    term.show_cursor()?;
+
+    println!("Lines: {:?}", textarea.lines());
+    Ok(())
+}
@@ -96,7 +96,6 @@ impl SearchBox<'_> {
 struct Buffer<'a> {
     textarea: TextArea<'a>,
     path: PathBuf,
-    modified: bool,
 }
 
 impl Buffer<'_> {
@@ -117,15 +116,11 @@ impl Buffer<'_> {
             TextArea::default() // File does not exist
         };
         textarea.set_line_number_style(Style::default().fg(Color::DarkGray));
-        Ok(Self {
-            textarea,
-            path,
-            modified: false,
-        })
+        Ok(Self { textarea, path })
     }
 
     fn save(&mut self) -> io::Result<()> {
-        if !self.modified {
+        if !self.textarea.is_modified() {
             return Ok(());
         }
         let mut f = io::BufWriter::new(fs::File::create(&self.path)?);
@@ -133,7 +128,7 @@ impl Buffer<'_> {
             f.write_all(line.as_bytes())?;
             f.write_all(b"\n")?;
         }
-        self.modified = false;
+        self.textarea.mark_saved();
         Ok(())
     }
 }
@@ -199,7 +194,11 @@ impl Editor<'_> {
                 f.render_widget(textarea, chunks[1]);
 
                 // Render status line
-                let modified = if buffer.modified { " [modified]" } else { "" };
+                let modified = if buffer.textarea.is_modified() {
+                    " [modified]"
+                } else {
+                    ""
+                };
                 let slot = format!("[{}/{}]", self.current + 1, self.buffers.len());
                 let path = format!(" {}{} ", buffer.path.display(), modified);
                 let (row, col) = textarea.cursor();
@@ -294,6 +293,7 @@ impl Editor<'_> {
                         if !textarea.search_forward(true) {
                             self.message = Some("Pattern not found".into());
                         }
+                        textarea.commit_search_pattern();
                         self.search.close();
                         textarea.set_search_pattern("").unwrap();
                     }
@@ -340,8 +340,7 @@ impl Editor<'_> {
                         self.search.open();
                     }
                     input => {
-                        let buffer = &mut self.buffers[self.current];
-                        buffer.modified = buffer.textarea.input(input);
+                        self.buffers[self.current].textarea.input(input);
                     }
                 }
             }
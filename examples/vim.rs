@@ -11,7 +11,9 @@ use std::fmt;
 use std::fs;
 use std::io;
 use std::io::BufRead;
-use tui_textarea::{CursorMove, Input, Key, Scrolling, TextArea};
+use tui_textarea::{
+    Case, CursorMove, Input, Key, Scrolling, TextArea, TextObject, UndoCursor, YankKind,
+};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Mode {
@@ -92,6 +94,194 @@ impl Vim {
         match self.mode {
             Mode::Normal | Mode::Visual | Mode::Operator(_) => {
                 match input {
+                    Input {
+                        key: Key::Char(c @ ('e' | 'E')),
+                        ctrl: false,
+                        ..
+                    } if matches!(
+                        self.pending,
+                        Input {
+                            key: Key::Char('g'),
+                            ctrl: false,
+                            ..
+                        }
+                    ) =>
+                    {
+                        textarea.move_cursor(if c == 'e' {
+                            CursorMove::WordEndBack
+                        } else {
+                            CursorMove::WORDEndBack
+                        });
+                        constrain_cursor_for_normal_mode(textarea);
+                    }
+                    Input {
+                        key: Key::Char(c @ ('z' | 't' | 'b')),
+                        ctrl: false,
+                        ..
+                    } if matches!(
+                        self.pending,
+                        Input {
+                            key: Key::Char('z'),
+                            ctrl: false,
+                            ..
+                        }
+                    ) =>
+                    {
+                        textarea.scroll(match c {
+                            'z' => Scrolling::CursorCenter,
+                            't' => Scrolling::CursorTop,
+                            _ => Scrolling::CursorBottom,
+                        });
+                    }
+                    Input {
+                        key: Key::Char(c),
+                        ctrl: false,
+                        ..
+                    } if matches!(
+                        self.pending,
+                        Input {
+                            key: Key::Char('f' | 'F' | 't' | 'T'),
+                            ctrl: false,
+                            ..
+                        }
+                    ) =>
+                    {
+                        let m = match self.pending.key {
+                            Key::Char('f') => CursorMove::FindCharForward(c),
+                            Key::Char('F') => CursorMove::FindCharBack(c),
+                            Key::Char('t') => CursorMove::TillCharForward(c),
+                            _ => CursorMove::TillCharBack(c),
+                        };
+                        let before = textarea.cursor();
+                        textarea.move_cursor(m);
+                        if matches!(self.mode, Mode::Operator(_)) && before < textarea.cursor() {
+                            textarea.move_cursor(CursorMove::Forward); // Include the text under the cursor
+                        }
+                        constrain_cursor_for_normal_mode(textarea);
+                    }
+                    Input {
+                        key: Key::Char(c),
+                        ctrl: false,
+                        ..
+                    } if matches!(self.mode, Mode::Operator(_))
+                        && matches!(
+                            self.pending,
+                            Input {
+                                key: Key::Char('i' | 'a'),
+                                ctrl: false,
+                                ..
+                            }
+                        ) =>
+                    {
+                        // Handle text objects like diw, ci", ya(
+                        let inner = self.pending.key == Key::Char('i');
+                        let pair = match c {
+                            '(' | ')' | 'b' => Some(('(', ')')),
+                            '{' | '}' | 'B' => Some(('{', '}')),
+                            '[' | ']' => Some(('[', ']')),
+                            '<' | '>' => Some(('<', '>')),
+                            _ => None,
+                        };
+                        let obj = match (c, pair) {
+                            ('w', _) if inner => TextObject::InnerWord,
+                            ('w', _) => TextObject::AWord,
+                            ('p', _) if inner => TextObject::InnerParagraph,
+                            ('p', _) => TextObject::AParagraph,
+                            ('"' | '\'' | '`', _) if inner => TextObject::InnerQuote(c),
+                            ('"' | '\'' | '`', _) => TextObject::AQuote(c),
+                            (_, Some((open, close))) if inner => TextObject::InnerPair(open, close),
+                            (_, Some((open, close))) => TextObject::APair(open, close),
+                            _ => {
+                                textarea.cancel_selection();
+                                return Transition::Mode(Mode::Normal);
+                            }
+                        };
+                        if !textarea.select_text_object(obj) {
+                            textarea.cancel_selection();
+                            return Transition::Mode(Mode::Normal);
+                        }
+                    }
+                    Input {
+                        key: Key::Char('i' | 'a'),
+                        ctrl: false,
+                        ..
+                    } if matches!(self.mode, Mode::Operator(_)) => {
+                        return Transition::Pending(input);
+                    }
+                    Input {
+                        key: key @ (Key::Char(_) | Key::Enter),
+                        ctrl: false,
+                        ..
+                    } if self.mode == Mode::Normal
+                        && matches!(
+                            self.pending,
+                            Input {
+                                key: Key::Char('r'),
+                                ctrl: false,
+                                ..
+                            }
+                        ) =>
+                    {
+                        let c = match key {
+                            Key::Char(c) => c,
+                            _ => '\n',
+                        };
+                        textarea.replace_char(c);
+                        constrain_cursor_for_normal_mode(textarea);
+                        return Transition::Mode(Mode::Normal);
+                    }
+                    Input {
+                        key: Key::Char(c),
+                        ctrl: false,
+                        ..
+                    } if self.mode == Mode::Normal
+                        && matches!(
+                            self.pending,
+                            Input {
+                                key: Key::Char('m'),
+                                ctrl: false,
+                                ..
+                            }
+                        ) =>
+                    {
+                        textarea.set_mark(c);
+                        return Transition::Mode(Mode::Normal);
+                    }
+                    Input {
+                        key: Key::Char(c),
+                        ctrl: false,
+                        ..
+                    } if matches!(
+                        self.pending,
+                        Input {
+                            key: Key::Char('`' | '\''),
+                            ctrl: false,
+                            ..
+                        }
+                    ) =>
+                    {
+                        if textarea.jump_to_mark(c) && self.pending.key == Key::Char('\'') {
+                            textarea.move_cursor(CursorMove::FirstNonWhitespace);
+                        }
+                        constrain_cursor_for_normal_mode(textarea);
+                    }
+                    Input {
+                        key: Key::Char(c @ (';' | ',')),
+                        ctrl: false,
+                        ..
+                    } => {
+                        let m = if c == ';' {
+                            CursorMove::RepeatFind
+                        } else {
+                            CursorMove::RepeatFindReverse
+                        };
+                        let before = textarea.cursor();
+                        textarea.move_cursor(m);
+                        if matches!(self.mode, Mode::Operator(_)) && before < textarea.cursor() {
+                            textarea.move_cursor(CursorMove::Forward); // Include the text under the cursor
+                        }
+                        constrain_cursor_for_normal_mode(textarea);
+                    }
                     Input {
                         key: Key::Char('h'),
                         ..
@@ -146,11 +336,86 @@ impl Vim {
                         textarea.move_cursor(CursorMove::WordBack);
                         constrain_cursor_for_normal_mode(textarea);
                     }
+                    Input {
+                        key: Key::Char('%'),
+                        ..
+                    } => {
+                        let before = textarea.cursor();
+                        textarea.move_cursor(CursorMove::MatchingBracket);
+                        if matches!(self.mode, Mode::Operator(_)) && before < textarea.cursor() {
+                            textarea.move_cursor(CursorMove::Forward); // Include the bracket under the cursor
+                        }
+                        constrain_cursor_for_normal_mode(textarea);
+                    }
+                    Input {
+                        key: Key::Char('}'),
+                        ..
+                    } => {
+                        textarea.move_cursor(CursorMove::ParagraphForward);
+                        constrain_cursor_for_normal_mode(textarea);
+                    }
+                    Input {
+                        key: Key::Char('{'),
+                        ..
+                    } => {
+                        textarea.move_cursor(CursorMove::ParagraphBack);
+                        constrain_cursor_for_normal_mode(textarea);
+                    }
+                    Input {
+                        key: Key::Char(')'),
+                        ..
+                    } => {
+                        textarea.move_cursor(CursorMove::SentenceForward);
+                        constrain_cursor_for_normal_mode(textarea);
+                    }
+                    Input {
+                        key: Key::Char('('),
+                        ..
+                    } => {
+                        textarea.move_cursor(CursorMove::SentenceBack);
+                        constrain_cursor_for_normal_mode(textarea);
+                    }
+                    Input {
+                        key: Key::Char(c @ ('H' | 'M' | 'L')),
+                        ctrl: false,
+                        ..
+                    } => {
+                        textarea.move_cursor(match c {
+                            'H' => CursorMove::ViewportTop,
+                            'M' => CursorMove::ViewportMiddle,
+                            _ => CursorMove::ViewportBottom,
+                        });
+                        constrain_cursor_for_normal_mode(textarea);
+                    }
+                    Input {
+                        key: Key::Char('W'),
+                        ..
+                    } => {
+                        textarea.move_cursor(CursorMove::WORDForward);
+                        constrain_cursor_for_normal_mode(textarea);
+                    }
+                    Input {
+                        key: Key::Char('E'),
+                        ..
+                    } => {
+                        textarea.move_cursor(CursorMove::WORDEnd);
+                        if matches!(self.mode, Mode::Operator(_)) {
+                            textarea.move_cursor(CursorMove::Forward); // Include the text under the cursor
+                        }
+                        constrain_cursor_for_normal_mode(textarea);
+                    }
+                    Input {
+                        key: Key::Char('B'),
+                        ..
+                    } => {
+                        textarea.move_cursor(CursorMove::WORDBack);
+                        constrain_cursor_for_normal_mode(textarea);
+                    }
                     Input {
                         key: Key::Char('^'),
                         ..
                     } => {
-                        textarea.move_cursor(CursorMove::Head);
+                        textarea.move_cursor(CursorMove::FirstNonWhitespace);
                         constrain_cursor_for_normal_mode(textarea);
                     }
                     Input {
@@ -172,6 +437,7 @@ impl Vim {
                         key: Key::Char('C'),
                         ..
                     } => {
+                        textarea.begin_history_group(); // Repeat the change and the typed text with `.`
                         textarea.delete_line_by_end();
                         textarea.cancel_selection();
                         return Transition::Mode(Mode::Insert);
@@ -192,6 +458,29 @@ impl Vim {
                         constrain_cursor_for_normal_mode(textarea);
                         return Transition::Mode(Mode::Normal);
                     }
+                    Input {
+                        key: Key::Char(c @ ('~' | 'U' | 'u')),
+                        ctrl: false,
+                        ..
+                    } if self.mode == Mode::Visual => {
+                        let case = match c {
+                            '~' => Case::Toggle,
+                            'U' => Case::Upper,
+                            _ => Case::Lower,
+                        };
+                        textarea.convert_selection_case(case);
+                        textarea.cancel_selection();
+                        return Transition::Mode(Mode::Normal);
+                    }
+                    Input {
+                        key: Key::Char('~'),
+                        ctrl: false,
+                        ..
+                    } => {
+                        textarea.convert_selection_case(Case::Toggle);
+                        constrain_cursor_for_normal_mode(textarea);
+                        return Transition::Mode(Mode::Normal);
+                    }
                     Input {
                         key: Key::Char('u'),
                         ctrl: false,
@@ -210,6 +499,14 @@ impl Vim {
                         constrain_cursor_for_normal_mode(textarea);
                         return Transition::Mode(Mode::Normal);
                     }
+                    Input {
+                        key: Key::Char(c @ ('a' | 'x')),
+                        ctrl: true,
+                        ..
+                    } if self.mode == Mode::Normal => {
+                        textarea.increment_number(if c == 'a' { 1 } else { -1 });
+                        return Transition::Mode(Mode::Normal);
+                    }
                     Input {
                         key: Key::Char('x'),
                         ..
@@ -218,11 +515,29 @@ impl Vim {
                         constrain_cursor_for_normal_mode(textarea);
                         return Transition::Mode(Mode::Normal);
                     }
+                    Input {
+                        key: Key::Char('.'),
+                        ctrl: false,
+                        ..
+                    } if self.mode == Mode::Normal => {
+                        textarea.repeat_last_edit();
+                        constrain_cursor_for_normal_mode(textarea);
+                        return Transition::Mode(Mode::Normal);
+                    }
+                    Input {
+                        key: Key::Char('J'),
+                        ..
+                    } => {
+                        textarea.join_lines(1);
+                        constrain_cursor_for_normal_mode(textarea);
+                        return Transition::Mode(Mode::Normal);
+                    }
                     Input {
                         key: Key::Char('i'),
                         ..
                     } => {
                         textarea.cancel_selection();
+                        textarea.begin_history_group();
                         return Transition::Mode(Mode::Insert);
                     }
                     Input {
@@ -231,6 +546,7 @@ impl Vim {
                     } => {
                         textarea.cancel_selection();
                         textarea.move_cursor(CursorMove::Forward);
+                        textarea.begin_history_group();
                         return Transition::Mode(Mode::Insert);
                     }
                     Input {
@@ -239,13 +555,35 @@ impl Vim {
                     } => {
                         textarea.cancel_selection();
                         textarea.move_cursor(CursorMove::End);
+                        textarea.begin_history_group();
                         return Transition::Mode(Mode::Insert);
                     }
+                    Input {
+                        key: Key::Char('o'),
+                        ctrl: true,
+                        ..
+                    } => {
+                        textarea.jump_back();
+                        constrain_cursor_for_normal_mode(textarea);
+                    }
+                    // Terminals send Ctrl-I as Tab
+                    Input { key: Key::Tab, .. } => {
+                        textarea.jump_forward();
+                        constrain_cursor_for_normal_mode(textarea);
+                    }
+                    Input {
+                        key: Key::Char('o'),
+                        ctrl: false,
+                        ..
+                    } if self.mode == Mode::Visual => {
+                        textarea.swap_selection_ends();
+                    }
                     Input {
                         key: Key::Char('o'),
                         ..
                     } => {
                         textarea.move_cursor(CursorMove::End);
+                        textarea.begin_history_group();
                         textarea.insert_newline();
                         return Transition::Mode(Mode::Insert);
                     }
@@ -254,6 +592,7 @@ impl Vim {
                         ..
                     } => {
                         textarea.move_cursor(CursorMove::Head);
+                        textarea.begin_history_group();
                         textarea.insert_newline();
                         textarea.move_cursor(CursorMove::Up);
                         return Transition::Mode(Mode::Insert);
@@ -263,7 +602,8 @@ impl Vim {
                         ..
                     } => {
                         textarea.cancel_selection();
-                        textarea.move_cursor(CursorMove::Head);
+                        textarea.move_cursor(CursorMove::FirstNonWhitespace);
+                        textarea.begin_history_group();
                         return Transition::Mode(Mode::Insert);
                     }
                     Input {
@@ -331,9 +671,15 @@ impl Vim {
                         ctrl: false,
                         ..
                     } if self.mode == Mode::Normal => {
-                        textarea.move_cursor(CursorMove::Head);
-                        textarea.start_selection();
-                        textarea.move_cursor(CursorMove::End);
+                        textarea.start_line_selection();
+                        return Transition::Mode(Mode::Visual);
+                    }
+                    Input {
+                        key: Key::Char('v'),
+                        ctrl: true,
+                        ..
+                    } if self.mode == Mode::Normal => {
+                        textarea.start_block_selection();
                         return Transition::Mode(Mode::Visual);
                     }
                     Input { key: Key::Esc, .. }
@@ -375,17 +721,7 @@ impl Vim {
                         ..
                     } if self.mode == Mode::Operator(c) => {
                         // Handle yy, dd, cc. Select the entire line
-                        textarea.move_cursor(CursorMove::Head);
-                        textarea.start_selection();
-                        let cursor = textarea.cursor();
-                        textarea.move_cursor(CursorMove::Down);
-                        if cursor == textarea.cursor() {
-                            // At the last line, select to end of line
-                            textarea.move_cursor(CursorMove::End);
-                        } else {
-                            // Move to beginning of next line to include the newline
-                            textarea.move_cursor(CursorMove::Head);
-                        }
+                        textarea.start_line_selection();
                     }
                     Input {
                         key: Key::Char(op @ ('y' | 'd' | 'c')),
@@ -395,6 +731,19 @@ impl Vim {
                         textarea.start_selection();
                         return Transition::Mode(Mode::Operator(op));
                     }
+                    Input {
+                        key: Key::Char(c @ ('>' | '<')),
+                        ctrl: false,
+                        ..
+                    } if self.mode == Mode::Visual => {
+                        if c == '>' {
+                            textarea.indent_selection();
+                        } else {
+                            textarea.dedent_selection();
+                        }
+                        textarea.cancel_selection();
+                        return Transition::Mode(Mode::Normal);
+                    }
                     Input {
                         key: Key::Char('y'),
                         ctrl: false,
@@ -422,6 +771,7 @@ impl Vim {
                     } if self.mode == Mode::Visual => {
                         // In Vim, visual selection is inclusive, but TextArea selection is already inclusive
                         // Don't move cursor forward as it adds extra characters
+                        textarea.begin_history_group();
                         textarea.cut();
                         return Transition::Mode(Mode::Insert);
                     }
@@ -441,6 +791,7 @@ impl Vim {
                         Transition::Mode(Mode::Normal)
                     }
                     Mode::Operator('c') => {
+                        textarea.begin_history_group();
                         textarea.cut();
                         Transition::Mode(Mode::Insert)
                     }
@@ -489,19 +840,16 @@ fn constrain_cursor_for_normal_mode(textarea: &mut TextArea) {
     let col = col.min(max_col);
     
     if (row, col) != textarea.cursor() {
-        textarea.move_cursor(CursorMove::Jump(row as u16, col as u16));
+        textarea.move_cursor(CursorMove::Jump(row, col));
     }
 }
 
 /// Paste below the current line (vim 'p' behavior for line-wise yanks)
 /// For character-wise yanks, paste after the cursor
 fn vim_paste_below(textarea: &mut TextArea) {
-    match &textarea.yank_text() {
-        text if text.contains('\n') => {
-            // Line-wise paste: move to end of current line and paste
-            // The yanked text already contains newlines, so no need to insert one
-            textarea.move_cursor(CursorMove::End);
-            textarea.paste();
+    match textarea.yank_kind() {
+        YankKind::Line => {
+            textarea.paste_linewise_below();
         }
         _ => {
             // Character-wise paste: paste after cursor (move forward then paste)
@@ -514,10 +862,9 @@ fn vim_paste_below(textarea: &mut TextArea) {
 /// Paste above the current line (vim 'P' behavior for line-wise yanks)
 /// For character-wise yanks, paste before the cursor
 fn vim_paste_above(textarea: &mut TextArea) {
-    match &textarea.yank_text() {
-        text if text.contains('\n') => {
-            textarea.move_cursor(CursorMove::Head);
-            textarea.paste();
+    match textarea.yank_kind() {
+        YankKind::Line => {
+            textarea.paste_linewise_above();
         }
         _ => {
             textarea.paste();
@@ -545,6 +892,8 @@ fn main() -> io::Result<()> {
 
     textarea.set_block(Mode::Normal.block());
     textarea.set_cursor_style(Mode::Normal.cursor_style());
+    textarea.set_edit_recording(true);
+    textarea.set_undo_cursor(UndoCursor::Restore);
     let mut vim = Vim::new(Mode::Normal);
     
     // Apply initial cursor constraints for normal mode
@@ -555,6 +904,9 @@ fn main() -> io::Result<()> {
 
         vim = match vim.transition(crossterm::event::read()?.into(), &mut textarea) {
             Transition::Mode(mode) if vim.mode != mode => {
+                if vim.mode == Mode::Insert {
+                    textarea.end_history_group();
+                }
                 textarea.set_block(mode.block());
                 textarea.set_cursor_style(mode.cursor_style());
                 if mode == Mode::Normal {
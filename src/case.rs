@@ -0,0 +1,63 @@
+#[cfg(feature = "arbitrary")]
+use arbitrary::Arbitrary;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Case conversion of text. See [`crate::TextArea::convert_selection_case`].
+///
+/// This type is marked as `#[non_exhaustive]` since more variations may be supported in the future.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Case {
+    /// Convert characters to upper case. This is similar to the 'gU' mapping of Vim.
+    Upper,
+    /// Convert characters to lower case. This is similar to the 'gu' mapping of Vim.
+    Lower,
+    /// Convert upper case characters to lower case and lower case characters to upper case. This is similar to the
+    /// '~' mapping of Vim.
+    Toggle,
+}
+
+impl Case {
+    // The result may have a different number of characters from the input (e.g. 'ß' to "SS")
+    pub(crate) fn convert(self, s: &str) -> String {
+        match self {
+            Case::Upper => s.to_uppercase(),
+            Case::Lower => s.to_lowercase(),
+            Case::Toggle => {
+                let mut converted = String::with_capacity(s.len());
+                for c in s.chars() {
+                    if c.is_uppercase() {
+                        converted.extend(c.to_lowercase());
+                    } else if c.is_lowercase() {
+                        converted.extend(c.to_uppercase());
+                    } else {
+                        converted.push(c);
+                    }
+                }
+                converted
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn convert_case() {
+        for (case, input, want) in [
+            (Case::Upper, "abc XYZ 123", "ABC XYZ 123"),
+            (Case::Lower, "abc XYZ 123", "abc xyz 123"),
+            (Case::Toggle, "abc XYZ 123", "ABC xyz 123"),
+            (Case::Upper, "straße", "STRASSE"),
+            (Case::Toggle, "ßİ", "SSi\u{307}"),
+            (Case::Toggle, "あ🐶", "あ🐶"),
+        ] {
+            assert_eq!(case.convert(input), want, "{:?} {:?}", case, input);
+        }
+    }
+}
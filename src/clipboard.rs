@@ -0,0 +1,275 @@
+use std::error::Error;
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::process::{Command, Stdio};
+
+/// Error of accessing the system clipboard. See [`crate::TextArea::copy_to_clipboard`].
+///
+/// This error is returned when no clipboard is available on the system (e.g. a headless Linux server) or when the
+/// clipboard command failed.
+#[derive(Debug)]
+pub struct ClipboardError {
+    message: String,
+}
+
+impl ClipboardError {
+    fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ClipboardError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "cannot access clipboard: {}", self.message)
+    }
+}
+
+impl Error for ClipboardError {}
+
+// Encoding of the text written to the clipboard command
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Encoding {
+    Utf8,
+    // `clip` on Windows reads the text in the ANSI code page unless it starts with the UTF-16LE byte order mark
+    Utf16Le,
+}
+
+impl Encoding {
+    fn encode(self, text: &str) -> Vec<u8> {
+        match self {
+            Self::Utf8 => text.as_bytes().to_vec(),
+            Self::Utf16Le => {
+                let mut bytes = Vec::with_capacity(text.len() * 2 + 2);
+                bytes.extend_from_slice(&[0xff, 0xfe]);
+                for u in text.encode_utf16() {
+                    bytes.extend_from_slice(&u.to_le_bytes());
+                }
+                bytes
+            }
+        }
+    }
+}
+
+// Pair of commands to write and read the system clipboard, and the encoding of the written text
+type Commands = (&'static [&'static str], &'static [&'static str], Encoding);
+
+// Platform which decides the clipboard commands
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Platform {
+    MacOs,
+    Windows,
+    Unix { wayland: bool, x11: bool },
+}
+
+impl Platform {
+    fn current() -> Self {
+        if cfg!(target_os = "macos") {
+            Self::MacOs
+        } else if cfg!(windows) {
+            Self::Windows
+        } else {
+            Self::Unix {
+                wayland: std::env::var_os("WAYLAND_DISPLAY").is_some(),
+                x11: std::env::var_os("DISPLAY").is_some(),
+            }
+        }
+    }
+
+    // Clipboard commands available on the platform. They are tried in order until one of them succeeds
+    fn commands(self) -> Vec<Commands> {
+        match self {
+            Self::MacOs => vec![(&["pbcopy"], &["pbpaste"], Encoding::Utf8)],
+            // PowerShell writes the output in the console code page unless the encoding is set
+            Self::Windows => vec![(
+                &["clip"],
+                &[
+                    "powershell",
+                    "-NoProfile",
+                    "-Command",
+                    "[Console]::OutputEncoding = [Text.Encoding]::UTF8; Get-Clipboard -Raw",
+                ],
+                Encoding::Utf16Le,
+            )],
+            Self::Unix { wayland, x11 } => {
+                let mut cmds: Vec<Commands> = vec![];
+                if wayland {
+                    cmds.push((&["wl-copy"], &["wl-paste", "--no-newline"], Encoding::Utf8));
+                }
+                if x11 {
+                    cmds.push((
+                        &["xclip", "-selection", "clipboard"],
+                        &["xclip", "-selection", "clipboard", "-out"],
+                        Encoding::Utf8,
+                    ));
+                    cmds.push((
+                        &["xsel", "--clipboard", "--input"],
+                        &["xsel", "--clipboard", "--output"],
+                        Encoding::Utf8,
+                    ));
+                }
+                cmds
+            }
+        }
+    }
+}
+
+fn write_with(cmd: &[&str], text: &[u8]) -> io::Result<()> {
+    let mut child = Command::new(cmd[0])
+        .args(&cmd[1..])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(text)?;
+    }
+    let status = child.wait()?;
+    if !status.success() {
+        return Err(io::Error::new(io::ErrorKind::Other, status.to_string()));
+    }
+    Ok(())
+}
+
+fn read_with(cmd: &[&str]) -> io::Result<String> {
+    let mut child = Command::new(cmd[0])
+        .args(&cmd[1..])
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()?;
+    let mut text = String::new();
+    if let Some(mut stdout) = child.stdout.take() {
+        stdout.read_to_string(&mut text)?;
+    }
+    let status = child.wait()?;
+    if !status.success() {
+        return Err(io::Error::new(io::ErrorKind::Other, status.to_string()));
+    }
+    // PowerShell on Windows always appends a newline to the output
+    if cfg!(windows) && text.ends_with("\r\n") {
+        text.truncate(text.len() - 2);
+    }
+    Ok(text)
+}
+
+pub fn set_text(text: &str) -> Result<(), ClipboardError> {
+    set_text_with(&Platform::current().commands(), text)
+}
+
+fn set_text_with(commands: &[Commands], text: &str) -> Result<(), ClipboardError> {
+    let mut last_err = None;
+    for &(write, _, encoding) in commands {
+        match write_with(write, &encoding.encode(text)) {
+            Ok(()) => return Ok(()),
+            Err(err) => last_err = Some(format!("{}: {}", write[0], err)),
+        }
+    }
+    Err(ClipboardError::new(last_err.unwrap_or_else(|| {
+        "no clipboard is available on this system".to_string()
+    })))
+}
+
+pub fn get_text() -> Result<String, ClipboardError> {
+    get_text_with(&Platform::current().commands())
+}
+
+fn get_text_with(commands: &[Commands]) -> Result<String, ClipboardError> {
+    let mut last_err = None;
+    for &(_, read, _) in commands {
+        match read_with(read) {
+            Ok(text) => return Ok(text),
+            Err(err) => last_err = Some(format!("{}: {}", read[0], err)),
+        }
+    }
+    Err(ClipboardError::new(last_err.unwrap_or_else(|| {
+        "no clipboard is available on this system".to_string()
+    })))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(platform: Platform) -> Vec<(&'static str, &'static str)> {
+        platform
+            .commands()
+            .into_iter()
+            .map(|(write, read, _)| (write[0], read[0]))
+            .collect()
+    }
+
+    #[test]
+    fn select_commands() {
+        assert_eq!(names(Platform::MacOs), [("pbcopy", "pbpaste")]);
+        assert_eq!(names(Platform::Windows), [("clip", "powershell")]);
+        let (wayland, x11) = (true, true);
+        assert_eq!(
+            names(Platform::Unix { wayland, x11 }),
+            [
+                ("wl-copy", "wl-paste"),
+                ("xclip", "xclip"),
+                ("xsel", "xsel")
+            ],
+        );
+        let x11 = false;
+        assert_eq!(
+            names(Platform::Unix { wayland, x11 }),
+            [("wl-copy", "wl-paste")],
+        );
+        let (wayland, x11) = (false, false);
+        assert_eq!(names(Platform::Unix { wayland, x11 }), []);
+
+        // Only `clip` needs the text in UTF-16LE
+        for platform in [
+            Platform::MacOs,
+            Platform::Unix {
+                wayland: true,
+                x11: true,
+            },
+        ] {
+            assert!(platform.commands().iter().all(|c| c.2 == Encoding::Utf8));
+        }
+        assert_eq!(Platform::Windows.commands()[0].2, Encoding::Utf16Le);
+    }
+
+    #[test]
+    fn encode_text() {
+        assert_eq!(Encoding::Utf8.encode("aあ"), "aあ".as_bytes());
+        assert_eq!(
+            Encoding::Utf16Le.encode("aあ"),
+            [0xff, 0xfe, 0x61, 0x00, 0x42, 0x30],
+        );
+    }
+
+    #[test]
+    fn clipboard_errors() {
+        let err = set_text_with(&[], "foo").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "cannot access clipboard: no clipboard is available on this system",
+        );
+        let err = get_text_with(&[]).unwrap_err();
+        assert!(err.to_string().contains("no clipboard"), "{}", err);
+
+        // The error of the last failed command is reported
+        let missing: Commands = (
+            &["tui-textarea-missing-copy"],
+            &["tui-textarea-missing-paste"],
+            Encoding::Utf8,
+        );
+        let err = set_text_with(&[missing], "foo").unwrap_err();
+        assert!(
+            err.to_string().contains("tui-textarea-missing-copy: "),
+            "{}",
+            err
+        );
+        let err = get_text_with(&[missing]).unwrap_err();
+        assert!(
+            err.to_string().contains("tui-textarea-missing-paste: "),
+            "{}",
+            err
+        );
+    }
+}
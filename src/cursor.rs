@@ -1,5 +1,7 @@
 use crate::widget::Viewport;
 use crate::word::{
+    find_big_word_inclusive_end_backward, find_big_word_inclusive_end_forward,
+    find_big_word_start_backward, find_big_word_start_forward, find_word_inclusive_end_backward,
     find_word_inclusive_end_forward, find_word_start_backward, find_word_start_forward,
 };
 #[cfg(feature = "arbitrary")]
@@ -41,7 +43,8 @@ pub enum CursorMove {
     /// assert_eq!(textarea.cursor(), (0, 1));
     /// ```
     Back,
-    /// Move cursor up by one line.
+    /// Move cursor up by one line. When the line is shorter than the column where vertical moves started, the cursor
+    /// is moved to the end of the line and the column is restored on a longer line later.
     /// ```
     /// use tui_textarea::{TextArea, CursorMove};
     ///
@@ -53,7 +56,8 @@ pub enum CursorMove {
     /// assert_eq!(textarea.cursor(), (1, 0));
     /// ```
     Up,
-    /// Move cursor down by one line.
+    /// Move cursor down by one line. Like [`CursorMove::Up`], the column where vertical moves started is restored when
+    /// the line is long enough.
     /// ```
     /// use tui_textarea::{TextArea, CursorMove};
     ///
@@ -63,6 +67,14 @@ pub enum CursorMove {
     /// assert_eq!(textarea.cursor(), (1, 0));
     /// textarea.move_cursor(CursorMove::Down);
     /// assert_eq!(textarea.cursor(), (2, 0));
+    ///
+    /// let mut textarea = TextArea::from(["abc", "d", "efg"]);
+    ///
+    /// textarea.move_cursor(CursorMove::End);
+    /// textarea.move_cursor(CursorMove::Down);
+    /// assert_eq!(textarea.cursor(), (1, 1));
+    /// textarea.move_cursor(CursorMove::Down);
+    /// assert_eq!(textarea.cursor(), (2, 3));
     /// ```
     Down,
     /// Move cursor to the head of line. When the cursor is at the head of line, it moves to the end of previous line.
@@ -77,6 +89,21 @@ pub enum CursorMove {
     /// assert_eq!(textarea.cursor(), (0, 0));
     /// ```
     Head,
+    /// Move cursor to the first non-whitespace character of line. When the line only contains whitespaces, the cursor
+    /// moves to the end of line. This is similar to the '^' mapping of Vim in normal mode.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["    abc", "   "]);
+    ///
+    /// textarea.move_cursor(CursorMove::FirstNonWhitespace);
+    /// assert_eq!(textarea.cursor(), (0, 4));
+    ///
+    /// textarea.move_cursor(CursorMove::Down);
+    /// textarea.move_cursor(CursorMove::FirstNonWhitespace);
+    /// assert_eq!(textarea.cursor(), (1, 3));
+    /// ```
+    FirstNonWhitespace,
     /// Move cursor to the end of line. When the cursor is at the end of line, it moves to the head of next line.
     /// ```
     /// use tui_textarea::{TextArea, CursorMove};
@@ -167,7 +194,94 @@ pub enum CursorMove {
     /// assert_eq!(textarea.cursor(), (0, 0));
     /// ```
     WordBack,
-    /// Move cursor down by one paragraph. Paragraph is a chunk of non-empty lines. Cursor moves to the first line of paragraph.
+    /// Move cursor backward to the previous end of word. Word boundary appears at spaces, punctuations, and others.
+    /// When no word ends before the cursor in the line, it moves to the end of the last word of the previous line.
+    /// Empty lines and spaces are skipped. When no word ends before the cursor, it moves to the head of the first
+    /// line. This is similar to the 'ge' mapping of Vim in normal mode.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from([
+    ///     "aaa",
+    ///     "",
+    ///     "bbb [[[ccc]]]",
+    /// ]);
+    ///
+    /// textarea.move_cursor(CursorMove::Bottom);
+    /// textarea.move_cursor(CursorMove::End);
+    /// textarea.move_cursor(CursorMove::WordEndBack);
+    /// assert_eq!(textarea.cursor(), (2, 12));     // At the end of ']]]'
+    /// textarea.move_cursor(CursorMove::WordEndBack);
+    /// assert_eq!(textarea.cursor(), (2, 9));      // At the end of 'ccc'
+    /// textarea.move_cursor(CursorMove::WordEndBack);
+    /// assert_eq!(textarea.cursor(), (2, 6));      // At the end of '[[['
+    /// textarea.move_cursor(CursorMove::WordEndBack);
+    /// assert_eq!(textarea.cursor(), (2, 2));      // At the end of 'bbb'
+    /// textarea.move_cursor(CursorMove::WordEndBack);
+    /// assert_eq!(textarea.cursor(), (0, 2));      // At the end of 'aaa'
+    /// textarea.move_cursor(CursorMove::WordEndBack);
+    /// assert_eq!(textarea.cursor(), (0, 0));
+    /// ```
+    WordEndBack,
+    /// Move cursor forward by one WORD. WORD is a sequence of non-whitespace characters. For example `foo.bar() baz`
+    /// consists of WORDs `foo.bar()` and `baz`. When the cursor is at the end of line, it moves to the head of next
+    /// line. This is similar to the 'W' mapping of Vim in normal mode.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["foo.bar() baz"]);
+    ///
+    /// textarea.move_cursor(CursorMove::WORDForward);
+    /// assert_eq!(textarea.cursor(), (0, 10));
+    /// ```
+    WORDForward,
+    /// Move cursor forward to the next end of WORD. WORD is a sequence of non-whitespace characters. For example
+    /// `foo.bar() baz` consists of WORDs `foo.bar()` and `baz`. When the cursor is at the end of line, it moves to
+    /// the end of the first WORD of the next line. This is similar to the 'E' mapping of Vim in normal mode.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["foo.bar() baz"]);
+    ///
+    /// textarea.move_cursor(CursorMove::WORDEnd);
+    /// assert_eq!(textarea.cursor(), (0, 8));
+    /// textarea.move_cursor(CursorMove::WORDEnd);
+    /// assert_eq!(textarea.cursor(), (0, 12));
+    /// ```
+    WORDEnd,
+    /// Move cursor backward by one WORD. WORD is a sequence of non-whitespace characters. For example
+    /// `foo.bar() baz` consists of WORDs `foo.bar()` and `baz`. When the cursor is at the head of line, it moves to
+    /// the end of previous line. This is similar to the 'B' mapping of Vim in normal mode.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["foo.bar() baz"]);
+    ///
+    /// textarea.move_cursor(CursorMove::End);
+    /// textarea.move_cursor(CursorMove::WORDBack);
+    /// assert_eq!(textarea.cursor(), (0, 10));
+    /// textarea.move_cursor(CursorMove::WORDBack);
+    /// assert_eq!(textarea.cursor(), (0, 0));
+    /// ```
+    WORDBack,
+    /// Move cursor backward to the previous end of WORD. WORD is a sequence of non-whitespace characters. See
+    /// [`CursorMove::WordEndBack`] for the details. This is similar to the 'gE' mapping of Vim in normal mode.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["foo.bar() baz"]);
+    ///
+    /// textarea.move_cursor(CursorMove::End);
+    /// textarea.move_cursor(CursorMove::WORDEndBack);
+    /// assert_eq!(textarea.cursor(), (0, 12));
+    /// textarea.move_cursor(CursorMove::WORDEndBack);
+    /// assert_eq!(textarea.cursor(), (0, 8));
+    /// ```
+    WORDEndBack,
+    /// Move cursor down by one paragraph. Paragraph is a chunk of non-blank lines and blank line is a line which is
+    /// empty or only contains whitespaces. Cursor moves to the head of the blank line after the paragraph.
+    /// Consecutive blank lines are one boundary. When there is no blank line after the paragraph, the cursor moves to
+    /// the end of the last line. This is similar to the '}' mapping of Vim in normal mode.
     /// ```
     /// use tui_textarea::{TextArea, CursorMove};
     ///
@@ -180,12 +294,17 @@ pub enum CursorMove {
     /// let mut textarea = TextArea::from(["aaa", "", "bbb", "", "ccc", "ddd"]);
     ///
     /// textarea.move_cursor(CursorMove::ParagraphForward);
-    /// assert_eq!(textarea.cursor(), (2, 0));
+    /// assert_eq!(textarea.cursor(), (1, 0));
     /// textarea.move_cursor(CursorMove::ParagraphForward);
-    /// assert_eq!(textarea.cursor(), (4, 0));
+    /// assert_eq!(textarea.cursor(), (3, 0));
+    /// textarea.move_cursor(CursorMove::ParagraphForward);
+    /// assert_eq!(textarea.cursor(), (5, 3));
     /// ```
     ParagraphForward,
-    /// Move cursor up by one paragraph. Paragraph is a chunk of non-empty lines. Cursor moves to the first line of paragraph.
+    /// Move cursor up by one paragraph. Paragraph is a chunk of non-blank lines and blank line is a line which is empty
+    /// or only contains whitespaces. Cursor moves to the head of the blank line before the paragraph. Consecutive blank
+    /// lines are one boundary. When there is no blank line before the paragraph, the cursor moves to the head of the
+    /// first line. This is similar to the '{' mapping of Vim in normal mode.
     /// ```
     /// use tui_textarea::{TextArea, CursorMove};
     ///
@@ -199,13 +318,155 @@ pub enum CursorMove {
     ///
     /// textarea.move_cursor(CursorMove::Bottom);
     /// textarea.move_cursor(CursorMove::ParagraphBack);
-    /// assert_eq!(textarea.cursor(), (4, 0));
+    /// assert_eq!(textarea.cursor(), (3, 0));
     /// textarea.move_cursor(CursorMove::ParagraphBack);
-    /// assert_eq!(textarea.cursor(), (2, 0));
+    /// assert_eq!(textarea.cursor(), (1, 0));
     /// textarea.move_cursor(CursorMove::ParagraphBack);
     /// assert_eq!(textarea.cursor(), (0, 0));
     /// ```
     ParagraphBack,
+    /// Move cursor forward to the start of the next sentence. Sentence ends at '.', '!' or '?' followed by whitespace
+    /// or the end of line. Closing characters ')', ']', '"' and '\'' are allowed between them. Sentence can be wrapped
+    /// over several lines and a blank line also ends a sentence. When there is no sentence after the cursor, the cursor
+    /// moves to the end of the last line. This is similar to the ')' mapping of Vim in normal mode.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["Hello. How are", "you? Fine!"]);
+    ///
+    /// textarea.move_cursor(CursorMove::SentenceForward);
+    /// assert_eq!(textarea.cursor(), (0, 7));
+    /// textarea.move_cursor(CursorMove::SentenceForward);
+    /// assert_eq!(textarea.cursor(), (1, 5));
+    /// textarea.move_cursor(CursorMove::SentenceForward);
+    /// assert_eq!(textarea.cursor(), (1, 10));
+    /// ```
+    SentenceForward,
+    /// Move cursor backward to the start of the current sentence, or the previous sentence when the cursor is already
+    /// at the start. See [`CursorMove::SentenceForward`] for the definition of sentence. When there is no sentence
+    /// before the cursor, the cursor moves to the head of the first line. This is similar to the '(' mapping of Vim
+    /// in normal mode.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["Hello. How are", "you? Fine!"]);
+    ///
+    /// textarea.move_cursor(CursorMove::Bottom);
+    /// textarea.move_cursor(CursorMove::End);
+    /// textarea.move_cursor(CursorMove::SentenceBack);
+    /// assert_eq!(textarea.cursor(), (1, 5));
+    /// textarea.move_cursor(CursorMove::SentenceBack);
+    /// assert_eq!(textarea.cursor(), (0, 7));
+    /// textarea.move_cursor(CursorMove::SentenceBack);
+    /// assert_eq!(textarea.cursor(), (0, 0));
+    /// ```
+    SentenceBack,
+    /// Move cursor forward to the next occurrence of the character in the current line. The character under the
+    /// cursor is not accepted. When the character is not found, the cursor does not move. This is similar to the 'f'
+    /// mapping of Vim in normal mode.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["a,b,c"]);
+    ///
+    /// textarea.move_cursor(CursorMove::FindCharForward(','));
+    /// assert_eq!(textarea.cursor(), (0, 1));
+    /// textarea.move_cursor(CursorMove::FindCharForward(','));
+    /// assert_eq!(textarea.cursor(), (0, 3));
+    /// textarea.move_cursor(CursorMove::FindCharForward(','));
+    /// assert_eq!(textarea.cursor(), (0, 3));
+    /// ```
+    FindCharForward(char),
+    /// Move cursor backward to the previous occurrence of the character in the current line. When the character is
+    /// not found, the cursor does not move. This is similar to the 'F' mapping of Vim in normal mode.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["a,b,c"]);
+    ///
+    /// textarea.move_cursor(CursorMove::End);
+    /// textarea.move_cursor(CursorMove::FindCharBack(','));
+    /// assert_eq!(textarea.cursor(), (0, 3));
+    /// textarea.move_cursor(CursorMove::FindCharBack(','));
+    /// assert_eq!(textarea.cursor(), (0, 1));
+    /// ```
+    FindCharBack(char),
+    /// Move cursor forward to just before the next occurrence of the character in the current line. When the
+    /// character is not found, the cursor does not move. This is similar to the 't' mapping of Vim in normal mode.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["a,b,c"]);
+    ///
+    /// textarea.move_cursor(CursorMove::TillCharForward('c'));
+    /// assert_eq!(textarea.cursor(), (0, 3));
+    /// ```
+    TillCharForward(char),
+    /// Move cursor backward to just after the previous occurrence of the character in the current line. When the
+    /// character is not found, the cursor does not move. This is similar to the 'T' mapping of Vim in normal mode.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["a,b,c"]);
+    ///
+    /// textarea.move_cursor(CursorMove::End);
+    /// textarea.move_cursor(CursorMove::TillCharBack('a'));
+    /// assert_eq!(textarea.cursor(), (0, 1));
+    /// ```
+    TillCharBack(char),
+    /// Repeat the last [`CursorMove::FindCharForward`], [`CursorMove::FindCharBack`], [`CursorMove::TillCharForward`],
+    /// or [`CursorMove::TillCharBack`] move. Repeating the till moves does not stop just before the same character
+    /// again and moves to the next occurrence. When no find move was made, the cursor does not move. This is similar
+    /// to the ';' mapping of Vim in normal mode. See also [`crate::TextArea::last_find`].
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["a,b,c"]);
+    ///
+    /// textarea.move_cursor(CursorMove::TillCharForward(','));
+    /// assert_eq!(textarea.cursor(), (0, 0));
+    /// textarea.move_cursor(CursorMove::RepeatFind);
+    /// assert_eq!(textarea.cursor(), (0, 2));
+    /// ```
+    RepeatFind,
+    /// Repeat the last find move in the opposite direction. This is similar to the ',' mapping of Vim in normal mode.
+    /// See [`CursorMove::RepeatFind`] for more details.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["a,b,c"]);
+    ///
+    /// textarea.move_cursor(CursorMove::End);
+    /// textarea.move_cursor(CursorMove::FindCharBack(','));
+    /// assert_eq!(textarea.cursor(), (0, 3));
+    /// textarea.move_cursor(CursorMove::FindCharBack(','));
+    /// assert_eq!(textarea.cursor(), (0, 1));
+    /// textarea.move_cursor(CursorMove::RepeatFindReverse);
+    /// assert_eq!(textarea.cursor(), (0, 3));
+    /// ```
+    RepeatFindReverse,
+    /// Move cursor to the bracket matching the bracket under the cursor. `()`, `[]`, and `{}` are supported and nested
+    /// brackets are matched by counting them. When the cursor is not on a bracket, the first bracket after the cursor
+    /// in the line is used. The matching bracket is searched across lines. When no matching bracket is found, the
+    /// cursor does not move. This is similar to the '%' mapping of Vim in normal mode.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["fn f(a: [u8]) {", "}"]);
+    ///
+    /// // The first bracket after the cursor is used
+    /// textarea.move_cursor(CursorMove::MatchingBracket);
+    /// assert_eq!(textarea.cursor(), (0, 12));
+    /// textarea.move_cursor(CursorMove::MatchingBracket);
+    /// assert_eq!(textarea.cursor(), (0, 4));
+    ///
+    /// // Matching bracket in another line
+    /// textarea.move_cursor(CursorMove::End);
+    /// textarea.move_cursor(CursorMove::Back);
+    /// textarea.move_cursor(CursorMove::MatchingBracket);
+    /// assert_eq!(textarea.cursor(), (1, 0));
+    /// ```
+    MatchingBracket,
     /// Move cursor to (row, col) position. When the position points outside the text, the cursor position is made fit
     /// within the text. Note that row and col are 0-based. (0, 0) means the first character of the first line.
     ///
@@ -222,7 +483,7 @@ pub enum CursorMove {
     /// textarea.move_cursor(CursorMove::Jump(10,  10));
     /// assert_eq!(textarea.cursor(), (2, 4));
     /// ```
-    Jump(u16, u16),
+    Jump(usize, usize),
     /// Move cursor to keep it within the viewport. For example, when a viewport displays line 8 to line 16:
     ///
     /// - cursor at line 4 is moved to line 8
@@ -255,9 +516,118 @@ pub enum CursorMove {
     /// assert_eq!(textarea.cursor(), (7, 0));
     /// ```
     InViewport,
+    /// Move cursor to the first non-whitespace character of the top line in the viewport. This is similar to the 'H'
+    /// mapping of Vim in normal mode. The cursor does not move until the textarea is rendered at least once since
+    /// the viewport is unknown.
+    /// ```
+    /// # use ratatui::buffer::Buffer;
+    /// # use ratatui::layout::Rect;
+    /// # use ratatui::widgets::Widget as _;
+    /// use tui_textarea::{TextArea, CursorMove, Scrolling};
+    ///
+    /// // Let's say terminal height is 8.
+    ///
+    /// // Create textarea with 20 lines "  0", "  1", "  2", "  3", ...
+    /// let mut textarea: TextArea = (0..20).into_iter().map(|i| format!("  {}", i)).collect();
+    /// # // Call `render` at least once to populate terminal size
+    /// # let r = Rect { x: 0, y: 0, width: 24, height: 8 };
+    /// # let mut b = Buffer::empty(r.clone());
+    /// # textarea.render(r, &mut b);
+    ///
+    /// // The viewport is displaying from line 3 to line 10
+    /// textarea.scroll((2, 0));
+    ///
+    /// textarea.move_cursor(CursorMove::ViewportTop);
+    /// assert_eq!(textarea.cursor(), (2, 2));
+    /// ```
+    ViewportTop,
+    /// Move cursor to the first non-whitespace character of the middle line in the viewport. When the text ends
+    /// before the bottom of the viewport, the middle of the displayed lines is used. This is similar to the 'M'
+    /// mapping of Vim in normal mode. The cursor does not move until the textarea is rendered at least once since
+    /// the viewport is unknown.
+    /// ```
+    /// # use ratatui::buffer::Buffer;
+    /// # use ratatui::layout::Rect;
+    /// # use ratatui::widgets::Widget as _;
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// // Let's say terminal height is 8.
+    ///
+    /// // Create textarea with 20 lines "0", "1", "2", "3", ...
+    /// // The viewport is displaying from line 1 to line 8.
+    /// let mut textarea: TextArea = (0..20).into_iter().map(|i| i.to_string()).collect();
+    /// # // Call `render` at least once to populate terminal size
+    /// # let r = Rect { x: 0, y: 0, width: 24, height: 8 };
+    /// # let mut b = Buffer::empty(r.clone());
+    /// # textarea.render(r, &mut b);
+    ///
+    /// textarea.move_cursor(CursorMove::ViewportMiddle);
+    /// assert_eq!(textarea.cursor(), (3, 0));
+    /// ```
+    ViewportMiddle,
+    /// Move cursor to the first non-whitespace character of the bottom line in the viewport. When the text ends
+    /// before the bottom of the viewport, the last line is used. This is similar to the 'L' mapping of Vim in normal
+    /// mode. The cursor does not move until the textarea is rendered at least once since the viewport is unknown.
+    /// ```
+    /// # use ratatui::buffer::Buffer;
+    /// # use ratatui::layout::Rect;
+    /// # use ratatui::widgets::Widget as _;
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// // Let's say terminal height is 8.
+    ///
+    /// // Create textarea with 20 lines "0", "1", "2", "3", ...
+    /// // The viewport is displaying from line 1 to line 8.
+    /// let mut textarea: TextArea = (0..20).into_iter().map(|i| i.to_string()).collect();
+    /// # // Call `render` at least once to populate terminal size
+    /// # let r = Rect { x: 0, y: 0, width: 24, height: 8 };
+    /// # let mut b = Buffer::empty(r.clone());
+    /// # textarea.render(r, &mut b);
+    ///
+    /// textarea.move_cursor(CursorMove::ViewportBottom);
+    /// assert_eq!(textarea.cursor(), (7, 0));
+    /// ```
+    ViewportBottom,
+}
+
+/// How the cursor is rendered. See [`crate::TextArea::set_cursor_rendering`].
+///
+/// This type is marked as `#[non_exhaustive]` since more variations may be supported in the future.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum CursorRendering {
+    /// The cell at the cursor is painted in the cursor style set by [`crate::TextArea::set_cursor_style`].
+    #[default]
+    Cell,
+    /// No cell is painted for the cursor. Instead, the terminal cursor should be put at the position returned from
+    /// [`crate::TextArea::screen_cursor_position`] by the application. This mode allows the cursor to blink and to
+    /// keep the colors of the text under it.
+    Hardware,
 }
 
 impl CursorMove {
+    pub(crate) fn is_find(&self) -> bool {
+        use CursorMove::*;
+        matches!(
+            self,
+            FindCharForward(_) | FindCharBack(_) | TillCharForward(_) | TillCharBack(_)
+        )
+    }
+
+    // Same find move in the opposite direction
+    pub(crate) fn reversed_find(&self) -> Self {
+        use CursorMove::*;
+        match *self {
+            FindCharForward(c) => FindCharBack(c),
+            FindCharBack(c) => FindCharForward(c),
+            TillCharForward(c) => TillCharBack(c),
+            TillCharBack(c) => TillCharForward(c),
+            m => m,
+        }
+    }
+
     pub(crate) fn next_cursor(
         &self,
         (row, col): (usize, usize),
@@ -270,6 +640,10 @@ impl CursorMove {
             cmp::min(col, line.chars().count())
         }
 
+        fn is_blank(line: &str) -> bool {
+            line.chars().all(char::is_whitespace)
+        }
+
         match self {
             Forward if col >= lines[row].chars().count() => {
                 (row + 1 < lines.len()).then(|| (row + 1, 0))
@@ -286,15 +660,25 @@ impl CursorMove {
             }
             Down => Some((row + 1, fit_col(col, lines.get(row + 1)?))),
             Head => Some((row, 0)),
+            FirstNonWhitespace => {
+                let line = &lines[row];
+                let col = line.chars().position(|c| !c.is_whitespace());
+                Some((row, col.unwrap_or_else(|| line.chars().count())))
+            }
             End => Some((row, lines[row].chars().count())),
             Top => Some((0, fit_col(col, &lines[0]))),
             Bottom => {
                 let row = lines.len() - 1;
                 Some((row, fit_col(col, &lines[row])))
             }
-            WordEnd => {
+            WordEnd | WORDEnd => {
+                let find_end: fn(&str, usize) -> Option<usize> = if *self == WordEnd {
+                    find_word_inclusive_end_forward
+                } else {
+                    find_big_word_inclusive_end_forward
+                };
                 // `+ 1` for not accepting the current cursor position
-                if let Some(col) = find_word_inclusive_end_forward(&lines[row], col + 1) {
+                if let Some(col) = find_end(&lines[row], col + 1) {
                     Some((row, col))
                 } else {
                     let mut row = row;
@@ -303,14 +687,41 @@ impl CursorMove {
                             break Some((row, lines[row].chars().count()));
                         }
                         row += 1;
-                        if let Some(col) = find_word_inclusive_end_forward(&lines[row], 0) {
+                        if let Some(col) = find_end(&lines[row], 0) {
+                            break Some((row, col));
+                        }
+                    }
+                }
+            }
+            WordEndBack | WORDEndBack => {
+                let find_end: fn(&str, usize) -> Option<usize> = if *self == WordEndBack {
+                    find_word_inclusive_end_backward
+                } else {
+                    find_big_word_inclusive_end_backward
+                };
+                if let Some(col) = find_end(&lines[row], col) {
+                    Some((row, col))
+                } else {
+                    let mut row = row;
+                    loop {
+                        if row == 0 {
+                            break Some((0, 0));
+                        }
+                        row -= 1;
+                        let line = &lines[row];
+                        if let Some(col) = find_end(line, line.chars().count()) {
                             break Some((row, col));
                         }
                     }
                 }
             }
-            WordForward => {
-                if let Some(col) = find_word_start_forward(&lines[row], col) {
+            WordForward | WORDForward => {
+                let find_start: fn(&str, usize) -> Option<usize> = if *self == WordForward {
+                    find_word_start_forward
+                } else {
+                    find_big_word_start_forward
+                };
+                if let Some(col) = find_start(&lines[row], col) {
                     Some((row, col))
                 } else if row + 1 < lines.len() {
                     Some((row + 1, 0))
@@ -318,8 +729,13 @@ impl CursorMove {
                     Some((row, lines[row].chars().count()))
                 }
             }
-            WordBack => {
-                if let Some(col) = find_word_start_backward(&lines[row], col) {
+            WordBack | WORDBack => {
+                let find_start: fn(&str, usize) -> Option<usize> = if *self == WordBack {
+                    find_word_start_backward
+                } else {
+                    find_big_word_start_backward
+                };
+                if let Some(col) = find_start(&lines[row], col) {
                     Some((row, col))
                 } else if row > 0 {
                     Some((row - 1, lines[row - 1].chars().count()))
@@ -328,41 +744,79 @@ impl CursorMove {
                 }
             }
             ParagraphForward => {
-                let mut prev_is_empty = lines[row].is_empty();
-                for row in row + 1..lines.len() {
-                    let line = &lines[row];
-                    let is_empty = line.is_empty();
-                    if !is_empty && prev_is_empty {
-                        return Some((row, fit_col(col, line)));
-                    }
-                    prev_is_empty = is_empty;
+                // Skip the blank lines at the cursor and then the paragraph
+                let blank = (row..lines.len())
+                    .skip_while(|r| is_blank(&lines[*r]))
+                    .find(|r| is_blank(&lines[*r]));
+                if let Some(row) = blank {
+                    Some((row, 0))
+                } else {
+                    let row = lines.len() - 1;
+                    Some((row, lines[row].chars().count()))
                 }
-                let row = lines.len() - 1;
-                Some((row, fit_col(col, &lines[row])))
             }
             ParagraphBack => {
-                let row = row.checked_sub(1)?;
-                let mut prev_is_empty = lines[row].is_empty();
-                for row in (0..row).rev() {
-                    let is_empty = lines[row].is_empty();
-                    if is_empty && !prev_is_empty {
-                        return Some((row + 1, fit_col(col, &lines[row + 1])));
-                    }
-                    prev_is_empty = is_empty;
+                let blank = (0..=row)
+                    .rev()
+                    .skip_while(|r| is_blank(&lines[*r]))
+                    .find(|r| is_blank(&lines[*r]));
+                Some((blank.unwrap_or(0), 0))
+            }
+            SentenceForward => {
+                if let Some(pos) = find_sentence_start_forward(lines, row, col) {
+                    Some(pos)
+                } else {
+                    let row = lines.len() - 1;
+                    Some((row, lines[row].chars().count()))
                 }
-                Some((0, fit_col(col, &lines[0])))
             }
+            SentenceBack => Some(find_sentence_start_backward(lines, row, col).unwrap_or((0, 0))),
+            MatchingBracket => find_matching_bracket(lines, row, col),
+            FindCharForward(c) => {
+                let col = find_char_forward(&lines[row], col + 1, *c)?;
+                Some((row, col))
+            }
+            FindCharBack(c) => {
+                let col = find_char_back(&lines[row], col, *c)?;
+                Some((row, col))
+            }
+            TillCharForward(c) => {
+                let col = find_char_forward(&lines[row], col + 1, *c)?;
+                Some((row, col - 1))
+            }
+            TillCharBack(c) => {
+                let col = find_char_back(&lines[row], col, *c)?;
+                Some((row, col + 1))
+            }
+            // Repeating moves are resolved by `TextArea` since they depend on the last find move
+            RepeatFind | RepeatFindReverse => None,
             Jump(row, col) => {
-                let row = cmp::min(*row as usize, lines.len() - 1);
-                let col = fit_col(*col as usize, &lines[row]);
+                let row = cmp::min(*row, lines.len() - 1);
+                let col = fit_col(*col, &lines[row]);
                 Some((row, col))
             }
+            ViewportTop | ViewportMiddle | ViewportBottom => {
+                let (_, _, _, height) = viewport.rect();
+                if height == 0 {
+                    return None; // The viewport is unknown until the first render
+                }
+                let (row_top, _, row_bottom, _) = viewport.position();
+                let last_row = lines.len() - 1;
+                let top = cmp::min(row_top, last_row);
+                let bottom = cmp::min(row_bottom, last_row);
+                let row = match self {
+                    ViewportTop => top,
+                    ViewportMiddle => (top + bottom) / 2,
+                    _ => bottom,
+                };
+                FirstNonWhitespace.next_cursor((row, 0), lines, viewport)
+            }
             InViewport => {
                 let (row_top, col_top, row_bottom, col_bottom) = viewport.position();
 
-                let row = row.clamp(row_top as usize, row_bottom as usize);
+                let row = row.clamp(row_top, row_bottom);
                 let row = cmp::min(row, lines.len() - 1);
-                let col = col.clamp(col_top as usize, col_bottom as usize);
+                let col = col.clamp(col_top, col_bottom);
                 let col = fit_col(col, &lines[row]);
 
                 Some((row, col))
@@ -371,6 +825,168 @@ impl CursorMove {
     }
 }
 
+// Column of the first occurrence of the character at or after the column
+fn find_char_forward(line: &str, col: usize, c: char) -> Option<usize> {
+    line.chars()
+        .enumerate()
+        .skip(col)
+        .find(|(_, ch)| *ch == c)
+        .map(|(i, _)| i)
+}
+
+// Column of the last occurrence of the character before the column
+fn find_char_back(line: &str, col: usize, c: char) -> Option<usize> {
+    line.chars()
+        .take(col)
+        .enumerate()
+        .filter(|(_, ch)| *ch == c)
+        .last()
+        .map(|(i, _)| i)
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum SentenceState {
+    Inside,
+    // Just after the punctuation which ends a sentence
+    End,
+    // After the whitespaces following the end of sentence
+    Between,
+}
+
+impl SentenceState {
+    fn next(self, c: char) -> Self {
+        match c {
+            c if c.is_whitespace() && self == Self::End => Self::Between,
+            c if c.is_whitespace() => self,
+            '.' | '!' | '?' => Self::End,
+            ')' | ']' | '"' | '\'' if self == Self::End => Self::End,
+            _ => Self::Inside,
+        }
+    }
+
+    // State at the head of the line. Line break is a whitespace and a blank line ends a sentence
+    fn at_line_head(lines: &[String], row: usize) -> Self {
+        if row == 0 || lines[row - 1].chars().all(char::is_whitespace) {
+            return Self::Between;
+        }
+        lines[row - 1]
+            .chars()
+            .fold(Self::Inside, Self::next)
+            .next('\n')
+    }
+}
+
+// Positions of the sentence starts in the line
+fn sentence_starts(lines: &[String], row: usize) -> impl Iterator<Item = usize> + '_ {
+    let mut state = SentenceState::at_line_head(lines, row);
+    lines[row].chars().enumerate().filter_map(move |(i, c)| {
+        let start = state == SentenceState::Between && !c.is_whitespace();
+        state = state.next(c);
+        start.then_some(i)
+    })
+}
+
+fn find_sentence_start_forward(lines: &[String], row: usize, col: usize) -> Option<(usize, usize)> {
+    (row..lines.len()).find_map(|r| {
+        let mut starts = sentence_starts(lines, r);
+        let c = if r == row {
+            starts.find(|c| *c > col)
+        } else {
+            starts.next()
+        };
+        c.map(|c| (r, c))
+    })
+}
+
+fn find_sentence_start_backward(
+    lines: &[String],
+    row: usize,
+    col: usize,
+) -> Option<(usize, usize)> {
+    (0..=row).rev().find_map(|r| {
+        let starts = sentence_starts(lines, r);
+        let c = if r == row {
+            starts.take_while(|c| *c < col).last()
+        } else {
+            starts.last()
+        };
+        c.map(|c| (r, c))
+    })
+}
+
+const BRACKETS: [(char, char); 3] = [('(', ')'), ('[', ']'), ('{', '}')];
+
+// Position of the bracket matching the first bracket at or after the column in the line
+fn find_matching_bracket(lines: &[String], row: usize, col: usize) -> Option<(usize, usize)> {
+    let col = lines[row]
+        .chars()
+        .skip(col)
+        .position(is_bracket)
+        .map(|i| col + i)?;
+    find_bracket_pair(lines, (row, col), usize::MAX)
+}
+
+pub fn is_bracket(c: char) -> bool {
+    BRACKETS.iter().any(|&(o, e)| c == o || c == e)
+}
+
+// Position of the bracket matching the bracket at the position. Nested brackets are matched by counting them. Lines
+// more than `max_lines` away from the row are not searched
+pub fn find_bracket_pair(
+    lines: &[String],
+    (row, col): (usize, usize),
+    max_lines: usize,
+) -> Option<(usize, usize)> {
+    let c = lines[row].chars().nth(col)?;
+    let mut depth = 0;
+
+    if let Some(&(open, close)) = BRACKETS.iter().find(|(o, _)| *o == c) {
+        let rows = lines
+            .iter()
+            .enumerate()
+            .skip(row)
+            .take(max_lines.saturating_add(1));
+        for (r, line) in rows {
+            let start = if r == row { col + 1 } else { 0 };
+            for (i, c) in line.chars().enumerate().skip(start) {
+                if c == open {
+                    depth += 1;
+                } else if c == close {
+                    if depth == 0 {
+                        return Some((r, i));
+                    }
+                    depth -= 1;
+                }
+            }
+        }
+        return None;
+    }
+
+    let &(open, close) = BRACKETS.iter().find(|(_, e)| *e == c)?;
+    for r in (row.saturating_sub(max_lines)..=row).rev() {
+        let line = &lines[r];
+        let (end, offset) = if r == row {
+            (
+                col,
+                line.char_indices().nth(col).map_or(line.len(), |(i, _)| i),
+            )
+        } else {
+            (line.chars().count(), line.len())
+        };
+        for (i, c) in line[..offset].chars().rev().enumerate() {
+            if c == close {
+                depth += 1;
+            } else if c == open {
+                if depth == 0 {
+                    return Some((r, end - i - 1));
+                }
+                depth -= 1;
+            }
+        }
+    }
+    None
+}
+
 #[cfg(test)]
 mod tests {
     // Separate tests for tui-rs support
@@ -397,4 +1013,54 @@ mod tests {
         textarea.move_cursor(CursorMove::InViewport);
         assert_eq!(textarea.cursor(), (7, 0));
     }
+
+    #[test]
+    fn viewport_top_middle_bottom() {
+        use crate::ratatui::buffer::Buffer;
+        use crate::ratatui::layout::Rect;
+        use crate::ratatui::widgets::Widget as _;
+        use crate::{CursorMove, TextArea};
+
+        let mut textarea: TextArea = (0..20).map(|i| format!(" {}", i)).collect();
+
+        // Cursor does not move before the first render
+        for m in [
+            CursorMove::ViewportTop,
+            CursorMove::ViewportMiddle,
+            CursorMove::ViewportBottom,
+        ] {
+            textarea.move_cursor(m);
+            assert_eq!(textarea.cursor(), (0, 0), "{:?}", m);
+        }
+
+        let r = Rect {
+            x: 0,
+            y: 0,
+            width: 24,
+            height: 8,
+        };
+        let mut b = Buffer::empty(r);
+        textarea.render(r, &mut b);
+        textarea.scroll((4, 0));
+        for (m, pos) in [
+            (CursorMove::ViewportBottom, (11, 1)),
+            (CursorMove::ViewportTop, (4, 1)),
+            (CursorMove::ViewportMiddle, (7, 1)),
+        ] {
+            textarea.move_cursor(m);
+            assert_eq!(textarea.cursor(), pos, "{:?}", m);
+        }
+
+        // Text ends before the bottom of the viewport
+        let mut textarea: TextArea = (0..5).map(|i| i.to_string()).collect();
+        textarea.render(r, &mut b);
+        for (m, pos) in [
+            (CursorMove::ViewportBottom, (4, 0)),
+            (CursorMove::ViewportMiddle, (2, 0)),
+            (CursorMove::ViewportTop, (0, 0)),
+        ] {
+            textarea.move_cursor(m);
+            assert_eq!(textarea.cursor(), pos, "{:?}", m);
+        }
+    }
 }
@@ -0,0 +1,235 @@
+use crate::history::Edit;
+use crate::ratatui::style::Style;
+use crate::ratatui::text::Span;
+use crate::util::spaces;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::mem;
+use std::sync::Arc;
+use unicode_width::UnicodeWidthChar as _;
+
+/// Information of a row passed to the line number formatter set by [`crate::TextArea::set_line_number_formatter`].
+///
+/// This type is marked as `#[non_exhaustive]` since more information may be added in the future.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LineNumberContext {
+    /// Row index of the line (0-base).
+    pub row: usize,
+    /// `true` when the cursor is on the line.
+    pub cursor_row: bool,
+    /// Width of the line number column in cells. The span returned from the formatter is padded or truncated to this
+    /// width.
+    pub width: usize,
+    /// Style of line numbers set by [`crate::TextArea::set_line_number_style`].
+    pub style: Style,
+}
+
+type FormatFn = dyn Fn(LineNumberContext) -> Span<'static> + Send + Sync;
+
+#[derive(Clone)]
+pub struct LineNumberFormatter(Arc<FormatFn>);
+
+impl LineNumberFormatter {
+    pub fn new<F>(f: F) -> Self
+    where
+        F: Fn(LineNumberContext) -> Span<'static> + Send + Sync + 'static,
+    {
+        Self(Arc::new(f))
+    }
+
+    // Format the line number and fit the span to the width of the column so that text is aligned among rows
+    pub fn format(&self, ctx: LineNumberContext) -> Span<'static> {
+        let span = (self.0)(ctx);
+        fit_width(span, ctx.width)
+    }
+}
+
+impl fmt::Debug for LineNumberFormatter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("LineNumberFormatter")
+    }
+}
+
+/// A sign rendered in the sign column on the left of line numbers, such as a breakpoint or a diagnostic marker. See
+/// [`crate::TextArea::set_sign`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Sign {
+    /// Text of the sign. The sign column is 2 cells wide so the text is padded or truncated to the width.
+    pub symbol: String,
+    /// Style of the sign.
+    pub style: Style,
+}
+
+impl Sign {
+    /// Create a new sign with the text and the style.
+    /// ```
+    /// use ratatui::style::{Style, Color};
+    /// use tui_textarea::Sign;
+    ///
+    /// let sign = Sign::new("●", Style::default().fg(Color::Red));
+    /// assert_eq!(sign.symbol, "●");
+    /// ```
+    pub fn new(symbol: impl Into<String>, style: Style) -> Self {
+        Self {
+            symbol: symbol.into(),
+            style,
+        }
+    }
+}
+
+// Width of the sign column in cells
+pub const SIGN_WIDTH: usize = 2;
+
+// Signs set by `TextArea::set_sign` for each row. The signs are moved along with their lines when some lines are
+// inserted or deleted above them
+#[derive(Clone, Debug, Default)]
+pub struct Signs {
+    signs: BTreeMap<usize, Sign>,
+}
+
+impl Signs {
+    pub fn set(&mut self, row: usize, sign: Option<Sign>) {
+        match sign {
+            Some(sign) => self.signs.insert(row, sign),
+            None => self.signs.remove(&row),
+        };
+    }
+
+    pub fn get(&self, row: usize) -> Option<&Sign> {
+        self.signs.get(&row)
+    }
+
+    pub fn clear(&mut self) {
+        self.signs.clear();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.signs.is_empty()
+    }
+
+    // The span of the sign column for the row
+    pub fn span(&self, row: usize) -> Span<'static> {
+        match self.signs.get(&row) {
+            Some(sign) => fit_width(Span::styled(sign.symbol.clone(), sign.style), SIGN_WIDTH),
+            None => Span::raw(spaces(SIGN_WIDTH as u8)),
+        }
+    }
+
+    // Move the signs across the edits. Edits must be given in the order they were applied. A sign is removed when its
+    // whole line is deleted, and a sign on a line joined to the previous line moves to the previous line unless the
+    // line already has a sign
+    pub fn apply<'e>(&mut self, edits: impl IntoIterator<Item = &'e Edit>) {
+        if self.signs.is_empty() {
+            return;
+        }
+        for edit in edits {
+            edit.for_each_text(&mut |insert, text, (start_row, start_col)| {
+                let rows = text.matches('\n').count();
+                let end_row = start_row + rows;
+                let signs = mem::take(&mut self.signs);
+                for (row, sign) in signs {
+                    let row = if insert {
+                        if row < start_row || row == start_row && start_col > 0 {
+                            row
+                        } else {
+                            row + rows
+                        }
+                    } else if (start_row, start_col) <= (row, 0) && row < end_row {
+                        continue; // The whole line including the newline was deleted
+                    } else if row <= start_row {
+                        row
+                    } else if row <= end_row {
+                        start_row
+                    } else {
+                        row - rows
+                    };
+                    self.signs.entry(row).or_insert(sign);
+                }
+            });
+        }
+    }
+}
+
+// Truncate the span to the width or pad it with spaces in the style of the span
+fn fit_width(span: Span<'static>, width: usize) -> Span<'static> {
+    let mut text = String::with_capacity(width);
+    let mut w = 0;
+    for c in span.content.chars() {
+        let cw = c.width().unwrap_or(0);
+        if w + cw > width {
+            break;
+        }
+        text.push(c);
+        w += cw;
+    }
+    text.extend(std::iter::repeat(' ').take(width - w));
+    Span::styled(text, span.style)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn apply_edits() {
+        use crate::history::EditKind;
+        use crate::util::Pos;
+
+        let edit = |kind, before: (usize, usize), after: (usize, usize)| {
+            Edit::new(
+                kind,
+                Pos::new(before.0, before.1, 0),
+                Pos::new(after.0, after.1, 0),
+            )
+        };
+        for (edit, want) in [
+            // Insert a line above row 1
+            (
+                edit(EditKind::InsertStr("x\n".into()), (1, 0), (2, 0)),
+                vec![0, 2, 3],
+            ),
+            // Split row 1 in the middle
+            (edit(EditKind::InsertNewline, (1, 2), (2, 0)), vec![0, 1, 3]),
+            // Insert text without newlines
+            (
+                edit(EditKind::InsertStr("abc".into()), (1, 0), (1, 3)),
+                vec![0, 1, 2],
+            ),
+            // Delete row 1 with its newline
+            (
+                edit(EditKind::DeleteStr("abc\n".into()), (1, 0), (1, 0)),
+                vec![0, 1],
+            ),
+            // Join row 2 to row 1, which already has a sign
+            (edit(EditKind::DeleteNewline, (2, 0), (1, 3)), vec![0, 1]),
+            // Delete from the middle of row 0 to the middle of row 2
+            (
+                edit(EditKind::DeleteStr("bc\ndef\ng".into()), (0, 1), (0, 1)),
+                vec![0],
+            ),
+        ] {
+            let mut signs = Signs::default();
+            for row in 0..3 {
+                signs.set(row, Some(Sign::new(row.to_string(), Style::default())));
+            }
+            signs.apply([&edit]);
+            let have: Vec<_> = signs.signs.keys().copied().collect();
+            assert_eq!(have, want, "{:?}", edit);
+        }
+    }
+
+    #[test]
+    fn fit() {
+        for (input, width, want) in [
+            ("12", 4, "12  "),
+            ("1234", 4, "1234"),
+            ("123456", 4, "1234"),
+            ("", 2, "  "),
+            ("あい", 3, "あ "),
+        ] {
+            let span = fit_width(Span::raw(input), width);
+            assert_eq!(span.content, want, "{:?} width={}", input, width);
+        }
+    }
+}
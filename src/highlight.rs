@@ -1,64 +1,99 @@
 use crate::ratatui::style::Style;
 use crate::ratatui::text::Span;
-use crate::util::{num_digits, spaces};
+use crate::util::{control_char_text, num_digits, spaces};
 #[cfg(feature = "ratatui")]
 use ratatui::text::Line;
 use std::borrow::Cow;
-use std::cmp::Ordering;
 use std::iter;
 #[cfg(feature = "tuirs")]
 use tui::text::Spans as Line;
 use unicode_width::UnicodeWidthChar as _;
 
-enum Boundary {
-    Cursor(Style),
+// Styles applied to byte ranges of a line. Layers with higher rank are applied later so they take precedence over
+// lower ones. All layers patch the style under them except the cursor, which replaces it to stay visible.
+enum Layer {
+    Syntax(Style),
+    Marker(Style),
+    Control(Style),
+    Whitespace(Style),
+    Bracket(Style),
     Select(Style),
     #[cfg(feature = "search")]
     Search(Style),
-    End,
+    Cursor(Style),
 }
 
-impl Boundary {
-    fn cmp(&self, other: &Boundary) -> Ordering {
-        fn rank(b: &Boundary) -> u8 {
-            match b {
-                Boundary::Cursor(_) => 3,
-                #[cfg(feature = "search")]
-                Boundary::Search(_) => 2,
-                Boundary::Select(_) => 1,
-                Boundary::End => 0,
-            }
+impl Layer {
+    fn rank(&self) -> u8 {
+        match self {
+            Layer::Cursor(_) => 5,
+            #[cfg(feature = "search")]
+            Layer::Search(_) => 4,
+            Layer::Select(_) => 3,
+            Layer::Whitespace(_) | Layer::Bracket(_) => 2,
+            Layer::Marker(_) | Layer::Control(_) => 1,
+            Layer::Syntax(_) => 0,
         }
-        rank(self).cmp(&rank(other))
     }
 
-    fn style(&self) -> Option<Style> {
+    fn apply(&self, base: Style) -> Style {
         match self {
-            Boundary::Cursor(s) => Some(*s),
-            Boundary::Select(s) => Some(*s),
+            Layer::Cursor(s) => *s,
+            Layer::Select(s)
+            | Layer::Whitespace(s)
+            | Layer::Bracket(s)
+            | Layer::Marker(s)
+            | Layer::Control(s)
+            | Layer::Syntax(s) => base.patch(*s),
             #[cfg(feature = "search")]
-            Boundary::Search(s) => Some(*s),
-            Boundary::End => None,
+            Layer::Search(s) => base.patch(*s),
         }
     }
 }
 
-struct DisplayTextBuilder {
+/// Markers to render whitespace characters like `list` and `listchars` options of Vim. See
+/// [`crate::TextArea::set_show_whitespace`]. Each marker should be a character of one cell width. `None` renders the
+/// whitespaces as they are.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct WhitespaceRender {
+    /// Marker put at the head of a tab. The rest of the tab until the next tab stop is filled with spaces.
+    pub tab: Option<char>,
+    /// Marker rendered in place of a space.
+    pub space: Option<char>,
+    /// Marker rendered at the end of a line.
+    pub eol: Option<char>,
+    /// Style of the markers.
+    pub style: Style,
+}
+
+pub struct DisplayTextBuilder {
     tab_len: u8,
     width: usize,
     mask: Option<char>,
+    tab_marker: Option<char>,
+    space_marker: Option<char>,
 }
 
 impl DisplayTextBuilder {
-    fn new(tab_len: u8, mask: Option<char>) -> Self {
+    pub fn new(tab_len: u8, mask: Option<char>) -> Self {
         Self {
             tab_len,
             width: 0,
             mask,
+            tab_marker: None,
+            space_marker: None,
+        }
+    }
+
+    fn markers(mut self, markers: Option<WhitespaceRender>) -> Self {
+        if let Some(m) = markers {
+            self.tab_marker = m.tab;
+            self.space_marker = m.space;
         }
+        self
     }
 
-    fn build<'s>(&mut self, s: &'s str) -> Cow<'s, str> {
+    pub fn build<'s>(&mut self, s: &'s str) -> Cow<'s, str> {
         if let Some(ch) = self.mask {
             // Note: We don't need to track width on masking text since width of tab character is fixed
             let masked = iter::repeat(ch).take(s.chars().count()).collect();
@@ -75,9 +110,29 @@ impl DisplayTextBuilder {
                 }
                 if self.tab_len > 0 {
                     let len = self.tab_len as usize - (self.width % self.tab_len as usize);
-                    buf.push_str(&tab[..len]);
+                    match self.tab_marker {
+                        Some(m) => {
+                            buf.push(m);
+                            buf.push_str(&tab[..len - 1]);
+                        }
+                        None => buf.push_str(&tab[..len]),
+                    }
                     self.width += len;
                 }
+            } else if let (' ', Some(m)) = (c, self.space_marker) {
+                if buf.is_empty() {
+                    buf.reserve(s.len());
+                    buf.push_str(&s[..i]);
+                }
+                buf.push(m);
+                self.width += 1;
+            } else if let Some(text) = control_char_text(c) {
+                if buf.is_empty() {
+                    buf.reserve(s.len() + text.len());
+                    buf.push_str(&s[..i]);
+                }
+                buf.push_str(&text);
+                self.width += text.len();
             } else {
                 if !buf.is_empty() {
                     buf.push(c);
@@ -97,7 +152,7 @@ impl DisplayTextBuilder {
 pub struct LineHighlighter<'a> {
     line: &'a str,
     spans: Vec<Span<'a>>,
-    boundaries: Vec<(Boundary, usize)>, // TODO: Consider smallvec
+    layers: Vec<(Layer, usize, usize)>,
     style_begin: Style,
     cursor_at_end: bool,
     cursor_style: Style,
@@ -105,6 +160,9 @@ pub struct LineHighlighter<'a> {
     mask: Option<char>,
     select_at_end: bool,
     select_style: Style,
+    markers: Option<WhitespaceRender>,
+    ghost: Option<(usize, Span<'a>)>,
+    virtual_text: Option<Span<'a>>,
 }
 
 impl<'a> LineHighlighter<'a> {
@@ -118,7 +176,7 @@ impl<'a> LineHighlighter<'a> {
         Self {
             line,
             spans: vec![],
-            boundaries: vec![],
+            layers: vec![],
             style_begin: Style::default(),
             cursor_at_end: false,
             cursor_style,
@@ -126,7 +184,61 @@ impl<'a> LineHighlighter<'a> {
             mask,
             select_at_end: false,
             select_style,
+            markers: None,
+            ghost: None,
+            virtual_text: None,
+        }
+    }
+
+    // Put the text which is not in the line at the char index. At the end of the line, it is put after the cursor
+    pub fn ghost_text(&mut self, col: usize, span: Span<'a>) {
+        let offset = self
+            .line
+            .char_indices()
+            .nth(col)
+            .map_or(self.line.len(), |(i, _)| i);
+        self.ghost = Some((offset, span));
+    }
+
+    // Put the text which is not in the line after the end of the line
+    pub fn virtual_text(&mut self, span: Span<'a>) {
+        self.virtual_text = Some(span);
+    }
+
+    pub fn control_chars(&mut self, style: Style) {
+        if self.mask.is_some() {
+            return;
+        }
+        for (i, c) in self.line.char_indices() {
+            if control_char_text(c).is_some() {
+                self.layers
+                    .push((Layer::Control(style), i, i + c.len_utf8()));
+            }
+        }
+    }
+
+    pub fn whitespace_markers(&mut self, markers: WhitespaceRender) {
+        if self.mask.is_some() {
+            return; // Masked text does not show its whitespaces
+        }
+        let is_marked =
+            |c| c == '\t' && markers.tab.is_some() || c == ' ' && markers.space.is_some();
+        let mut run = None;
+        for (i, c) in self
+            .line
+            .char_indices()
+            .chain(Some((self.line.len(), '\n')))
+        {
+            match (run, is_marked(c)) {
+                (None, true) => run = Some(i),
+                (Some(start), false) => {
+                    self.layers.push((Layer::Marker(markers.style), start, i));
+                    run = None;
+                }
+                _ => {}
+            }
         }
+        self.markers = Some(markers);
     }
 
     pub fn line_number(&mut self, row: usize, lnum_len: u8, style: Style) {
@@ -135,27 +247,71 @@ impl<'a> LineHighlighter<'a> {
             .push(Span::styled(format!("{}{} ", pad, row + 1), style));
     }
 
+    // Put the span built by the line number formatter instead of the line number
+    pub fn gutter(&mut self, span: Span<'a>) {
+        self.spans.push(span);
+    }
+
     pub fn cursor_line(&mut self, cursor_col: usize, style: Style) {
         if let Some((start, c)) = self.line.char_indices().nth(cursor_col) {
-            self.boundaries
-                .push((Boundary::Cursor(self.cursor_style), start));
-            self.boundaries.push((Boundary::End, start + c.len_utf8()));
+            self.layers.push((
+                Layer::Cursor(self.cursor_style),
+                start,
+                start + c.len_utf8(),
+            ));
         } else {
             self.cursor_at_end = true;
         }
         self.style_begin = style;
     }
 
+    pub fn line_style(&mut self, style: Style) {
+        self.style_begin = self.style_begin.patch(style);
+    }
+
+    // `current` is the byte offset where the current match starts and its style patched on the search style
     #[cfg(feature = "search")]
-    pub fn search(&mut self, matches: impl Iterator<Item = (usize, usize)>, style: Style) {
+    pub fn search(
+        &mut self,
+        matches: impl Iterator<Item = (usize, usize)>,
+        style: Style,
+        current: Option<(usize, Style)>,
+    ) {
         for (start, end) in matches {
             if start != end {
-                self.boundaries.push((Boundary::Search(style), start));
-                self.boundaries.push((Boundary::End, end));
+                let style = match current {
+                    Some((offset, current)) if offset == start => style.patch(current),
+                    _ => style,
+                };
+                self.layers.push((Layer::Search(style), start, end));
             }
         }
     }
 
+    pub fn syntax(&mut self, ranges: impl Iterator<Item = (usize, usize, Style)>) {
+        for (start, end, style) in ranges {
+            if start != end {
+                self.layers.push((Layer::Syntax(style), start, end));
+            }
+        }
+    }
+
+    // Highlight the bracket at the char index in the line
+    pub fn bracket(&mut self, col: usize, style: Style) {
+        if let Some((start, c)) = self.line.char_indices().nth(col) {
+            self.layers
+                .push((Layer::Bracket(style), start, start + c.len_utf8()));
+        }
+    }
+
+    pub fn trailing_whitespace(&mut self, style: Style) {
+        let start = self.line.trim_end_matches([' ', '\t']).len();
+        if start != self.line.len() {
+            self.layers
+                .push((Layer::Whitespace(style), start, self.line.len()));
+        }
+    }
+
     pub fn selection(
         &mut self,
         current_row: usize,
@@ -180,17 +336,74 @@ impl<'a> LineHighlighter<'a> {
             return;
         };
         if start != end {
-            self.boundaries
-                .push((Boundary::Select(self.select_style), start));
-            self.boundaries.push((Boundary::End, end));
+            self.layers
+                .push((Layer::Select(self.select_style), start, end));
         }
     }
 
     pub fn into_spans(self) -> Line<'a> {
+        let (mut spans, text) = self.build();
+        spans.extend(text.into_iter().map(|(span, _)| span));
+        Line::from(spans)
+    }
+
+    // Build the line split into display rows which start at the display columns in `xs`. The line number is put
+    // only on the first row and the following rows are indented with blanks in the line number style instead. The
+    // following rows start with `prefix` after the blanks
+    pub fn into_wrapped_lines(self, xs: &[usize], prefix: &[Span<'a>]) -> Vec<Line<'a>> {
+        let (lnum, text) = self.build();
+        let mut gutter: Vec<_> = lnum
+            .iter()
+            .map(|s| Span::styled(spaces(s.width() as u8), s.style))
+            .collect();
+        gutter.extend(prefix.iter().cloned());
+
+        let mut rows = vec![lnum];
+        let mut next = xs.iter().skip(1).peekable();
+        let mut x = 0;
+        for (span, is_virtual) in text {
+            if is_virtual {
+                // Text not in the line does not occupy display columns of the line
+                rows.last_mut().unwrap().push(span);
+                continue;
+            }
+            let mut start = 0;
+            for (i, c) in span.content.char_indices() {
+                if next.peek().is_some_and(|&&b| x >= b) {
+                    if start < i {
+                        let piece = slice_cow(&span.content, start, i);
+                        rows.last_mut()
+                            .unwrap()
+                            .push(Span::styled(piece, span.style));
+                    }
+                    start = i;
+                    next.next();
+                    rows.push(gutter.clone());
+                }
+                x += c.width().unwrap_or(0);
+            }
+            if start < span.content.len() {
+                let piece = slice_cow(&span.content, start, span.content.len());
+                rows.last_mut()
+                    .unwrap()
+                    .push(Span::styled(piece, span.style));
+            }
+        }
+        // Rows only for the cell reserved at the end of the line are not produced when nothing is rendered there
+        while rows.len() < xs.len() {
+            rows.push(gutter.clone());
+        }
+
+        rows.into_iter().map(Line::from).collect()
+    }
+
+    // Build the spans of the line number and of the text separately. Each span of the text has a flag which is `true`
+    // for a virtual text which is not in the line
+    fn build(self) -> (Vec<Span<'a>>, Vec<(Span<'a>, bool)>) {
         let Self {
             line,
-            mut spans,
-            mut boundaries,
+            spans,
+            mut layers,
             tab_len,
             style_begin,
             cursor_style,
@@ -198,56 +411,97 @@ impl<'a> LineHighlighter<'a> {
             mask,
             select_at_end,
             select_style,
+            markers,
+            mut ghost,
+            virtual_text,
         } = self;
-        let mut builder = DisplayTextBuilder::new(tab_len, mask);
+        let mut builder = DisplayTextBuilder::new(tab_len, mask).markers(markers);
+        let mut text = Vec::with_capacity(layers.len() * 2 + 3);
+
+        // The stable sort keeps the order of layers with the same rank so the index of a layer is its application order
+        layers.sort_by_key(|(l, _, _)| l.rank());
 
-        if boundaries.is_empty() {
-            let built = builder.build(line);
-            if !built.is_empty() {
-                spans.push(Span::styled(built, style_begin));
+        // Each layer opens at its start offset and closes at its end offset. At the same offset closing events come
+        // first so that adjacent layers don't overlap. Empty layers cover no character and are skipped
+        let mut events: Vec<_> = layers
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, start, end))| start < end)
+            .flat_map(|(i, (_, start, end))| [(*start, true, i), (*end, false, i)])
+            .collect();
+        events.sort_unstable_by_key(|&(offset, open, _)| (offset, open));
+
+        // Split the line at every layer boundary so that each segment is covered by a fixed set of layers
+        let mut offsets: Vec<_> = events.iter().map(|&(offset, _, _)| offset).collect();
+        offsets.push(0);
+        offsets.push(line.len());
+        if let Some((offset, _)) = &ghost {
+            offsets.push(*offset);
+        }
+        offsets.sort_unstable();
+        offsets.dedup();
+
+        // Sweep the segments from the head of the line while tracking the indices of the layers covering the current
+        // segment in application order. Only those layers are folded into the style of each segment
+        let mut events = events.into_iter().peekable();
+        let mut active: Vec<usize> = Vec::with_capacity(layers.len());
+        for w in offsets.windows(2) {
+            let (start, end) = (w[0], w[1]);
+            while let Some((_, open, i)) = events.next_if(|&(offset, _, _)| offset <= start) {
+                match active.binary_search(&i) {
+                    Err(pos) if open => active.insert(pos, i),
+                    Ok(pos) if !open => {
+                        active.remove(pos);
+                    }
+                    _ => {}
+                }
             }
-            if cursor_at_end {
-                spans.push(Span::styled(" ", cursor_style));
-            } else if select_at_end {
-                spans.push(Span::styled(" ", select_style));
+            let style = active
+                .iter()
+                .fold(style_begin, |style, &i| layers[i].0.apply(style));
+            if ghost.as_ref().is_some_and(|(o, _)| *o == start) {
+                text.push((ghost.take().unwrap().1, true));
             }
-            return Line::from(spans);
+            text.push((Span::styled(builder.build(&line[start..end]), style), false));
         }
 
-        boundaries.sort_unstable_by(|(l, i), (r, j)| match i.cmp(j) {
-            Ordering::Equal => l.cmp(r),
-            o => o,
-        });
-
-        let mut style = style_begin;
-        let mut start = 0;
-        let mut stack = vec![];
-
-        for (next_boundary, end) in boundaries {
-            if start < end {
-                spans.push(Span::styled(builder.build(&line[start..end]), style));
+        if !cursor_at_end {
+            if let Some((_, span)) = ghost.take() {
+                text.push((span, true));
             }
+        }
 
-            style = if let Some(s) = next_boundary.style() {
-                stack.push(style);
-                s
+        let eol = markers.and_then(|m| Some((m.eol?, m.style)));
+        if let Some((c, style)) = eol {
+            let style = if cursor_at_end {
+                cursor_style
+            } else if select_at_end {
+                select_style
             } else {
-                stack.pop().unwrap_or(style_begin)
+                style_begin.patch(style)
             };
-            start = end;
+            text.push((Span::styled(c.to_string(), style), false));
+        } else if cursor_at_end {
+            text.push((Span::styled(" ", cursor_style), false));
+        } else if select_at_end {
+            text.push((Span::styled(" ", select_style), false));
         }
 
-        if start != line.len() {
-            spans.push(Span::styled(builder.build(&line[start..]), style));
+        if let Some((_, span)) = ghost {
+            text.push((span, true));
         }
-
-        if cursor_at_end {
-            spans.push(Span::styled(" ", cursor_style));
-        } else if select_at_end {
-            spans.push(Span::styled(" ", select_style));
+        if let Some(span) = virtual_text {
+            text.push((span, true));
         }
 
-        Line::from(spans)
+        (spans, text)
+    }
+}
+
+fn slice_cow<'a>(s: &Cow<'a, str>, start: usize, end: usize) -> Cow<'a, str> {
+    match s {
+        Cow::Borrowed(s) => Cow::Borrowed(&s[start..end]),
+        Cow::Owned(s) => Cow::Owned(s[start..end].to_string()),
     }
 }
 
@@ -477,7 +731,7 @@ mod tests {
         for test in tests {
             let (line, matches, want) = test;
             let mut lh = LineHighlighter::new(line, CUR, 4, None, SEL);
-            lh.search(matches.iter().copied(), SEARCH);
+            lh.search(matches.iter().copied(), SEARCH, None);
             assert_spans(lh, want, test);
         }
     }
@@ -522,8 +776,126 @@ mod tests {
         }
     }
 
+    #[test]
+    fn into_spans_syntax() {
+        const KW: Style = Style::new().fg(Color::Magenta);
+        const ID: Style = Style::new().fg(Color::Cyan);
+
+        let mut lh = LineHighlighter::new("fn f()", CUR, 4, None, SEL);
+        lh.syntax([(0, 2, KW), (2, 3, DEFAULT), (3, 4, ID), (4, 6, DEFAULT)].into_iter());
+        assert_spans(
+            lh,
+            &[("fn", KW), (" ", DEFAULT), ("f", ID), ("()", DEFAULT)],
+            "syntax only",
+        );
+
+        // Selection and cursor line are layered over syntax styles. The cursor replaces them
+        let mut lh = LineHighlighter::new("fn f()", CUR, 4, None, SEL);
+        lh.cursor_line(3, LINE);
+        lh.selection(0, 0, 1, 0, 3);
+        lh.syntax([(0, 2, KW), (2, 3, DEFAULT), (3, 4, ID), (4, 6, DEFAULT)].into_iter());
+        let want = [
+            ("f", LINE.patch(KW)),
+            ("n", LINE.patch(KW).patch(SEL)),
+            (" ", SEL),
+            ("f", CUR),
+            ("()", LINE),
+        ];
+        assert_spans(lh, &want, "syntax with cursor and selection");
+    }
+
+    #[test]
+    fn into_spans_trailing_whitespace() {
+        const WS: Style = Style::new().bg(Color::Magenta);
+
+        let tests = [
+            ("", &[][..]),
+            ("abc", &[("abc", DEFAULT)][..]),
+            ("a b \t", &[("a b", DEFAULT), ("     ", WS)][..]),
+            ("  ", &[("  ", WS)][..]),
+        ];
+        for test in tests {
+            let (line, want) = test;
+            let mut lh = LineHighlighter::new(line, CUR, 4, None, SEL);
+            lh.trailing_whitespace(WS);
+            assert_spans(lh, want, test);
+        }
+
+        // Selection is layered over the trailing whitespace
+        let mut lh = LineHighlighter::new("a  ", CUR, 4, None, SEL);
+        lh.trailing_whitespace(WS);
+        lh.selection(0, 0, 2, 0, 3);
+        assert_spans(
+            lh,
+            &[("a", DEFAULT), (" ", WS), (" ", WS.patch(SEL))],
+            "selection",
+        );
+    }
+
+    #[test]
+    fn into_spans_whitespace_markers() {
+        const WS: Style = Style::new().fg(Color::DarkGray);
+        let markers = WhitespaceRender {
+            tab: Some('>'),
+            space: Some('.'),
+            eol: Some('$'),
+            style: WS,
+        };
+
+        let tests = [
+            ("", &[("$", WS)][..]),
+            ("abc", &[("abc", DEFAULT), ("$", WS)][..]),
+            (
+                "a \tb",
+                &[("a", DEFAULT), (".> ", WS), ("b", DEFAULT), ("$", WS)][..],
+            ),
+            ("\t\t", &[(">   >   ", WS), ("$", WS)][..]),
+        ];
+        for test in tests {
+            let (line, want) = test;
+            let mut lh = LineHighlighter::new(line, CUR, 4, None, SEL);
+            lh.whitespace_markers(markers);
+            assert_spans(lh, want, test);
+        }
+
+        // Spaces are not marked when only tabs are marked
+        let mut lh = LineHighlighter::new("a \t", CUR, 4, None, SEL);
+        lh.whitespace_markers(WhitespaceRender {
+            space: None,
+            eol: None,
+            ..markers
+        });
+        assert_spans(lh, &[("a ", DEFAULT), ("> ", WS)], "tab only");
+
+        // The cursor and the selection are rendered over the markers
+        let mut lh = LineHighlighter::new("a b", CUR, 4, None, SEL);
+        lh.whitespace_markers(markers);
+        lh.selection(0, 0, 1, 0, 2);
+        lh.cursor_line(3, DEFAULT);
+        assert_spans(
+            lh,
+            &[
+                ("a", DEFAULT),
+                (".", WS.patch(SEL)),
+                ("b", DEFAULT),
+                ("$", CUR),
+            ],
+            "cursor and selection",
+        );
+
+        // Masked text does not show the markers
+        let mut lh = LineHighlighter::new("a b", CUR, 4, Some('*'), SEL);
+        lh.whitespace_markers(markers);
+        assert_spans(lh, &[("***", DEFAULT)], "mask");
+    }
+
     #[test]
     fn into_spans_mixed_highlights() {
+        #[cfg(feature = "search")]
+        const KW: Style = Style::new().fg(Color::Magenta);
+        #[cfg(feature = "search")]
+        const ID: Style = Style::new().fg(Color::Cyan);
+
         let tests = [
             (
                 "cursor on selection",
@@ -542,7 +914,7 @@ mod tests {
                     let mut lh = LineHighlighter::new("abcdefg", CUR, 4, None, SEL);
                     lh.cursor_line(3, LINE);
                     lh.selection(0, 0, 2, 0, 5);
-                    lh.search([(1, 2), (5, 6)].into_iter(), SEARCH);
+                    lh.search([(1, 2), (5, 6)].into_iter(), SEARCH, None);
                     lh
                 },
                 &[
@@ -595,6 +967,25 @@ mod tests {
                 },
                 &[("a", LINE), ("b", CUR), ("c", LINE)][..],
             ),
+            #[cfg(feature = "search")]
+            (
+                "syntax + selection + adjacent search matches",
+                {
+                    let mut lh = LineHighlighter::new("abcdef", CUR, 4, None, SEL);
+                    lh.selection(0, 0, 2, 0, 5);
+                    lh.search([(1, 2), (2, 4)].into_iter(), SEARCH, None);
+                    lh.syntax([(0, 3, KW), (3, 6, ID)].into_iter());
+                    lh
+                },
+                &[
+                    ("a", KW),
+                    ("b", KW.patch(SEARCH)),
+                    ("c", KW.patch(SEL).patch(SEARCH)),
+                    ("d", ID.patch(SEL).patch(SEARCH)),
+                    ("e", ID.patch(SEL)),
+                    ("f", ID),
+                ][..],
+            ),
         ];
 
         for (what, lh, want) in tests {
@@ -1,20 +1,109 @@
 use syntect::parsing::{SyntaxSet, SyntaxReference};
 use syntect::highlighting::{ThemeSet, Theme, Style as SyntectStyle, FontStyle};
-use std::sync::Arc;
+use std::fmt;
+use std::ops::Deref;
+use std::sync::{Arc, OnceLock};
 use crate::ratatui::style::{Color as RatatuiColor, Modifier as RatatuiModifier, Style as RatatuiStyle};
 
+// Syntax set loaded by `SyntaxHighlighter::new`. The extended set bundled by two-face is used with the
+// `extra-syntaxes` feature
+fn default_syntax_set() -> SyntaxSet {
+    #[cfg(feature = "extra-syntaxes")]
+    {
+        two_face::syntax::extra_newlines()
+    }
+    #[cfg(not(feature = "extra-syntaxes"))]
+    {
+        SyntaxSet::load_defaults_newlines()
+    }
+}
+
+/// Syntax set which is deserialized on the first access. It dereferences to [`SyntaxSet`].
+pub struct LazySyntaxSet {
+    set: OnceLock<SyntaxSet>,
+    loader: fn() -> SyntaxSet,
+}
+
+impl LazySyntaxSet {
+    fn new(loader: fn() -> SyntaxSet) -> Self {
+        Self {
+            set: OnceLock::new(),
+            loader,
+        }
+    }
+
+    fn loaded(syntax_set: SyntaxSet) -> Self {
+        let set = OnceLock::new();
+        let _ = set.set(syntax_set);
+        Self {
+            set,
+            loader: default_syntax_set,
+        }
+    }
+
+    /// Returns whether the syntax set has already been loaded.
+    pub fn is_loaded(&self) -> bool {
+        self.set.get().is_some()
+    }
+}
+
+impl Deref for LazySyntaxSet {
+    type Target = SyntaxSet;
+
+    fn deref(&self) -> &SyntaxSet {
+        self.set.get_or_init(self.loader)
+    }
+}
+
+impl fmt::Debug for LazySyntaxSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LazySyntaxSet")
+            .field("set", &self.set)
+            .finish()
+    }
+}
+
 /// Manages syntax highlighting state using syntect.
+///
+/// The syntax set is deserialized lazily, on the first syntax lookup (which also happens on the
+/// first highlighted render), because loading a syntax dump takes noticeable time. Clones share
+/// the same lazily loaded syntax set.
+///
+/// With the `extra-syntaxes` feature, [`SyntaxHighlighter::new`] loads the extended syntax set of
+/// [two-face](https://docs.rs/two-face) instead of the syntect defaults. It adds TOML, Dockerfile,
+/// TypeScript, TSX, Kotlin, Swift, Zig, Nix, Terraform, Elixir, Fish, PowerShell, Protocol Buffers,
+/// Svelte, Vue, GraphQL and many other languages.
 #[derive(Clone, Debug)] // Add derive for Clone and Debug
+#[allow(deprecated)]
 pub struct SyntaxHighlighter {
-    pub syntax_set: Arc<SyntaxSet>,
+    /// Syntax set loaded on the first access. Its type is `Arc<LazySyntaxSet>`, not `Arc<SyntaxSet>`, since it is
+    /// loaded lazily. It dereferences to [`SyntaxSet`].
+    #[deprecated(note = "use `SyntaxHighlighter::syntax_set` method instead")]
+    pub syntax_set: Arc<LazySyntaxSet>,
     pub theme_set: Arc<ThemeSet>,
 }
 
+impl Default for SyntaxHighlighter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[allow(deprecated)]
 impl SyntaxHighlighter {
-    /// Creates a new SyntaxHighlighter, loading default syntaxes and themes.
+    /// Creates a new SyntaxHighlighter with default syntaxes and themes. The default syntaxes are
+    /// not loaded until they are first needed. They are the extended syntaxes of two-face with the
+    /// `extra-syntaxes` feature.
     pub fn new() -> Self {
+        Self::with_syntax_set_loader(default_syntax_set)
+    }
+
+    /// Creates a new SyntaxHighlighter whose syntax set is produced by `loader` the first time it is
+    /// needed. Themes are the syntect defaults. This is the way to plug in a custom syntax set
+    /// without paying its loading cost up front.
+    pub fn with_syntax_set_loader(loader: fn() -> SyntaxSet) -> Self {
         Self {
-            syntax_set: Arc::new(SyntaxSet::load_defaults_newlines()),
+            syntax_set: Arc::new(LazySyntaxSet::new(loader)),
             theme_set: Arc::new(ThemeSet::load_defaults()),
         }
     }
@@ -23,19 +112,29 @@ impl SyntaxHighlighter {
     /// Useful for loading custom syntaxes or themes.
     pub fn from_sets(syntax_set: SyntaxSet, theme_set: ThemeSet) -> Self {
         Self {
-            syntax_set: Arc::new(syntax_set),
+            syntax_set: Arc::new(LazySyntaxSet::loaded(syntax_set)),
             theme_set: Arc::new(theme_set),
         }
     }
 
+    /// Returns the syntax set, loading it first if it has not been loaded yet.
+    pub fn syntax_set(&self) -> &SyntaxSet {
+        &self.syntax_set
+    }
+
+    /// Returns whether the syntax set has already been loaded.
+    pub fn is_syntax_set_loaded(&self) -> bool {
+        self.syntax_set.is_loaded()
+    }
+
     /// Finds a syntax definition by its name (e.g., "Rust").
     pub fn find_syntax_by_name(&self, name: &str) -> Option<&SyntaxReference> {
-        self.syntax_set.find_syntax_by_name(name)
+        self.syntax_set().find_syntax_by_name(name)
     }
 
     /// Finds a syntax definition by a file extension (e.g., "rs").
     pub fn find_syntax_by_extension(&self, extension: &str) -> Option<&SyntaxReference> {
-        self.syntax_set.find_syntax_by_extension(extension)
+        self.syntax_set().find_syntax_by_extension(extension)
     }
 
     /// Gets a reference to a theme by its name (e.g., "base16-ocean.dark").
@@ -1,5 +1,55 @@
+use crate::selection::SelectionMode;
 use crate::util::Pos;
+#[cfg(feature = "arbitrary")]
+use arbitrary::Arbitrary;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
+use std::ops::Range;
+use std::time::{Duration, Instant};
+
+/// Policy to merge consecutive single-character insertions and deletions into one undo unit. See
+/// [`crate::TextArea::set_history_coalescing`].
+///
+/// Except for [`Coalescing::Manual`], only edits which continue at the cursor position where the previous edit ended
+/// are merged. Moving the cursor, selecting text, switching between insertion and deletion, undo and redo always start
+/// a new undo unit.
+///
+/// This type is marked as `#[non_exhaustive]` since more variations may be supported in the future.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Coalescing {
+    /// Each character insertion or deletion is one undo unit. This is the default.
+    #[default]
+    PerChar,
+    /// Characters are merged until a word ends. A whitespace or a punctuation is merged into the preceding word and
+    /// the next word character starts a new undo unit. Typing `"hello world"` creates two undo units `"hello "` and
+    /// `"world"`.
+    PerWord,
+    /// Characters edited within the duration since the previous edit are merged.
+    TimeWindow(Duration),
+    /// Undo units are never split automatically. Only [`crate::TextArea::begin_history_group`] and
+    /// [`crate::TextArea::end_history_group`] create undo units: the edits between them are one undo unit and all other
+    /// edits are merged into one undo unit until the next call of them. Undo, redo and saving the text also end an undo
+    /// unit.
+    Manual,
+}
+
+/// Where the cursor is placed after undo and redo. See [`crate::TextArea::set_undo_cursor`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum UndoCursor {
+    /// Both undo and redo place the cursor at the start of the restored or removed text. When the edit was made while
+    /// selecting text, undo restores the cursor before the edit and redo places the cursor after the edit instead. This
+    /// is the default.
+    #[default]
+    EditStart,
+    /// Undo restores the cursor position before the edit and redo restores the cursor position after the edit.
+    Restore,
+}
 
 #[derive(Clone, Debug)]
 pub enum EditKind {
@@ -11,6 +61,7 @@ pub enum EditKind {
     DeleteStr(String),
     InsertChunk(Vec<String>),
     DeleteChunk(Vec<String>),
+    Group(Vec<Edit>),
 }
 
 impl EditKind {
@@ -61,7 +112,7 @@ impl EditKind {
                 // Remove middle lines of chunk
                 let mut last_line = lines
                     .drain(after.row + 1..after.row + c.len())
-                    .last()
+                    .next_back()
                     .unwrap();
                 // Remove last line of chunk
                 last_line.drain(..c[c.len() - 1].len());
@@ -71,6 +122,11 @@ impl EditKind {
                 first_line.truncate(after.offset);
                 first_line.push_str(&last_line);
             }
+            EditKind::Group(edits) => {
+                for edit in edits {
+                    edit.redo(lines);
+                }
+            }
         }
     }
 
@@ -85,15 +141,175 @@ impl EditKind {
             DeleteStr(s) => InsertStr(s),
             InsertChunk(c) => DeleteChunk(c),
             DeleteChunk(c) => InsertChunk(c),
+            Group(edits) => Group(
+                edits
+                    .iter()
+                    .rev()
+                    .map(|e| Edit::new(e.kind.invert(), e.after, e.before))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+/// Lines affected by a modification of the text. See [`crate::TextArea::last_edit_info`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EditInfo {
+    rows: Range<usize>,
+    lines_delta: isize,
+}
+
+impl EditInfo {
+    fn new<'e>(edits: impl IntoIterator<Item = &'e Edit>) -> Self {
+        let mut rows: Option<Range<usize>> = None;
+        let mut lines_delta = 0;
+        for edit in edits {
+            edit.for_each_row_change(&mut |first, last, delta| {
+                // Rows after the modified rows are shifted by the inserted or removed rows
+                let shift = |row: usize| {
+                    if row <= first {
+                        row
+                    } else if row <= last + 1 {
+                        (last as isize + 1 + delta) as usize
+                    } else {
+                        (row as isize + delta) as usize
+                    }
+                };
+                let end = (last as isize + 1 + delta) as usize;
+                rows = Some(match rows.take() {
+                    Some(r) => shift(r.start).min(first)..shift(r.end).max(end),
+                    None => first..end,
+                });
+                lines_delta += delta;
+            });
+        }
+        Self {
+            rows: rows.unwrap_or(0..0),
+            lines_delta,
+        }
+    }
+
+    /// Range of rows modified or inserted by the modification. The rows are counted in the text after the
+    /// modification. The rows at and after the end of the range are not modified, but they are shifted by
+    /// [`EditInfo::lines_delta`].
+    pub fn rows(&self) -> Range<usize> {
+        self.rows.clone()
+    }
+
+    /// Number of lines inserted (positive) or removed (negative) by the modification.
+    pub fn lines_delta(&self) -> isize {
+        self.lines_delta
+    }
+}
+
+/// Kind of the modification recorded in the edit history. See [`HistoryEntry::kind`].
+///
+/// This type is marked as `#[non_exhaustive]` since more variations may be supported in the future.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum HistoryEditKind {
+    /// Text was inserted.
+    Insert,
+    /// Text was deleted.
+    Delete,
+    /// Text was both deleted and inserted, for example by pasting text over the selection.
+    Replace,
+}
+
+/// Read-only view of an entry in the edit history. See [`crate::TextArea::history`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HistoryEntry {
+    kind: HistoryEditKind,
+    position: (usize, usize),
+    preview: String,
+    undone: bool,
+}
+
+impl HistoryEntry {
+    /// Max number of characters in [`HistoryEntry::preview`].
+    pub const PREVIEW_LEN: usize = 32;
+
+    fn new(edit: &Edit, undone: bool) -> Self {
+        let (mut inserted, mut deleted) = (String::new(), String::new());
+        let mut deleted_start = None;
+        edit.for_each_text(&mut |insert, text, pos| {
+            if insert {
+                inserted.push_str(text);
+            } else if deleted_start.is_some_and(|start| pos < start) {
+                // Characters deleted backward are visited from the last one
+                deleted.insert_str(0, text);
+                deleted_start = Some(pos);
+            } else {
+                deleted.push_str(text);
+                deleted_start.get_or_insert(pos);
+            }
+        });
+        let kind = match (inserted.is_empty(), deleted.is_empty()) {
+            (false, false) => HistoryEditKind::Replace,
+            (true, false) => HistoryEditKind::Delete,
+            _ => HistoryEditKind::Insert,
+        };
+        let text = if kind == HistoryEditKind::Delete {
+            deleted
+        } else {
+            inserted
+        };
+        let mut preview: String = text.chars().take(Self::PREVIEW_LEN).collect();
+        if preview.len() < text.len() {
+            preview.push('…');
+        }
+        Self {
+            kind,
+            position: edit.region_start(),
+            preview,
+            undone,
         }
     }
+
+    /// Kind of the modification.
+    pub fn kind(&self) -> HistoryEditKind {
+        self.kind
+    }
+
+    /// Position where the modified text starts as `(row, col)` pair. The row and the column are 0-base.
+    pub fn position(&self) -> (usize, usize) {
+        self.position
+    }
+
+    /// Preview of the inserted text, or the deleted text when the modification only deleted text. It is truncated
+    /// to [`HistoryEntry::PREVIEW_LEN`] characters and `…` is appended when truncated. Newlines are kept as `\n`.
+    pub fn preview(&self) -> &str {
+        &self.preview
+    }
+
+    /// Return `true` when this entry was undone and is restored by [`crate::TextArea::redo`], or `false` when it is
+    /// reverted by [`crate::TextArea::undo`].
+    pub fn is_undone(&self) -> bool {
+        self.undone
+    }
+}
+
+// Cursor positions and the ongoing text selection around the edits recorded as one history entry
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EditContext {
+    pub cursor_before: (usize, usize),
+    pub cursor_after: (usize, usize),
+    // Selection start position and mode before the edit
+    pub selection: Option<((usize, usize), SelectionMode)>,
 }
 
+type TextVisitor<'a> = dyn FnMut(bool, &str, (usize, usize)) + 'a;
+
 #[derive(Clone, Debug)]
 pub struct Edit {
     kind: EditKind,
     before: Pos,
     after: Pos,
+    context: Option<EditContext>,
+    // Positions of the marks before the edit. Only recorded in history entries to restore the marks on undo
+    marks: Vec<(char, (usize, usize))>,
 }
 
 impl Edit {
@@ -102,6 +318,8 @@ impl Edit {
             kind,
             before,
             after,
+            context: None,
+            marks: vec![],
         }
     }
 
@@ -120,6 +338,201 @@ impl Edit {
     pub fn cursor_after(&self) -> (usize, usize) {
         (self.after.row, self.after.col)
     }
+
+    pub fn marks(&self) -> &[(char, (usize, usize))] {
+        &self.marks
+    }
+
+    // Edit to revert this edit
+    pub fn inverted(&self) -> Self {
+        Self::new(self.kind.invert(), self.after, self.before)
+    }
+
+    // Call `f` with the first and last rows modified by this edit and the number of rows inserted (positive) or removed
+    // (negative) by it. Rows are counted in the buffer before the edit is applied. Grouped edits are visited in order
+    pub fn for_each_row_change(&self, f: &mut dyn FnMut(usize, usize, isize)) {
+        let (before, after) = (self.before.row, self.after.row);
+        match &self.kind {
+            EditKind::InsertChar(_) | EditKind::InsertStr(_) => f(before, before, 0),
+            EditKind::InsertNewline => f(before, before, 1),
+            EditKind::InsertChunk(c) => f(before, before, c.len() as isize - 1),
+            EditKind::DeleteChar(_) | EditKind::DeleteStr(_) => f(after, after, 0),
+            EditKind::DeleteNewline => f(after, before, -1),
+            EditKind::DeleteChunk(c) => f(after, after + c.len() - 1, 1 - c.len() as isize),
+            EditKind::Group(edits) => {
+                for edit in edits {
+                    edit.for_each_row_change(f);
+                }
+            }
+        }
+    }
+
+    // Character inserted (`true`) or deleted (`false`) by this edit when it is a single-character edit or a group of
+    // single-character edits in the same direction. The last character is returned for the group
+    fn last_char(&self) -> Option<(bool, char)> {
+        match &self.kind {
+            EditKind::InsertChar(c) => Some((true, *c)),
+            EditKind::DeleteChar(c) => Some((false, *c)),
+            EditKind::Group(edits) => edits.last()?.last_char(),
+            _ => None,
+        }
+    }
+
+    // Size of the text stored in this edit in bytes
+    fn size(&self) -> usize {
+        match &self.kind {
+            EditKind::InsertChar(c) | EditKind::DeleteChar(c) => c.len_utf8(),
+            EditKind::InsertNewline | EditKind::DeleteNewline => 0,
+            EditKind::InsertStr(s) | EditKind::DeleteStr(s) => s.len(),
+            EditKind::InsertChunk(c) | EditKind::DeleteChunk(c) => c.iter().map(String::len).sum(),
+            EditKind::Group(edits) => edits.iter().map(Edit::size).sum(),
+        }
+    }
+
+    // Call `f` with the text inserted (`true`) or deleted (`false`) by this edit and the start position of the text.
+    // Grouped edits are visited in order
+    pub fn for_each_text(&self, f: &mut TextVisitor<'_>) {
+        let (before, after) = (self.cursor_before(), self.cursor_after());
+        match &self.kind {
+            EditKind::InsertChar(c) => f(true, c.encode_utf8(&mut [0; 4]), before),
+            EditKind::DeleteChar(c) => f(false, c.encode_utf8(&mut [0; 4]), after),
+            EditKind::InsertNewline => f(true, "\n", before),
+            EditKind::DeleteNewline => f(false, "\n", after),
+            EditKind::InsertStr(s) => f(true, s, before),
+            EditKind::DeleteStr(s) => f(false, s, after),
+            EditKind::InsertChunk(c) => f(true, &c.join("\n"), before),
+            EditKind::DeleteChunk(c) => f(false, &c.join("\n"), after),
+            EditKind::Group(edits) => {
+                for edit in edits {
+                    edit.for_each_text(f);
+                }
+            }
+        }
+    }
+
+    // Start of the region inserted or deleted by this edit. The cursor is placed here after both undo and redo when the
+    // cursor positions around the edit were not recorded
+    pub fn region_start(&self) -> (usize, usize) {
+        match &self.kind {
+            EditKind::Group(edits) => edits
+                .iter()
+                .map(Edit::region_start)
+                .min()
+                .unwrap_or_else(|| self.cursor_before()),
+            _ => self.cursor_before().min(self.cursor_after()),
+        }
+    }
+}
+
+// Position of a modification in a `Change` relative to the position where the change began. The column is relative
+// only when the modification is in the same row. Otherwise it is the column in the row
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct RelativePos {
+    row: isize,
+    col: isize,
+}
+
+impl RelativePos {
+    fn new(pos: (usize, usize), origin: (usize, usize)) -> Self {
+        let row = pos.0 as isize - origin.0 as isize;
+        let col = if row == 0 {
+            pos.1 as isize - origin.1 as isize
+        } else {
+            pos.1 as isize
+        };
+        Self { row, col }
+    }
+
+    // The position is not clamped to the text
+    fn resolve(self, origin: (usize, usize)) -> (usize, usize) {
+        let row = origin.0 as isize + self.row;
+        let col = if self.row == 0 {
+            origin.1 as isize + self.col
+        } else {
+            self.col
+        };
+        (row.max(0) as usize, col.max(0) as usize)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ChangeOp {
+    Insert(String),
+    // Number of characters to delete. A line break is counted as one character
+    Delete(usize),
+    // Number of entire lines to delete
+    DeleteLines(usize),
+}
+
+// Modifications recorded as one history entry, which can be applied again at another position
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Change {
+    ops: Vec<(RelativePos, ChangeOp)>,
+    cursor_after: RelativePos,
+    // Columns are counted from the head of the line instead of the cursor since the change was made on selected lines
+    linewise: bool,
+}
+
+impl Change {
+    fn new(edit: &Edit) -> Option<Self> {
+        let ctx = edit.context?;
+        let before = ctx.cursor_before;
+        let (origin, linewise) = match ctx.selection {
+            Some((start, SelectionMode::Line)) => ((start.0.min(before.0), 0), true),
+            Some((start, SelectionMode::Block)) => {
+                ((start.0.min(before.0), start.1.min(before.1)), false)
+            }
+            Some((start, SelectionMode::Char)) => (start.min(before), false),
+            None => (before, false),
+        };
+        let mut ops = vec![];
+        edit.for_each_text(&mut |insert, text, (row, col)| {
+            let newlines = text.matches('\n').count();
+            let (pos, op) = if insert {
+                ((row, col), ChangeOp::Insert(text.to_string()))
+            } else if linewise && col == 0 && text.ends_with('\n') {
+                ((row, 0), ChangeOp::DeleteLines(newlines))
+            } else if linewise && text.starts_with('\n') {
+                // The last lines were deleted with the line break before them
+                ((row + 1, 0), ChangeOp::DeleteLines(newlines))
+            } else {
+                ((row, col), ChangeOp::Delete(text.chars().count()))
+            };
+            ops.push((RelativePos::new(pos, origin), op));
+        });
+        if ops.is_empty() {
+            return None;
+        }
+        Some(Self {
+            ops,
+            cursor_after: RelativePos::new(ctx.cursor_after, origin),
+            linewise,
+        })
+    }
+
+    fn origin(&self, cursor: (usize, usize)) -> (usize, usize) {
+        if self.linewise {
+            (cursor.0, 0)
+        } else {
+            cursor
+        }
+    }
+
+    // Modifications with their positions when the change is applied again at the cursor. Each position is in the text
+    // after the preceding modifications were applied
+    pub fn ops(
+        &self,
+        cursor: (usize, usize),
+    ) -> impl Iterator<Item = ((usize, usize), &ChangeOp)> + '_ {
+        let origin = self.origin(cursor);
+        self.ops
+            .iter()
+            .map(move |(pos, op)| (pos.resolve(origin), op))
+    }
+
+    pub fn cursor_after(&self, cursor: (usize, usize)) -> (usize, usize) {
+        self.cursor_after.resolve(self.origin(cursor))
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -127,6 +540,30 @@ pub struct History {
     index: usize,
     max_items: usize,
     edits: VecDeque<Edit>,
+    group: Option<((usize, usize), Vec<Edit>)>,
+    // Selection start position and mode when the outermost group began
+    group_selection: Option<((usize, usize), SelectionMode)>,
+    // Positions of the marks when the outermost group began
+    group_mark_positions: Vec<(char, (usize, usize))>,
+    // Number of edits in `group` when each nested group began
+    group_marks: Vec<usize>,
+    // Number of modifications applied to the text so far. This is used to detect modifications between two points
+    changes: usize,
+    // Value of `index` when the text was saved. `None` means the saved state is no longer reachable by undo or redo
+    saved: Option<usize>,
+    coalescing: Coalescing,
+    undo_cursor: UndoCursor,
+    max_bytes: usize,
+    // Total size of the text stored in `edits`
+    bytes: usize,
+    // Lines affected by the last edit, undo or redo
+    last_edit: Option<EditInfo>,
+    // When the last entry was pushed, if the next edit can be merged into it. Only single-character edits and the
+    // entries merged from them can be merged into
+    last_coalesced: Option<Instant>,
+    // The last change recorded as a history entry to apply it again. Only recorded while `record_changes` is enabled
+    last_change: Option<Change>,
+    record_changes: bool,
 }
 
 impl History {
@@ -135,25 +572,312 @@ impl History {
             index: 0,
             max_items,
             edits: VecDeque::new(),
+            group: None,
+            group_selection: None,
+            group_mark_positions: vec![],
+            group_marks: vec![],
+            changes: 0,
+            saved: Some(0),
+            coalescing: Coalescing::default(),
+            undo_cursor: UndoCursor::default(),
+            max_bytes: usize::MAX,
+            bytes: 0,
+            last_edit: None,
+            last_coalesced: None,
+            last_change: None,
+            record_changes: false,
         }
     }
 
     pub fn push(&mut self, edit: Edit) {
+        self.changes = self.changes.wrapping_add(1);
+        self.last_edit = Some(EditInfo::new([&edit]));
+        // Edits in a group are recorded even if the history is disabled so that they can be rolled back
+        if let Some((_, group)) = &mut self.group {
+            group.push(edit);
+            return;
+        }
+
         if self.max_items == 0 {
+            self.saved = None;
+            if self.record_changes {
+                self.last_change = Change::new(&edit);
+            }
             return;
         }
 
-        if self.edits.len() == self.max_items {
-            self.edits.pop_front();
-            self.index = self.index.saturating_sub(1);
+        let edit = match self.coalesce(edit) {
+            Some(edit) => edit,
+            None => {
+                // The edit was merged into the last entry
+                if self.record_changes {
+                    self.last_change = self.edits.back().and_then(Change::new);
+                }
+                trace!(
+                    entries = self.edits.len(),
+                    "edit merged into last history entry"
+                );
+                return self.shrink_to_max_bytes();
+            }
+        };
+        if self.record_changes {
+            self.last_change = Change::new(&edit);
         }
 
-        if self.index < self.edits.len() {
-            self.edits.truncate(self.index);
+        if self.edits.len() == self.max_items {
+            self.remove_oldest();
         }
+        self.truncate_redo();
 
+        debug!(
+            entry = ?HistoryEntry::new(&edit, false),
+            entries = self.index + 1,
+            "history entry pushed",
+        );
         self.index += 1;
+        self.bytes += edit.size();
         self.edits.push_back(edit);
+        self.shrink_to_max_bytes();
+    }
+
+    fn remove_oldest(&mut self) {
+        // Entries to redo cannot be applied without the oldest entry
+        if self.index == 0 {
+            self.truncate_redo();
+            return;
+        }
+        if let Some(edit) = self.edits.pop_front() {
+            self.bytes -= edit.size();
+        }
+        self.index -= 1;
+        self.saved = self.saved.and_then(|i| i.checked_sub(1));
+    }
+
+    fn truncate_redo(&mut self) {
+        for edit in self.edits.drain(self.index..) {
+            self.bytes -= edit.size();
+        }
+        // The saved state was undone and it is no longer redoable
+        if self.saved.is_some_and(|i| i > self.index) {
+            self.saved = None;
+        }
+    }
+
+    fn shrink_to_max_bytes(&mut self) {
+        // The newest entry is kept even if it is larger than the limit so that the last edit can always be undone
+        while self.bytes > self.max_bytes && self.index > 1 {
+            self.remove_oldest();
+        }
+        if self.bytes > self.max_bytes {
+            self.truncate_redo();
+        }
+    }
+
+    // Merge the edit into the last entry following the coalescing policy. The edit is returned back when it was not
+    // merged
+    fn coalesce(&mut self, edit: Edit) -> Option<Edit> {
+        let last_time = self.last_coalesced.take();
+        if self.coalescing == Coalescing::Manual {
+            return self.coalesce_manually(edit, last_time.is_some());
+        }
+        // Groups such as the edits between `begin_group` and `end_group` are never merged
+        if matches!(edit.kind, EditKind::Group(_)) {
+            return Some(edit);
+        }
+        let (insert, c) = match (edit.last_char(), edit.context) {
+            (Some(ch), Some(ctx)) if ctx.selection.is_none() => ch,
+            _ => return Some(edit),
+        };
+        let now = Instant::now();
+        self.last_coalesced = Some(now);
+
+        let last_time = match last_time {
+            Some(t) if self.index == self.edits.len() && self.saved != Some(self.index) => t,
+            _ => return Some(edit),
+        };
+        let last = match self.edits.back_mut() {
+            Some(last) => last,
+            None => return Some(edit),
+        };
+        let (last_insert, last_c) = match last.last_char() {
+            Some(ch) => ch,
+            None => return Some(edit),
+        };
+        let (last_ctx, ctx) = match (last.context, edit.context) {
+            (Some(l), Some(c)) if l.cursor_after == c.cursor_before => (l, c),
+            _ => return Some(edit),
+        };
+        if last_insert != insert {
+            return Some(edit);
+        }
+        let is_boundary = |c: char| c.is_whitespace() || c.is_ascii_punctuation();
+        let merge = match self.coalescing {
+            Coalescing::PerChar | Coalescing::Manual => false,
+            Coalescing::PerWord => !is_boundary(last_c) || is_boundary(c),
+            Coalescing::TimeWindow(window) => now.duration_since(last_time) < window,
+        };
+        if !merge {
+            return Some(edit);
+        }
+        let context = EditContext {
+            cursor_before: last_ctx.cursor_before,
+            cursor_after: ctx.cursor_after,
+            selection: None,
+        };
+        self.merge_into_last(edit, context);
+        None
+    }
+
+    // Merge any edit into the last entry with `Coalescing::Manual` until the undo unit is ended by `end_undo_unit`,
+    // undo, redo or saving. `continued` is false when the last entry was ended
+    fn coalesce_manually(&mut self, edit: Edit, continued: bool) -> Option<Edit> {
+        self.last_coalesced = Some(Instant::now());
+        if !continued || self.index != self.edits.len() || self.saved == Some(self.index) {
+            return Some(edit);
+        }
+        let context = match (
+            self.edits.back().and_then(|last| last.context),
+            edit.context,
+        ) {
+            (Some(l), Some(c)) => EditContext {
+                cursor_before: l.cursor_before,
+                cursor_after: c.cursor_after,
+                selection: l.selection,
+            },
+            _ => return Some(edit),
+        };
+        self.merge_into_last(edit, context);
+        None
+    }
+
+    // Append the edit to the last entry. `context` is the cursor positions and the selection of the merged entry
+    fn merge_into_last(&mut self, edit: Edit, context: EditContext) {
+        let last = self
+            .edits
+            .back_mut()
+            .expect("last entry to merge the edit into");
+        let mut edits = match std::mem::replace(&mut last.kind, EditKind::Group(vec![])) {
+            EditKind::Group(edits) => edits,
+            kind => vec![Edit::new(kind, last.before, last.after)],
+        };
+        self.bytes += edit.size();
+        match edit.kind {
+            EditKind::Group(group) => edits.extend(group),
+            kind => edits.push(Edit::new(kind, edit.before, edit.after)),
+        }
+        last.kind = EditKind::Group(edits);
+        last.after = edit.after;
+        last.context = Some(context);
+    }
+
+    // Start a new undo unit on the next edit. With `Coalescing::Manual` this separates undo units
+    pub fn end_undo_unit(&mut self) {
+        self.last_coalesced = None;
+    }
+
+    // Edits pushed until the matching `end_group` call are recorded as one history entry. Nested groups are flattened.
+    // The cursor positions at the beginning and the end of the outermost group are restored on undo and redo
+    pub fn begin_group(&mut self, cursor: (usize, usize)) {
+        let (_, edits) = self.group.get_or_insert_with(|| (cursor, vec![]));
+        self.group_marks.push(edits.len());
+    }
+
+    // Same as `begin_group` but the selection is also restored on undo when this begins the outermost group
+    pub fn begin_group_with_selection(
+        &mut self,
+        cursor: (usize, usize),
+        selection: ((usize, usize), SelectionMode),
+    ) {
+        if self.group.is_none() {
+            self.group_selection = Some(selection);
+        }
+        self.begin_group(cursor);
+    }
+
+    // Record the positions of the marks before the edits in the group to restore them on undo. This must be called
+    // before the outermost `begin_group` call
+    pub fn set_mark_positions(&mut self, marks: Vec<(char, (usize, usize))>) {
+        if self.group.is_none() {
+            self.group_mark_positions = marks;
+        }
+    }
+
+    pub fn end_group(&mut self, cursor: (usize, usize)) {
+        debug_assert!(
+            !self.group_marks.is_empty(),
+            "end_group without begin_group"
+        );
+        self.group_marks.pop();
+        self.close_group(cursor);
+    }
+
+    fn close_group(&mut self, cursor: (usize, usize)) {
+        if !self.group_marks.is_empty() {
+            return;
+        }
+        let (before, edits) = match self.group.take() {
+            Some(group) => group,
+            None => return,
+        };
+        let marks = std::mem::take(&mut self.group_mark_positions);
+        let context = EditContext {
+            cursor_before: before,
+            cursor_after: cursor,
+            selection: self.group_selection.take(),
+        };
+        let mut edit = match edits.len() {
+            0 => return,
+            1 => edits.into_iter().next().unwrap(),
+            _ => {
+                let before = Pos::new(before.0, before.1, 0);
+                let after = Pos::new(cursor.0, cursor.1, 0);
+                Edit::new(EditKind::Group(edits), before, after)
+            }
+        };
+        edit.context = Some(context);
+        edit.marks = marks;
+        self.push(edit);
+    }
+
+    pub fn in_group(&self) -> bool {
+        !self.group_marks.is_empty()
+    }
+
+    // Some edits were pushed to the open group and they are not recorded as a history entry yet
+    pub fn has_pending_group(&self) -> bool {
+        self.group
+            .as_ref()
+            .is_some_and(|(_, edits)| !edits.is_empty())
+    }
+
+    // Edits pushed since the innermost `begin_group` call
+    pub fn pending_edits(&self) -> &[Edit] {
+        match (&self.group, self.group_marks.last()) {
+            (Some((_, edits)), Some(&mark)) => &edits[mark..],
+            _ => &[],
+        }
+    }
+
+    // Revert the edits since the innermost `begin_group` call and close the group. Returns the cursor position before
+    // the reverted edits
+    pub fn rollback_group(&mut self, lines: &mut Vec<String>) -> Option<(usize, usize)> {
+        let mark = self.group_marks.pop()?;
+        self.changes = self.changes.wrapping_add(1);
+        let (cursor, edits) = self.group.as_mut()?;
+        let mut cursor = if mark == 0 {
+            *cursor
+        } else {
+            edits[mark - 1].cursor_after()
+        };
+        let reverted: Vec<_> = edits.drain(mark..).rev().map(|e| e.inverted()).collect();
+        for edit in &reverted {
+            edit.redo(lines);
+            cursor = edit.cursor_after();
+        }
+        self.last_edit = Some(EditInfo::new(&reverted));
+        debug!(edits = reverted.len(), "edits in history group rolled back");
+        self.close_group(cursor);
+        Some(cursor)
     }
 
     pub fn redo(&mut self, lines: &mut Vec<String>) -> Option<(usize, usize)> {
@@ -161,21 +885,163 @@ impl History {
             return None;
         }
         let edit = &self.edits[self.index];
+        debug!(entry = ?HistoryEntry::new(edit, true), index = self.index, "history entry redone");
         edit.redo(lines);
         self.index += 1;
-        Some(edit.cursor_after())
+        self.last_coalesced = None;
+        self.last_edit = Some(EditInfo::new([edit]));
+        self.changes = self.changes.wrapping_add(1);
+        Some(self.cursor_after_undo_redo(edit, false))
     }
 
     pub fn undo(&mut self, lines: &mut Vec<String>) -> Option<(usize, usize)> {
         self.index = self.index.checked_sub(1)?;
         let edit = &self.edits[self.index];
+        debug!(entry = ?HistoryEntry::new(edit, false), index = self.index, "history entry undone");
         edit.undo(lines);
-        Some(edit.cursor_before())
+        self.last_coalesced = None;
+        self.last_edit = Some(EditInfo::new([&edit.inverted()]));
+        self.changes = self.changes.wrapping_add(1);
+        Some(self.cursor_after_undo_redo(edit, true))
+    }
+
+    // Cursor position after undoing (`undo` is `true`) or redoing the edit following the `undo_cursor` setting
+    fn cursor_after_undo_redo(&self, edit: &Edit, undo: bool) -> (usize, usize) {
+        match &edit.context {
+            Some(c) if self.undo_cursor == UndoCursor::Restore || c.selection.is_some() => {
+                if undo {
+                    c.cursor_before
+                } else {
+                    c.cursor_after
+                }
+            }
+            _ => edit.region_start(),
+        }
+    }
+
+    // Selection start position and mode restored by the next `undo` call
+    pub fn undo_selection(&self) -> Option<((usize, usize), SelectionMode)> {
+        let i = self.index.checked_sub(1)?;
+        self.edits[i].context.and_then(|c| c.selection)
+    }
+
+    // Edit applied by the next `undo` call
+    pub fn peek_undo(&self) -> Option<Edit> {
+        let i = self.index.checked_sub(1)?;
+        Some(self.edits[i].inverted())
+    }
+
+    // History entry reverted by the next `undo` call
+    pub fn peek_undo_entry(&self) -> Option<&Edit> {
+        let i = self.index.checked_sub(1)?;
+        self.edits.get(i)
+    }
+
+    // Edit applied by the next `redo` call
+    pub fn peek_redo(&self) -> Option<&Edit> {
+        self.edits.get(self.index)
+    }
+
+    // Entries from the oldest one
+    pub fn entries(&self) -> impl Iterator<Item = HistoryEntry> + '_ {
+        let index = self.index;
+        self.edits
+            .iter()
+            .enumerate()
+            .map(move |(i, edit)| HistoryEntry::new(edit, i >= index))
+    }
+
+    pub fn last_edit(&self) -> Option<&EditInfo> {
+        self.last_edit.as_ref()
+    }
+
+    pub fn set_coalescing(&mut self, coalescing: Coalescing) {
+        self.coalescing = coalescing;
+        self.last_coalesced = None;
+    }
+
+    pub fn coalescing(&self) -> Coalescing {
+        self.coalescing
+    }
+
+    pub fn set_undo_cursor(&mut self, undo_cursor: UndoCursor) {
+        self.undo_cursor = undo_cursor;
+    }
+
+    pub fn undo_cursor(&self) -> UndoCursor {
+        self.undo_cursor
+    }
+
+    pub fn set_max_bytes(&mut self, max: usize) {
+        self.max_bytes = max;
+        self.shrink_to_max_bytes();
+    }
+
+    pub fn max_bytes(&self) -> usize {
+        self.max_bytes
+    }
+
+    pub fn bytes(&self) -> usize {
+        self.bytes
     }
 
     pub fn max_items(&self) -> usize {
         self.max_items
     }
+
+    /// Number of entries which can be undone
+    pub fn undo_len(&self) -> usize {
+        self.index
+    }
+
+    // Forget the saved state so that the text is considered modified until the next `mark_saved` call
+    pub fn forget_saved(&mut self) {
+        self.saved = None;
+    }
+
+    pub fn mark_saved(&mut self) {
+        self.saved = (!self.has_pending_group()).then_some(self.index);
+    }
+
+    pub fn is_modified(&self) -> bool {
+        self.has_pending_group() || self.saved != Some(self.index)
+    }
+
+    pub fn changes(&self) -> usize {
+        self.changes
+    }
+
+    // Continue counting modifications from the value of another history so that positions remembered with the
+    // counter are still valid after the history was replaced
+    pub fn set_changes(&mut self, changes: usize) {
+        self.changes = changes;
+    }
+
+    /// Number of entries which can be redone
+    pub fn redo_len(&self) -> usize {
+        self.edits.len() - self.index
+    }
+
+    pub fn set_record_changes(&mut self, enabled: bool) {
+        self.record_changes = enabled;
+        if !enabled {
+            self.last_change = None;
+        }
+    }
+
+    pub fn record_changes(&self) -> bool {
+        self.record_changes
+    }
+
+    pub fn last_change(&self) -> Option<&Change> {
+        self.last_change.as_ref()
+    }
+
+    pub fn set_last_change(&mut self, change: Option<Change>) {
+        if self.record_changes {
+            self.last_change = change;
+        }
+    }
 }
 
 #[cfg(test)]
@@ -647,4 +1513,189 @@ mod tests {
             assert_eq!(&lines, &before, "{test:?}");
         }
     }
+
+    #[test]
+    fn group() {
+        let mut lines = vec!["ab".to_string()];
+        let mut history = History::new(10);
+
+        // Empty group adds nothing
+        history.begin_group((0, 0));
+        history.end_group((0, 0));
+        assert!(history.undo(&mut lines).is_none());
+
+        // Nested groups are flattened into one entry
+        history.begin_group((0, 1));
+        for (i, c) in ['x', 'y'].into_iter().enumerate() {
+            history.begin_group((0, 0));
+            let before = Pos::new(0, i, i);
+            let after = Pos::new(0, i + 1, i + 1);
+            let edit = Edit::new(EditKind::InsertChar(c), before, after);
+            edit.redo(&mut lines);
+            history.push(edit);
+            history.end_group((0, 0));
+        }
+        history.end_group((0, 2));
+        assert_eq!(lines, ["xyab"]);
+
+        // Cursor is placed at the start of the edited region
+        assert_eq!(history.undo(&mut lines), Some((0, 0)));
+        assert_eq!(lines, ["ab"]);
+        assert!(history.undo(&mut lines).is_none());
+        assert_eq!(history.redo(&mut lines), Some((0, 0)));
+        assert_eq!(lines, ["xyab"]);
+        assert!(history.redo(&mut lines).is_none());
+
+        // Cursor positions at the beginning and the end of the outermost group are restored
+        history.set_undo_cursor(UndoCursor::Restore);
+        assert_eq!(history.undo(&mut lines), Some((0, 1)));
+        assert_eq!(lines, ["ab"]);
+        assert_eq!(history.redo(&mut lines), Some((0, 2)));
+        assert_eq!(lines, ["xyab"]);
+    }
+
+    #[test]
+    fn rollback_group() {
+        let mut lines = vec!["ab".to_string()];
+        let mut history = History::new(10);
+
+        history.begin_group((0, 0));
+        for (i, c) in ['x', 'y'].into_iter().enumerate() {
+            let before = Pos::new(0, i, i);
+            let after = Pos::new(0, i + 1, i + 1);
+            let edit = Edit::new(EditKind::InsertChar(c), before, after);
+            edit.redo(&mut lines);
+            history.push(edit);
+        }
+        assert_eq!(lines, ["xyab"]);
+
+        // Only the edits in the inner group are reverted
+        history.begin_group((0, 2));
+        let edit = Edit::new(
+            EditKind::InsertChar('z'),
+            Pos::new(0, 2, 2),
+            Pos::new(0, 3, 3),
+        );
+        edit.redo(&mut lines);
+        history.push(edit);
+        assert_eq!(history.rollback_group(&mut lines), Some((0, 2)));
+        assert_eq!(lines, ["xyab"]);
+
+        assert_eq!(history.rollback_group(&mut lines), Some((0, 0)));
+        assert_eq!(lines, ["ab"]);
+        assert!(history.undo(&mut lines).is_none());
+        assert!(history.rollback_group(&mut lines).is_none());
+    }
+
+    #[test]
+    fn saved_state() {
+        let mut lines = vec![String::new()];
+        let mut history = History::new(2);
+        let insert = |history: &mut History, lines: &mut Vec<String>, i: usize| {
+            let edit = Edit::new(
+                EditKind::InsertChar('a'),
+                Pos::new(0, i, i),
+                Pos::new(0, i + 1, i + 1),
+            );
+            edit.redo(lines);
+            history.push(edit);
+        };
+        assert!(!history.is_modified());
+
+        insert(&mut history, &mut lines, 0);
+        assert!(history.is_modified());
+        history.mark_saved();
+        assert!(!history.is_modified());
+
+        // Undo and redo back to the saved state
+        history.undo(&mut lines);
+        assert!(history.is_modified());
+        history.redo(&mut lines);
+        assert!(!history.is_modified());
+
+        // Pending edits in a group are modifications
+        history.begin_group((0, 1));
+        insert(&mut history, &mut lines, 1);
+        assert!(history.is_modified());
+        history.rollback_group(&mut lines);
+        assert!(!history.is_modified());
+
+        // The saved state is no longer reachable after new edit is pushed on undo
+        history.undo(&mut lines);
+        insert(&mut history, &mut lines, 0);
+        history.undo(&mut lines);
+        assert!(history.is_modified());
+
+        // The saved state is no longer reachable after the oldest entry is removed
+        history.mark_saved();
+        for i in 0..3 {
+            insert(&mut history, &mut lines, i);
+        }
+        while history.undo(&mut lines).is_some() {}
+        assert!(history.is_modified());
+        history.forget_saved();
+        assert!(history.is_modified());
+
+        // Disabled history
+        let mut history = History::new(0);
+        insert(&mut history, &mut lines, 0);
+        assert!(history.is_modified());
+        history.mark_saved();
+        assert!(!history.is_modified());
+    }
+
+    #[test]
+    fn last_change() {
+        let mut lines = vec!["ab".to_string(), "cd".to_string()];
+        let mut history = History::new(10);
+        let push_group = |history: &mut History, lines: &mut Vec<String>, edits: Vec<Edit>| {
+            history.begin_group(edits[0].cursor_before());
+            let after = edits.last().unwrap().cursor_after();
+            for edit in edits {
+                edit.redo(lines);
+                history.push(edit);
+            }
+            history.end_group(after);
+        };
+        let insert = |c, row, col| {
+            let before = Pos::new(row, col, col);
+            let after = Pos::new(row, col + 1, col + 1);
+            Edit::new(EditKind::InsertChar(c), before, after)
+        };
+
+        // Not recorded by default
+        push_group(&mut history, &mut lines, vec![insert('x', 0, 1)]);
+        assert!(history.last_change().is_none());
+
+        history.set_record_changes(true);
+        let edits = vec![
+            insert('y', 0, 1),
+            Edit::new(
+                EditKind::InsertNewline,
+                Pos::new(0, 2, 2),
+                Pos::new(1, 0, 0),
+            ),
+            insert('z', 1, 0),
+        ];
+        push_group(&mut history, &mut lines, edits);
+        assert_eq!(lines, ["ay", "zxb", "cd"]);
+
+        let change = history.last_change().unwrap().clone();
+        let ops: Vec<_> = change.ops((5, 3)).collect();
+        let want = [
+            ((5, 3), &ChangeOp::Insert("y".into())),
+            ((5, 4), &ChangeOp::Insert("\n".into())),
+            ((6, 0), &ChangeOp::Insert("z".into())),
+        ];
+        assert_eq!(ops, want);
+        assert_eq!(change.cursor_after((5, 3)), (6, 1));
+
+        // Undo and redo do not replace the change
+        history.undo(&mut lines);
+        history.redo(&mut lines);
+        assert_eq!(history.last_change(), Some(&change));
+
+        history.set_record_changes(false);
+        assert!(history.last_change().is_none());
+    }
 }
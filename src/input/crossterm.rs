@@ -25,7 +25,7 @@ impl From<KeyCode> for Key {
             KeyCode::Right => Key::Right,
             KeyCode::Up => Key::Up,
             KeyCode::Down => Key::Down,
-            KeyCode::Tab => Key::Tab,
+            KeyCode::Tab | KeyCode::BackTab => Key::Tab,
             KeyCode::Delete => Key::Delete,
             KeyCode::Home => Key::Home,
             KeyCode::End => Key::End,
@@ -49,7 +49,8 @@ impl From<KeyEvent> for Input {
 
         let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
         let alt = key.modifiers.contains(KeyModifiers::ALT);
-        let shift = key.modifiers.contains(KeyModifiers::SHIFT);
+        // Shift+Tab is reported as `BackTab`
+        let shift = key.modifiers.contains(KeyModifiers::SHIFT) || key.code == KeyCode::BackTab;
         let key = Key::from(key.code);
 
         Self {
@@ -142,6 +143,10 @@ mod tests {
                 ),
                 input(Key::F(1), true, true, true),
             ),
+            (
+                key_event(KeyCode::BackTab, KeyModifiers::empty()),
+                input(Key::Tab, false, false, true),
+            ),
             (
                 key_event(KeyCode::NumLock, KeyModifiers::CONTROL),
                 input(Key::Null, true, false, false),
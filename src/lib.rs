@@ -7,17 +7,34 @@
 #[cfg(all(feature = "ratatui", feature = "tuirs"))]
 compile_error!("ratatui support and tui-rs support are exclusive. only one of them can be enabled at the same time. see https://github.com/rhysd/tui-textarea#installation");
 
+#[macro_use]
+mod trace;
+
+mod case;
+#[cfg(feature = "clipboard")]
+mod clipboard;
 mod cursor;
+mod gutter;
 mod highlight;
 mod history;
 mod input;
+mod list;
+mod marks;
+mod number;
+mod osc52;
+mod protected;
 mod scroll;
 #[cfg(feature = "search")]
 mod search;
+mod selection;
+mod sort;
 mod textarea;
+mod textobject;
 mod util;
 mod widget;
 mod word;
+mod wrap;
+mod yank;
 
 mod highlighting;
 
@@ -39,8 +56,22 @@ use termion;
 #[cfg(feature = "tuirs-termion")]
 use termion_15 as termion;
 
-pub use cursor::CursorMove;
+pub use case::Case;
+#[cfg(feature = "clipboard")]
+pub use clipboard::ClipboardError;
+pub use cursor::{CursorMove, CursorRendering};
+pub use gutter::{LineNumberContext, Sign};
+pub use highlight::WhitespaceRender;
+pub use history::{Coalescing, EditInfo, HistoryEditKind, HistoryEntry, UndoCursor};
 pub use input::{Input, Key};
+pub use list::{ListContinuation, ListMarker};
+pub use protected::ProtectedRowError;
 pub use scroll::Scrolling;
+#[cfg(feature = "search")]
+pub use search::{SearchCase, SearchHistory, SearchResult, SearchScope};
+pub use selection::{SelectionMode, SelectionUnit};
+pub use sort::SortOrder;
 pub use textarea::TextArea;
-pub use highlighting::SyntaxHighlighter;
+pub use textobject::TextObject;
+pub use yank::YankKind;
+pub use highlighting::{LazySyntaxSet, SyntaxHighlighter};
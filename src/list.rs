@@ -0,0 +1,123 @@
+#[cfg(feature = "arbitrary")]
+use arbitrary::Arbitrary;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Kind of list item markers continued on inserting a newline. See [`ListContinuation`].
+///
+/// This type is marked as `#[non_exhaustive]` since more variations may be supported in the future.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ListMarker {
+    /// Bullet list item starting with `- `.
+    Dash,
+    /// Bullet list item starting with `* `.
+    Asterisk,
+    /// Numbered list item starting with `1. ` or `1) `. The number is incremented on the continued line.
+    Numbered,
+    /// Task list item starting with `- [ ] ` or `- [x] `. The checkbox is unchecked on the continued line.
+    Checkbox,
+}
+
+impl ListMarker {
+    // Returns the byte length of the marker at the head of `s` and the marker for the next item
+    fn parse(self, s: &str) -> Option<(usize, String)> {
+        match self {
+            ListMarker::Dash => s.starts_with("- ").then(|| (2, "- ".to_string())),
+            ListMarker::Asterisk => s.starts_with("* ").then(|| (2, "* ".to_string())),
+            ListMarker::Numbered => {
+                let digits = s.bytes().take_while(u8::is_ascii_digit).count();
+                if digits == 0 || digits > 9 {
+                    return None;
+                }
+                let delim = &s[digits..];
+                let delim = if delim.starts_with(". ") {
+                    '.'
+                } else if delim.starts_with(") ") {
+                    ')'
+                } else {
+                    return None;
+                };
+                let n: u32 = s[..digits].parse().ok()?;
+                Some((digits + 2, format!("{}{} ", n + 1, delim)))
+            }
+            ListMarker::Checkbox => ["- [ ] ", "- [x] ", "- [X] "]
+                .iter()
+                .any(|m| s.starts_with(m))
+                .then(|| (6, "- [ ] ".to_string())),
+        }
+    }
+}
+
+/// Configuration of list continuation for [`crate::TextArea::set_list_continuation`]. When enabled, inserting a
+/// newline in a list item starts the next item with the same indentation and marker. Inserting a newline in an empty
+/// list item outdents it by one indent level, or removes its marker when it is not indented.
+///
+/// ```
+/// use tui_textarea::{ListContinuation, ListMarker, TextArea};
+///
+/// let mut textarea = TextArea::from(["1. first"]);
+/// textarea.set_list_continuation(Some(ListContinuation::new([ListMarker::Numbered])));
+///
+/// textarea.move_cursor(tui_textarea::CursorMove::End);
+/// textarea.insert_newline();
+/// assert_eq!(textarea.lines(), ["1. first", "2. "]);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ListContinuation {
+    markers: Vec<ListMarker>,
+}
+
+impl Default for ListContinuation {
+    /// Continue all kinds of list markers.
+    fn default() -> Self {
+        Self::new([
+            ListMarker::Dash,
+            ListMarker::Asterisk,
+            ListMarker::Numbered,
+            ListMarker::Checkbox,
+        ])
+    }
+}
+
+impl ListContinuation {
+    /// Create a list continuation configuration which continues list items with the given markers.
+    pub fn new(markers: impl IntoIterator<Item = ListMarker>) -> Self {
+        Self {
+            markers: markers.into_iter().collect(),
+        }
+    }
+
+    /// Markers of list items which are continued.
+    pub fn markers(&self) -> &[ListMarker] {
+        &self.markers
+    }
+
+    // Checkbox is checked first since its marker is prefixed with the dash marker
+    pub(crate) fn parse(&self, line: &str) -> Option<ListItem> {
+        let body = line.trim_start_matches([' ', '\t']);
+        let indent = &line[..line.len() - body.len()];
+        let (len, next) = [
+            ListMarker::Checkbox,
+            ListMarker::Dash,
+            ListMarker::Asterisk,
+            ListMarker::Numbered,
+        ]
+        .iter()
+        .filter(|m| self.markers.contains(m))
+        .find_map(|m| m.parse(body))?;
+        Some(ListItem {
+            prefix_len: indent.len() + len,
+            next_prefix: format!("{}{}", indent, next),
+        })
+    }
+}
+
+pub(crate) struct ListItem {
+    /// Byte length of the indentation and the marker
+    pub prefix_len: usize,
+    /// Indentation and marker of the next list item
+    pub next_prefix: String,
+}
@@ -0,0 +1,156 @@
+use crate::history::Edit;
+use std::collections::BTreeMap;
+
+// Position after the text inserted at `start`
+pub fn text_end((row, col): (usize, usize), text: &str) -> (usize, usize) {
+    match text.rfind('\n') {
+        Some(i) => (
+            row + text.matches('\n').count(),
+            text[i + 1..].chars().count(),
+        ),
+        None => (row, col + text.chars().count()),
+    }
+}
+
+// Move the position across the text inserted or deleted at `start`. `end` is the position after the text. When
+// `after` is true, the position moves after the text inserted at the position. Otherwise it stays before the text
+pub fn move_pos(
+    pos: (usize, usize),
+    insert: bool,
+    start: (usize, usize),
+    end: (usize, usize),
+    after: bool,
+) -> (usize, usize) {
+    let (row, col) = pos;
+    if insert {
+        if pos < start || pos == start && !after {
+            pos
+        } else if row == start.0 {
+            (end.0, end.1 + col - start.1)
+        } else {
+            (row + end.0 - start.0, col)
+        }
+    } else if pos <= start {
+        pos
+    } else if pos < end {
+        start
+    } else if row == end.0 {
+        (start.0, start.1 + col - end.1)
+    } else {
+        (row + start.0 - end.0, col)
+    }
+}
+
+// Named positions set by `TextArea::set_mark`. The positions are moved along with the text when some text is inserted
+// or deleted before them
+#[derive(Clone, Debug, Default)]
+pub struct Marks {
+    marks: BTreeMap<char, (usize, usize)>,
+}
+
+impl Marks {
+    pub fn set(&mut self, name: char, pos: (usize, usize)) {
+        self.marks.insert(name, pos);
+    }
+
+    pub fn get(&self, name: char) -> Option<(usize, usize)> {
+        self.marks.get(&name).copied()
+    }
+
+    pub fn remove(&mut self, name: char) -> Option<(usize, usize)> {
+        self.marks.remove(&name)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (char, (usize, usize))> + '_ {
+        self.marks.iter().map(|(&name, &pos)| (name, pos))
+    }
+
+    // Move the marks across the edits. Edits must be given in the order they were applied. Text inserted at a mark is
+    // inserted before the mark, and a mark in deleted text moves to the start of the deleted text
+    pub fn apply<'e>(&mut self, edits: impl IntoIterator<Item = &'e Edit>) {
+        if self.marks.is_empty() {
+            return;
+        }
+        for edit in edits {
+            edit.for_each_text(&mut |insert, text, start| {
+                let end = text_end(start, text);
+                for pos in self.marks.values_mut() {
+                    *pos = move_pos(*pos, insert, start, end, true);
+                }
+            });
+        }
+    }
+
+    // Move the marks across the undo of the history entry. A mark which was collapsed into deleted text cannot be
+    // restored from the inverted edit, so the marks which were not moved since the edit are put back at the positions
+    // before the edit
+    pub fn undo(&mut self, entry: &Edit) {
+        if self.marks.is_empty() {
+            return;
+        }
+        let mut moved = Marks {
+            marks: entry.marks().iter().copied().collect(),
+        };
+        moved.apply([entry]);
+        let unmoved: Vec<_> = entry
+            .marks()
+            .iter()
+            .filter(|(name, _)| self.get(*name) == moved.get(*name))
+            .copied()
+            .collect();
+        self.apply([&entry.inverted()]);
+        for (name, pos) in unmoved {
+            self.set(name, pos);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::history::EditKind;
+    use crate::util::Pos;
+
+    #[test]
+    fn apply_edits() {
+        let mut marks = Marks::default();
+        marks.set('a', (0, 1));
+        marks.set('b', (1, 2));
+        marks.set('c', (3, 0));
+
+        // Insert "x\ny" at (0, 0)
+        let chunk = vec!["x".to_string(), "y".to_string()];
+        let edit = Edit::new(
+            EditKind::InsertChunk(chunk),
+            Pos::new(0, 0, 0),
+            Pos::new(1, 1, 1),
+        );
+        marks.apply([&edit]);
+        let have: Vec<_> = marks.iter().collect();
+        assert_eq!(have, [('a', (1, 2)), ('b', (2, 2)), ('c', (4, 0))]);
+
+        // Delete "\nab" at the end of the row 1
+        let chunk = vec!["".to_string(), "ab".to_string()];
+        let edit = Edit::new(
+            EditKind::DeleteChunk(chunk),
+            Pos::new(2, 0, 0),
+            Pos::new(1, 3, 3),
+        );
+        marks.apply([&edit]);
+        let have: Vec<_> = marks.iter().collect();
+        assert_eq!(have, [('a', (1, 2)), ('b', (1, 3)), ('c', (3, 0))]);
+
+        // Delete a character before the mark in the same row
+        let edit = Edit::new(
+            EditKind::DeleteChar('z'),
+            Pos::new(1, 1, 1),
+            Pos::new(1, 0, 0),
+        );
+        marks.apply([&edit]);
+        assert_eq!(marks.get('a'), Some((1, 1)));
+        assert_eq!(marks.get('b'), Some((1, 2)));
+
+        assert_eq!(marks.remove('a'), Some((1, 1)));
+        assert_eq!(marks.get('a'), None);
+    }
+}
@@ -0,0 +1,117 @@
+// A number in a line found by `find_number`. Columns are in characters and `end` is exclusive
+struct Number {
+    start: usize,
+    end: usize,
+    negative: bool,
+    hex: bool,
+    digits: String,
+}
+
+impl Number {
+    fn add(&self, delta: i64) -> Option<String> {
+        if self.hex {
+            // Hexadecimal numbers are unsigned and wrap around like Vim
+            let n = u64::from_str_radix(&self.digits, 16).ok()?;
+            let n = n.wrapping_add(delta as u64);
+            let upper = self.digits.chars().any(|c| c.is_ascii_uppercase());
+            let width = self.digits.len();
+            let digits = if upper {
+                format!("{:0width$X}", n, width = width)
+            } else {
+                format!("{:0width$x}", n, width = width)
+            };
+            return Some(format!("0x{}", digits));
+        }
+
+        let n: i128 = self.digits.parse().ok()?;
+        let n = if self.negative { -n } else { n } + delta as i128;
+        // Leading zeros are kept by padding the number to the same width
+        let width = if self.digits.len() > 1 && self.digits.starts_with('0') {
+            self.digits.len()
+        } else {
+            0
+        };
+        let sign = if n < 0 { "-" } else { "" };
+        Some(format!("{}{:0width$}", sign, n.abs(), width = width))
+    }
+}
+
+// Find the number under the cursor or the first number after the cursor in the line. A decimal number may have a
+// leading `-` and a hexadecimal number starts with `0x` or `0X`
+fn find_number(line: &str, col: usize) -> Option<Number> {
+    let chars: Vec<char> = line.chars().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let is_hex = c == '0'
+            && matches!(chars.get(i + 1), Some('x' | 'X'))
+            && chars.get(i + 2).is_some_and(char::is_ascii_hexdigit);
+        let (start, end, negative, digits_start) = if is_hex {
+            let end = (i + 2..chars.len())
+                .find(|&j| !chars[j].is_ascii_hexdigit())
+                .unwrap_or(chars.len());
+            (i, end, false, i + 2)
+        } else if c.is_ascii_digit() {
+            let end = (i..chars.len())
+                .find(|&j| !chars[j].is_ascii_digit())
+                .unwrap_or(chars.len());
+            let negative = i > 0 && chars[i - 1] == '-';
+            let start = if negative { i - 1 } else { i };
+            (start, end, negative, i)
+        } else {
+            i += 1;
+            continue;
+        };
+        if col < end {
+            let digits = chars[digits_start..end].iter().collect();
+            return Some(Number {
+                start,
+                end,
+                negative,
+                hex: is_hex,
+                digits,
+            });
+        }
+        i = end;
+    }
+    None
+}
+
+// Add `delta` to the number found at or after `col` in the line. Returns the start and end columns of the number and
+// the replacement text
+pub fn increment_number(line: &str, col: usize, delta: i64) -> Option<(usize, usize, String)> {
+    let number = find_number(line, col)?;
+    let text = number.add(delta)?;
+    Some((number.start, number.end, text))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn increment() {
+        for (line, col, delta, want) in [
+            ("42", 0, 1, Some((0, 2, "43"))),
+            ("42", 1, -50, Some((0, 2, "-8"))),
+            ("x = 9;", 0, 1, Some((4, 5, "10"))),
+            ("x = 9;", 5, 1, None),
+            ("a1 b2", 2, 1, Some((4, 5, "3"))),
+            ("-5", 0, 3, Some((0, 2, "-2"))),
+            ("x-5", 2, 10, Some((1, 3, "5"))),
+            ("007", 2, 1, Some((0, 3, "008"))),
+            ("010", 0, -11, Some((0, 3, "-001"))),
+            ("0xff", 0, 1, Some((0, 4, "0x100"))),
+            ("0x0F", 3, 1, Some((0, 4, "0x10"))),
+            ("0x00", 1, -1, Some((0, 4, "0xffffffffffffffff"))),
+            ("0x", 0, 1, Some((0, 1, "1"))),
+            ("あ1", 0, 1, Some((1, 2, "2"))),
+            ("", 0, 1, None),
+            ("99999999999999999999999999999999999999999", 0, 1, None),
+        ] {
+            let have = increment_number(line, col, delta);
+            let want = want.map(|(s, e, t)| (s, e, t.to_string()));
+            assert_eq!(have, want, "{:?} col={} delta={}", line, col, delta);
+        }
+    }
+}
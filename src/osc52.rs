@@ -0,0 +1,135 @@
+use std::env;
+use std::str;
+
+const BASE64_CHARS: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+// Many terminals (e.g. hterm, xterm, tmux) ignore OSC 52 sequences whose payload is longer than this
+pub const MAX_PAYLOAD_LEN: usize = 100_000;
+
+// GNU screen drops a DCS sequence longer than its buffer so the passthrough sequence is split into chunks
+const SCREEN_CHUNK_LEN: usize = 76;
+
+// How the sequence is sent to the outer terminal. Terminal multiplexers consume escape sequences, so the sequence is
+// wrapped in the DCS passthrough sequence of the multiplexer
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Passthrough {
+    Direct,
+    Tmux,
+    Screen,
+}
+
+impl Passthrough {
+    // Detect the terminal multiplexer from the environment variables
+    pub fn detect() -> Self {
+        if env::var_os("TMUX").is_some() {
+            Self::Tmux
+        } else if env::var("TERM").is_ok_and(|t| t.starts_with("screen")) {
+            Self::Screen
+        } else {
+            Self::Direct
+        }
+    }
+}
+
+fn encoded_len(len: usize) -> usize {
+    (len + 2) / 3 * 4
+}
+
+fn base64(input: &[u8]) -> String {
+    let mut out = String::with_capacity(encoded_len(input.len()));
+    for chunk in input.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                let idx = (n >> (18 - 6 * i)) & 0x3f;
+                out.push(BASE64_CHARS[idx as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+// Build the OSC 52 sequence to set the clipboard ('c' selection) to the text. `None` is returned when the payload
+// exceeds the length limit
+pub fn sequence(text: &str, passthrough: Passthrough) -> Option<String> {
+    if encoded_len(text.len()) > MAX_PAYLOAD_LEN {
+        return None;
+    }
+    let seq = format!("\x1b]52;c;{}\x07", base64(text.as_bytes()));
+    let seq = match passthrough {
+        Passthrough::Direct => seq,
+        // ESC in the sequence is doubled to pass through tmux
+        Passthrough::Tmux => format!("\x1bPtmux;{}\x1b\\", seq.replace('\x1b', "\x1b\x1b")),
+        Passthrough::Screen => {
+            let mut out = String::with_capacity(seq.len() + seq.len() / SCREEN_CHUNK_LEN * 4 + 4);
+            for chunk in seq.as_bytes().chunks(SCREEN_CHUNK_LEN) {
+                out.push_str("\x1bP");
+                out.push_str(str::from_utf8(chunk).unwrap()); // The sequence only contains ASCII characters
+                out.push_str("\x1b\\");
+            }
+            out
+        }
+    };
+    Some(seq)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_base64() {
+        for (input, want) in [
+            ("", ""),
+            ("f", "Zg=="),
+            ("fo", "Zm8="),
+            ("foo", "Zm9v"),
+            ("foob", "Zm9vYg=="),
+            ("fooba", "Zm9vYmE="),
+            ("foobar", "Zm9vYmFy"),
+            ("あ\n", "44GCCg=="),
+        ] {
+            assert_eq!(base64(input.as_bytes()), want, "input={:?}", input);
+        }
+    }
+
+    #[test]
+    fn osc52_sequence() {
+        let direct = Passthrough::Direct;
+        assert_eq!(sequence("foo", direct).unwrap(), "\x1b]52;c;Zm9v\x07");
+
+        let max = "a".repeat(MAX_PAYLOAD_LEN / 4 * 3);
+        assert_eq!(sequence(&max, direct).unwrap().len(), MAX_PAYLOAD_LEN + 8);
+        let too_long = "a".repeat(MAX_PAYLOAD_LEN / 4 * 3 + 1);
+        assert_eq!(sequence(&too_long, direct), None);
+    }
+
+    #[test]
+    fn osc52_passthrough() {
+        assert_eq!(
+            sequence("foo", Passthrough::Tmux).unwrap(),
+            "\x1bPtmux;\x1b\x1b]52;c;Zm9v\x07\x1b\\",
+        );
+        assert_eq!(
+            sequence("foo", Passthrough::Screen).unwrap(),
+            "\x1bP\x1b]52;c;Zm9v\x07\x1b\\",
+        );
+
+        // The sequence is split into chunks for screen
+        let text = "a".repeat(60);
+        let seq = sequence(&text, Passthrough::Screen).unwrap();
+        let chunks: Vec<_> = seq.split("\x1b\\").filter(|c| !c.is_empty()).collect();
+        assert_eq!(chunks.len(), 2);
+        assert!(chunks.iter().all(|c| c.starts_with("\x1bP")));
+        assert_eq!(chunks[0].len(), SCREEN_CHUNK_LEN + 2);
+        let joined: String = chunks.iter().map(|c| &c[2..]).collect();
+        assert_eq!(joined, sequence(&text, Passthrough::Direct).unwrap());
+    }
+}
@@ -0,0 +1,80 @@
+use crate::history::Edit;
+use std::error::Error;
+use std::fmt;
+use std::ops::Range;
+
+/// Error of an edit rejected since it modifies a protected row. See [`crate::TextArea::set_protected_rows`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ProtectedRowError {
+    row: usize,
+}
+
+impl ProtectedRowError {
+    /// The protected row which the rejected edit tried to modify. The row is 0-base.
+    pub fn row(&self) -> usize {
+        self.row
+    }
+}
+
+impl fmt::Display for ProtectedRowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "row {} is protected and cannot be edited", self.row + 1)
+    }
+}
+
+impl Error for ProtectedRowError {}
+
+#[derive(Clone, Debug, Default)]
+pub struct ProtectedRows {
+    ranges: Vec<Range<usize>>,
+}
+
+impl ProtectedRows {
+    pub fn new(ranges: Vec<Range<usize>>) -> Self {
+        let ranges = ranges.into_iter().filter(|r| !r.is_empty()).collect();
+        Self { ranges }
+    }
+
+    pub fn ranges(&self) -> &[Range<usize>] {
+        &self.ranges
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    pub fn contains(&self, row: usize) -> bool {
+        self.ranges.iter().any(|r| r.contains(&row))
+    }
+
+    // Returns the protected rows after applying the edits, or an error when some edit modifies a protected row. Edits
+    // must be given in the order they were applied
+    pub fn apply<'e>(
+        &self,
+        edits: impl IntoIterator<Item = &'e Edit>,
+    ) -> Result<Self, ProtectedRowError> {
+        let mut ranges = self.ranges.clone();
+        let mut result = Ok(());
+        for edit in edits {
+            edit.for_each_row_change(&mut |first, last, delta| {
+                if result.is_err() {
+                    return;
+                }
+                for range in ranges.iter_mut() {
+                    if first < range.end && range.start <= last {
+                        let row = first.max(range.start);
+                        result = Err(ProtectedRowError { row });
+                        return;
+                    }
+                    // Rows inserted or removed after the modified rows shift the ranges below them
+                    if last < range.start {
+                        range.start = (range.start as isize + delta) as usize;
+                        range.end = (range.end as isize + delta) as usize;
+                    }
+                }
+            });
+            result?;
+        }
+        Ok(Self { ranges })
+    }
+}
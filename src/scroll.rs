@@ -152,11 +152,114 @@ pub enum Scrolling {
     /// assert_eq!(textarea.cursor(), (11, 0));
     /// ```
     HalfPageUp,
+    /// Scroll the textarea so that the cursor is at the top of the viewport without moving the cursor, like
+    /// `zt` in Vim. The viewport does not go beyond the last line so the cursor is put below the top when it is near
+    /// the bottom of the text.
+    ///
+    /// ```
+    /// # use ratatui::buffer::Buffer;
+    /// # use ratatui::layout::Rect;
+    /// # use ratatui::widgets::Widget as _;
+    /// use tui_textarea::{TextArea, Scrolling, CursorMove};
+    ///
+    /// // Let's say terminal height is 8.
+    ///
+    /// // Create textarea with 20 lines "0", "1", "2", "3", ...
+    /// let mut textarea: TextArea = (0..20).into_iter().map(|i| i.to_string()).collect();
+    /// # // Call `render` at least once to populate terminal size
+    /// # let r = Rect { x: 0, y: 0, width: 24, height: 8 };
+    /// # let mut b = Buffer::empty(r.clone());
+    /// # textarea.render(r.clone(), &mut b);
+    ///
+    /// textarea.move_cursor(CursorMove::Jump(10, 0));
+    /// textarea.scroll(Scrolling::CursorTop);
+    /// # textarea.render(r.clone(), &mut b);
+    /// assert_eq!(textarea.cursor(), (10, 0));
+    /// assert_eq!(textarea.viewport_offset(), (10, 0));
+    ///
+    /// // The viewport does not go below the last line
+    /// textarea.move_cursor(CursorMove::Jump(18, 0));
+    /// textarea.scroll(Scrolling::CursorTop);
+    /// # textarea.render(r.clone(), &mut b);
+    /// assert_eq!(textarea.viewport_offset(), (12, 0));
+    /// ```
+    CursorTop,
+    /// Scroll the textarea so that the cursor is at the middle of the viewport without moving the cursor, like
+    /// `zz` in Vim. When the viewport has an even number of rows, the cursor is at the upper
+    /// one of the two middle rows. The textarea is scrolled as far as possible when the cursor is near the top or the
+    /// bottom of the text.
+    ///
+    /// ```
+    /// # use ratatui::buffer::Buffer;
+    /// # use ratatui::layout::Rect;
+    /// # use ratatui::widgets::Widget as _;
+    /// use tui_textarea::{TextArea, Scrolling, CursorMove};
+    ///
+    /// // Let's say terminal height is 8.
+    ///
+    /// // Create textarea with 20 lines "0", "1", "2", "3", ...
+    /// let mut textarea: TextArea = (0..20).into_iter().map(|i| i.to_string()).collect();
+    /// # // Call `render` at least once to populate terminal size
+    /// # let r = Rect { x: 0, y: 0, width: 24, height: 8 };
+    /// # let mut b = Buffer::empty(r.clone());
+    /// # textarea.render(r.clone(), &mut b);
+    ///
+    /// textarea.move_cursor(CursorMove::Jump(10, 0));
+    /// textarea.scroll(Scrolling::CursorCenter);
+    /// # textarea.render(r.clone(), &mut b);
+    /// assert_eq!(textarea.cursor(), (10, 0));
+    /// assert_eq!(textarea.viewport_offset(), (7, 0));
+    ///
+    /// // The viewport does not go above the first line
+    /// textarea.move_cursor(CursorMove::Jump(1, 0));
+    /// textarea.scroll(Scrolling::CursorCenter);
+    /// # textarea.render(r.clone(), &mut b);
+    /// assert_eq!(textarea.viewport_offset(), (0, 0));
+    ///
+    /// // The viewport does not go below the last line
+    /// textarea.move_cursor(CursorMove::Jump(18, 0));
+    /// textarea.scroll(Scrolling::CursorCenter);
+    /// # textarea.render(r.clone(), &mut b);
+    /// assert_eq!(textarea.viewport_offset(), (12, 0));
+    /// ```
+    CursorCenter,
+    /// Scroll the textarea so that the cursor is at the bottom of the viewport without moving the cursor, like
+    /// `zb` in Vim. The textarea is scrolled as far as possible when the cursor is near the
+    /// top of the text.
+    ///
+    /// ```
+    /// # use ratatui::buffer::Buffer;
+    /// # use ratatui::layout::Rect;
+    /// # use ratatui::widgets::Widget as _;
+    /// use tui_textarea::{TextArea, Scrolling, CursorMove};
+    ///
+    /// // Let's say terminal height is 8.
+    ///
+    /// // Create textarea with 20 lines "0", "1", "2", "3", ...
+    /// let mut textarea: TextArea = (0..20).into_iter().map(|i| i.to_string()).collect();
+    /// # // Call `render` at least once to populate terminal size
+    /// # let r = Rect { x: 0, y: 0, width: 24, height: 8 };
+    /// # let mut b = Buffer::empty(r.clone());
+    /// # textarea.render(r.clone(), &mut b);
+    ///
+    /// textarea.move_cursor(CursorMove::Jump(10, 0));
+    /// textarea.scroll(Scrolling::CursorBottom);
+    /// # textarea.render(r.clone(), &mut b);
+    /// assert_eq!(textarea.cursor(), (10, 0));
+    /// assert_eq!(textarea.viewport_offset(), (3, 0));
+    /// ```
+    CursorBottom,
 }
 
 impl Scrolling {
     pub(crate) fn scroll(self, viewport: &mut Viewport) {
-        let (rows, cols) = match self {
+        let (rows, cols) = self.delta(viewport);
+        viewport.scroll(rows, cols);
+    }
+
+    // Rows and columns to scroll the viewport by
+    pub(crate) fn delta(self, viewport: &Viewport) -> (i16, i16) {
+        match self {
             Self::Delta { rows, cols } => (rows, cols),
             Self::PageDown => {
                 let (_, _, _, height) = viewport.rect();
@@ -174,8 +277,22 @@ impl Scrolling {
                 let (_, _, _, height) = viewport.rect();
                 (-(height as i16) / 2, 0)
             }
-        };
-        viewport.scroll(rows, cols);
+            // These are handled by `TextArea` since text lines are necessary to compute the position
+            Self::CursorTop | Self::CursorCenter | Self::CursorBottom => (0, 0),
+        }
+    }
+
+    // Display row in the viewport where the cursor should be put by scrolling. `None` when the scrolling does not
+    // align the viewport to the cursor
+    pub(crate) fn cursor_offset(self, viewport: &Viewport) -> Option<usize> {
+        let (_, _, _, height) = viewport.rect();
+        let last = height.saturating_sub(1) as usize;
+        match self {
+            Self::CursorTop => Some(0),
+            Self::CursorCenter => Some(last / 2),
+            Self::CursorBottom => Some(last),
+            _ => None,
+        }
     }
 }
 
@@ -1,10 +1,260 @@
+use crate::history::Edit;
+use crate::marks::{move_pos, text_end};
 use crate::ratatui::style::{Color, Style};
-use regex::Regex;
+use crate::util::Pos;
+use regex::{Match, Regex, RegexBuilder};
+
+/// Case sensitivity of text search. See [`crate::TextArea::set_search_case`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum SearchCase {
+    /// Match letters case-sensitively. This is the default.
+    #[default]
+    Sensitive,
+    /// Match letters case-insensitively.
+    Insensitive,
+    /// Match letters case-insensitively unless the pattern contains an uppercase letter.
+    Smart,
+}
+
+/// Result of text search with [`crate::TextArea::search_forward_ex`] and [`crate::TextArea::search_back_ex`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SearchResult {
+    /// Some match was found and the cursor moved to it. `wrapped` is `true` when the search reached the end (or the
+    /// beginning on searching backward) of the text buffer and continued from the other end.
+    Found { wrapped: bool },
+    /// No match was found. The cursor did not move.
+    NotFound,
+}
+
+impl SearchResult {
+    /// Returns `true` when some match was found.
+    ///
+    /// ```
+    /// use tui_textarea::SearchResult;
+    ///
+    /// assert!(SearchResult::Found { wrapped: true }.is_found());
+    /// assert!(!SearchResult::NotFound.is_found());
+    /// ```
+    pub fn is_found(self) -> bool {
+        matches!(self, Self::Found { .. })
+    }
+}
+
+/// Range of text searched by text search. See [`crate::TextArea::set_search_scope`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum SearchScope {
+    /// Search the entire text buffer. This is the default.
+    #[default]
+    Buffer,
+    /// Search only the text selected when the scope was set.
+    Selection,
+}
+
+/// History of search queries. [`crate::TextArea`] records every query successfully set by
+/// [`crate::TextArea::set_search_pattern`] in its history. Consecutive identical queries are recorded only once and
+/// the oldest query is removed when the number of queries exceeds the maximum length.
+///
+/// The history remembers a position to recall queries one by one, which is useful to implement recalling the previous
+/// queries with up/down arrow keys in a search prompt.
+///
+/// ```
+/// use tui_textarea::SearchHistory;
+///
+/// let mut history = SearchHistory::new(2);
+/// history.push("foo");
+/// history.push("bar");
+/// history.push("bar");
+/// history.push("baz");
+/// assert_eq!(history.entries(), ["bar", "baz"]);
+///
+/// assert_eq!(history.recall_previous(), Some("baz"));
+/// assert_eq!(history.recall_previous(), Some("bar"));
+/// assert_eq!(history.recall_previous(), Some("bar")); // Stays at the oldest query
+/// assert_eq!(history.recall_next(), Some("baz"));
+/// assert_eq!(history.recall_next(), None); // Back to the new query
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SearchHistory {
+    entries: Vec<String>,
+    max_len: usize,
+    // Index of the entry last recalled. `None` means nothing is recalled
+    recalled: Option<usize>,
+}
+
+impl Default for SearchHistory {
+    /// Create a history remembering 50 queries.
+    fn default() -> Self {
+        Self::new(50)
+    }
+}
+
+impl SearchHistory {
+    /// Create an empty history remembering at most `max_len` queries. Setting 0 disables the history.
+    pub fn new(max_len: usize) -> Self {
+        Self {
+            entries: vec![],
+            max_len,
+            recalled: None,
+        }
+    }
+
+    /// Record the query as the newest entry. Empty queries and queries which are the same as the newest entry are not
+    /// recorded. This resets the position to recall queries unless the query is the one last recalled, so that setting
+    /// a recalled query as the search pattern does not interrupt recalling older queries.
+    pub fn push(&mut self, query: impl Into<String>) {
+        let query = query.into();
+        if let Some(i) = self.recalled {
+            if self.entries[i] == query {
+                return;
+            }
+        }
+        self.recalled = None;
+        if self.max_len == 0 || query.is_empty() || self.entries.last() == Some(&query) {
+            return;
+        }
+        if self.entries.len() >= self.max_len {
+            self.entries.drain(..self.entries.len() + 1 - self.max_len);
+        }
+        self.entries.push(query);
+    }
+
+    /// Recorded queries from the oldest to the newest.
+    pub fn entries(&self) -> &[String] {
+        &self.entries
+    }
+
+    /// Recall the query older than the one last recalled. The first call after recording a query returns the newest
+    /// query. When the oldest query was already recalled, it is returned again. `None` is returned when the history
+    /// is empty.
+    pub fn recall_previous(&mut self) -> Option<&str> {
+        let i = match self.recalled {
+            Some(i) => i.saturating_sub(1),
+            None => self.entries.len().checked_sub(1)?,
+        };
+        self.recalled = Some(i);
+        Some(&self.entries[i])
+    }
+
+    /// Recall the query newer than the one last recalled. `None` is returned when the newest query was last recalled
+    /// or nothing has been recalled yet, which means the user went back to a new query.
+    pub fn recall_next(&mut self) -> Option<&str> {
+        let i = self.recalled? + 1;
+        if i < self.entries.len() {
+            self.recalled = Some(i);
+            Some(&self.entries[i])
+        } else {
+            self.recalled = None;
+            None
+        }
+    }
+
+    /// Maximum number of queries remembered by the history.
+    pub fn max_len(&self) -> usize {
+        self.max_len
+    }
+
+    /// Set the maximum number of queries remembered by the history. Oldest queries are removed when the history has
+    /// more queries than `max_len`.
+    pub fn set_max_len(&mut self, max_len: usize) {
+        self.max_len = max_len;
+        if self.entries.len() > max_len {
+            self.entries.drain(..self.entries.len() - max_len);
+            self.recalled = None;
+        }
+    }
+}
+
+impl SearchCase {
+    fn ignores_case(self, query: &str) -> bool {
+        match self {
+            Self::Sensitive => false,
+            Self::Insensitive => true,
+            Self::Smart => !has_uppercase(query),
+        }
+    }
+}
+
+// Uppercase letters in escape sequences such as `\S` or `\p{Greek}` are not counted
+fn has_uppercase(query: &str) -> bool {
+    let mut chars = query.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            if let Some('p' | 'P') = chars.next() {
+                // Skip the name of Unicode class like `\pL` or `\p{Lu}`
+                if chars.next() == Some('{') {
+                    chars.find(|&c| c == '}');
+                }
+            }
+        } else if c.is_uppercase() {
+            return true;
+        }
+    }
+    false
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+// Whole word match requires word boundaries only on the sides of the match which are word characters. For example,
+// `->` matches in `a->b` while `in` doesn't match in `print`
+fn is_whole_word(line: &str, m: &Match<'_>) -> bool {
+    let text = m.as_str();
+    let (first, last) = match (text.chars().next(), text.chars().next_back()) {
+        (Some(first), Some(last)) => (first, last),
+        _ => return false, // Empty match is not a word
+    };
+    let before = line[..m.start()].chars().next_back();
+    let after = line[m.end()..].chars().next();
+    let left = !is_word_char(first) || !before.is_some_and(is_word_char);
+    let right = !is_word_char(last) || !after.is_some_and(is_word_char);
+    left && right
+}
+
+// Iterator of non-overlapping matches in a line. In whole word mode, matches which are not a whole word are skipped
+// and the next match is searched from the next character so that no candidate is hidden by the skipped matches
+pub enum Matches<'r, 'h> {
+    All(regex::Matches<'r, 'h>),
+    WholeWord {
+        pat: &'r Regex,
+        line: &'h str,
+        pos: usize,
+    },
+}
+
+impl<'r, 'h> Iterator for Matches<'r, 'h> {
+    type Item = Match<'h>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Matches::All(iter) => iter.next(),
+            Matches::WholeWord { pat, line, pos } => {
+                while *pos <= line.len() {
+                    let m = pat.find_at(line, *pos)?;
+                    if is_whole_word(line, &m) {
+                        *pos = m.end();
+                        return Some(m);
+                    }
+                    let next = line[m.start()..].chars().next().map_or(1, char::len_utf8);
+                    *pos = m.start() + next;
+                }
+                None
+            }
+        }
+    }
+}
 
 #[derive(Clone, Debug)]
 pub struct Search {
     pub pat: Option<Regex>,
     pub style: Style,
+    // Start position of the match which the cursor last jumped to
+    pub current: Option<(usize, usize)>,
+    pub current_style: Style,
+    case: SearchCase,
+    whole_word: bool,
+    // Start and end positions of the searched region. `None` means the entire buffer
+    region: Option<(Pos, Pos)>,
 }
 
 impl Default for Search {
@@ -12,6 +262,11 @@ impl Default for Search {
         Self {
             pat: None,
             style: Style::default().bg(Color::Blue),
+            current: None,
+            current_style: Style::default(),
+            case: SearchCase::default(),
+            whole_word: false,
+            region: None,
         }
     }
 }
@@ -22,55 +277,210 @@ impl Search {
         line: &'a str,
     ) -> Option<impl Iterator<Item = (usize, usize)> + 'a> {
         let pat = self.pat.as_ref()?;
-        let matches = pat.find_iter(line).map(|m| (m.start(), m.end()));
+        let matches = self.find_iter(pat, line).map(|m| (m.start(), m.end()));
         Some(matches)
     }
 
+    // Matches in the line at `row` which are contained in the searched region
+    pub fn row_matches<'a>(
+        &'a self,
+        row: usize,
+        line: &'a str,
+    ) -> Option<impl Iterator<Item = (usize, usize)> + 'a> {
+        let matches = self.matches(line)?;
+        Some(matches.filter(move |&(start, end)| self.in_region(row, start, end)))
+    }
+
+    // Check if the match between byte offsets `start` and `end` in the line at `row` is inside the searched region
+    pub fn in_region(&self, row: usize, start: usize, end: usize) -> bool {
+        match &self.region {
+            Some((s, e)) => (s.row, s.offset) <= (row, start) && (row, end) <= (e.row, e.offset),
+            None => true,
+        }
+    }
+
+    pub fn region(&self) -> Option<(Pos, Pos)> {
+        self.region
+    }
+
+    pub fn set_region(&mut self, region: Option<(Pos, Pos)>) {
+        self.region = region;
+    }
+
+    // Move the searched region across the edits so that it keeps covering the same text. Text inserted at the edges of
+    // the region is included in the region. `lines` is the text after the edits. The current match is cleared since
+    // the text at the position may no longer match
+    pub fn apply_edits(&mut self, edits: &[Edit], lines: &[String]) {
+        if edits.is_empty() {
+            return;
+        }
+        self.current = None;
+        let (start, end) = match &mut self.region {
+            Some(region) => region,
+            None => return,
+        };
+        let (mut s, mut e) = ((start.row, start.col), (end.row, end.col));
+        for edit in edits {
+            edit.for_each_text(&mut |insert, text, at| {
+                let to = text_end(at, text);
+                s = move_pos(s, insert, at, to, false);
+                e = move_pos(e, insert, at, to, true);
+            });
+        }
+        let offset = |(row, col): (usize, usize)| {
+            let line = &lines[row];
+            let offset = line.char_indices().nth(col).map(|(i, _)| i);
+            Pos::new(row, col, offset.unwrap_or(line.len()))
+        };
+        *start = offset(s);
+        *end = offset(e);
+    }
+
+    pub fn find_iter<'r, 'h>(&self, pat: &'r Regex, line: &'h str) -> Matches<'r, 'h> {
+        if self.whole_word {
+            Matches::WholeWord { pat, line, pos: 0 }
+        } else {
+            Matches::All(pat.find_iter(line))
+        }
+    }
+
+    // Find the first match starting at or after the byte offset `start`
+    fn find_at<'h>(&self, pat: &Regex, line: &'h str, start: usize) -> Option<Match<'h>> {
+        if self.whole_word {
+            let mut matches = Matches::WholeWord {
+                pat,
+                line,
+                pos: start,
+            };
+            matches.find(|m| m.start() >= start)
+        } else {
+            pat.find_at(line, start)
+        }
+    }
+
+    pub fn positions<'a>(
+        &'a self,
+        lines: &'a [String],
+    ) -> impl Iterator<Item = ((usize, usize), (usize, usize))> + 'a {
+        let rows = match &self.region {
+            Some((start, end)) => start.row.min(lines.len())..lines.len().min(end.row + 1),
+            None => 0..lines.len(),
+        };
+        lines[rows.clone()]
+            .iter()
+            .zip(rows)
+            .flat_map(move |(line, row)| {
+                self.row_matches(row, line)
+                    .into_iter()
+                    .flatten()
+                    .filter(|(start, end)| start != end)
+                    .map(move |(start, end)| {
+                        let start_col = line[..start].chars().count();
+                        let end_col = start_col + line[start..end].chars().count();
+                        ((row, start_col), (row, end_col))
+                    })
+            })
+    }
+
     pub fn set_pattern(&mut self, query: &str) -> Result<(), regex::Error> {
         match &self.pat {
-            Some(r) if r.as_str() == query => {}
+            Some(r) if r.as_str() == query => return Ok(()),
             _ if query.is_empty() => self.pat = None,
-            _ => self.pat = Some(Regex::new(query)?),
+            _ => self.pat = Some(self.build(query)?),
         }
+        self.current = None;
         Ok(())
     }
 
+    fn build(&self, query: &str) -> Result<Regex, regex::Error> {
+        RegexBuilder::new(query)
+            .case_insensitive(self.case.ignores_case(query))
+            .build()
+    }
+
+    // Recompile the current pattern since the options to build it changed
+    fn rebuild(&mut self) {
+        if let Some(pat) = &self.pat {
+            // Building the pattern never fails since it was already built successfully with the same query
+            if let Ok(pat) = self.build(pat.as_str()) {
+                self.pat = Some(pat);
+            }
+        }
+        self.current = None;
+    }
+
+    pub fn case(&self) -> SearchCase {
+        self.case
+    }
+
+    pub fn set_case(&mut self, case: SearchCase) {
+        if self.case != case {
+            self.case = case;
+            self.rebuild();
+        }
+    }
+
+    pub fn whole_word(&self) -> bool {
+        self.whole_word
+    }
+
+    pub fn set_whole_word(&mut self, enabled: bool) {
+        if self.whole_word != enabled {
+            self.whole_word = enabled;
+            self.current = None;
+        }
+    }
+
     pub fn forward(
         &mut self,
         lines: &[String],
         cursor: (usize, usize),
         match_cursor: bool,
-    ) -> Option<(usize, usize)> {
+        wrap: bool,
+    ) -> Option<((usize, usize), bool)> {
         let pat = if let Some(pat) = &self.pat {
             pat
         } else {
             return None;
         };
+        if self.region.is_some() {
+            let mut positions = self.positions(lines).map(|(start, _)| start);
+            let next = positions.find(|&p| p > cursor || match_cursor && p == cursor);
+            return match next {
+                Some(pos) => Some((pos, false)),
+                None if wrap => self.positions(lines).next().map(|(p, _)| (p, true)),
+                None => None,
+            };
+        }
         let (row, col) = cursor;
         let current_line = &lines[row];
 
         // Search current line after cursor
         let start_col = if match_cursor { col } else { col + 1 };
         if let Some((i, _)) = current_line.char_indices().nth(start_col) {
-            if let Some(m) = pat.find_at(current_line, i) {
+            if let Some(m) = self.find_at(pat, current_line, i) {
                 let col = start_col + current_line[i..m.start()].chars().count();
-                return Some((row, col));
+                return Some(((row, col), false));
             }
         }
 
         // Search lines after cursor
         for (i, line) in lines[row + 1..].iter().enumerate() {
-            if let Some(m) = pat.find(line) {
+            if let Some(m) = self.find_at(pat, line, 0) {
                 let col = line[..m.start()].chars().count();
-                return Some((row + 1 + i, col));
+                return Some(((row + 1 + i, col), false));
             }
         }
 
+        if !wrap {
+            return None;
+        }
+
         // Search lines before cursor (wrap)
         for (i, line) in lines[..row].iter().enumerate() {
-            if let Some(m) = pat.find(line) {
+            if let Some(m) = self.find_at(pat, line, 0) {
                 let col = line[..m.start()].chars().count();
-                return Some((i, col));
+                return Some(((i, col), true));
             }
         }
 
@@ -80,11 +490,11 @@ impl Search {
             .nth(col)
             .map(|(i, _)| i)
             .unwrap_or(current_line.len());
-        if let Some(m) = pat.find(current_line) {
+        if let Some(m) = self.find_at(pat, current_line, 0) {
             let i = m.start();
             if i <= col_idx {
                 let col = current_line[..i].chars().count();
-                return Some((row, col));
+                return Some(((row, col), true));
             }
         }
 
@@ -96,55 +506,76 @@ impl Search {
         lines: &[String],
         cursor: (usize, usize),
         match_cursor: bool,
-    ) -> Option<(usize, usize)> {
+        wrap: bool,
+    ) -> Option<((usize, usize), bool)> {
         let pat = if let Some(pat) = &self.pat {
             pat
         } else {
             return None;
         };
+        if self.region.is_some() {
+            let prev = self
+                .positions(lines)
+                .map(|(start, _)| start)
+                .take_while(|&p| p < cursor || match_cursor && p == cursor)
+                .last();
+            return match prev {
+                Some(pos) => Some((pos, false)),
+                None if wrap => self.positions(lines).last().map(|(p, _)| (p, true)),
+                None => None,
+            };
+        }
         let (row, col) = cursor;
         let current_line = &lines[row];
 
         // Search current line before cursor
         if col > 0 || match_cursor {
             let start_col = if match_cursor { col } else { col - 1 };
-            if let Some((i, _)) = current_line.char_indices().nth(start_col) {
-                if let Some(m) = pat
-                    .find_iter(current_line)
-                    .take_while(|m| m.start() <= i)
-                    .last()
-                {
-                    let col = current_line[..m.start()].chars().count();
-                    return Some((row, col));
-                }
+            // The cursor can be at the end of line
+            let i = current_line
+                .char_indices()
+                .nth(start_col)
+                .map(|(i, _)| i)
+                .unwrap_or(current_line.len());
+            if let Some(m) = self
+                .find_iter(pat, current_line)
+                .take_while(|m| m.start() <= i)
+                .last()
+            {
+                let col = current_line[..m.start()].chars().count();
+                return Some(((row, col), false));
             }
         }
 
         // Search lines before cursor
         for (i, line) in lines[..row].iter().enumerate().rev() {
-            if let Some(m) = pat.find_iter(line).last() {
+            if let Some(m) = self.find_iter(pat, line).last() {
                 let col = line[..m.start()].chars().count();
-                return Some((i, col));
+                return Some(((i, col), false));
             }
         }
 
+        if !wrap {
+            return None;
+        }
+
         // Search lines after cursor (wrap)
         for (i, line) in lines[row + 1..].iter().enumerate().rev() {
-            if let Some(m) = pat.find_iter(line).last() {
+            if let Some(m) = self.find_iter(pat, line).last() {
                 let col = line[..m.start()].chars().count();
-                return Some((row + 1 + i, col));
+                return Some(((row + 1 + i, col), true));
             }
         }
 
         // Search current line after cursor
         if let Some((i, _)) = current_line.char_indices().nth(col) {
-            if let Some(m) = pat
-                .find_iter(current_line)
+            if let Some(m) = self
+                .find_iter(pat, current_line)
                 .skip_while(|m| m.start() < i)
                 .last()
             {
                 let col = col + current_line[i..m.start()].chars().count();
-                return Some((row, col));
+                return Some(((row, col), true));
             }
         }
 
@@ -167,4 +598,61 @@ mod tests {
         s.set_pattern("").unwrap();
         assert!(s.matches("fo foo bar fooo").is_none());
     }
+
+    #[test]
+    fn has_uppercase() {
+        for (query, want) in [
+            ("", false),
+            ("foo", false),
+            ("Foo", true),
+            ("fOO", true),
+            (r"\S+", false),
+            (r"\W\D\B", false),
+            (r"\p{Greek}", false),
+            (r"\P{Lu}x", false),
+            (r"\p{Lu}X", true),
+            (r"\pLx", false),
+            (r"\\S", true),
+            ("ÄÖ", true),
+        ] {
+            assert_eq!(super::has_uppercase(query), want, "{query:?}");
+        }
+    }
+
+    #[test]
+    fn search_history() {
+        let mut h = SearchHistory::new(3);
+        assert_eq!(h.recall_previous(), None);
+        assert_eq!(h.recall_next(), None);
+
+        for q in ["a", "", "b", "b", "a", "c"] {
+            h.push(q);
+        }
+        assert_eq!(h.entries(), ["b", "a", "c"]);
+
+        for want in ["c", "a", "b", "b"] {
+            assert_eq!(h.recall_previous(), Some(want));
+        }
+        assert_eq!(h.recall_next(), Some("a"));
+
+        // Pushing the recalled query keeps the recall position
+        h.push("a");
+        assert_eq!(h.entries(), ["b", "a", "c"]);
+        assert_eq!(h.recall_previous(), Some("b"));
+
+        // Pushing a query resets the recall position
+        h.push("d");
+        assert_eq!(h.entries(), ["a", "c", "d"]);
+        assert_eq!(h.recall_next(), None);
+        assert_eq!(h.recall_previous(), Some("d"));
+
+        // Shrinking the history removes the oldest queries
+        h.set_max_len(1);
+        assert_eq!(h.entries(), ["d"]);
+        assert_eq!(h.recall_previous(), Some("d"));
+
+        h.set_max_len(0);
+        h.push("e");
+        assert!(h.entries().is_empty());
+    }
 }
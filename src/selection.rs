@@ -0,0 +1,43 @@
+#[cfg(feature = "arbitrary")]
+use arbitrary::Arbitrary;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Mode of text selection. See [`crate::TextArea::selection_mode`].
+///
+/// This type is marked as `#[non_exhaustive]` since more variations may be supported in the future.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SelectionMode {
+    /// Character-wise selection started by [`crate::TextArea::start_selection`]. The text between the selection
+    /// start position and the cursor is selected.
+    #[default]
+    Char,
+    /// Line-wise selection started by [`crate::TextArea::start_line_selection`]. The complete lines between the
+    /// selection start row and the cursor row are selected regardless of the cursor column.
+    Line,
+    /// Rectangular selection started by [`crate::TextArea::start_block_selection`]. The columns between the
+    /// selection start column and the cursor column are selected on each line between the selection start row and
+    /// the cursor row.
+    Block,
+}
+
+/// Unit of text to expand the selection to. See [`crate::TextArea::expand_selection`].
+///
+/// This type is marked as `#[non_exhaustive]` since more variations may be supported in the future.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SelectionUnit {
+    /// The word around the selection. Word boundaries are the same as [`crate::CursorMove::WordForward`].
+    Word,
+    /// The lines covered by the selection, excluding the newline at the end of the last line.
+    Line,
+    /// The paragraphs covered by the selection. Paragraphs are blocks of lines separated by empty lines.
+    Paragraph,
+    /// The entire text.
+    Buffer,
+}
@@ -0,0 +1,51 @@
+#[cfg(feature = "arbitrary")]
+use arbitrary::Arbitrary;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Order of sorting lines. Lines are compared as plain `str` values, which means byte-wise order of UTF-8 and is
+/// case-sensitive. See [`crate::TextArea::sort_lines`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SortOrder {
+    /// Sort lines from the smallest to the largest.
+    Ascending,
+    /// Sort lines from the largest to the smallest.
+    Descending,
+}
+
+impl SortOrder {
+    // The sort is stable. Adjacent duplicates are removed after sorting when `unique` is true
+    pub(crate) fn sort(self, lines: &mut Vec<String>, unique: bool) {
+        match self {
+            SortOrder::Ascending => lines.sort(),
+            SortOrder::Descending => lines.sort_by(|a, b| b.cmp(a)),
+        }
+        if unique {
+            lines.dedup();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sort_lines() {
+        for (order, unique, want) in [
+            (SortOrder::Ascending, false, &["", "B", "a", "a", "b"][..]),
+            (SortOrder::Ascending, true, &["", "B", "a", "b"][..]),
+            (SortOrder::Descending, false, &["b", "a", "a", "B", ""][..]),
+            (SortOrder::Descending, true, &["b", "a", "B", ""][..]),
+        ] {
+            let mut lines: Vec<String> = ["a", "", "b", "B", "a"]
+                .iter()
+                .map(|s| s.to_string())
+                .collect();
+            order.sort(&mut lines, unique);
+            assert_eq!(lines, want, "{:?} unique={}", order, unique);
+        }
+    }
+}
@@ -1,29 +1,89 @@
-use crate::cursor::CursorMove;
-use crate::history::{Edit, EditKind, History};
+use crate::case::Case;
+#[cfg(feature = "clipboard")]
+use crate::clipboard::{self, ClipboardError};
+use crate::cursor::{find_bracket_pair, is_bracket, CursorMove, CursorRendering};
+use crate::gutter::{LineNumberContext, LineNumberFormatter, Sign, Signs, SIGN_WIDTH};
+use crate::highlight::{DisplayTextBuilder, LineHighlighter, WhitespaceRender};
 use crate::highlighting::{syntect_style_to_ratatui, SyntaxHighlighter};
+use crate::history::{
+    ChangeOp, Coalescing, Edit, EditInfo, EditKind, History, HistoryEntry, UndoCursor,
+};
 use crate::input::{Input, Key};
-use crate::ratatui::layout::Alignment;
+use crate::list::ListContinuation;
+use crate::marks::{move_pos, text_end, Marks};
+use crate::number::increment_number;
+use crate::osc52;
+use crate::protected::{ProtectedRowError, ProtectedRows};
+use crate::ratatui::layout::{Alignment, Rect};
 use crate::ratatui::style::{Color, Modifier, Style};
-use crate::ratatui::text::{Line, Span};
+use crate::ratatui::text::{Line, Span, Text};
 use crate::ratatui::widgets::{Block, Widget};
 use crate::scroll::Scrolling;
 #[cfg(feature = "search")]
-use crate::search::Search;
-#[cfg(feature = "search")]
-use regex;
-use crate::util::{spaces, Pos};
+use crate::search::{Search, SearchCase, SearchHistory, SearchResult, SearchScope};
+use crate::selection::{SelectionMode, SelectionUnit};
+use crate::sort::SortOrder;
+use crate::textobject::TextObject;
+use crate::util::{cell_width, char_width, num_digits, spaces, split_lines, Pos};
 use crate::widget::Viewport;
-use crate::word::{find_word_exclusive_end_forward, find_word_start_backward};
-use std::cmp::Ordering;
+use crate::word::{
+    find_big_word_exclusive_end_forward, find_big_word_start_backward, find_word_at,
+    find_word_exclusive_end_forward, find_word_start_backward,
+};
+use crate::wrap::Wrap;
+use crate::yank::YankKind;
+use std::borrow::Cow;
+use std::cmp::{self, Ordering, Reverse};
+use std::collections::{BTreeMap, HashMap, VecDeque};
 use std::fmt;
-use std::iter::repeat;
+use std::io;
+use std::iter;
+use std::mem;
+use std::ops::{ControlFlow, Range};
+use std::sync::atomic::{AtomicUsize, Ordering as AtomicOrdering};
 use syntect::easy::HighlightLines;
-use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+use unicode_width::UnicodeWidthStr as _;
+
+// Selection start position, cursor position, and selection mode
+type SelectionState = (Option<(usize, usize)>, (usize, usize), SelectionMode);
+
+// Length of the text in bytes with the change counter of the history when it was calculated. The text is not modified
+// while the change counter stays the same. Atomics are used since `TextArea::byte_len` takes `&self` and the textarea
+// should be `Sync`. The length is stored with +1 so that 0 means it is not calculated yet
+#[derive(Debug, Default)]
+struct ByteLen {
+    changes: AtomicUsize,
+    len: AtomicUsize,
+}
+
+impl Clone for ByteLen {
+    fn clone(&self) -> Self {
+        Self {
+            changes: AtomicUsize::new(self.changes.load(AtomicOrdering::Relaxed)),
+            len: AtomicUsize::new(self.len.load(AtomicOrdering::Relaxed)),
+        }
+    }
+}
+
+impl ByteLen {
+    fn get(&self, changes: usize, calc: impl FnOnce() -> usize) -> usize {
+        let len = self.len.load(AtomicOrdering::Relaxed);
+        if len > 0 && self.changes.load(AtomicOrdering::Relaxed) == changes {
+            return len - 1;
+        }
+        let len = calc();
+        self.changes.store(changes, AtomicOrdering::Relaxed);
+        self.len.store(len + 1, AtomicOrdering::Relaxed);
+        len
+    }
+}
 
 #[derive(Debug, Clone)]
 enum YankText {
     Piece(String),
     Chunk(Vec<String>),
+    Lines(Vec<String>),
+    Block(Vec<String>),
 }
 
 impl Default for YankText {
@@ -47,12 +107,168 @@ impl From<Vec<String>> for YankText {
     }
 }
 
+impl YankText {
+    // `\r\n` and `\r` are also recognized as newline when `normalize` is `true`. See `util::split_lines`
+    fn from_text(text: &str, normalize: bool) -> Self {
+        split_lines(text, normalize).into()
+    }
+
+    fn kind(&self) -> YankKind {
+        match self {
+            Self::Piece(_) | Self::Chunk(_) => YankKind::Char,
+            Self::Lines(_) => YankKind::Line,
+            Self::Block(_) => YankKind::Block,
+        }
+    }
+
+    // Lines of the text. The empty line after the last newline of character-wise text is not included
+    fn into_lines(self) -> Vec<String> {
+        match self {
+            Self::Piece(s) => vec![s],
+            Self::Chunk(mut ss) => {
+                if ss.last().is_some_and(String::is_empty) {
+                    ss.pop();
+                }
+                ss
+            }
+            Self::Lines(ss) | Self::Block(ss) => ss,
+        }
+    }
+
+    // Repeat the text `count` times. Rows of block-wise text are padded to the same width so that the copies are put
+    // side by side
+    fn repeat(&self, count: usize) -> Self {
+        match self {
+            Self::Piece(_) | Self::Chunk(_) => {
+                Self::from_text(&self.to_string().repeat(count), false)
+            }
+            Self::Lines(ss) => {
+                Self::Lines(iter::repeat(ss).take(count).flatten().cloned().collect())
+            }
+            Self::Block(ss) => {
+                let width = ss.iter().map(|s| s.chars().count()).max().unwrap_or(0);
+                let rows = ss
+                    .iter()
+                    .map(|s| {
+                        let pad = " ".repeat(width - s.chars().count());
+                        let mut row = format!("{}{}", s, pad).repeat(count - 1);
+                        row.push_str(s);
+                        row
+                    })
+                    .collect();
+                Self::Block(rows)
+            }
+        }
+    }
+
+    // Concatenate the other text. The result is line-wise when either of them is line-wise
+    fn append(self, other: Self) -> Self {
+        if self.to_string().is_empty() {
+            return other;
+        }
+        match (self, other) {
+            (Self::Block(mut a), Self::Block(b)) => {
+                a.extend(b);
+                Self::Block(a)
+            }
+            (a, b) if a.kind() == YankKind::Line || b.kind() == YankKind::Line => {
+                let mut lines = a.into_lines();
+                lines.extend(b.into_lines());
+                Self::Lines(lines)
+            }
+            (a, b) => Self::from_text(&format!("{}{}", a, b), false),
+        }
+    }
+}
+
 impl fmt::Display for YankText {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::Piece(s) => write!(f, "{}", s),
-            Self::Chunk(ss) => write!(f, "{}", ss.join("\n")),
+            Self::Chunk(ss) | Self::Block(ss) => write!(f, "{}", ss.join("\n")),
+            Self::Lines(ss) => writeln!(f, "{}", ss.join("\n")),
+        }
+    }
+}
+
+// Texts yanked previously for `TextArea::paste_previous_yank`. The newest text is at the front
+#[derive(Debug, Clone, Default)]
+struct YankHistory {
+    entries: VecDeque<YankText>,
+    max_len: usize,
+    // Index of the entry which is the current yank text
+    index: usize,
+}
+
+impl YankHistory {
+    fn push(&mut self, yank: &YankText) {
+        self.index = 0;
+        if self.max_len == 0 || yank.to_string().is_empty() {
+            return;
+        }
+        self.entries.push_front(yank.clone());
+        self.entries.truncate(self.max_len);
+    }
+}
+
+// Cursor moves by more lines than this in one operation are remembered in the jump list
+const JUMP_MIN_LINES: usize = 1;
+
+// Positions before big cursor jumps for `TextArea::jump_back` and `TextArea::jump_forward`. The oldest position is
+// at the front
+#[derive(Debug, Clone)]
+struct JumpList {
+    entries: Vec<(usize, usize)>,
+    max_len: usize,
+    // Index of the entry the cursor jumped to by walking the list. Equals to the length when not walking the list
+    index: usize,
+}
+
+impl Default for JumpList {
+    fn default() -> Self {
+        Self {
+            entries: vec![],
+            max_len: 100,
+            index: 0,
+        }
+    }
+}
+
+impl JumpList {
+    fn push(&mut self, pos: (usize, usize)) {
+        // Jumping while walking the list discards the positions after the current one
+        self.entries.truncate(self.index);
+        if self.entries.last() != Some(&pos) {
+            self.entries.push(pos);
+        }
+        if self.entries.len() > self.max_len {
+            self.entries.remove(0);
+        }
+        self.index = self.entries.len();
+    }
+
+    fn back(&mut self, cursor: (usize, usize)) -> Option<(usize, usize)> {
+        if self.index == 0 {
+            return None;
+        }
+        if self.index == self.entries.len() {
+            // Remember the current position to come back by `forward`
+            self.push(cursor);
+            self.index = self.entries.len() - 1;
+            if self.index == 0 {
+                return None;
+            }
         }
+        self.index -= 1;
+        Some(self.entries[self.index])
+    }
+
+    fn forward(&mut self) -> Option<(usize, usize)> {
+        if self.index + 1 >= self.entries.len() {
+            return None;
+        }
+        self.index += 1;
+        Some(self.entries[self.index])
     }
 }
 
@@ -112,23 +328,79 @@ pub struct TextArea<'a> {
     cursor: (usize, usize), // 0-base
     tab_len: u8,
     hard_tab_indent: bool,
+    indent_empty_lines: bool,
+    auto_indent: bool,
+    auto_pairs: bool,
+    auto_pair_chars: Vec<(char, char)>,
+    // Positions of the opening characters of the pairs inserted by auto pairs. Only these pairs are deleted at once
+    auto_pair_opens: Vec<(usize, usize)>,
+    abbreviations: HashMap<String, String>,
+    normalize_newlines: bool,
+    block_insert_padding: bool,
+    comment_prefix: String,
     history: History,
     cursor_line_style: Style,
+    cursor_line_fill: bool,
     line_number_style: Option<Style>,
+    line_number_formatter: Option<LineNumberFormatter>,
+    signs: Signs,
+    sign_column: bool,
+    trailing_whitespace_style: Option<Style>,
+    trailing_whitespace_at_cursor: bool,
+    control_char_style: Style,
+    matching_bracket_style: Option<Style>,
+    unmatched_bracket_style: Option<Style>,
+    bracket_match_distance: usize,
+    show_whitespace: Option<WhitespaceRender>,
     pub(crate) viewport: Viewport,
+    byte_len: ByteLen,
     pub(crate) cursor_style: Style,
+    cursor_rendering: CursorRendering,
     yank: YankText,
+    yank_history: YankHistory,
+    registers: BTreeMap<char, YankText>,
+    // Cursor position and the number of text changes just after the last paste
+    last_paste: Option<((usize, usize), usize)>,
+    last_find: Option<CursorMove>,
+    jumps: JumpList,
+    marks: Marks,
+    // Display column the cursor tries to keep on vertical moves, and the cursor position where it is valid
+    desired_col: Option<(usize, (usize, usize))>,
     #[cfg(feature = "search")]
     search: Search,
+    #[cfg(feature = "search")]
+    search_history: SearchHistory,
     alignment: Alignment,
+    wrap: bool,
+    move_by_display_rows: bool,
+    scroll_margin: u16,
+    wrap_indicator: Option<(String, Style)>,
+    wrap_hanging_indent: bool,
+    overflow_indicators: (Option<char>, Option<char>),
+    overflow_indicator_style: Style,
+    color_columns: Vec<(u16, Style)>,
+    ghost_text: Option<(String, Style)>,
+    virtual_texts: BTreeMap<usize, (String, Style)>,
+    end_of_buffer_char: Option<(char, Style)>,
     pub(crate) placeholder: String,
     pub(crate) placeholder_style: Style,
     mask: Option<char>,
     selection_start: Option<(usize, usize)>,
+    selection_mode: SelectionMode,
+    // Selection states before and after each expansion by `TextArea::expand_selection`
+    selection_expansions: Vec<(SelectionState, SelectionState)>,
     select_style: Style,
+    inactive_select_style: Style,
+    focused: bool,
+    unfocused_cursor_style: Option<Style>,
     syntax_highlighter: Option<SyntaxHighlighter>,
     syntax_name: Option<String>,
     theme_name: Option<String>,
+    list_continuation: Option<ListContinuation>,
+    protected_rows: ProtectedRows,
+    protected_row_style: Style,
+    protected_row_error: Option<ProtectedRowError>,
+    guarding: bool,
 }
 
 /// Convert any iterator whose elements can be converted into [`String`] into [`TextArea`]. Each [`String`] element is
@@ -220,26 +492,109 @@ impl<'a> TextArea<'a> {
             cursor: (0, 0),
             tab_len: 4,
             hard_tab_indent: false,
+            indent_empty_lines: false,
+            auto_indent: false,
+            auto_pairs: false,
+            auto_pair_chars: vec![
+                ('(', ')'),
+                ('[', ']'),
+                ('{', '}'),
+                ('"', '"'),
+                ('\'', '\''),
+                ('`', '`'),
+            ],
+            auto_pair_opens: vec![],
+            abbreviations: HashMap::new(),
+            normalize_newlines: true,
+            block_insert_padding: false,
+            comment_prefix: String::new(),
             history: History::new(50),
             cursor_line_style: Style::default().add_modifier(Modifier::UNDERLINED),
+            cursor_line_fill: false,
             line_number_style: None,
+            line_number_formatter: None,
+            signs: Signs::default(),
+            sign_column: false,
+            trailing_whitespace_style: None,
+            trailing_whitespace_at_cursor: false,
+            control_char_style: Style::default().fg(Color::DarkGray),
+            matching_bracket_style: None,
+            unmatched_bracket_style: None,
+            bracket_match_distance: 1000,
+            show_whitespace: None,
             viewport: Viewport::default(),
+            byte_len: ByteLen::default(),
             cursor_style: Style::default().add_modifier(Modifier::REVERSED),
+            cursor_rendering: CursorRendering::default(),
             yank: YankText::default(),
+            yank_history: YankHistory::default(),
+            registers: BTreeMap::new(),
+            last_paste: None,
+            last_find: None,
+            jumps: JumpList::default(),
+            marks: Marks::default(),
+            desired_col: None,
             #[cfg(feature = "search")]
             search: Search::default(),
+            #[cfg(feature = "search")]
+            search_history: SearchHistory::default(),
             alignment: Alignment::Left,
+            wrap: false,
+            move_by_display_rows: true,
+            scroll_margin: 0,
+            wrap_indicator: None,
+            wrap_hanging_indent: false,
+            overflow_indicators: (None, None),
+            overflow_indicator_style: Style::default(),
+            color_columns: vec![],
+            ghost_text: None,
+            virtual_texts: BTreeMap::new(),
+            end_of_buffer_char: None,
             placeholder: String::new(),
             placeholder_style: Style::default().fg(Color::DarkGray),
             mask: None,
             selection_start: None,
+            selection_mode: SelectionMode::default(),
+            selection_expansions: vec![],
             select_style: Style::default().bg(Color::LightBlue),
+            inactive_select_style: Style::default().bg(Color::DarkGray),
+            focused: true,
+            unfocused_cursor_style: None,
             syntax_highlighter: None,
             syntax_name: None,
             theme_name: None,
+            list_continuation: None,
+            protected_rows: ProtectedRows::default(),
+            protected_row_style: Style::default(),
+            protected_row_error: None,
+            guarding: false,
         }
     }
 
+    /// Create [`TextArea`] instance from the contents of [`Text`]. Styles of the text are discarded and contents of spans
+    /// in each line are concatenated into a line of the textarea. This is the inverse of [`TextArea::to_styled_text`]
+    /// except for tab expansion.
+    /// ```
+    /// use ratatui::style::{Style, Color};
+    /// use ratatui::text::{Line, Span, Text};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let text = Text::from(vec![
+    ///     Line::from(vec![Span::raw("hello, "), Span::styled("world", Style::default().fg(Color::Red))]),
+    ///     Line::from("goodbye"),
+    /// ]);
+    /// let textarea = TextArea::from_text(text);
+    /// assert_eq!(textarea.lines(), ["hello, world", "goodbye"]);
+    /// ```
+    pub fn from_text(text: Text<'_>) -> Self {
+        let lines = text
+            .lines
+            .iter()
+            .map(|line| line.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect();
+        Self::new(lines)
+    }
+
     /// Handle a key input with default key mappings. For default key mappings, see the table in
     /// [the module document](./index.html).
     /// `crossterm`, `termion`, and `termwiz` features enable conversion from their own key event types into
@@ -278,6 +633,11 @@ impl<'a> TextArea<'a> {
     /// ```
     pub fn input(&mut self, input: impl Into<Input>) -> bool {
         let input = input.into();
+        if self.needs_guard() {
+            #[cfg(feature = "tracing")]
+            let _span = tracing::debug_span!("input", ?input).entered();
+            return self.guard(false, |t| t.input(input));
+        }
         let modified = match input {
             Input {
                 key: Key::Char('m'),
@@ -306,6 +666,18 @@ impl<'a> TextArea<'a> {
                 self.insert_char(c);
                 true
             }
+            Input {
+                key: Key::Tab,
+                ctrl: false,
+                alt: false,
+                shift: true,
+            } => self.dedent_selection(),
+            Input {
+                key: Key::Tab,
+                ctrl: false,
+                alt: false,
+                ..
+            } if self.selection_start.is_some() => self.indent_selection(),
             Input {
                 key: Key::Tab,
                 ctrl: false,
@@ -378,6 +750,24 @@ impl<'a> TextArea<'a> {
                 alt: true,
                 ..
             } => self.delete_next_word(),
+            Input {
+                key: Key::Char('t'),
+                ctrl: true,
+                alt: false,
+                ..
+            } => self.transpose_chars(),
+            Input {
+                key: Key::Up,
+                ctrl: false,
+                alt: true,
+                ..
+            } => self.move_lines_up(),
+            Input {
+                key: Key::Down,
+                ctrl: false,
+                alt: true,
+                ..
+            } => self.move_lines_down(),
             Input {
                 key: Key::Char('n'),
                 ctrl: true,
@@ -684,6 +1074,7 @@ impl<'a> TextArea<'a> {
             input,
         );
 
+        trace!(modified, "input handled");
         modified
     }
 
@@ -700,6 +1091,12 @@ impl<'a> TextArea<'a> {
     /// This method is useful when you want to define your own key mappings and don't want default key mappings.
     /// See 'Define your own key mappings' section in [the module document](./index.html).
     pub fn input_without_shortcuts(&mut self, input: impl Into<Input>) -> bool {
+        if self.needs_guard() {
+            let input = input.into();
+            #[cfg(feature = "tracing")]
+            let _span = tracing::debug_span!("input_without_shortcuts", ?input).entered();
+            return self.guard(false, |t| t.input_without_shortcuts(input));
+        }
         match input.into() {
             Input {
                 key: Key::Char(c),
@@ -747,13 +1144,137 @@ impl<'a> TextArea<'a> {
         }
     }
 
+    // Edits called from public methods need to run through `guard` to record the cursor positions and the selection in
+    // the history entry, and to check protected rows
+    fn needs_guard(&self) -> bool {
+        !self.guarding
+    }
+
+    // Run the edit by `f` as one history entry and revert it when it modified some protected row. `rejected` is returned
+    // on the revert. The cursor positions before and after the edit and the ongoing selection are recorded in the
+    // history entry to restore them on undo and redo
+    fn guard<T>(&mut self, rejected: T, f: impl FnOnce(&mut Self) -> T) -> T {
+        let (cursor, selection_start, selection_mode) =
+            (self.cursor, self.selection_start, self.selection_mode);
+        // Yanked text is only restored when the edit is reverted
+        let yank = if self.protected_rows.is_empty() {
+            None
+        } else {
+            Some((
+                self.yank.clone(),
+                self.yank_history.clone(),
+                self.registers.clone(),
+            ))
+        };
+        self.guarding = true;
+        self.protected_row_error = None;
+        self.history.set_mark_positions(self.marks.iter().collect());
+        match selection_start {
+            Some(start) => self
+                .history
+                .begin_group_with_selection(cursor, (start, selection_mode)),
+            None => self.history.begin_group(cursor),
+        }
+        let ret = f(self);
+        self.guarding = false;
+        self.desired_col = None;
+
+        if self.protected_rows.is_empty() {
+            self.marks.apply(self.history.pending_edits());
+            self.signs.apply(self.history.pending_edits());
+            #[cfg(feature = "search")]
+            self.search
+                .apply_edits(self.history.pending_edits(), &self.lines);
+            self.history.end_group(self.cursor);
+            #[cfg(feature = "tracing")]
+            self.trace_selection(selection_start.map(|s| (s, selection_mode)));
+            return ret;
+        }
+        match self.protected_rows.apply(self.history.pending_edits()) {
+            Ok(rows) => {
+                self.protected_rows = rows;
+                self.marks.apply(self.history.pending_edits());
+                self.signs.apply(self.history.pending_edits());
+                #[cfg(feature = "search")]
+                self.search
+                    .apply_edits(self.history.pending_edits(), &self.lines);
+                self.history.end_group(self.cursor);
+                #[cfg(feature = "tracing")]
+                self.trace_selection(selection_start.map(|s| (s, selection_mode)));
+                ret
+            }
+            Err(err) => {
+                debug!(error = ?err, "edit reverted since it modified protected rows");
+                self.history.rollback_group(&mut self.lines);
+                self.auto_pair_opens.clear();
+                self.cursor = cursor;
+                self.selection_start = selection_start;
+                self.selection_mode = selection_mode;
+                if let Some((yank, yank_history, registers)) = yank {
+                    self.yank = yank;
+                    self.yank_history = yank_history;
+                    self.registers = registers;
+                }
+                self.protected_row_error = Some(err);
+                rejected
+            }
+        }
+    }
+
+    #[cfg(feature = "tracing")]
+    fn trace_selection(&self, before: Option<((usize, usize), SelectionMode)>) {
+        let after = self.selection_start.map(|s| (s, self.selection_mode));
+        if before != after {
+            debug!(?before, ?after, cursor = ?self.cursor, "selection changed");
+        }
+    }
+
+    // Check the edit applied by undo or redo does not modify protected rows
+    fn check_protected_rows(&mut self, edit: Option<&Edit>) -> bool {
+        let edit = match edit {
+            Some(edit) if !self.protected_rows.is_empty() => edit,
+            _ => return true,
+        };
+        match self.protected_rows.apply(iter::once(edit)) {
+            Ok(rows) => {
+                self.protected_rows = rows;
+                self.protected_row_error = None;
+                true
+            }
+            Err(err) => {
+                self.protected_row_error = Some(err);
+                false
+            }
+        }
+    }
+
     fn push_history(&mut self, kind: EditKind, before: Pos, after_offset: usize) {
+        self.clear_virtual_texts();
         let (row, col) = self.cursor;
         let after = Pos::new(row, col, after_offset);
         let edit = Edit::new(kind, before, after);
+        self.move_auto_pairs(&edit);
         self.history.push(edit);
     }
 
+    // Move the tracked pairs inserted by auto pairs across the edit. Pairs whose opening character was deleted are
+    // forgotten
+    fn move_auto_pairs(&mut self, edit: &Edit) {
+        if self.auto_pair_opens.is_empty() {
+            return;
+        }
+        edit.for_each_text(&mut |insert, text, start| {
+            let end = text_end(start, text);
+            self.auto_pair_opens.retain_mut(|pos| {
+                if !insert && start <= *pos && *pos < end {
+                    return false;
+                }
+                *pos = move_pos(*pos, insert, start, end, true);
+                true
+            });
+        });
+    }
+
     /// Insert a single character at current cursor position.
     /// ```
     /// use tui_textarea::TextArea;
@@ -764,12 +1285,20 @@ impl<'a> TextArea<'a> {
     /// assert_eq!(textarea.lines(), ["a"]);
     /// ```
     pub fn insert_char(&mut self, c: char) {
+        if self.needs_guard() {
+            return self.guard((), |t| t.insert_char(c));
+        }
         if c == '\n' || c == '\r' {
             self.insert_newline();
             return;
         }
 
-        self.delete_selection(false);
+        if !self.delete_selection(false) && is_word_boundary(c) {
+            self.expand_abbreviation();
+        }
+        if self.auto_pairs && self.insert_auto_pair(c) {
+            return;
+        }
         let (row, col) = self.cursor;
         let line = &mut self.lines[row];
         let i = line
@@ -786,8 +1315,62 @@ impl<'a> TextArea<'a> {
         );
     }
 
+    // Replace the word just before the cursor with its expansion set by `set_abbreviations`. This is called before a
+    // word boundary character is inserted
+    fn expand_abbreviation(&mut self) {
+        if self.abbreviations.is_empty() {
+            return;
+        }
+        let (row, col) = self.cursor;
+        let chars: Vec<char> = self.lines[row].chars().take(col).collect();
+        let start = chars
+            .iter()
+            .rposition(|&c| is_word_boundary(c))
+            .map_or(0, |i| i + 1);
+        if start == col {
+            return;
+        }
+        let word: String = chars[start..].iter().collect();
+        let expansion = match self.abbreviations.get(&word) {
+            Some(expansion) => expansion.clone(),
+            None => return,
+        };
+        let start = Pos::new(row, start, self.line_offset(row, start));
+        let end = Pos::new(row, col, self.line_offset(row, col));
+        self.remove_range(start, end, false);
+        self.insert_str(expansion);
+    }
+
+    // Handle the character typed while auto pairs are enabled. Typing an opening character inserts the pair and typing a
+    // closing character steps over the same character next to the cursor. Returns `false` when the character should be
+    // inserted as usual
+    fn insert_auto_pair(&mut self, c: char) -> bool {
+        let (row, col) = self.cursor;
+        let line = &self.lines[row];
+        let prev = col.checked_sub(1).and_then(|i| line.chars().nth(i));
+        let next = line.chars().nth(col);
+
+        if next == Some(c) && self.auto_pair_chars.iter().any(|&(_, close)| close == c) {
+            self.cursor.1 += 1;
+            return true;
+        }
+        let close = match self.auto_pair_chars.iter().find(|&&(open, _)| open == c) {
+            Some(&(_, close)) => close,
+            None => return false,
+        };
+        // Quotes after a word character are likely apostrophes like "don't"
+        if c == close && prev.is_some_and(|p| p.is_alphanumeric() || p == '_') {
+            return false;
+        }
+        self.insert_piece(format!("{}{}", c, close));
+        self.cursor.1 -= 1;
+        self.auto_pair_opens.push((row, col));
+        true
+    }
+
     /// Insert a string at current cursor position. This method returns if some text was inserted or not in the textarea.
-    /// Both `\n` and `\r\n` are recognized as newlines but `\r` isn't.
+    /// `\n`, `\r\n` and `\r` are recognized as newlines by default. When [`TextArea::set_normalize_newlines`] is
+    /// disabled, only `\n` is recognized and carriage returns are kept in the lines.
     /// ```
     /// use tui_textarea::TextArea;
     ///
@@ -796,16 +1379,15 @@ impl<'a> TextArea<'a> {
     /// textarea.insert_str("hello");
     /// assert_eq!(textarea.lines(), ["hello"]);
     ///
-    /// textarea.insert_str(", world\ngoodbye, world");
-    /// assert_eq!(textarea.lines(), ["hello, world", "goodbye, world"]);
+    /// textarea.insert_str(", world\r\ngoodbye\rworld");
+    /// assert_eq!(textarea.lines(), ["hello, world", "goodbye", "world"]);
     /// ```
     pub fn insert_str<S: AsRef<str>>(&mut self, s: S) -> bool {
+        if self.needs_guard() {
+            return self.guard(false, |t| t.insert_str(s));
+        }
         let modified = self.delete_selection(false);
-        let mut lines: Vec<_> = s
-            .as_ref()
-            .split('\n')
-            .map(|s| s.strip_suffix('\r').unwrap_or(s).to_string())
-            .collect();
+        let mut lines = split_lines(s.as_ref(), self.normalize_newlines);
         match lines.len() {
             0 => modified,
             1 => self.insert_piece(lines.remove(0)),
@@ -813,88 +1395,508 @@ impl<'a> TextArea<'a> {
         }
     }
 
-    fn insert_chunk(&mut self, chunk: Vec<String>) -> bool {
-        debug_assert!(chunk.len() > 1, "Chunk size must be > 1: {:?}", chunk);
-
-        let (row, col) = self.cursor;
-        let line = &mut self.lines[row];
-        let i = line
-            .char_indices()
-            .nth(col)
-            .map(|(i, _)| i)
-            .unwrap_or(line.len());
-        let before = Pos::new(row, col, i);
-
-        let (row, col) = (
-            row + chunk.len() - 1,
-            chunk[chunk.len() - 1].chars().count(),
-        );
-        self.cursor = (row, col);
-
-        let end_offset = chunk.last().unwrap().len();
-
-        let edit = EditKind::InsertChunk(chunk);
-        edit.apply(&mut self.lines, &before, &Pos::new(row, col, end_offset));
+    /// Insert a string at current cursor position splitting it into chunks of `chunk_lines` lines. This is useful for
+    /// inserting a large text (e.g. pasting a file content) without blocking the application for a long time.
+    /// `progress` is called with the number of inserted lines and the number of all lines after each chunk including the
+    /// last one. When it returns [`ControlFlow::Break`], the text inserted so far is reverted and this method returns
+    /// `false`. Otherwise this method returns if some text was inserted or not in the textarea like
+    /// [`TextArea::insert_str`].
+    ///
+    /// The entire insertion is recorded as one entry in the edit history so one [`TextArea::undo`] call reverts it.
+    /// ```
+    /// use std::ops::ControlFlow;
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// let mut calls = vec![];
+    /// textarea.insert_str_chunked("a\nb\nc\nd\ne", 2, |inserted, total| {
+    ///     calls.push((inserted, total));
+    ///     ControlFlow::Continue(())
+    /// });
+    /// assert_eq!(textarea.lines(), ["a", "b", "c", "d", "e"]);
+    /// assert_eq!(calls, [(2, 5), (4, 5), (5, 5)]);
+    ///
+    /// // Abort the insertion
+    /// let mut textarea = TextArea::default();
+    /// let inserted = textarea.insert_str_chunked("a\nb\nc", 1, |_, _| ControlFlow::Break(()));
+    /// assert!(!inserted);
+    /// assert_eq!(textarea.lines(), [""]);
+    /// ```
+    pub fn insert_str_chunked<S: AsRef<str>>(
+        &mut self,
+        s: S,
+        chunk_lines: usize,
+        mut progress: impl FnMut(usize, usize) -> ControlFlow<()>,
+    ) -> bool {
+        let chunk_lines = chunk_lines.max(1);
+        if self.needs_guard() {
+            return self.guard(false, |t| t.insert_str_chunked(s, chunk_lines, progress));
+        }
+        let lines = split_lines(s.as_ref(), self.normalize_newlines);
+        let total = lines.len();
+
+        let (selection_start, selection_mode) = (self.selection_start, self.selection_mode);
+        let cursor = self.cursor;
+        self.history.begin_group(cursor);
+        let mut modified = self.delete_selection(false);
+
+        // The rest of the cursor line is moved to the next line once by the first chunk. Following chunks are inserted
+        // as complete lines after the end of the previous line so that the rest of the line is not moved by every
+        // chunk. The last line of the text is finally inserted at the head of the rest of the line
+        let mut lines = lines.into_iter();
+        let mut inserted = 0;
+        let mut split = false;
+        while inserted < total {
+            let mut chunk: Vec<_> = lines.by_ref().take(chunk_lines).collect();
+            inserted += chunk.len();
+            let last = if inserted == total { chunk.pop() } else { None };
+            if !chunk.is_empty() {
+                if split {
+                    let row = self.cursor.0 - 1;
+                    self.cursor = (row, self.lines[row].chars().count());
+                    chunk.insert(0, String::new());
+                    self.insert_chunk(chunk);
+                    self.cursor = (self.cursor.0 + 1, 0);
+                } else {
+                    chunk.push(String::new());
+                    self.insert_chunk(chunk);
+                    split = true;
+                }
+                modified = true;
+            }
+            if let Some(line) = last {
+                modified |= self.insert_piece(line);
+            }
+            if let ControlFlow::Break(()) = progress(inserted, total) {
+                self.history.rollback_group(&mut self.lines);
+                self.auto_pair_opens.clear();
+                self.cursor = cursor;
+                self.selection_start = selection_start;
+                self.selection_mode = selection_mode;
+                return false;
+            }
+        }
 
-        self.push_history(edit, before, end_offset);
-        true
+        self.history.end_group(self.cursor);
+        modified
     }
 
-    fn insert_piece(&mut self, s: String) -> bool {
-        if s.is_empty() {
+    /// Insert a string at the left column of the block selection on every row of the block, like `I` in the block-wise
+    /// visual mode of Vim. Lines shorter than the column are skipped unless [`TextArea::set_block_insert_padding`] is
+    /// enabled. The block selection is canceled and the cursor moves to the end of the inserted text on the first row.
+    /// When block selection is not ongoing, the string is inserted at the cursor like [`TextArea::insert_str`]. Only
+    /// the first line of the string is inserted. This method returns if some text was inserted or not in the textarea.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["foo", "", "bar"]);
+    ///
+    /// textarea.start_block_selection();
+    /// textarea.move_cursor(CursorMove::Bottom);
+    /// textarea.insert_str_block("// ");
+    /// assert_eq!(textarea.lines(), ["// foo", "// ", "// bar"]);
+    /// assert_eq!(textarea.cursor(), (0, 3));
+    /// ```
+    pub fn insert_str_block(&mut self, text: &str) -> bool {
+        if self.needs_guard() {
+            return self.guard(false, |t| t.insert_str_block(text));
+        }
+        let lines = split_lines(text, self.normalize_newlines);
+        let text = lines[0].as_str();
+        let (top, bottom, left, _) = match self.block_selection() {
+            Some(block) => block,
+            None => return self.insert_str(text),
+        };
+        self.cancel_selection();
+        if text.is_empty() {
             return false;
         }
 
-        let (row, col) = self.cursor;
-        let line = &mut self.lines[row];
-        debug_assert!(
-            !s.contains('\n'),
-            "string given to TextArea::insert_piece must not contain newline: {:?}",
-            line,
-        );
-
-        let i = line
-            .char_indices()
-            .nth(col)
-            .map(|(i, _)| i)
-            .unwrap_or(line.len());
-        line.insert_str(i, &s);
-        let end_offset = i + s.len();
-
-        self.cursor.1 += s.chars().count();
-        self.push_history(EditKind::InsertStr(s), Pos::new(row, col, i), end_offset);
-        true
-    }
-
-    fn delete_range(&mut self, start: Pos, end: Pos, should_yank: bool) {
-        self.cursor = (start.row, start.col);
-
-        if start.row == end.row {
-            let removed = self.lines[start.row]
-                .drain(start.offset..end.offset)
-                .as_str()
-                .to_string();
-            if should_yank {
-                self.yank = removed.clone().into();
+        let mut modified = false;
+        for row in top..=bottom {
+            let len = self.lines[row].chars().count();
+            if len < left {
+                if !self.block_insert_padding {
+                    continue;
+                }
+                self.cursor = (row, len);
+                self.insert_piece(" ".repeat(left - len));
             }
-            self.push_history(EditKind::DeleteStr(removed), end, start.offset);
-            return;
+            self.cursor = (row, left);
+            modified |= self.insert_piece(text.to_string());
         }
+        self.cursor = (top, left + text.chars().count());
+        modified
+    }
 
-        let mut deleted = vec![self.lines[start.row]
-            .drain(start.offset..)
-            .as_str()
-            .to_string()];
-        deleted.extend(self.lines.drain(start.row + 1..end.row));
-        if start.row + 1 < self.lines.len() {
-            let mut last_line = self.lines.remove(start.row + 1);
-            self.lines[start.row].push_str(&last_line[end.offset..]);
-            last_line.truncate(end.offset);
+    /// Insert a string at the position of `row` and `col` without moving the cursor there. The cursor, the selection
+    /// and the viewport are kept on the same text. For example, when lines are inserted above the cursor, the cursor
+    /// moves down by the number of the inserted lines. The position is clamped to the text. The insertion can be undone
+    /// as usual. This method returns if some text was inserted or not in the textarea.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["fn main() {}"]);
+    /// textarea.move_cursor(CursorMove::End);
+    ///
+    /// textarea.insert_str_at(0, 0, "use std::io;\n\n");
+    /// assert_eq!(textarea.lines(), ["use std::io;", "", "fn main() {}"]);
+    /// assert_eq!(textarea.cursor(), (2, 12));
+    ///
+    /// textarea.undo();
+    /// assert_eq!(textarea.lines(), ["fn main() {}"]);
+    /// ```
+    pub fn insert_str_at<S: AsRef<str>>(&mut self, row: usize, col: usize, s: S) -> bool {
+        if self.needs_guard() {
+            return self.guard(false, |t| t.insert_str_at(row, col, s));
+        }
+        let (row, col) = self.fit_cursor((row, col));
+        let pos = Pos::new(row, col, self.line_offset(row, col));
+        self.edit_keeping_cursor(pos, pos, |t| {
+            t.cursor = (row, col);
+            t.insert_str(s)
+        })
+    }
+
+    /// Delete `chars` characters from the position of `row` and `col` without moving the cursor there. Newlines at the
+    /// end of lines are counted in the number like [`TextArea::delete_str`]. The cursor, the selection and the viewport
+    /// are kept on the same text in the same way as [`TextArea::insert_str_at`]. The position is clamped to the text
+    /// and the deleted text is not yanked. This method returns if some text was deleted or not in the textarea.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["use std::io;", "", "fn main() {}"]);
+    /// textarea.move_cursor(CursorMove::Bottom);
+    ///
+    /// textarea.delete_str_at(0, 0, 14);
+    /// assert_eq!(textarea.lines(), ["fn main() {}"]);
+    /// assert_eq!(textarea.cursor(), (0, 0));
+    /// ```
+    pub fn delete_str_at(&mut self, row: usize, col: usize, chars: usize) -> bool {
+        if self.needs_guard() {
+            return self.guard(false, |t| t.delete_str_at(row, col, chars));
+        }
+        let (row, col) = self.fit_cursor((row, col));
+        let start = Pos::new(row, col, self.line_offset(row, col));
+        let end = self.pos_after_chars((row, col), chars);
+        if (start.row, start.col) == (end.row, end.col) {
+            return false;
+        }
+        self.edit_keeping_cursor(start, end, |t| {
+            t.remove_range(start, end, false);
+            true
+        })
+    }
+
+    /// Delete the text between the two positions of `(row, col)` and return the deleted text. Lines of the deleted text
+    /// are joined with `\n`. The positions can be given in any order and are clamped to the text. The cursor moves to
+    /// the start of the deleted range. The text selection is not used nor canceled, and the deleted text is not yanked.
+    /// The deletion is recorded as one entry in the edit history.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["fn foo(", "    a,", "    b,", ")"]);
+    ///
+    /// assert_eq!(textarea.delete_range((2, 6), (0, 7)), "\n    a,\n    b,");
+    /// assert_eq!(textarea.lines(), ["fn foo(", ")"]);
+    /// assert_eq!(textarea.cursor(), (0, 7));
+    ///
+    /// // The end position is clamped to the end of the text
+    /// assert_eq!(textarea.delete_range((0, 2), (9, 9)), " foo(\n)");
+    /// assert_eq!(textarea.lines(), ["fn"]);
+    /// ```
+    pub fn delete_range(&mut self, start: (usize, usize), end: (usize, usize)) -> String {
+        if self.needs_guard() {
+            return self.guard(String::new(), |t| t.delete_range(start, end));
+        }
+        let (start, end) = (self.fit_cursor(start), self.fit_cursor(end));
+        let (start, end) = if start <= end {
+            (start, end)
+        } else {
+            (end, start)
+        };
+        self.cursor = start;
+        if start == end {
+            return String::new();
+        }
+        let start = Pos::new(start.0, start.1, self.line_offset(start.0, start.1));
+        let end = Pos::new(end.0, end.1, self.line_offset(end.0, end.1));
+        let removed = self.range_text(start, end).to_string();
+        let anchor = self.selection_start.take();
+        self.remove_range(start, end, false);
+        self.selection_start = anchor.map(|pos| shift_pos(pos, start, end, (start.row, start.col)));
+        removed
+    }
+
+    /// Replace the text between the two positions of `(row, col)` with `text` without moving the cursor there. This is
+    /// useful to apply edits from outside such as an LSP server or a collaborative editing session. The cursor, the
+    /// selection and the viewport are kept on the same text in the same way as [`TextArea::insert_str_at`]. For
+    /// example, when lines are removed above the cursor, the cursor moves up by the number of the removed lines, and the
+    /// cursor stays when the edit is after it. The cursor inside the replaced text moves to the start of the edit. The
+    /// positions can be given in any order and are clamped to the text. The replaced text is not yanked and the edit is
+    /// recorded as one entry in the edit history. This method returns if some text was modified or not in the textarea.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["let x = foo();", "bar(x);"]);
+    /// textarea.move_cursor(CursorMove::Jump(1, 4));
+    ///
+    /// assert!(textarea.apply_edit((0, 0), (0, 14), "// removed\n// lines"));
+    /// assert_eq!(textarea.lines(), ["// removed", "// lines", "bar(x);"]);
+    /// assert_eq!(textarea.cursor(), (2, 4));
+    ///
+    /// textarea.undo();
+    /// assert_eq!(textarea.lines(), ["let x = foo();", "bar(x);"]);
+    /// ```
+    pub fn apply_edit<S: AsRef<str>>(
+        &mut self,
+        start: (usize, usize),
+        end: (usize, usize),
+        text: S,
+    ) -> bool {
+        if self.needs_guard() {
+            return self.guard(false, |t| t.apply_edit(start, end, text));
+        }
+        let (start, end) = (self.fit_cursor(start), self.fit_cursor(end));
+        let (start, end) = if start <= end {
+            (start, end)
+        } else {
+            (end, start)
+        };
+        let start = Pos::new(start.0, start.1, self.line_offset(start.0, start.1));
+        let end = Pos::new(end.0, end.1, self.line_offset(end.0, end.1));
+        self.edit_keeping_cursor(start, end, |t| {
+            let mut modified = false;
+            if start.offset != end.offset || start.row != end.row {
+                t.remove_range(start, end, false);
+                modified = true;
+            }
+            t.cursor = (start.row, start.col);
+            t.insert_str(text) || modified
+        })
+    }
+
+    /// Apply multiple edits by [`TextArea::apply_edit`] as one entry in the edit history. Each edit is a tuple of the
+    /// start position, the end position and the replacing text. All positions refer to the text before any of the
+    /// edits is applied, like text edits of LSP, so the edits must not overlap. Edits inserting text at the same
+    /// position are inserted in the given order. This method returns if some text was modified or not in the textarea.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["foo(a, b)", "foo(c)"]);
+    ///
+    /// let edits = vec![
+    ///     ((0, 0), (0, 3), "bar"),
+    ///     ((1, 0), (1, 3), "bar"),
+    ///     ((1, 5), (1, 5), ", d"),
+    /// ];
+    /// assert!(textarea.apply_edits(edits));
+    /// assert_eq!(textarea.lines(), ["bar(a, b)", "bar(c, d)"]);
+    ///
+    /// // One undo reverts all edits
+    /// textarea.undo();
+    /// assert_eq!(textarea.lines(), ["foo(a, b)", "foo(c)"]);
+    /// ```
+    pub fn apply_edits<I, S>(&mut self, edits: I) -> bool
+    where
+        I: IntoIterator<Item = ((usize, usize), (usize, usize), S)>,
+        S: AsRef<str>,
+    {
+        if self.needs_guard() {
+            return self.guard(false, |t| t.apply_edits(edits));
+        }
+        let mut edits: Vec<_> = edits
+            .into_iter()
+            .enumerate()
+            .map(|(i, (start, end, text))| (start.min(end), i, end.max(start), text))
+            .collect();
+        // Apply the edits from the end of the text so that the positions of the remaining edits are not affected
+        edits.sort_by_key(|&(start, i, _, _)| Reverse((start, i)));
+        let mut modified = false;
+        for (start, _, end, text) in edits {
+            modified |= self.apply_edit(start, end, text);
+        }
+        modified
+    }
+
+    // Return the position `chars` characters after `pos` counting newlines at the end of lines. The position is clamped
+    // to the end of the text
+    fn pos_after_chars(&self, (mut row, mut col): (usize, usize), chars: usize) -> Pos {
+        let mut remaining = chars;
+        loop {
+            let len = self.lines[row].chars().count();
+            if remaining <= len - col {
+                col += remaining;
+                break;
+            }
+            if row + 1 == self.lines.len() {
+                col = len;
+                break;
+            }
+            remaining -= len - col + 1;
+            row += 1;
+            col = 0;
+        }
+        Pos::new(row, col, self.line_offset(row, col))
+    }
+
+    // Run the edit by `f` which replaces the text between `start` and `end`, and move the cursor, the selection anchor,
+    // and the top of the viewport back onto the same text they pointed to before the edit
+    fn edit_keeping_cursor(
+        &mut self,
+        start: Pos,
+        end: Pos,
+        f: impl FnOnce(&mut Self) -> bool,
+    ) -> bool {
+        let (cursor, anchor) = (self.cursor, self.selection_start.take());
+        let modified = f(self);
+        let new_end = self.cursor;
+        let shift = |pos: (usize, usize)| {
+            // Text inserted at the position is placed before it
+            if start.row == end.row && start.offset == end.offset && pos == (start.row, start.col) {
+                new_end
+            } else {
+                shift_pos(pos, start, end, new_end)
+            }
+        };
+        self.cursor = shift(cursor);
+        self.selection_start = anchor.map(shift);
+
+        // The viewport only needs to follow when the edit changed lines above it
+        let (top, col) = self.viewport.scroll_top();
+        if start.row < top {
+            let offset = self.viewport.wrap_top();
+            self.viewport.set_scroll_top(shift((top, 0)).0, col);
+            self.viewport.set_wrap_top(offset);
+        }
+        modified
+    }
+
+    fn insert_chunk(&mut self, chunk: Vec<String>) -> bool {
+        debug_assert!(chunk.len() > 1, "Chunk size must be > 1: {:?}", chunk);
+
+        let (row, col) = self.cursor;
+        let line = &mut self.lines[row];
+        let i = line
+            .char_indices()
+            .nth(col)
+            .map(|(i, _)| i)
+            .unwrap_or(line.len());
+        let before = Pos::new(row, col, i);
+
+        let (row, col) = (
+            row + chunk.len() - 1,
+            chunk[chunk.len() - 1].chars().count(),
+        );
+        self.cursor = (row, col);
+
+        let end_offset = chunk.last().unwrap().len();
+
+        let edit = EditKind::InsertChunk(chunk);
+        edit.apply(&mut self.lines, &before, &Pos::new(row, col, end_offset));
+
+        self.push_history(edit, before, end_offset);
+        true
+    }
+
+    fn insert_piece(&mut self, s: String) -> bool {
+        if s.is_empty() {
+            return false;
+        }
+
+        let (row, col) = self.cursor;
+        let line = &mut self.lines[row];
+        debug_assert!(
+            !s.contains('\n'),
+            "string given to TextArea::insert_piece must not contain newline: {:?}",
+            line,
+        );
+
+        let i = line
+            .char_indices()
+            .nth(col)
+            .map(|(i, _)| i)
+            .unwrap_or(line.len());
+        line.insert_str(i, &s);
+        let end_offset = i + s.len();
+
+        self.cursor.1 += s.chars().count();
+        self.push_history(EditKind::InsertStr(s), Pos::new(row, col, i), end_offset);
+        true
+    }
+
+    // Insert the complete lines before the `row` row and move the cursor to the head of the first inserted line. When
+    // `row` is the number of lines, the lines are inserted after the last line
+    fn insert_lines(&mut self, row: usize, mut lines: Vec<String>) -> bool {
+        if row < self.lines.len() {
+            self.cursor = (row, 0);
+            lines.push(String::new());
+        } else {
+            let last = self.lines.len() - 1;
+            self.cursor = (last, self.lines[last].chars().count());
+            lines.insert(0, String::new());
+        }
+        self.insert_chunk(lines);
+        self.cursor = (row, 0);
+        true
+    }
+
+    // Insert the lines of the block at the cursor column on the successive lines from the cursor line. Lines are added
+    // when the block is longer than the rest of the text, and lines shorter than the cursor column are padded with
+    // spaces. The cursor stays at the top-left corner of the inserted block
+    fn insert_block(&mut self, block: Vec<String>) -> bool {
+        let (row, col) = self.cursor;
+        let mut modified = false;
+        self.history.begin_group(self.cursor);
+        for (i, s) in block.into_iter().enumerate() {
+            let r = row + i;
+            if r == self.lines.len() {
+                self.cursor = (r - 1, self.lines[r - 1].chars().count());
+                modified |= self.insert_chunk(vec![String::new(), String::new()]);
+            }
+            let len = self.lines[r].chars().count();
+            if len < col && !s.is_empty() {
+                self.cursor = (r, len);
+                modified |= self.insert_piece(" ".repeat(col - len));
+            }
+            self.cursor = (r, col.min(self.lines[r].chars().count()));
+            modified |= self.insert_piece(s);
+        }
+        self.cursor = (row, col);
+        self.history.end_group(self.cursor);
+        modified
+    }
+
+    fn remove_range(&mut self, start: Pos, end: Pos, should_yank: bool) {
+        self.cursor = (start.row, start.col);
+
+        if start.row == end.row {
+            let removed = self.lines[start.row]
+                .drain(start.offset..end.offset)
+                .as_str()
+                .to_string();
+            if should_yank {
+                self.set_yank(removed.clone().into());
+            }
+            self.push_history(EditKind::DeleteStr(removed), end, start.offset);
+            return;
+        }
+
+        let mut deleted = vec![self.lines[start.row]
+            .drain(start.offset..)
+            .as_str()
+            .to_string()];
+        deleted.extend(self.lines.drain(start.row + 1..end.row));
+        if start.row + 1 < self.lines.len() {
+            let mut last_line = self.lines.remove(start.row + 1);
+            self.lines[start.row].push_str(&last_line[end.offset..]);
+            last_line.truncate(end.offset);
             deleted.push(last_line);
         }
 
         if should_yank {
-            self.yank = YankText::Chunk(deleted.clone());
+            self.set_yank(YankText::Chunk(deleted.clone()));
         }
 
         let edit = if deleted.len() == 1 {
@@ -924,6 +1926,9 @@ impl<'a> TextArea<'a> {
     /// assert_eq!(textarea.lines(), ["🐱", "🐮"]);
     /// ```
     pub fn delete_str(&mut self, chars: usize) -> bool {
+        if self.needs_guard() {
+            return self.guard(false, |t| t.delete_str(chars));
+        }
         if self.delete_selection(false) {
             return true;
         }
@@ -967,7 +1972,7 @@ impl<'a> TextArea<'a> {
                 .drain(start_offset..end_offset)
                 .as_str()
                 .to_string();
-            self.yank = removed.clone().into();
+            self.set_yank(removed.clone().into());
             self.push_history(
                 EditKind::DeleteStr(removed),
                 Pos::new(start_row, end_col, end_offset),
@@ -992,7 +1997,7 @@ impl<'a> TextArea<'a> {
 
         let start = Pos::new(start_row, start_col, start_offset);
         let end = Pos::new(r, col, offset);
-        self.delete_range(start, end, true);
+        self.remove_range(start, end, true);
         true
     }
 
@@ -1026,7 +2031,7 @@ impl<'a> TextArea<'a> {
                 Pos::new(row, col + chars, i + bytes),
                 i,
             );
-            self.yank = removed.into();
+            self.set_yank(removed.into());
             true
         } else {
             false
@@ -1048,6 +2053,9 @@ impl<'a> TextArea<'a> {
     /// assert_eq!(textarea.lines(), ["hi      "]);
     /// ```
     pub fn insert_tab(&mut self) -> bool {
+        if self.needs_guard() {
+            return self.guard(false, |t| t.insert_tab());
+        }
         let modified = self.delete_selection(false);
         if self.tab_len == 0 {
             return modified;
@@ -1059,16 +2067,13 @@ impl<'a> TextArea<'a> {
         }
 
         let (row, col) = self.cursor;
-        let width: usize = self.lines[row]
-            .chars()
-            .take(col)
-            .map(|c| c.width().unwrap_or(0))
-            .sum();
+        let width: usize = self.lines[row].chars().take(col).map(char_width).sum();
         let len = self.tab_len - (width % self.tab_len as usize) as u8;
         self.insert_piece(spaces(len).to_string())
     }
 
-    /// Insert a newline at current cursor position.
+    /// Insert a newline at current cursor position. When auto indentation is enabled by [`TextArea::set_auto_indent`],
+    /// the indentation of the cursor line is copied to the new line.
     /// ```
     /// use tui_textarea::{TextArea, CursorMove};
     ///
@@ -1079,9 +2084,41 @@ impl<'a> TextArea<'a> {
     /// assert_eq!(textarea.lines(), ["h", "i"]);
     /// ```
     pub fn insert_newline(&mut self) {
-        self.delete_selection(false);
+        if self.needs_guard() {
+            return self.guard((), |t| t.insert_newline());
+        }
+        if !self.delete_selection(false) {
+            self.expand_abbreviation();
+        }
+
+        if self.continue_list() {
+            return;
+        }
 
         let (row, col) = self.cursor;
+        if self.auto_indent {
+            let indent: String = self.lines[row]
+                .chars()
+                .take(col)
+                .take_while(|&c| c == ' ' || c == '\t')
+                .collect();
+            if !indent.is_empty() {
+                // Whitespaces after the cursor are replaced with the copied indentation like Vim
+                let line = &self.lines[row];
+                let start = Pos::new(row, col, self.line_offset(row, col));
+                let blanks = line[start.offset..]
+                    .chars()
+                    .take_while(|&c| c == ' ' || c == '\t')
+                    .count();
+                if blanks > 0 {
+                    let end = Pos::new(row, col + blanks, start.offset + blanks);
+                    self.remove_range(start, end, false);
+                }
+                self.insert_chunk(vec![String::new(), indent]);
+                return;
+            }
+        }
+
         let line = &mut self.lines[row];
         let offset = line
             .char_indices()
@@ -1096,6 +2133,50 @@ impl<'a> TextArea<'a> {
         self.push_history(EditKind::InsertNewline, Pos::new(row, col, offset), 0);
     }
 
+    // Handle a newline in a list item when list continuation is enabled. Returns `false` when the newline should be
+    // inserted as usual.
+    fn continue_list(&mut self) -> bool {
+        let (row, col) = self.cursor;
+        let line = &self.lines[row];
+        let item = match self.list_continuation.as_ref().and_then(|l| l.parse(line)) {
+            Some(item) => item,
+            None => return false,
+        };
+        let offset = line
+            .char_indices()
+            .nth(col)
+            .map(|(i, _)| i)
+            .unwrap_or(line.len());
+        if offset < item.prefix_len {
+            return false;
+        }
+
+        if line[item.prefix_len..].trim().is_empty() {
+            // Newline in an empty nested list item outdents it by one indent level like `dedent_selection`
+            let width = if line.starts_with('\t') {
+                1
+            } else {
+                let tab_len = self.tab_len as usize;
+                line.bytes()
+                    .take(tab_len)
+                    .take_while(|b| *b == b' ')
+                    .count()
+            };
+            if width > 0 {
+                self.remove_range(Pos::new(row, 0, 0), Pos::new(row, width, width), false);
+                self.cursor = (row, col - width);
+                return true;
+            }
+            // Newline in an empty top-level list item removes its marker instead of continuing the list
+            let removed = mem::take(&mut self.lines[row]);
+            self.cursor = (row, 0);
+            self.push_history(EditKind::DeleteStr(removed), Pos::new(row, col, offset), 0);
+            return true;
+        }
+
+        self.insert_chunk(vec![String::new(), item.next_prefix])
+    }
+
     /// Delete a newline from **head** of current cursor line. This method returns if a newline was deleted or not in
     /// the textarea. When some text is selected, it is deleted instead.
     /// ```
@@ -1108,6 +2189,9 @@ impl<'a> TextArea<'a> {
     /// assert_eq!(textarea.lines(), ["helloworld"]);
     /// ```
     pub fn delete_newline(&mut self) -> bool {
+        if self.needs_guard() {
+            return self.guard(false, |t| t.delete_newline());
+        }
         if self.delete_selection(false) {
             return true;
         }
@@ -1140,6 +2224,9 @@ impl<'a> TextArea<'a> {
     /// assert_eq!(textarea.lines(), ["bc"]);
     /// ```
     pub fn delete_char(&mut self) -> bool {
+        if self.needs_guard() {
+            return self.guard(false, |t| t.delete_char());
+        }
         if self.delete_selection(false) {
             return true;
         }
@@ -1149,6 +2236,22 @@ impl<'a> TextArea<'a> {
             return self.delete_newline();
         }
 
+        if self.auto_pairs {
+            let line = &self.lines[row];
+            let mut chars = line.chars().skip(col - 1);
+            if let (Some(open), Some(close)) = (chars.next(), chars.next()) {
+                if self.auto_pair_chars.contains(&(open, close))
+                    && self.auto_pair_opens.contains(&(row, col - 1))
+                {
+                    // Delete both characters of the empty pair inserted by auto pairs
+                    let start = Pos::new(row, col - 1, self.line_offset(row, col - 1));
+                    let end = Pos::new(row, col + 1, self.line_offset(row, col + 1));
+                    self.remove_range(start, end, false);
+                    return true;
+                }
+            }
+        }
+
         let line = &mut self.lines[row];
         if let Some((offset, c)) = line.char_indices().nth(col - 1) {
             line.remove(offset);
@@ -1176,6 +2279,9 @@ impl<'a> TextArea<'a> {
     /// assert_eq!(textarea.lines(), ["ac"]);
     /// ```
     pub fn delete_next_char(&mut self) -> bool {
+        if self.needs_guard() {
+            return self.guard(false, |t| t.delete_next_char());
+        }
         if self.delete_selection(false) {
             return true;
         }
@@ -1204,6 +2310,9 @@ impl<'a> TextArea<'a> {
     /// assert_eq!(textarea.lines(), ["ab"]);
     /// ```
     pub fn delete_line_by_end(&mut self) -> bool {
+        if self.needs_guard() {
+            return self.guard(false, |t| t.delete_line_by_end());
+        }
         if self.delete_selection(false) {
             return true;
         }
@@ -1228,6 +2337,9 @@ impl<'a> TextArea<'a> {
     /// assert_eq!(textarea.lines(), ["cde"]);
     /// ```
     pub fn delete_line_by_head(&mut self) -> bool {
+        if self.needs_guard() {
+            return self.guard(false, |t| t.delete_line_by_head());
+        }
         if self.delete_selection(false) {
             return true;
         }
@@ -1256,11 +2368,42 @@ impl<'a> TextArea<'a> {
     /// assert_eq!(textarea.lines(), ["aaa "]);
     /// ```
     pub fn delete_word(&mut self) -> bool {
+        if self.needs_guard() {
+            return self.guard(false, |t| t.delete_word());
+        }
+        self.delete_word_with(find_word_start_backward)
+    }
+
+    /// Delete a WORD before cursor. WORD is a sequence of non-whitespace characters, like WORD of Vim. For example
+    /// `cd foo/bar/baz` consists of WORDs `cd` and `foo/bar/baz`. This is useful to delete paths or URLs at once. When
+    /// the cursor is at head of line, the newline before the cursor will be removed.
+    ///
+    /// This method returns if some text was deleted or not in the textarea.
+    ///
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["cd foo/bar/baz"]);
+    ///
+    /// textarea.move_cursor(CursorMove::End);
+    ///
+    /// textarea.delete_big_word();
+    /// assert_eq!(textarea.lines(), ["cd "]);
+    /// assert_eq!(textarea.yank_text(), "foo/bar/baz");
+    /// ```
+    pub fn delete_big_word(&mut self) -> bool {
+        if self.needs_guard() {
+            return self.guard(false, |t| t.delete_big_word());
+        }
+        self.delete_word_with(find_big_word_start_backward)
+    }
+
+    fn delete_word_with(&mut self, find_start: fn(&str, usize) -> Option<usize>) -> bool {
         if self.delete_selection(false) {
             return true;
         }
         let (r, c) = self.cursor;
-        if let Some(col) = find_word_start_backward(&self.lines[r], c) {
+        if let Some(col) = find_start(&self.lines[r], c) {
             self.delete_piece(col, c - col)
         } else if c > 0 {
             self.delete_piece(0, c)
@@ -1286,12 +2429,41 @@ impl<'a> TextArea<'a> {
     /// assert_eq!(textarea.lines(), [" ccc"]);
     /// ```
     pub fn delete_next_word(&mut self) -> bool {
+        if self.needs_guard() {
+            return self.guard(false, |t| t.delete_next_word());
+        }
+        self.delete_next_word_with(find_word_exclusive_end_forward)
+    }
+
+    /// Delete a WORD next to cursor. WORD is a sequence of non-whitespace characters, like WORD of Vim. For example
+    /// `cd foo/bar/baz` consists of WORDs `cd` and `foo/bar/baz`. When the cursor is at end of line, the newline next
+    /// to the cursor will be removed.
+    ///
+    /// This method returns if some text was deleted or not in the textarea.
+    ///
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["foo/bar/baz qux"]);
+    ///
+    /// textarea.delete_next_big_word();
+    /// assert_eq!(textarea.lines(), [" qux"]);
+    /// assert_eq!(textarea.yank_text(), "foo/bar/baz");
+    /// ```
+    pub fn delete_next_big_word(&mut self) -> bool {
+        if self.needs_guard() {
+            return self.guard(false, |t| t.delete_next_big_word());
+        }
+        self.delete_next_word_with(find_big_word_exclusive_end_forward)
+    }
+
+    fn delete_next_word_with(&mut self, find_end: fn(&str, usize) -> Option<usize>) -> bool {
         if self.delete_selection(false) {
             return true;
         }
         let (r, c) = self.cursor;
         let line = &self.lines[r];
-        if let Some(col) = find_word_exclusive_end_forward(line, c) {
+        if let Some(col) = find_end(line, c) {
             self.delete_piece(c, col - c)
         } else {
             let end_col = line.chars().count();
@@ -1306,1097 +2478,5596 @@ impl<'a> TextArea<'a> {
         }
     }
 
-    /// Paste a string previously deleted by [`TextArea::delete_line_by_head`], [`TextArea::delete_line_by_end`],
-    /// [`TextArea::delete_word`], [`TextArea::delete_next_word`]. This method returns if some text was inserted or not
-    /// in the textarea.
+    /// Replace the character under the cursor with the character without entering insert mode, like 'r' of Vim. The
+    /// cursor stays on the replaced character. When the character is a newline, the character under the cursor is
+    /// replaced with a line break and the cursor moves to the head of the next line, like 'r<Enter>' of Vim. Nothing
+    /// happens when the cursor is at the end of line. The ongoing selection is cancelled.
+    ///
+    /// This method returns if some text was modified or not in the textarea.
+    ///
     /// ```
     /// use tui_textarea::{TextArea, CursorMove};
     ///
-    /// let mut textarea = TextArea::from(["aaa bbb ccc"]);
+    /// let mut textarea = TextArea::from(["abc"]);
+    ///
+    /// textarea.move_cursor(CursorMove::Forward);
+    /// assert!(textarea.replace_char('x'));
+    /// assert_eq!(textarea.lines(), ["axc"]);
+    /// assert_eq!(textarea.cursor(), (0, 1));
+    ///
+    /// assert!(textarea.replace_char('\n'));
+    /// assert_eq!(textarea.lines(), ["a", "c"]);
+    /// assert_eq!(textarea.cursor(), (1, 0));
     ///
-    /// textarea.delete_next_word();
     /// textarea.move_cursor(CursorMove::End);
-    /// textarea.paste();
-    /// assert_eq!(textarea.lines(), [" bbb cccaaa"]);
+    /// assert!(!textarea.replace_char('y'));
     /// ```
-    pub fn paste(&mut self) -> bool {
-        self.delete_selection(false);
-        match self.yank.clone() {
-            YankText::Piece(s) => self.insert_piece(s),
-            YankText::Chunk(c) => self.insert_chunk(c),
+    pub fn replace_char(&mut self, c: char) -> bool {
+        if self.needs_guard() {
+            return self.guard(false, |t| t.replace_char(c));
+        }
+        self.cancel_selection();
+        let (row, col) = self.cursor;
+        if col >= self.lines[row].chars().count() {
+            return false;
+        }
+        let start = Pos::new(row, col, self.line_offset(row, col));
+        let end = Pos::new(row, col + 1, self.line_offset(row, col + 1));
+        self.remove_range(start, end, false);
+        if c == '\n' || c == '\r' {
+            self.insert_chunk(vec![String::new(), String::new()]);
+        } else {
+            self.insert_piece(c.to_string());
+            self.cursor = (row, col);
         }
+        true
     }
 
-    /// Start text selection at the cursor position. If text selection is already ongoing, the start position is reset.
+    /// Add `delta` to the number under the cursor or the first number after the cursor in the cursor line, like
+    /// Ctrl-A and Ctrl-X of Vim. Decimal numbers with an optional leading `-` and hexadecimal numbers starting with
+    /// `0x` are supported. Leading zeros are kept by padding the result to the same width. The cursor moves to the
+    /// last character of the number. This method returns `false` when no number is found.
+    ///
     /// ```
-    /// use tui_textarea::{TextArea, CursorMove};
+    /// use tui_textarea::TextArea;
     ///
-    /// let mut textarea = TextArea::from(["aaa bbb ccc"]);
+    /// let mut textarea = TextArea::from(["let x = 9;"]);
     ///
-    /// textarea.start_selection();
-    /// textarea.move_cursor(CursorMove::WordForward);
-    /// textarea.copy();
-    /// assert_eq!(textarea.yank_text(), "aaa ");
+    /// textarea.increment_number(1);
+    /// assert_eq!(textarea.lines(), ["let x = 10;"]);
+    /// assert_eq!(textarea.cursor(), (0, 9));
+    ///
+    /// textarea.increment_number(-20);
+    /// assert_eq!(textarea.lines(), ["let x = -10;"]);
     /// ```
-    pub fn start_selection(&mut self) {
-        self.selection_start = Some(self.cursor);
+    pub fn increment_number(&mut self, delta: i64) -> bool {
+        if self.needs_guard() {
+            return self.guard(false, |t| t.increment_number(delta));
+        }
+        let (row, col) = self.cursor;
+        let (start, end, text) = match increment_number(&self.lines[row], col, delta) {
+            Some(found) => found,
+            None => return false,
+        };
+        let len = text.chars().count();
+        let start = Pos::new(row, start, self.line_offset(row, start));
+        let end = Pos::new(row, end, self.line_offset(row, end));
+        let modified = self.lines[row][start.offset..end.offset] != text;
+        if modified {
+            self.remove_range(start, end, false);
+            self.insert_piece(text);
+        }
+        self.cursor = (row, start.col + len - 1);
+        modified
     }
 
-    /// Stop the current text selection. This method does nothing if text selection is not ongoing.
+    /// Swap the character before the cursor with the character under the cursor and move the cursor forward, like
+    /// Ctrl+T of Emacs. When the cursor is at the end of line, the two characters before the cursor are swapped and the
+    /// cursor does not move. Nothing happens when the cursor is at the head of line or the line has less than two
+    /// characters. The ongoing selection is cancelled when the characters are swapped.
+    ///
+    /// This method returns if some text was modified or not in the textarea.
+    ///
     /// ```
     /// use tui_textarea::{TextArea, CursorMove};
     ///
-    /// let mut textarea = TextArea::from(["aaa bbb ccc"]);
-    ///
-    /// textarea.start_selection();
-    /// textarea.move_cursor(CursorMove::WordForward);
+    /// let mut textarea = TextArea::from(["abcd"]);
     ///
-    /// // Cancel the ongoing text selection
-    /// textarea.cancel_selection();
+    /// textarea.move_cursor(CursorMove::Forward);
+    /// textarea.transpose_chars();
+    /// assert_eq!(textarea.lines(), ["bacd"]);
+    /// assert_eq!(textarea.cursor(), (0, 2));
     ///
-    /// // As the result, this `copy` call does nothing
-    /// textarea.copy();
-    /// assert_eq!(textarea.yank_text(), "");
+    /// textarea.move_cursor(CursorMove::End);
+    /// textarea.transpose_chars();
+    /// assert_eq!(textarea.lines(), ["badc"]);
+    /// assert_eq!(textarea.cursor(), (0, 4));
     /// ```
-    pub fn cancel_selection(&mut self) {
-        self.selection_start = None;
+    pub fn transpose_chars(&mut self) -> bool {
+        if self.needs_guard() {
+            return self.guard(false, |t| t.transpose_chars());
+        }
+        let (row, col) = self.cursor;
+        let len = self.lines[row].chars().count();
+        if col == 0 || len < 2 {
+            return false;
+        }
+        self.cancel_selection();
+        let start = if col < len { col - 1 } else { len - 2 };
+        let pair: Vec<char> = self.lines[row].chars().skip(start).take(2).collect();
+        let swapped: String = pair.iter().rev().collect();
+        let start = Pos::new(row, start, self.line_offset(row, start));
+        let end = Pos::new(row, start.col + 2, self.line_offset(row, start.col + 2));
+        self.remove_range(start, end, false);
+        self.insert_piece(swapped)
     }
 
-    /// Select the entire text. Cursor moves to the end of the text buffer. When text selection is already ongoing,
-    /// it is canceled.
+    /// Swap the cursor line with the previous line, like Ctrl+X Ctrl+T of Emacs. The cursor follows the moved line so
+    /// it moves up by one line. Nothing happens when the cursor is at the first line. The ongoing selection is
+    /// cancelled when the lines are swapped.
+    ///
+    /// This method returns if some text was modified or not in the textarea.
+    ///
     /// ```
     /// use tui_textarea::{TextArea, CursorMove};
     ///
-    /// let mut textarea = TextArea::from(["aaa", "bbb", "ccc"]);
+    /// let mut textarea = TextArea::from(["a", "b", "c"]);
     ///
-    /// textarea.select_all();
+    /// textarea.move_cursor(CursorMove::Bottom);
+    /// textarea.transpose_lines();
+    /// assert_eq!(textarea.lines(), ["a", "c", "b"]);
+    /// assert_eq!(textarea.cursor(), (1, 0));
     ///
-    /// // Cut the entire text;
-    /// textarea.cut();
+    /// textarea.transpose_lines();
+    /// assert_eq!(textarea.lines(), ["c", "a", "b"]);
+    /// assert_eq!(textarea.cursor(), (0, 0));
     ///
-    /// assert_eq!(textarea.lines(), [""]); // Buffer is now empty
-    /// assert_eq!(textarea.yank_text(), "aaa\nbbb\nccc");
+    /// assert!(!textarea.transpose_lines());
     /// ```
-    pub fn select_all(&mut self) {
-        self.move_cursor(CursorMove::Jump(u16::MAX, u16::MAX));
-        self.selection_start = Some((0, 0));
+    pub fn transpose_lines(&mut self) -> bool {
+        if self.needs_guard() {
+            return self.guard(false, |t| t.transpose_lines());
+        }
+        let (row, col) = self.cursor;
+        if row == 0 {
+            return false;
+        }
+        self.cancel_selection();
+        let prev = self.lines[row - 1].clone();
+        self.remove_range(Pos::new(row - 1, 0, 0), Pos::new(row, 0, 0), false);
+        self.insert_lines(row, vec![prev]);
+        self.cursor = (row - 1, col);
+        true
     }
 
-    /// Return if text selection is ongoing or not.
+    /// Duplicate the cursor line or the selected text. When nothing is selected, the cursor line is copied below it and
+    /// the cursor moves to the copy at the same column. When text is selected, a copy of the selected text is inserted
+    /// right after the selection and the selection stays on the original text. Line-wise selected lines are copied
+    /// below them and block-wise selected text is copied on the right of the block. The yanked text is not changed.
+    ///
+    /// This method returns if some text was modified or not in the textarea.
+    ///
     /// ```
-    /// use tui_textarea::{TextArea};
+    /// use tui_textarea::{TextArea, CursorMove};
     ///
-    /// let mut textarea = TextArea::default();
+    /// let mut textarea = TextArea::from(["let a = 1;"]);
     ///
-    /// assert!(!textarea.is_selecting());
+    /// textarea.move_cursor(CursorMove::End);
+    /// textarea.duplicate();
+    /// assert_eq!(textarea.lines(), ["let a = 1;", "let a = 1;"]);
+    /// assert_eq!(textarea.cursor(), (1, 10));
+    ///
+    /// // Select "1" and duplicate it
+    /// textarea.move_cursor(CursorMove::Back);
     /// textarea.start_selection();
-    /// assert!(textarea.is_selecting());
-    /// textarea.cancel_selection();
-    /// assert!(!textarea.is_selecting());
+    /// textarea.move_cursor(CursorMove::Back);
+    /// textarea.duplicate();
+    /// assert_eq!(textarea.lines(), ["let a = 1;", "let a = 11;"]);
     /// ```
-    pub fn is_selecting(&self) -> bool {
-        self.selection_start.is_some()
-    }
+    pub fn duplicate(&mut self) -> bool {
+        if self.needs_guard() {
+            return self.guard(false, |t| t.duplicate());
+        }
+        let cursor = self.cursor;
+        if let Some((top, bottom, left, right)) = self.block_selection() {
+            let block = self.block_text(top, bottom, left, right);
+            self.cursor = (top, right);
+            let modified = self.insert_block(block);
+            self.cursor = cursor;
+            return modified;
+        }
+        if let Some((first, last)) = self.line_selection_rows() {
+            let lines = self.lines[first..=last].to_vec();
+            self.insert_lines(last + 1, lines);
+            self.cursor = cursor;
+            return true;
+        }
+        if let Some((start, end)) = self.selection_positions() {
+            self.cursor = (end.row, end.col);
+            match self.range_text(start, end) {
+                YankText::Chunk(chunk) => self.insert_chunk(chunk),
+                text => self.insert_piece(text.to_string()),
+            };
+            self.cursor = cursor;
+            return true;
+        }
 
-    fn line_offset(&self, row: usize, col: usize) -> usize {
-        let line = self
-            .lines
-            .get(row)
-            .unwrap_or(&self.lines[self.lines.len() - 1]);
-        line.char_indices()
-            .nth(col)
-            .map(|(i, _)| i)
-            .unwrap_or(line.len())
+        let (row, col) = cursor;
+        let line = self.lines[row].clone();
+        self.insert_lines(row + 1, vec![line]);
+        self.cursor = (row + 1, col);
+        true
     }
 
-    /// Set the style used for text selection. The default style is light blue.
+    /// Move the cursor line up by one line, swapping it with the previous line. When text is selected, all the lines
+    /// touched by the selection are moved together. The cursor and the selection move along with the lines. Nothing
+    /// happens when the lines are already at the top.
+    ///
+    /// This method returns if some text was modified or not in the textarea.
+    ///
     /// ```
-    /// use tui_textarea::TextArea;
-    /// use ratatui::style::{Style, Color};
+    /// use tui_textarea::{TextArea, CursorMove};
     ///
-    /// let mut textarea = TextArea::default();
+    /// let mut textarea = TextArea::from(["a", "b", "c"]);
     ///
-    /// // Change the selection color from the default to Red
-    /// textarea.set_selection_style(Style::default().bg(Color::Red));
-    /// assert_eq!(textarea.selection_style(), Style::default().bg(Color::Red));
+    /// textarea.move_cursor(CursorMove::Down);
+    /// textarea.start_selection();
+    /// textarea.move_cursor(CursorMove::Down);
+    /// textarea.move_lines_up();
+    /// assert_eq!(textarea.lines(), ["b", "c", "a"]);
+    /// assert_eq!(textarea.selection_range(), Some(((0, 0), (1, 0))));
+    ///
+    /// assert!(!textarea.move_lines_up());
     /// ```
-    pub fn set_selection_style(&mut self, style: Style) {
-        self.select_style = style;
+    pub fn move_lines_up(&mut self) -> bool {
+        if self.needs_guard() {
+            return self.guard(false, |t| t.move_lines_up());
+        }
+        let rows = self.selected_rows();
+        if rows.start == 0 {
+            return false;
+        }
+        let (cursor, anchor) = (self.cursor, self.selection_start);
+        let prev = self.lines[rows.start - 1].clone();
+        self.remove_range(
+            Pos::new(rows.start - 1, 0, 0),
+            Pos::new(rows.start, 0, 0),
+            false,
+        );
+        self.insert_lines(rows.end - 1, vec![prev]);
+        self.cursor = (cursor.0 - 1, cursor.1);
+        self.selection_start = anchor.map(|(r, c)| (r - 1, c));
+        true
     }
 
-    /// Get the style used for text selection.
+    /// Move the cursor line down by one line, swapping it with the next line. When text is selected, all the lines
+    /// touched by the selection are moved together. The cursor and the selection move along with the lines. Nothing
+    /// happens when the lines are already at the bottom.
+    ///
+    /// This method returns if some text was modified or not in the textarea.
+    ///
     /// ```
     /// use tui_textarea::TextArea;
-    /// use ratatui::style::{Style, Color};
     ///
-    /// let mut textarea = TextArea::default();
+    /// let mut textarea = TextArea::from(["a", "b", "c"]);
     ///
-    /// assert_eq!(textarea.selection_style(), Style::default().bg(Color::LightBlue));
+    /// textarea.move_lines_down();
+    /// assert_eq!(textarea.lines(), ["b", "a", "c"]);
+    /// assert_eq!(textarea.cursor(), (1, 0));
+    ///
+    /// textarea.move_lines_down();
+    /// assert_eq!(textarea.lines(), ["b", "c", "a"]);
+    /// assert!(!textarea.move_lines_down());
     /// ```
-    pub fn selection_style(&mut self) -> Style {
-        self.select_style
+    pub fn move_lines_down(&mut self) -> bool {
+        if self.needs_guard() {
+            return self.guard(false, |t| t.move_lines_down());
+        }
+        let rows = self.selected_rows();
+        if rows.end == self.lines.len() {
+            return false;
+        }
+        let (cursor, anchor) = (self.cursor, self.selection_start);
+        let next = self.lines[rows.end].clone();
+        let (start, end) = if rows.end + 1 < self.lines.len() {
+            (Pos::new(rows.end, 0, 0), Pos::new(rows.end + 1, 0, 0))
+        } else {
+            // The next line is the last line. Remove it with the newline before it
+            let prev = &self.lines[rows.end - 1];
+            (
+                Pos::new(rows.end - 1, prev.chars().count(), prev.len()),
+                Pos::new(rows.end, next.chars().count(), next.len()),
+            )
+        };
+        self.remove_range(start, end, false);
+        self.insert_lines(rows.start, vec![next]);
+        // Keep the positions in the buffer even when they were not in the moved lines
+        self.cursor = self.fit_cursor((cursor.0 + 1, cursor.1));
+        self.selection_start = anchor.map(|(r, c)| self.fit_cursor((r + 1, c)));
+        true
     }
 
-    fn selection_positions(&self) -> Option<(Pos, Pos)> {
-        let (sr, sc) = self.selection_start?;
-        let (er, ec) = self.cursor;
-        let (so, eo) = (self.line_offset(sr, sc), self.line_offset(er, ec));
-        let s = Pos::new(sr, sc, so);
-        let e = Pos::new(er, ec, eo);
-        match (sr, so).cmp(&(er, eo)) {
-            Ordering::Less => Some((s, e)),
-            Ordering::Equal => None,
-            Ordering::Greater => Some((e, s)),
+    /// Join the `count` lines following the cursor line onto the cursor line, like 'J' of Vim. Leading whitespaces of
+    /// each joined line are replaced with one space. No space is inserted when the line before the join point is empty
+    /// or ends with whitespace, or when the joined line is empty or starts with ')'. The cursor moves to the last join
+    /// point. When fewer lines follow the cursor line, all of them are joined. Nothing happens at the last line. The
+    /// ongoing selection is cancelled when some lines are joined.
+    ///
+    /// This method returns if some text was modified or not in the textarea.
+    ///
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["fn foo(", "    a,", "    b", ")"]);
+    ///
+    /// textarea.join_lines(3);
+    /// assert_eq!(textarea.lines(), ["fn foo( a, b)"]);
+    /// assert_eq!(textarea.cursor(), (0, 12));
+    ///
+    /// assert!(!textarea.join_lines(1));
+    /// ```
+    pub fn join_lines(&mut self, count: usize) -> bool {
+        if self.needs_guard() {
+            return self.guard(false, |t| t.join_lines(count));
+        }
+        let row = self.cursor.0;
+        let mut modified = false;
+        for _ in 0..count {
+            if row + 1 >= self.lines.len() {
+                break;
+            }
+            let line = &self.lines[row];
+            let next = &self.lines[row + 1];
+            let indent = next.chars().take_while(|c| c.is_whitespace()).count();
+            let joined = &next[next
+                .char_indices()
+                .nth(indent)
+                .map_or(next.len(), |(i, _)| i)..];
+            let space = !line.is_empty()
+                && !line.ends_with(char::is_whitespace)
+                && !joined.is_empty()
+                && !joined.starts_with(')');
+
+            let col = line.chars().count();
+            let start = Pos::new(row, col, line.len());
+            let end = Pos::new(row + 1, indent, next.len() - joined.len());
+            self.remove_range(start, end, false);
+            if space {
+                self.insert_piece(" ".to_string());
+            }
+            self.cursor = (row, col);
+            modified = true;
+        }
+        if modified {
+            self.cancel_selection();
         }
+        modified
     }
 
-    fn take_selection_positions(&mut self) -> Option<(Pos, Pos)> {
-        let range = self.selection_positions();
-        self.cancel_selection();
-        range
+    /// Paste a string previously deleted by [`TextArea::delete_line_by_head`], [`TextArea::delete_line_by_end`],
+    /// [`TextArea::delete_word`], [`TextArea::delete_next_word`], [`TextArea::delete_big_word`],
+    /// [`TextArea::delete_next_big_word`]. This method returns if some text was inserted or not in the textarea.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["aaa bbb ccc"]);
+    ///
+    /// textarea.delete_next_word();
+    /// textarea.move_cursor(CursorMove::End);
+    /// textarea.paste();
+    /// assert_eq!(textarea.lines(), [" bbb cccaaa"]);
+    /// ```
+    pub fn paste(&mut self) -> bool {
+        let pasted = if self.needs_guard() {
+            self.guard(false, |t| t.paste_yank())
+        } else {
+            self.paste_yank()
+        };
+        self.last_paste = pasted.then(|| (self.cursor, self.history.changes()));
+        pasted
     }
 
-    /// Copy the selection text to the yank buffer. When nothing is selected, this method does nothing.
-    /// To get the yanked text, use [`TextArea::yank_text`].
+    /// Paste the yanked text `count` times as one modification, like `3p` in Vim. Undo removes all the pasted copies
+    /// at once. The cursor moves in the same way as calling [`TextArea::paste`] once with the repeated text. Line-wise
+    /// yanked text is pasted as the successive copies of the lines. When `count` is 0, this method does nothing. This
+    /// method returns if some text was inserted or not.
     /// ```
-    /// use tui_textarea::{TextArea, Key, Input, CursorMove};
-    ///
-    /// let mut textarea = TextArea::from(["Hello World"]);
+    /// use tui_textarea::TextArea;
     ///
-    /// // Start text selection at 'W'
-    /// textarea.move_cursor(CursorMove::WordForward);
-    /// textarea.start_selection();
+    /// let mut textarea = TextArea::from(["ab"]);
     ///
-    /// // Select the word "World" and copy the selected text
-    /// textarea.move_cursor(CursorMove::End);
-    /// textarea.copy();
+    /// textarea.set_yank_text("xy");
+    /// assert!(textarea.paste_n(3));
+    /// assert_eq!(textarea.lines(), ["xyxyxyab"]);
+    /// assert_eq!(textarea.cursor(), (0, 6));
     ///
-    /// assert_eq!(textarea.yank_text(), "World");
-    /// assert_eq!(textarea.lines(), ["Hello World"]); // Text does not change
+    /// textarea.undo();
+    /// assert_eq!(textarea.lines(), ["ab"]);
     /// ```
-    pub fn copy(&mut self) {
-        if let Some((start, end)) = self.take_selection_positions() {
-            if start.row == end.row {
-                self.yank = self.lines[start.row][start.offset..end.offset]
-                    .to_string()
-                    .into();
-                return;
-            }
-            let mut chunk = vec![self.lines[start.row][start.offset..].to_string()];
-            chunk.extend(self.lines[start.row + 1..end.row].iter().cloned());
-            chunk.push(self.lines[end.row][..end.offset].to_string());
-            self.yank = YankText::Chunk(chunk);
+    pub fn paste_n(&mut self, count: usize) -> bool {
+        if count == 0 {
+            return false;
         }
+        let repeated = self.yank.repeat(count);
+        let yank = mem::replace(&mut self.yank, repeated);
+        let pasted = self.paste();
+        self.yank = yank;
+        pasted
     }
 
-    /// Cut the selected text and place it in the yank buffer. This method returns whether the text was modified.
-    /// The cursor will move to the start position of the text selection.
-    /// To get the yanked text, use [`TextArea::yank_text`].
+    /// Replace the text inserted by the last [`TextArea::paste`] call with the next older text in the yank history,
+    /// like `M-y` in Emacs. The yank history rotates so calling this method repeatedly cycles through all texts in
+    /// the history. This method only works immediately after [`TextArea::paste`] or this method. When the text or the
+    /// cursor was changed after the paste, or when the yank history has less than two texts, this method does nothing.
+    /// This method returns whether the pasted text was replaced or not. The yank history is disabled by default. To
+    /// enable it, use [`TextArea::set_yank_history_size`].
+    ///
+    /// The pasted text is replaced by undoing the paste. The undo history needs to be enabled.
     /// ```
-    /// use tui_textarea::{TextArea, Key, Input, CursorMove};
+    /// use tui_textarea::{TextArea, CursorMove};
     ///
-    /// let mut textarea = TextArea::from(["Hello World"]);
+    /// let mut textarea = TextArea::from(["aaa bbb ccc"]);
+    /// textarea.set_yank_history_size(10);
     ///
-    /// // Start text selection at 'W'
-    /// textarea.move_cursor(CursorMove::WordForward);
-    /// textarea.start_selection();
+    /// textarea.delete_next_word();
+    /// textarea.delete_next_word();
+    /// assert_eq!(textarea.lines(), [" ccc"]);
     ///
-    /// // Select the word "World" and copy the selected text
     /// textarea.move_cursor(CursorMove::End);
-    /// textarea.cut();
+    /// textarea.paste();
+    /// assert_eq!(textarea.lines(), [" ccc bbb"]);
     ///
-    /// assert_eq!(textarea.yank_text(), "World");
-    /// assert_eq!(textarea.lines(), ["Hello "]);
+    /// assert!(textarea.paste_previous_yank());
+    /// assert_eq!(textarea.lines(), [" cccaaa"]);
+    ///
+    /// // The yank history rotates
+    /// assert!(textarea.paste_previous_yank());
+    /// assert_eq!(textarea.lines(), [" ccc bbb"]);
     /// ```
-    pub fn cut(&mut self) -> bool {
-        self.delete_selection(true)
+    pub fn paste_previous_yank(&mut self) -> bool {
+        match self.last_paste {
+            Some(last) if last == (self.cursor, self.history.changes()) => {}
+            _ => return false,
+        }
+        let len = self.yank_history.entries.len();
+        if len < 2 || !self.undo() {
+            return false;
+        }
+        let index = (self.yank_history.index + 1) % len;
+        self.yank = self.yank_history.entries[index].clone();
+        self.yank_history.index = index;
+        self.paste()
     }
 
-    fn delete_selection(&mut self, should_yank: bool) -> bool {
-        if let Some((s, e)) = self.take_selection_positions() {
-            self.delete_range(s, e, should_yank);
-            return true;
+    /// Paste the yanked text as complete lines below the cursor line regardless of the cursor column and the kind of
+    /// the yanked text. The cursor moves to the head of the first pasted line. The ongoing text selection is cancelled.
+    /// This method returns if some text was inserted or not. This is useful to implement `p` in Vim for line-wise
+    /// yanked text. See [`TextArea::yank_kind`].
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["abc", "def"]);
+    ///
+    /// textarea.set_yank_text("x\ny");
+    /// textarea.paste_linewise_below();
+    /// assert_eq!(textarea.lines(), ["abc", "x", "y", "def"]);
+    /// assert_eq!(textarea.cursor(), (1, 0));
+    /// ```
+    pub fn paste_linewise_below(&mut self) -> bool {
+        if self.needs_guard() {
+            return self.guard(false, |t| t.paste_linewise_below());
         }
-        false
+        let row = self.cursor.0 + 1;
+        self.paste_lines(row)
     }
 
-    /// Move the cursor to the position specified by the [`CursorMove`] parameter. For each kind of cursor moves, see
-    /// the document of [`CursorMove`].
+    /// Paste the yanked text as complete lines above the cursor line regardless of the cursor column and the kind of
+    /// the yanked text. The cursor moves to the head of the first pasted line. The ongoing text selection is cancelled.
+    /// This method returns if some text was inserted or not. This is useful to implement `P` in Vim for line-wise
+    /// yanked text. See [`TextArea::yank_kind`].
     /// ```
-    /// use tui_textarea::{TextArea, CursorMove};
+    /// use tui_textarea::TextArea;
     ///
     /// let mut textarea = TextArea::from(["abc", "def"]);
     ///
-    /// textarea.move_cursor(CursorMove::Forward);
-    /// assert_eq!(textarea.cursor(), (0, 1));
-    /// textarea.move_cursor(CursorMove::Down);
-    /// assert_eq!(textarea.cursor(), (1, 1));
+    /// textarea.set_yank_text("x");
+    /// textarea.paste_linewise_above();
+    /// assert_eq!(textarea.lines(), ["x", "abc", "def"]);
+    /// assert_eq!(textarea.cursor(), (0, 0));
     /// ```
-    pub fn move_cursor(&mut self, m: CursorMove) {
-        self.move_cursor_with_shift(m, self.selection_start.is_some());
+    pub fn paste_linewise_above(&mut self) -> bool {
+        if self.needs_guard() {
+            return self.guard(false, |t| t.paste_linewise_above());
+        }
+        let row = self.cursor.0;
+        self.paste_lines(row)
     }
 
-    fn move_cursor_with_shift(&mut self, m: CursorMove, shift: bool) {
-        if let Some(cursor) = m.next_cursor(self.cursor, &self.lines, &self.viewport) {
-            if shift {
-                if self.selection_start.is_none() {
-                    self.start_selection();
-                }
-            } else {
-                self.cancel_selection();
+    fn paste_lines(&mut self, row: usize) -> bool {
+        self.cancel_selection();
+        if self.yank.to_string().is_empty() {
+            return false;
+        }
+        let lines = self.yank.clone().into_lines();
+        self.insert_lines(row, lines)
+    }
+
+    fn paste_yank(&mut self) -> bool {
+        let line_selection = self.line_selection_rows();
+        self.delete_selection(false);
+        match self.yank.clone() {
+            YankText::Piece(s) => self.insert_piece(s),
+            YankText::Chunk(c) => self.insert_chunk(c),
+            YankText::Lines(l) => {
+                // Put the lines where the selected lines were
+                let row = line_selection.map_or(self.cursor.0, |(first, _)| first);
+                self.insert_lines(row, l)
             }
-            self.cursor = cursor;
+            YankText::Block(b) => self.insert_block(b),
         }
     }
 
-    /// Undo the last modification. This method returns if the undo modified text contents or not in the textarea.
+    /// Start text selection at the cursor position. If text selection is already ongoing, the start position is reset.
     /// ```
     /// use tui_textarea::{TextArea, CursorMove};
     ///
-    /// let mut textarea = TextArea::from(["abc def"]);
+    /// let mut textarea = TextArea::from(["aaa bbb ccc"]);
     ///
-    /// textarea.delete_next_word();
-    /// assert_eq!(textarea.lines(), [" def"]);
-    /// textarea.undo();
-    /// assert_eq!(textarea.lines(), ["abc def"]);
+    /// textarea.start_selection();
+    /// textarea.move_cursor(CursorMove::WordForward);
+    /// textarea.copy();
+    /// assert_eq!(textarea.yank_text(), "aaa ");
     /// ```
-    pub fn undo(&mut self) -> bool {
-        if let Some(cursor) = self.history.undo(&mut self.lines) {
-            self.cancel_selection();
-            self.cursor = cursor;
-            true
-        } else {
-            false
-        }
+    pub fn start_selection(&mut self) {
+        self.selection_start = Some(self.cursor);
+        self.selection_mode = SelectionMode::Char;
+        #[cfg(feature = "search")]
+        self.search.set_region(None);
     }
 
-    /// Redo the last undo change. This method returns if the redo modified text contents or not in the textarea.
+    /// Start line-wise text selection at the cursor line. The complete lines between the start line and the cursor
+    /// line are selected including their newlines regardless of the cursor column. [`TextArea::copy`] and
+    /// [`TextArea::cut`] yank the selected lines line-wise, and [`TextArea::paste`] inserts line-wise yanked text as
+    /// complete lines before the cursor line. If text selection is already ongoing, the start position is reset.
     /// ```
     /// use tui_textarea::{TextArea, CursorMove};
     ///
-    /// let mut textarea = TextArea::from(["abc def"]);
+    /// let mut textarea = TextArea::from(["aaa", "bbb", "ccc"]);
     ///
-    /// textarea.delete_next_word();
-    /// assert_eq!(textarea.lines(), [" def"]);
-    /// textarea.undo();
-    /// assert_eq!(textarea.lines(), ["abc def"]);
-    /// textarea.redo();
-    /// assert_eq!(textarea.lines(), [" def"]);
+    /// textarea.move_cursor(CursorMove::Forward);
+    /// textarea.start_line_selection();
+    /// textarea.move_cursor(CursorMove::Down);
+    /// assert_eq!(textarea.selection_range(), Some(((0, 0), (2, 0))));
+    ///
+    /// textarea.cut();
+    /// assert_eq!(textarea.lines(), ["ccc"]);
+    /// assert_eq!(textarea.yank_text(), "aaa\nbbb\n");
+    ///
+    /// // Paste the lines before the cursor line
+    /// textarea.move_cursor(CursorMove::End);
+    /// textarea.paste();
+    /// assert_eq!(textarea.lines(), ["aaa", "bbb", "ccc"]);
+    /// assert_eq!(textarea.cursor(), (0, 0));
     /// ```
-    pub fn redo(&mut self) -> bool {
-        if let Some(cursor) = self.history.redo(&mut self.lines) {
-            self.cancel_selection();
-            self.cursor = cursor;
-            true
-        } else {
-            false
-        }
+    pub fn start_line_selection(&mut self) {
+        self.start_selection();
+        self.selection_mode = SelectionMode::Line;
     }
 
-    pub(crate) fn line_spans<'b>(&'b self, line: &'b str, row: usize, lnum_len: u8) -> Line<'b> {
-        // Handle cursor on empty line explicitly
-        if line.is_empty() && row == self.cursor.0 && self.cursor.1 == 0 {
-            let mut spans = vec![Span::styled(" ", self.cursor_style)];
-            if let Some(lnum_style) = self.line_number_style {
-                let lnum = format!(" {:width$} ", row + 1, width = lnum_len as usize);
-                spans.insert(0, Span::styled(lnum, lnum_style));
-            }
-            return Line::from(spans);
-        }
-
-        let expanded_line = self.expand_tabs(line);
-
-        // Perform syntax highlighting (if enabled)
-        let mut base_spans: Vec<Span<'b>> = if let (
-            Some(highlighter),
-            Some(syntax_name),
-            Some(theme_name)
-        ) = (
-            self.syntax_highlighter.as_ref(),
-            self.syntax_name.as_ref(),
-            self.theme_name.as_ref(),
-        ) {
-             if let (Some(syntax), Some(theme)) = (
-                highlighter.find_syntax_by_name(syntax_name),
-                highlighter.get_theme(theme_name),
-            ) {
-                let mut h = HighlightLines::new(syntax, theme);
-                match h.highlight_line(line, &highlighter.syntax_set) {
-                    Ok(ranges) => ranges
-                        .into_iter()
-                        .map(|(style, content)| {
-                            let ratatui_style = syntect_style_to_ratatui(style);
-                            Span::styled(self.expand_tabs(content), ratatui_style)
-                        })
-                        .collect(),
-                    Err(_) => vec![Span::raw(expanded_line.clone())],
-                }
-            } else {
-                vec![Span::raw(expanded_line.clone())]
-            }
-        } else {
-            vec![Span::raw(expanded_line.clone())]
-        };
+    /// Start rectangular (block) text selection at the cursor position. The characters between the start column and
+    /// the cursor column are selected on each line between the start line and the cursor line. Columns are counted
+    /// in characters and lines shorter than the rectangle are selected only for their existing characters.
+    /// [`TextArea::copy`] and [`TextArea::cut`] yank the rectangle, and [`TextArea::paste`] inserts the yanked
+    /// rectangle at the cursor column on the successive lines. If text selection is already ongoing, the start
+    /// position is reset.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["abcd", "efgh", "ijkl"]);
+    ///
+    /// textarea.move_cursor(CursorMove::Forward);
+    /// textarea.start_block_selection();
+    /// textarea.move_cursor(CursorMove::Jump(1, 3));
+    ///
+    /// textarea.cut();
+    /// assert_eq!(textarea.lines(), ["ad", "eh", "ijkl"]);
+    /// assert_eq!(textarea.yank_text(), "bc\nfg");
+    ///
+    /// // Paste the rectangle at the cursor column
+    /// textarea.move_cursor(CursorMove::Jump(1, 0));
+    /// textarea.paste();
+    /// assert_eq!(textarea.lines(), ["ad", "bceh", "fgijkl"]);
+    /// ```
+    pub fn start_block_selection(&mut self) {
+        self.start_selection();
+        self.selection_mode = SelectionMode::Block;
+    }
 
-        // Apply Masking (if enabled)
-        if let Some(mask_char) = self.mask {
-            let mask_str = mask_char.to_string();
-            for span in &mut base_spans {
-                let span_char_count = span.content.chars().count();
-                span.content = repeat(mask_str.as_str()).take(span_char_count).collect::<String>().into();
-                span.style = self.style.patch(span.style);
-            }
-        } else {
-            for span in &mut base_spans {
-                 span.style = self.style.patch(span.style);
-            }
-        }
+    /// Stop the current text selection. This method does nothing if text selection is not ongoing.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["aaa bbb ccc"]);
+    ///
+    /// textarea.start_selection();
+    /// textarea.move_cursor(CursorMove::WordForward);
+    ///
+    /// // Cancel the ongoing text selection
+    /// textarea.cancel_selection();
+    ///
+    /// // As the result, this `copy` call does nothing
+    /// textarea.copy();
+    /// assert_eq!(textarea.yank_text(), "");
+    /// ```
+    pub fn cancel_selection(&mut self) {
+        self.selection_start = None;
+        self.selection_mode = SelectionMode::Char;
+        #[cfg(feature = "search")]
+        self.search.set_region(None);
+    }
 
-        // Apply other styles by patching over base_spans
-        let mut spans_with_range: Vec<(usize, usize, Span)> = Vec::with_capacity(base_spans.len());
-        let mut total_width = 0;
-        for span in base_spans {
-            let span_width = span.width();
-            let start_width_idx = total_width;
-            total_width += span_width;
-            let end_width_idx = total_width;
-            spans_with_range.push((start_width_idx, end_width_idx, span));
-        }
-        let line_total_width = total_width;
+    /// Select the entire text. Cursor moves to the end of the text buffer. When text selection is already ongoing,
+    /// it is canceled. When the buffer is empty, the selection is zero-width and [`TextArea::copy`] and
+    /// [`TextArea::cut`] do nothing.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["aaa", "bbb", "ccc"]);
+    ///
+    /// textarea.select_all();
+    ///
+    /// // Cut the entire text;
+    /// textarea.cut();
+    ///
+    /// assert_eq!(textarea.lines(), [""]); // Buffer is now empty
+    /// assert_eq!(textarea.yank_text(), "aaa\nbbb\nccc");
+    ///
+    /// // Selecting the empty buffer selects nothing
+    /// textarea.select_all();
+    /// assert_eq!(textarea.selection_range(), Some(((0, 0), (0, 0))));
+    /// assert!(!textarea.cut());
+    /// ```
+    pub fn select_all(&mut self) {
+        self.move_cursor(CursorMove::Jump(usize::MAX, usize::MAX));
+        self.start_selection();
+        self.selection_start = Some((0, 0));
+    }
 
-        let cursor_col = self.cursor.1;
-        let selection = self.selection_positions();
-        let mut patched_spans: Vec<Span<'b>> = Vec::with_capacity(spans_with_range.len());
+    /// Select the text between the `start` and `end` positions directly. The positions are 0-base character-wise
+    /// (row, col) values and they are clamped to the text buffer. The cursor moves to `end` so `start` can be after
+    /// `end`. The selection works as if it was started at `start` by [`TextArea::start_selection`] and the cursor
+    /// was moved to `end`.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["Hello World", "Goodbye World"]);
+    ///
+    /// textarea.set_selection_range((0, 6), (1, 7));
+    /// assert_eq!(textarea.cursor(), (1, 7));
+    /// assert_eq!(textarea.selection_range(), Some(((0, 6), (1, 7))));
+    ///
+    /// // Out-of-range positions are clamped
+    /// textarea.set_selection_range((9, 99), (1, 8));
+    /// assert_eq!(textarea.cursor(), (1, 8));
+    /// assert_eq!(textarea.selection_range(), Some(((1, 8), (1, 13))));
+    ///
+    /// textarea.copy();
+    /// assert_eq!(textarea.yank_text(), "World");
+    /// ```
+    pub fn set_selection_range(&mut self, start: (usize, usize), end: (usize, usize)) {
+        let start = self.fit_cursor(start);
+        self.cursor = self.fit_cursor(end);
+        self.start_selection();
+        self.selection_start = Some(start);
+    }
 
-        let mut expanded_cursor_width_offset = None;
-        let mut expanded_selection_width_range: Option<(usize, usize)> = None;
+    fn fit_cursor(&self, (row, col): (usize, usize)) -> (usize, usize) {
+        let row = row.min(self.lines.len() - 1);
+        let col = col.min(self.lines[row].chars().count());
+        (row, col)
+    }
 
-        let mut current_orig_col = 0;
-        let mut current_width = 0;
-        for c in line.chars() {
-            let char_width = if c == '\t' {
-                self.tab_len as usize - (current_width % self.tab_len as usize)
-            } else {
-                c.width().unwrap_or(0)
-            };
+    /// Return if text selection is ongoing or not.
+    /// ```
+    /// use tui_textarea::{TextArea};
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// assert!(!textarea.is_selecting());
+    /// textarea.start_selection();
+    /// assert!(textarea.is_selecting());
+    /// textarea.cancel_selection();
+    /// assert!(!textarea.is_selecting());
+    /// ```
+    pub fn is_selecting(&self) -> bool {
+        self.selection_start.is_some()
+    }
 
-            if row == self.cursor.0 && current_orig_col == cursor_col {
-                expanded_cursor_width_offset = Some(current_width);
-            }
+    /// Swap the selection start position and the cursor position. The selected region does not change but the
+    /// selection can be extended in the opposite direction. The viewport follows the cursor on the next rendering.
+    /// This method does nothing if text selection is not ongoing.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["aaa bbb ccc"]);
+    ///
+    /// textarea.move_cursor(CursorMove::Jump(0, 4));
+    /// textarea.start_selection();
+    /// textarea.move_cursor(CursorMove::End);
+    ///
+    /// textarea.swap_selection_ends();
+    /// assert_eq!(textarea.cursor(), (0, 4));
+    /// assert_eq!(textarea.selection_range(), Some(((0, 4), (0, 11))));
+    ///
+    /// // Extend the selection backward
+    /// textarea.move_cursor(CursorMove::Head);
+    /// assert_eq!(textarea.selection_range(), Some(((0, 0), (0, 11))));
+    /// ```
+    pub fn swap_selection_ends(&mut self) {
+        if let Some(start) = self.selection_start {
+            self.selection_start = Some(self.cursor);
+            self.cursor = start;
+        }
+    }
 
-            if let Some((start_pos, end_pos)) = selection {
-                let sel_start_match = start_pos.row < row || (start_pos.row == row && current_orig_col >= start_pos.col);
-                let sel_end_match = end_pos.row > row || (end_pos.row == row && current_orig_col < end_pos.col);
+    /// Expand the selection to cover the enclosing `unit` of text. When text selection is not ongoing, a selection
+    /// around the cursor is created. The selection becomes character-wise and the cursor moves to the end of the
+    /// selection. This method returns if the selection was expanded or not. The expansion can be undone by
+    /// [`TextArea::shrink_selection`].
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove, SelectionUnit};
+    ///
+    /// let mut textarea = TextArea::from(["foo bar", "baz", "", "qux"]);
+    /// textarea.move_cursor(CursorMove::Jump(0, 5));
+    ///
+    /// textarea.expand_selection(SelectionUnit::Word);
+    /// assert_eq!(textarea.selection_range(), Some(((0, 4), (0, 7))));
+    ///
+    /// textarea.expand_selection(SelectionUnit::Line);
+    /// assert_eq!(textarea.selection_range(), Some(((0, 0), (0, 7))));
+    ///
+    /// textarea.expand_selection(SelectionUnit::Paragraph);
+    /// assert_eq!(textarea.selection_range(), Some(((0, 0), (1, 3))));
+    ///
+    /// // Shrink the selection back to the word
+    /// textarea.shrink_selection();
+    /// textarea.shrink_selection();
+    /// assert_eq!(textarea.selection_range(), Some(((0, 4), (0, 7))));
+    /// ```
+    pub fn expand_selection(&mut self, unit: SelectionUnit) -> bool {
+        let before = self.selection_state();
+        if !matches!(self.selection_expansions.last(), Some((_, after)) if *after == before) {
+            self.selection_expansions.clear();
+        }
 
-                if sel_start_match && sel_end_match {
-                    match expanded_selection_width_range {
-                        None => expanded_selection_width_range = Some((current_width, current_width + char_width)),
-                        Some((start, _)) => expanded_selection_width_range = Some((start, current_width + char_width)),
-                    }
+        let range = self.selection_range();
+        let (start, end) = range.unwrap_or((self.cursor, self.cursor));
+        let eol = |row: usize| (row, self.lines[row].chars().count());
+        let (new_start, new_end) = match unit {
+            SelectionUnit::Word if start == end => {
+                let (row, col) = start;
+                let line = &self.lines[row];
+                let word = find_word_at(line, col)
+                    .or_else(|| col.checked_sub(1).and_then(|c| find_word_at(line, c)));
+                word.map_or((start, end), |(s, e)| ((row, s), (row, e)))
+            }
+            SelectionUnit::Word => {
+                let (sr, sc) = start;
+                let sc = find_word_at(&self.lines[sr], sc).map_or(sc, |(s, _)| s);
+                let (er, ec) = end;
+                let ec = ec
+                    .checked_sub(1)
+                    .and_then(|c| find_word_at(&self.lines[er], c))
+                    .map_or(ec, |(_, e)| e);
+                ((sr, sc), (er, ec))
+            }
+            SelectionUnit::Line => ((start.0, 0), eol(end.0)),
+            SelectionUnit::Paragraph => {
+                let is_empty = |row: usize| self.lines[row].is_empty();
+                let mut first = start.0;
+                while first > 0 && is_empty(first - 1) == is_empty(start.0) {
+                    first -= 1;
                 }
+                let mut last = end.0;
+                while last + 1 < self.lines.len() && is_empty(last + 1) == is_empty(end.0) {
+                    last += 1;
+                }
+                ((first, 0), eol(last))
             }
+            SelectionUnit::Buffer => ((0, 0), eol(self.lines.len() - 1)),
+        };
 
-            current_orig_col += 1;
-            current_width += char_width;
-        }
-        if row == self.cursor.0 && current_orig_col == cursor_col {
-            expanded_cursor_width_offset = Some(current_width);
-        }
-        if let Some((start_pos, end_pos)) = selection {
-             if (start_pos.row < row || (start_pos.row == row && current_orig_col >= start_pos.col)) &&
-                (end_pos.row > row || (end_pos.row == row && current_orig_col == end_pos.col))
-             {
-                 match expanded_selection_width_range {
-                     None => expanded_selection_width_range = Some((current_width, current_width)),
-                     Some((start, _)) => expanded_selection_width_range = Some((start, current_width)),
-                 }
-             }
+        let unchanged = if self.selection_mode == SelectionMode::Char && range.is_some() {
+            (new_start, new_end) == (start, end)
+        } else {
+            new_start == new_end
+        };
+        if unchanged {
+            return false;
         }
+        self.set_selection_range(new_start, new_end);
+        let after = self.selection_state();
+        self.selection_expansions.push((before, after));
+        true
+    }
 
-        for (span_start_width, span_end_width, mut span) in spans_with_range {
-            let mut is_cursor_span = false;
-
-            // Apply search highlights
-            #[cfg(feature = "search")]
-            if let Some(re) = &self.search.pat {
-                for m in re.find_iter(&expanded_line) {
-                    let match_start_byte = m.start();
-                    let match_end_byte = m.end();
-                    let match_start_width = expanded_line[..match_start_byte].width();
-                    let match_end_width = match_start_width + expanded_line[match_start_byte..match_end_byte].width();
-                    if match_start_width < span_end_width && match_end_width > span_start_width {
-                        span.style = span.style.patch(self.search.style);
-                    }
-                }
+    /// Undo the last expansion of the selection by [`TextArea::expand_selection`]. The selection and the cursor
+    /// before the expansion are restored. This method returns if the selection was shrunk or not. When the selection
+    /// or the cursor was changed after the last expansion, this method does nothing.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove, SelectionUnit};
+    ///
+    /// let mut textarea = TextArea::from(["foo bar"]);
+    ///
+    /// textarea.expand_selection(SelectionUnit::Word);
+    /// assert_eq!(textarea.selection_range(), Some(((0, 0), (0, 3))));
+    ///
+    /// assert!(textarea.shrink_selection());
+    /// assert!(!textarea.is_selecting());
+    /// assert!(!textarea.shrink_selection());
+    /// ```
+    pub fn shrink_selection(&mut self) -> bool {
+        let current = self.selection_state();
+        match self.selection_expansions.pop() {
+            Some(((start, cursor, mode), after)) if after == current => {
+                self.cancel_selection();
+                // Positions may be out of the text when the text was replaced after the expansion
+                self.selection_start = start.map(|pos| self.fit_cursor(pos));
+                self.selection_mode = mode;
+                self.cursor = self.fit_cursor(cursor);
+                true
             }
-
-            // Apply selection highlight
-            if let Some((sel_start_width, sel_end_width)) = expanded_selection_width_range {
-                if sel_start_width < span_end_width && sel_end_width > span_start_width {
-                    span.style = span.style.patch(self.select_style);
-                }
+            _ => {
+                self.selection_expansions.clear();
+                false
             }
+        }
+    }
 
-            // Apply cursor line style
-            if row == self.cursor.0 {
-                span.style = span.style.patch(self.cursor_line_style);
-            }
-
-            // Check if cursor falls within this span and split if necessary
-            if row == self.cursor.0 {
-                if let Some(exp_cursor_width) = expanded_cursor_width_offset {
-                    if exp_cursor_width >= span_start_width && exp_cursor_width < span_end_width {
-                        is_cursor_span = true;
-                        let mut current_span_width = 0;
-                        let mut target_byte_offset = 0;
-                        let mut target_char_len = 0;
-                        let mut char_idx_in_span = 0;
-                        for (char_i, (byte_offset, c)) in span.content.char_indices().enumerate() {
-                             let char_width = c.width().unwrap_or(0);
-                             let char_start_width_in_span = current_span_width;
-                             let char_end_width_in_span = current_span_width + char_width;
-                             current_span_width = char_end_width_in_span;
-                             if exp_cursor_width >= (span_start_width + char_start_width_in_span)
-                                 && exp_cursor_width < (span_start_width + char_end_width_in_span)
-                             {
-                                 target_byte_offset = byte_offset;
-                                 target_char_len = c.len_utf8();
-                                 char_idx_in_span = char_i;
-                                 break;
-                             }
-                        }
-
-                        // Split the span content and clone slices into owned Strings
-                        let content_str = span.content.as_ref(); 
-                        let before_cursor = &content_str[..target_byte_offset];
-                        let cursor_char_str = &content_str[target_byte_offset..target_byte_offset + target_char_len];
-                        let after_cursor = &content_str[target_byte_offset + target_char_len..];
-
-                        if !before_cursor.is_empty() {
-                            patched_spans.push(Span::styled(String::from(before_cursor), span.style)); // Clone slice
-                        }
-                        // Apply cursor style directly to the character under the cursor (block cursor)
-                        patched_spans.push(Span::styled(String::from(cursor_char_str), self.cursor_style)); // Clone slice & apply style
-                        if !after_cursor.is_empty() {
-                            patched_spans.push(Span::styled(String::from(after_cursor), span.style)); // Clone slice
-                        }
-
-                    // Correctly handle cursor at the end of the line
-                    } else if exp_cursor_width == line_total_width && span_end_width == line_total_width {
-                        is_cursor_span = true;
-                        // Push the original span content first
-                        if !span.content.is_empty() {
-                            patched_spans.push(span.clone()); // Clone the span
-                        }
-                        // Then add the styled space for the cursor *after* the content
-                        patched_spans.push(Span::styled(String::from(" "), self.cursor_style)); // Clone space
-                    }
-                }
-            }
+    /// Get the range of the text object around the cursor as a pair of the start position and the exclusive end
+    /// position. `None` is returned when the text object is not found. The range can be passed to
+    /// [`TextArea::delete_range`] or [`TextArea::set_selection_range`] to implement Vim's operations like `diw`, `ci"`
+    /// and `da(`. See [`TextObject`] for the text objects.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove, TextObject};
+    ///
+    /// let mut textarea = TextArea::from([r#"f("foo bar", x)"#]);
+    /// textarea.move_cursor(CursorMove::Jump(0, 4));
+    ///
+    /// assert_eq!(textarea.text_object_range(TextObject::InnerWord), Some(((0, 3), (0, 6))));
+    /// assert_eq!(textarea.text_object_range(TextObject::InnerQuote('"')), Some(((0, 3), (0, 10))));
+    /// assert_eq!(textarea.text_object_range(TextObject::APair('(', ')')), Some(((0, 1), (0, 15))));
+    /// assert_eq!(textarea.text_object_range(TextObject::InnerPair('[', ']')), None);
+    ///
+    /// // Implement `ci"`
+    /// let (start, end) = textarea.text_object_range(TextObject::InnerQuote('"')).unwrap();
+    /// textarea.delete_range(start, end);
+    /// textarea.insert_str("baz");
+    /// assert_eq!(textarea.lines(), [r#"f("baz", x)"#]);
+    /// ```
+    pub fn text_object_range(&self, obj: TextObject) -> Option<((usize, usize), (usize, usize))> {
+        obj.range(&self.lines, self.cursor)
+    }
 
-            // If the cursor was not in this span, push a clone of the original span
-            if !is_cursor_span {
-                 patched_spans.push(span.clone()); // Clone the span
+    /// Select the text object around the cursor. The cursor moves to the end of the text object. This method returns
+    /// `false` and does nothing when the text object is not found. See [`TextArea::text_object_range`] for more
+    /// details.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove, TextObject};
+    ///
+    /// let mut textarea = TextArea::from(["foo(bar, baz)"]);
+    /// textarea.move_cursor(CursorMove::Jump(0, 5));
+    ///
+    /// assert!(textarea.select_text_object(TextObject::InnerPair('(', ')')));
+    /// textarea.cut();
+    /// assert_eq!(textarea.lines(), ["foo()"]);
+    /// ```
+    pub fn select_text_object(&mut self, obj: TextObject) -> bool {
+        match self.text_object_range(obj) {
+            Some((start, end)) => {
+                self.set_selection_range(start, end);
+                true
             }
+            None => false,
         }
+    }
 
-        // Prepend line number
-        if let Some(lnum_style) = self.line_number_style {
-            let lnum = format!(" {:width$} ", row + 1, width = lnum_len as usize);
-            patched_spans.insert(0, Span::styled(lnum, lnum_style));
-        }
-
-        Line::from(patched_spans)
+    fn selection_state(&self) -> SelectionState {
+        (self.selection_start, self.cursor, self.selection_mode)
     }
 
-    /// Build a ratatui (or tui-rs) widget to render the current state of the textarea. The widget instance returned
-    /// from this method can be rendered with [`ratatui::Frame::render_widget`].
+    /// Return the mode of the ongoing text selection. When text selection is not ongoing, this method returns `None`.
+    /// ```
+    /// use tui_textarea::{TextArea, SelectionMode};
     ///
-    /// This method was deprecated at v0.5.3 and is no longer necessary. Instead you can directly pass `&TextArea`
-    /// reference to the `Frame::render_widget` method call.
-    /// ```no_run
-    /// # use ratatui::layout::Rect;
-    /// # use ratatui::Terminal;
-    /// # use ratatui::widgets::Widget as _;
-    /// # use ratatui::backend::CrosstermBackend;
-    /// # use tui_textarea::TextArea;
-    /// #
-    /// # let backend = CrosstermBackend::new(std::io::stdout());
-    /// # let mut term = Terminal::new(backend).unwrap();
-    /// # let textarea = TextArea::default();
-    /// #
-    /// # #[allow(deprecated)]
-    /// # term.draw(|f| {
-    /// #   let rect = Rect {
-    /// #       x: 0,
-    /// #       y: 0,
-    /// #       width: 24,
-    /// #       height: 8,
-    /// #   };
-    /// // v0.5.2 or earlier
-    /// f.render_widget(textarea.widget(), rect);
+    /// let mut textarea = TextArea::default();
+    /// assert_eq!(textarea.selection_mode(), None);
     ///
-    /// // v0.5.3 or later
-    /// f.render_widget(&textarea, rect);
-    /// # }).unwrap();
+    /// textarea.start_selection();
+    /// assert_eq!(textarea.selection_mode(), Some(SelectionMode::Char));
+    ///
+    /// textarea.start_line_selection();
+    /// assert_eq!(textarea.selection_mode(), Some(SelectionMode::Line));
+    /// ```
+    pub fn selection_mode(&self) -> Option<SelectionMode> {
+        self.selection_start.map(|_| self.selection_mode)
+    }
+
+    fn line_offset(&self, row: usize, col: usize) -> usize {
+        let line = self
+            .lines
+            .get(row)
+            .unwrap_or(&self.lines[self.lines.len() - 1]);
+        line.char_indices()
+            .nth(col)
+            .map(|(i, _)| i)
+            .unwrap_or(line.len())
+    }
+
+    /// Set the style used for text selection. The default style is light blue.
+    /// ```
+    /// use tui_textarea::TextArea;
+    /// use ratatui::style::{Style, Color};
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// // Change the selection color from the default to Red
+    /// textarea.set_selection_style(Style::default().bg(Color::Red));
+    /// assert_eq!(textarea.selection_style(), Style::default().bg(Color::Red));
+    /// ```
+    pub fn set_selection_style(&mut self, style: Style) {
+        self.select_style = style;
+    }
+
+    /// Get the style used for text selection.
+    /// ```
+    /// use tui_textarea::TextArea;
+    /// use ratatui::style::{Style, Color};
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// assert_eq!(textarea.selection_style(), Style::default().bg(Color::LightBlue));
+    /// ```
+    pub fn selection_style(&mut self) -> Style {
+        self.select_style
+    }
+
+    /// Set the style used for text selection while the textarea is not focused. See [`TextArea::set_focused`]. The
+    /// default style is dark gray.
+    /// ```
+    /// use tui_textarea::TextArea;
+    /// use ratatui::style::{Style, Color};
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// textarea.set_inactive_selection_style(Style::default().bg(Color::Gray));
+    /// assert_eq!(textarea.inactive_selection_style(), Style::default().bg(Color::Gray));
+    /// ```
+    pub fn set_inactive_selection_style(&mut self, style: Style) {
+        self.inactive_select_style = style;
+    }
+
+    /// Get the style used for text selection while the textarea is not focused.
+    /// ```
+    /// use tui_textarea::TextArea;
+    /// use ratatui::style::{Style, Color};
+    ///
+    /// let textarea = TextArea::default();
+    ///
+    /// assert_eq!(textarea.inactive_selection_style(), Style::default().bg(Color::DarkGray));
+    /// ```
+    pub fn inactive_selection_style(&self) -> Style {
+        self.inactive_select_style
+    }
+
+    /// Set if the textarea is focused or not. This only affects rendering: while the textarea is not focused, text
+    /// selection is rendered with [`TextArea::inactive_selection_style`] and the cursor is rendered with
+    /// [`TextArea::unfocused_cursor_style`], which hides the cursor by default. This is useful when rendering multiple
+    /// textareas on the screen. The textarea is focused by default.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// assert!(textarea.is_focused());
+    ///
+    /// textarea.set_focused(false);
+    /// assert!(!textarea.is_focused());
+    /// ```
+    pub fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+
+    /// Return if the textarea is focused or not. See [`TextArea::set_focused`].
+    pub fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    /// Set the style of the cursor while the textarea is not focused. See [`TextArea::set_focused`]. The cursor is not
+    /// rendered while the textarea is not focused by default.
+    /// ```
+    /// use ratatui::buffer::Buffer;
+    /// use ratatui::layout::Rect;
+    /// use ratatui::style::{Color, Style};
+    /// use ratatui::widgets::Widget as _;
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["abc"]);
+    /// textarea.set_focused(false);
+    /// textarea.set_unfocused_cursor_style(Style::default().bg(Color::DarkGray));
+    ///
+    /// let r = Rect { x: 0, y: 0, width: 5, height: 1 };
+    /// let mut buf = Buffer::empty(r);
+    /// textarea.render(r, &mut buf);
+    /// assert_eq!(buf[(0, 0)].bg, Color::DarkGray);
+    /// ```
+    pub fn set_unfocused_cursor_style(&mut self, style: Style) {
+        self.unfocused_cursor_style = Some(style);
+    }
+
+    /// Remove the style of the cursor set by [`TextArea::set_unfocused_cursor_style`] to hide the cursor while the
+    /// textarea is not focused.
+    /// ```
+    /// use ratatui::style::{Color, Style};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// textarea.set_unfocused_cursor_style(Style::default().bg(Color::DarkGray));
+    /// textarea.remove_unfocused_cursor_style();
+    /// assert_eq!(textarea.unfocused_cursor_style(), None);
+    /// ```
+    pub fn remove_unfocused_cursor_style(&mut self) {
+        self.unfocused_cursor_style = None;
+    }
+
+    /// Get the style of the cursor while the textarea is not focused. `None` means the cursor is hidden.
+    pub fn unfocused_cursor_style(&self) -> Option<Style> {
+        self.unfocused_cursor_style
+    }
+
+    // Rows of the first and the last lines of the ongoing line-wise selection
+    fn line_selection_rows(&self) -> Option<(usize, usize)> {
+        if self.selection_mode != SelectionMode::Line {
+            return None;
+        }
+        let (row, _) = self.selection_start?;
+        let cursor = self.cursor.0;
+        Some((row.min(cursor), row.max(cursor)))
+    }
+
+    // Rows and columns of the ongoing block selection as `(top, bottom, left, right)`. `right` is exclusive
+    fn block_selection(&self) -> Option<(usize, usize, usize, usize)> {
+        if self.selection_mode != SelectionMode::Block {
+            return None;
+        }
+        let (r, c) = self.selection_start?;
+        let (cr, cc) = self.cursor;
+        Some((r.min(cr), r.max(cr), c.min(cc), c.max(cc)))
+    }
+
+    // Positions of the block selection columns on the line. The columns are clamped to the line length
+    fn block_row_positions(&self, row: usize, left: usize, right: usize) -> (Pos, Pos) {
+        let len = self.lines[row].chars().count();
+        let (left, right) = (left.min(len), right.min(len));
+        let start = Pos::new(row, left, self.line_offset(row, left));
+        let end = Pos::new(row, right, self.line_offset(row, right));
+        (start, end)
+    }
+
+    fn selection_positions(&self) -> Option<(Pos, Pos)> {
+        if let Some((top, bottom, left, right)) = self.block_selection() {
+            let (start, _) = self.block_row_positions(top, left, right);
+            let (_, end) = self.block_row_positions(bottom, left, right);
+            return Some((start, end));
+        }
+        if let Some((first, last)) = self.line_selection_rows() {
+            // The anchor may be stale after lines were removed
+            let last = cmp::min(last, self.lines.len() - 1);
+            let first = cmp::min(first, last);
+            let end = if last + 1 < self.lines.len() {
+                Pos::new(last + 1, 0, 0)
+            } else {
+                let line = &self.lines[last];
+                Pos::new(last, line.chars().count(), line.len())
+            };
+            return Some((Pos::new(first, 0, 0), end));
+        }
+        let (sr, sc) = self.selection_start?;
+        let (er, ec) = self.cursor;
+        let (so, eo) = (self.line_offset(sr, sc), self.line_offset(er, ec));
+        let s = Pos::new(sr, sc, so);
+        let e = Pos::new(er, ec, eo);
+        match (sr, so).cmp(&(er, eo)) {
+            Ordering::Less => Some((s, e)),
+            Ordering::Equal => None,
+            Ordering::Greater => Some((e, s)),
+        }
+    }
+
+    fn take_selection_positions(&mut self) -> Option<(Pos, Pos)> {
+        let range = self.selection_positions();
+        if self.selection_start.is_some() {
+            self.cancel_selection();
+        }
+        range
+    }
+
+    /// Copy the selection text to the yank buffer. When nothing is selected, this method does nothing.
+    /// To get the yanked text, use [`TextArea::yank_text`].
+    /// ```
+    /// use tui_textarea::{TextArea, Key, Input, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["Hello World"]);
+    ///
+    /// // Start text selection at 'W'
+    /// textarea.move_cursor(CursorMove::WordForward);
+    /// textarea.start_selection();
+    ///
+    /// // Select the word "World" and copy the selected text
+    /// textarea.move_cursor(CursorMove::End);
+    /// textarea.copy();
+    ///
+    /// assert_eq!(textarea.yank_text(), "World");
+    /// assert_eq!(textarea.lines(), ["Hello World"]); // Text does not change
+    /// ```
+    pub fn copy(&mut self) {
+        if let Some(yank) = self.selected_yank() {
+            self.set_yank(yank);
+        }
+        if self.selection_start.is_some() {
+            self.cancel_selection();
+        }
+    }
+
+    /// Copy the selection text and append it to the yank buffer instead of replacing the yank buffer, like `"Ay` in
+    /// Vim. When either the yanked text or the selected text is line-wise, the result is line-wise and its lines are
+    /// separated with newlines. Otherwise the texts are concatenated directly. When nothing is selected, this method
+    /// does nothing.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove, YankKind};
+    ///
+    /// let mut textarea = TextArea::from(["Hello World", "Goodbye"]);
+    ///
+    /// textarea.start_selection();
+    /// textarea.move_cursor(CursorMove::WordForward);
+    /// textarea.copy();
+    ///
+    /// textarea.move_cursor(CursorMove::Jump(0, 6));
+    /// textarea.start_selection();
+    /// textarea.move_cursor(CursorMove::End);
+    /// textarea.copy_append();
+    /// assert_eq!(textarea.yank_text(), "Hello World");
+    ///
+    /// // Appending line-wise text makes the yanked text line-wise
+    /// textarea.move_cursor(CursorMove::Down);
+    /// textarea.start_line_selection();
+    /// textarea.copy_append();
+    /// assert_eq!(textarea.yank_text(), "Hello World\nGoodbye\n");
+    /// assert_eq!(textarea.yank_kind(), YankKind::Line);
+    /// ```
+    pub fn copy_append(&mut self) {
+        self.copy_to(None, true);
+    }
+
+    /// Cut the selected text and append it to the yank buffer instead of replacing the yank buffer. The texts are
+    /// concatenated in the same way as [`TextArea::copy_append`]. This method returns whether the text was modified.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["foo bar baz"]);
+    ///
+    /// textarea.delete_next_word();
+    /// textarea.move_cursor(CursorMove::Jump(0, 5));
+    /// textarea.start_selection();
+    /// textarea.move_cursor(CursorMove::End);
+    /// textarea.cut_append();
+    /// assert_eq!(textarea.yank_text(), "foobaz");
+    /// assert_eq!(textarea.lines(), [" bar "]);
+    /// ```
+    pub fn cut_append(&mut self) -> bool {
+        self.cut_to(None, true)
+    }
+
+    /// Copy the selection text to the register named `name` instead of the yank buffer, like `"ay` in Vim. Any
+    /// character can be a name of register. The yank buffer and the yank history are not changed. The text in the
+    /// register can be pasted with [`TextArea::paste_from_register`]. When nothing is selected, this method does
+    /// nothing.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["Hello World"]);
+    ///
+    /// textarea.start_selection();
+    /// textarea.move_cursor(CursorMove::Jump(0, 5));
+    /// textarea.copy_to_register('a');
+    /// assert_eq!(textarea.register_text('a').as_deref(), Some("Hello"));
+    /// assert_eq!(textarea.yank_text(), "");
+    /// ```
+    pub fn copy_to_register(&mut self, name: char) {
+        self.copy_to(Some(name), false);
+    }
+
+    /// Copy the selection text and append it to the text in the register named `name`, like `"Ay` in Vim. The texts are
+    /// concatenated in the same way as [`TextArea::copy_append`]. When the register is empty, this method works in the
+    /// same way as [`TextArea::copy_to_register`].
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["foo", "bar"]);
+    ///
+    /// textarea.start_selection();
+    /// textarea.move_cursor(CursorMove::End);
+    /// textarea.copy_append_to_register('a');
+    ///
+    /// textarea.move_cursor(CursorMove::Down);
+    /// textarea.start_line_selection();
+    /// textarea.copy_append_to_register('a');
+    /// assert_eq!(textarea.register_text('a').as_deref(), Some("foo\nbar\n"));
+    /// ```
+    pub fn copy_append_to_register(&mut self, name: char) {
+        self.copy_to(Some(name), true);
+    }
+
+    /// Cut the selected text to the register named `name` instead of the yank buffer, like `"ad` in Vim. The yank
+    /// buffer and the yank history are not changed. This method returns whether the text was modified.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["foo bar"]);
+    ///
+    /// textarea.start_selection();
+    /// textarea.move_cursor(CursorMove::WordForward);
+    /// assert!(textarea.cut_to_register('a'));
+    /// assert_eq!(textarea.lines(), ["bar"]);
+    /// assert_eq!(textarea.register_text('a').as_deref(), Some("foo "));
+    /// ```
+    pub fn cut_to_register(&mut self, name: char) -> bool {
+        self.cut_to(Some(name), false)
+    }
+
+    /// Cut the selected text and append it to the text in the register named `name`, like `"Ad` in Vim. The texts are
+    /// concatenated in the same way as [`TextArea::copy_append`]. This method returns whether the text was modified.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["foo bar baz"]);
+    ///
+    /// textarea.start_selection();
+    /// textarea.move_cursor(CursorMove::WordForward);
+    /// textarea.cut_append_to_register('a');
+    /// textarea.move_cursor(CursorMove::WordForward);
+    /// textarea.start_selection();
+    /// textarea.move_cursor(CursorMove::End);
+    /// textarea.cut_append_to_register('a');
+    /// assert_eq!(textarea.lines(), ["bar "]);
+    /// assert_eq!(textarea.register_text('a').as_deref(), Some("foo baz"));
+    /// ```
+    pub fn cut_append_to_register(&mut self, name: char) -> bool {
+        self.cut_to(Some(name), true)
+    }
+
+    /// Paste the text in the register named `name` in the same way as [`TextArea::paste`], like `"ap` in Vim. The
+    /// yank buffer is not changed. When the register is empty, this method does nothing. This method returns if some
+    /// text was inserted or not.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["ab"]);
+    ///
+    /// textarea.start_selection();
+    /// textarea.move_cursor(CursorMove::Forward);
+    /// textarea.copy_to_register('x');
+    ///
+    /// textarea.move_cursor(CursorMove::End);
+    /// assert!(textarea.paste_from_register('x'));
+    /// assert_eq!(textarea.lines(), ["aba"]);
+    /// assert!(!textarea.paste_from_register('y'));
+    /// ```
+    pub fn paste_from_register(&mut self, name: char) -> bool {
+        let text = match self.registers.get(&name) {
+            Some(text) => text.clone(),
+            None => return false,
+        };
+        let yank = mem::replace(&mut self.yank, text);
+        let pasted = self.paste();
+        self.yank = yank;
+        pasted
+    }
+
+    /// Get the text in the register named `name`. Lines are joined with `\n` in the same way as
+    /// [`TextArea::yank_text`]. `None` is returned when nothing was stored in the register.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let textarea = TextArea::default();
+    /// assert_eq!(textarea.register_text('a'), None);
+    /// ```
+    pub fn register_text(&self, name: char) -> Option<String> {
+        self.registers.get(&name).map(|y| y.to_string())
+    }
+
+    // Copy the selected text to the register, or to the yank buffer when `register` is `None`
+    fn copy_to(&mut self, register: Option<char>, append: bool) {
+        if let Some(yank) = self.selected_yank() {
+            self.store_yank(yank, register, append);
+        }
+        if self.selection_start.is_some() {
+            self.cancel_selection();
+        }
+    }
+
+    // Cut the selected text to the register, or to the yank buffer when `register` is `None`
+    fn cut_to(&mut self, register: Option<char>, append: bool) -> bool {
+        if self.needs_guard() {
+            return self.guard(false, |t| t.cut_to(register, append));
+        }
+        let yank = self.selected_yank();
+        if !self.delete_selection(false) {
+            return false;
+        }
+        if let Some(yank) = yank {
+            self.store_yank(yank, register, append);
+        }
+        true
+    }
+
+    fn store_yank(&mut self, yank: YankText, register: Option<char>, append: bool) {
+        match register {
+            Some(name) if append => {
+                let text = self.registers.entry(name).or_default();
+                *text = mem::take(text).append(yank);
+            }
+            Some(name) => {
+                self.registers.insert(name, yank);
+            }
+            None if append => {
+                let appended = mem::take(&mut self.yank).append(yank);
+                self.set_yank(appended);
+            }
+            None => self.set_yank(yank),
+        }
+    }
+
+    // The selected text in the form of yanked text. `None` is returned when the selection is empty
+    fn selected_yank(&self) -> Option<YankText> {
+        if let Some((top, bottom, left, right)) = self.block_selection() {
+            return Some(YankText::Block(self.block_text(top, bottom, left, right)));
+        }
+        if let Some((first, last)) = self.line_selection_rows() {
+            return Some(YankText::Lines(self.lines[first..=last].to_vec()));
+        }
+        let (start, end) = self.selection_positions()?;
+        Some(self.range_text(start, end))
+    }
+
+    fn range_text(&self, start: Pos, end: Pos) -> YankText {
+        if start.row == end.row {
+            let piece = self.lines[start.row][start.offset..end.offset].to_string();
+            return piece.into();
+        }
+        let mut chunk = vec![self.lines[start.row][start.offset..].to_string()];
+        chunk.extend(self.lines[start.row + 1..end.row].iter().cloned());
+        chunk.push(self.lines[end.row][..end.offset].to_string());
+        YankText::Chunk(chunk)
+    }
+
+    /// Get the selected text without modifying the yank buffer nor canceling the selection. Lines of the selected
+    /// text are joined with `\n`. Line-wise selected text ends with `\n` and lines of block selected text are the
+    /// rectangle columns of each line. When text selection is not ongoing, this method returns `None`.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["Hello", "World"]);
+    /// assert_eq!(textarea.selected_text(), None);
+    ///
+    /// textarea.move_cursor(CursorMove::Jump(1, 3));
+    /// textarea.start_selection();
+    /// textarea.move_cursor(CursorMove::Jump(0, 1));
+    /// assert_eq!(textarea.selected_text().as_deref(), Some("ello\nWor"));
+    ///
+    /// // The selection and the yank buffer are not changed
+    /// assert!(textarea.is_selecting());
+    /// assert_eq!(textarea.yank_text(), "");
+    /// ```
+    pub fn selected_text(&self) -> Option<String> {
+        self.selection_start?;
+        let text = self.selected_yank().map(|y| y.to_string());
+        Some(text.unwrap_or_default())
+    }
+
+    /// Build an [OSC 52][osc52] escape sequence which sets the system clipboard to the selected text, or to the yanked
+    /// text when nothing is selected. Writing the sequence to the terminal copies the text even over SSH where the
+    /// system clipboard is not directly accessible. `None` is returned when the text is empty or when its base64
+    /// payload is longer than 100000 bytes, which many terminals reject. Inside tmux or GNU screen (detected by `TMUX`
+    /// and `TERM` environment variables), the sequence is wrapped in the passthrough sequence of the multiplexer so
+    /// that it reaches the outer terminal. It is split into chunks for screen. tmux needs `allow-passthrough` option
+    /// to be enabled.
+    ///
+    /// [osc52]: https://invisible-island.net/xterm/ctlseqs/ctlseqs.html#h3-Operating-System-Commands
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["foo bar"]);
+    /// assert_eq!(textarea.yank_as_osc52(), None);
+    ///
+    /// textarea.start_selection();
+    /// textarea.move_cursor(CursorMove::Jump(0, 3));
+    /// let seq = textarea.yank_as_osc52().unwrap();
+    /// assert!(seq.contains("\x1b]52;c;Zm9v\x07")); // Wrapped for passthrough inside tmux or screen
+    ///
+    /// // The sequence can be written to the terminal directly
+    /// // write!(stdout, "{}", seq)?;
+    /// ```
+    pub fn yank_as_osc52(&self) -> Option<String> {
+        let text = match self.selected_text() {
+            Some(text) if !text.is_empty() => text,
+            _ => self.yank_text(),
+        };
+        if text.is_empty() {
+            return None;
+        }
+        osc52::sequence(&text, osc52::Passthrough::detect())
+    }
+
+    /// Cut the selected text and place it in the yank buffer. This method returns whether the text was modified.
+    /// The cursor will move to the start position of the text selection.
+    /// To get the yanked text, use [`TextArea::yank_text`].
+    /// ```
+    /// use tui_textarea::{TextArea, Key, Input, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["Hello World"]);
+    ///
+    /// // Start text selection at 'W'
+    /// textarea.move_cursor(CursorMove::WordForward);
+    /// textarea.start_selection();
+    ///
+    /// // Select the word "World" and copy the selected text
+    /// textarea.move_cursor(CursorMove::End);
+    /// textarea.cut();
+    ///
+    /// assert_eq!(textarea.yank_text(), "World");
+    /// assert_eq!(textarea.lines(), ["Hello "]);
+    /// ```
+    pub fn cut(&mut self) -> bool {
+        if self.needs_guard() {
+            return self.guard(false, |t| t.cut());
+        }
+        self.delete_selection(true)
+    }
+
+    /// Copy the selection text to the yank buffer and the system clipboard. When nothing is selected, this method does
+    /// nothing. The yank buffer is updated even if the system clipboard is not available, for example on a headless
+    /// server. In the case, this method returns an error.
+    /// ```no_run
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["Hello World"]);
+    ///
+    /// textarea.start_selection();
+    /// textarea.move_cursor(CursorMove::Jump(0, 5));
+    /// textarea.copy_to_clipboard().unwrap();
+    /// assert_eq!(textarea.yank_text(), "Hello");
+    /// ```
+    #[cfg(feature = "clipboard")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "clipboard")))]
+    pub fn copy_to_clipboard(&mut self) -> Result<(), ClipboardError> {
+        if self.selection_start.is_none() {
+            return Ok(());
+        }
+        self.copy();
+        clipboard::set_text(&self.yank_text())
+    }
+
+    /// Cut the selected text and place it in the yank buffer and the system clipboard. This method returns whether
+    /// the text was modified. Like [`TextArea::copy_to_clipboard`], the text is cut into the yank buffer even if the
+    /// system clipboard is not available. In the case, this method returns an error.
+    /// ```no_run
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["Hello World"]);
+    ///
+    /// textarea.start_selection();
+    /// textarea.move_cursor(CursorMove::WordForward);
+    /// assert!(textarea.cut_to_clipboard().unwrap());
+    /// assert_eq!(textarea.lines(), ["World"]);
+    /// ```
+    #[cfg(feature = "clipboard")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "clipboard")))]
+    pub fn cut_to_clipboard(&mut self) -> Result<bool, ClipboardError> {
+        if !self.cut() {
+            return Ok(false);
+        }
+        clipboard::set_text(&self.yank_text())?;
+        Ok(true)
+    }
+
+    /// Paste the text in the system clipboard. The clipboard text replaces the yank buffer and then it is inserted as
+    /// [`TextArea::paste`] does. This method returns whether some text was inserted or not. When the system clipboard
+    /// is not available, this method returns an error and nothing is changed. Use [`TextArea::paste`] to fall back on
+    /// the yank buffer.
+    /// ```no_run
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// if textarea.paste_from_clipboard().is_err() {
+    ///     textarea.paste();
+    /// }
+    /// ```
+    #[cfg(feature = "clipboard")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "clipboard")))]
+    pub fn paste_from_clipboard(&mut self) -> Result<bool, ClipboardError> {
+        let text = clipboard::get_text()?;
+        self.set_yank_text(text);
+        Ok(self.paste())
+    }
+
+    fn delete_selection(&mut self, should_yank: bool) -> bool {
+        if let Some((top, bottom, left, right)) = self.block_selection() {
+            self.cancel_selection();
+            return self.delete_block(top, bottom, left, right, should_yank);
+        }
+        if let Some((first, last)) = self.line_selection_rows() {
+            self.cancel_selection();
+            return self.delete_lines(first, last, should_yank);
+        }
+        if let Some((s, e)) = self.take_selection_positions() {
+            self.remove_range(s, e, should_yank);
+            return true;
+        }
+        false
+    }
+
+    fn block_text(&self, top: usize, bottom: usize, left: usize, right: usize) -> Vec<String> {
+        (top..=bottom)
+            .map(|row| {
+                let (start, end) = self.block_row_positions(row, left, right);
+                self.lines[row][start.offset..end.offset].to_string()
+            })
+            .collect()
+    }
+
+    // Delete the columns between `left` and `right` on each line between the `top` and `bottom` rows as one edit
+    fn delete_block(
+        &mut self,
+        top: usize,
+        bottom: usize,
+        left: usize,
+        right: usize,
+        should_yank: bool,
+    ) -> bool {
+        if should_yank {
+            self.set_yank(YankText::Block(self.block_text(top, bottom, left, right)));
+        }
+        let mut modified = false;
+        self.history.begin_group(self.cursor);
+        for row in top..=bottom {
+            let (start, end) = self.block_row_positions(row, left, right);
+            if start.offset < end.offset {
+                self.remove_range(start, end, false);
+                modified = true;
+            }
+        }
+        self.cursor = (top, left.min(self.lines[top].chars().count()));
+        self.history.end_group(self.cursor);
+        modified
+    }
+
+    // Delete the complete lines between the `first` and `last` rows including their newlines
+    fn delete_lines(&mut self, first: usize, last: usize, should_yank: bool) -> bool {
+        if should_yank {
+            self.set_yank(YankText::Lines(self.lines[first..=last].to_vec()));
+        }
+        let eol = |row: usize| {
+            let line = &self.lines[row];
+            Pos::new(row, line.chars().count(), line.len())
+        };
+        let (start, end) = if last + 1 < self.lines.len() {
+            (Pos::new(first, 0, 0), Pos::new(last + 1, 0, 0))
+        } else if first > 0 {
+            // The last line has no newline after it so delete the newline before the first line instead
+            (eol(first - 1), eol(last))
+        } else {
+            (Pos::new(0, 0, 0), eol(last))
+        };
+        if start.row == end.row && start.offset == end.offset {
+            self.cursor = (first, 0);
+            return false;
+        }
+        self.remove_range(start, end, false);
+        self.cursor = (first.min(self.lines.len() - 1), 0);
+        true
+    }
+
+    /// Move the cursor to the position specified by the [`CursorMove`] parameter. For each kind of cursor moves, see
+    /// the document of [`CursorMove`].
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["abc", "def"]);
+    ///
+    /// textarea.move_cursor(CursorMove::Forward);
+    /// assert_eq!(textarea.cursor(), (0, 1));
+    /// textarea.move_cursor(CursorMove::Down);
+    /// assert_eq!(textarea.cursor(), (1, 1));
+    /// ```
+    pub fn move_cursor(&mut self, m: CursorMove) {
+        self.move_cursor_with_shift(m, self.selection_start.is_some());
+    }
+
+    /// Move the cursor by applying the [`CursorMove`] `n` times. This behaves the same as calling
+    /// [`TextArea::move_cursor`] `n` times, but it is more efficient for big counts. This is useful to implement
+    /// counts of Vim motions like `5j` or `3w`.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["abc def ghi", "jkl", "mno", "pqr"]);
+    ///
+    /// textarea.move_cursor_n(CursorMove::WordForward, 2);
+    /// assert_eq!(textarea.cursor(), (0, 8));
+    ///
+    /// // The cursor stops at the edge of the text
+    /// textarea.move_cursor_n(CursorMove::Down, 10);
+    /// assert_eq!(textarea.cursor(), (3, 3));
+    /// ```
+    pub fn move_cursor_n(&mut self, m: CursorMove, n: usize) {
+        self.move_cursor_n_with_shift(m, n, self.selection_start.is_some());
+    }
+
+    fn move_cursor_with_shift(&mut self, m: CursorMove, shift: bool) {
+        self.move_cursor_n_with_shift(m, 1, shift);
+    }
+
+    fn move_cursor_n_with_shift(&mut self, m: CursorMove, n: usize, shift: bool) {
+        if n == 0 {
+            return;
+        }
+        trace!(cursor_move = ?m, n, shift, "move cursor");
+        self.last_paste = None;
+        if m.is_find() {
+            self.last_find = Some(m);
+        }
+        let (row, col) = self.cursor;
+        let wrap = match m {
+            CursorMove::Up | CursorMove::Down if self.wrap && self.move_by_display_rows => {
+                self.viewport.wrap_width().map(|w| self.wrap_layout(w))
+            }
+            CursorMove::InViewport if self.wrap => {
+                self.viewport.wrap_width().map(|w| self.wrap_layout(w))
+            }
+            _ => None,
+        };
+        let mut desired_x = 0;
+        let next = match m {
+            CursorMove::Up | CursorMove::Down if wrap.is_some() => wrap
+                .and_then(|w| self.move_display_rows(m == CursorMove::Up, n, &w))
+                .map(|(c, x)| {
+                    desired_x = x;
+                    c
+                }),
+            CursorMove::InViewport if wrap.is_some() => wrap
+                .and_then(|w| self.cursor_in_wrapped_viewport(&w))
+                .map(|(c, x)| {
+                    desired_x = x;
+                    c
+                }),
+            // Vertical moves don't need to be repeated since the column is decided by the desired column
+            CursorMove::Up => (row > 0).then(|| (row.saturating_sub(n), col)),
+            CursorMove::Down => {
+                let last = self.lines.len() - 1;
+                (row < last).then(|| (row.saturating_add(n).min(last), col))
+            }
+            m => {
+                let mut next = None;
+                let mut cursor = self.cursor;
+                for _ in 0..n {
+                    let c = match self.step_cursor(m, cursor) {
+                        Some(c) => c,
+                        None => break,
+                    };
+                    if m != CursorMove::InViewport {
+                        self.record_jump(cursor, c);
+                    }
+                    cursor = c;
+                    next = Some(c);
+                }
+                next
+            }
+        };
+        if let Some(mut cursor) = next {
+            if shift {
+                if self.selection_start.is_none() {
+                    self.start_selection();
+                }
+            } else {
+                self.cancel_selection();
+            }
+            let desired_col = match self.desired_col {
+                Some((width, pos)) if pos == self.cursor => width,
+                _ => self.display_col(self.cursor),
+            };
+            match m {
+                CursorMove::Up | CursorMove::Down | CursorMove::InViewport if wrap.is_some() => {
+                    self.desired_col = Some((desired_x, cursor));
+                }
+                CursorMove::Up | CursorMove::Down | CursorMove::InViewport => {
+                    if cursor.0 != self.cursor.0 {
+                        cursor.1 = self.col_at_display_col(cursor.0, desired_col);
+                    }
+                    self.desired_col = Some((desired_col, cursor));
+                }
+                CursorMove::ParagraphForward | CursorMove::ParagraphBack => {
+                    self.desired_col = Some((desired_col, cursor));
+                }
+                _ => self.desired_col = None,
+            }
+            self.cursor = cursor;
+        }
+    }
+
+    fn step_cursor(&self, m: CursorMove, cursor: (usize, usize)) -> Option<(usize, usize)> {
+        match m {
+            CursorMove::RepeatFind | CursorMove::RepeatFindReverse => self.repeat_find(m, cursor),
+            CursorMove::InViewport => self.cursor_in_viewport(cursor),
+            m => m.next_cursor(cursor, &self.lines, &self.viewport),
+        }
+    }
+
+    // Move the cursor into the viewport for `CursorMove::InViewport`. The cursor is kept off the top and bottom edges
+    // of the viewport by the scroll margin unless the edges are at the start or the end of the text. Columns of the
+    // viewport are compared with the display columns of characters so that a double-width character is not put
+    // across the left or right edge
+    fn cursor_in_viewport(&self, (row, col): (usize, usize)) -> Option<(usize, usize)> {
+        let (_, _, _, height) = self.viewport.rect();
+        let (row_top, col_top, row_bottom, col_bottom) = self.viewport.position();
+        let margin = self.scroll_margin_in(height);
+        let top = if row_top == 0 { 0 } else { row_top + margin };
+        let bottom = if row_bottom >= self.lines.len() - 1 {
+            row_bottom
+        } else {
+            row_bottom.saturating_sub(margin)
+        };
+        let row = cmp::min(cmp::min(cmp::max(row, top), bottom), self.lines.len() - 1);
+
+        let col = cmp::min(col, self.lines[row].chars().count());
+        let x = self.display_col((row, col));
+        let col = if x < col_top {
+            let c = self.col_at_display_col(row, col_top);
+            if self.display_col((row, c)) < col_top {
+                cmp::min(c + 1, self.lines[row].chars().count())
+            } else {
+                c
+            }
+        } else if x > col_bottom {
+            let c = self.col_at_display_col(row, col_bottom);
+            let start = self.display_col((row, c));
+            if c > 0 && start > col_top && self.display_col((row, c + 1)) > col_bottom + 1 {
+                c - 1
+            } else {
+                c
+            }
+        } else {
+            col
+        };
+        Some((row, col))
+    }
+
+    // Remember the position in the jump list when the cursor is moving far from it
+    fn record_jump(&mut self, from: (usize, usize), to: (usize, usize)) {
+        let (f, t) = (from.0, to.0);
+        if f.max(t) - f.min(t) > JUMP_MIN_LINES {
+            self.jumps.push(from);
+        }
+    }
+
+    /// Move the cursor back to the position before the last big jump, like Ctrl-O of Vim. The cursor position is
+    /// remembered when the cursor moves by more than one line in one operation such as [`CursorMove::Top`],
+    /// [`CursorMove::Bottom`], [`CursorMove::Jump`], paragraph moves, or text search. Moves by one line like
+    /// [`CursorMove::Up`] and scrolling the viewport are not remembered. This method returns `true` when the cursor
+    /// moved. The positions are clamped when they are no longer in the text after some edits.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["a", "b", "c", "d"]);
+    ///
+    /// textarea.move_cursor(CursorMove::Down);
+    /// textarea.move_cursor(CursorMove::Bottom);
+    /// assert_eq!(textarea.cursor(), (3, 0));
+    ///
+    /// assert!(textarea.jump_back());
+    /// assert_eq!(textarea.cursor(), (1, 0));
+    /// assert!(!textarea.jump_back());
+    ///
+    /// assert!(textarea.jump_forward());
+    /// assert_eq!(textarea.cursor(), (3, 0));
+    /// ```
+    pub fn jump_back(&mut self) -> bool {
+        match self.jumps.back(self.cursor) {
+            Some(pos) => self.jump_cursor(pos),
+            None => false,
+        }
+    }
+
+    /// Move the cursor forward to the position where [`TextArea::jump_back`] moved from, like Ctrl-I of Vim. This
+    /// method returns `true` when the cursor moved. See [`TextArea::jump_back`] for more details.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["a", "b", "c", "d"]);
+    ///
+    /// textarea.move_cursor(CursorMove::Bottom);
+    /// assert!(!textarea.jump_forward());
+    ///
+    /// textarea.jump_back();
+    /// assert_eq!(textarea.cursor(), (0, 0));
+    /// assert!(textarea.jump_forward());
+    /// assert_eq!(textarea.cursor(), (3, 0));
+    /// ```
+    pub fn jump_forward(&mut self) -> bool {
+        match self.jumps.forward() {
+            Some(pos) => self.jump_cursor(pos),
+            None => false,
+        }
+    }
+
+    fn jump_cursor(&mut self, (row, col): (usize, usize)) -> bool {
+        let row = row.min(self.lines.len() - 1);
+        let col = col.min(self.lines[row].chars().count());
+        self.last_paste = None;
+        let moved = self.cursor != (row, col);
+        self.cursor = (row, col);
+        moved
+    }
+
+    /// Get the positions remembered in the jump list, from the oldest to the newest. This is useful for debugging.
+    /// See [`TextArea::jump_back`] for the positions remembered.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["a", "b", "c", "d"]);
+    ///
+    /// textarea.move_cursor(CursorMove::Bottom);
+    /// textarea.move_cursor(CursorMove::Up);
+    /// textarea.move_cursor(CursorMove::Top);
+    /// assert_eq!(textarea.jumps(), [(0, 0), (2, 0)]);
+    /// ```
+    pub fn jumps(&self) -> &[(usize, usize)] {
+        &self.jumps.entries
+    }
+
+    /// Set the mark named `name` at the cursor position like `m` command of Vim. Any character can be a name and the
+    /// existing mark with the same name is overwritten. Marks move along with the text when text is inserted or
+    /// deleted before them, including the modifications made by [`TextArea::undo`] and [`TextArea::redo`]. Text
+    /// inserted at a mark is inserted before the mark. When the text including a mark is deleted (e.g. the marked line
+    /// is deleted), the mark moves to the position where the text was deleted, and undoing the deletion puts the mark
+    /// back unless it was set again after the deletion.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["a", "b", "c"]);
+    ///
+    /// textarea.move_cursor(CursorMove::Jump(1, 1));
+    /// textarea.set_mark('a');
+    /// assert_eq!(textarea.mark('a'), Some((1, 1)));
+    ///
+    /// // Insert a line above the mark
+    /// textarea.move_cursor(CursorMove::Top);
+    /// textarea.insert_newline();
+    /// assert_eq!(textarea.mark('a'), Some((2, 1)));
+    ///
+    /// textarea.undo();
+    /// assert_eq!(textarea.mark('a'), Some((1, 1)));
+    /// ```
+    pub fn set_mark(&mut self, name: char) {
+        self.marks.set(name, self.cursor);
+    }
+
+    /// Move the cursor to the mark named `name` like `` ` `` command of Vim. The position before the jump is remembered
+    /// in the jump list as described in [`TextArea::jump_back`]. This method returns `false` when the mark does not
+    /// exist. The position is clamped when it is no longer in the text.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["abc", "def"]);
+    ///
+    /// textarea.move_cursor(CursorMove::Jump(1, 2));
+    /// textarea.set_mark('x');
+    /// textarea.move_cursor(CursorMove::Top);
+    ///
+    /// assert!(textarea.jump_to_mark('x'));
+    /// assert_eq!(textarea.cursor(), (1, 2));
+    /// assert!(!textarea.jump_to_mark('y'));
+    /// ```
+    pub fn jump_to_mark(&mut self, name: char) -> bool {
+        let pos = match self.marks.get(name) {
+            Some(pos) => self.fit_cursor(pos),
+            None => return false,
+        };
+        self.record_jump(self.cursor, pos);
+        self.jump_cursor(pos);
+        true
+    }
+
+    /// Get the position of the mark named `name`. `None` is returned when the mark does not exist. See
+    /// [`TextArea::set_mark`].
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["abc"]);
+    /// assert_eq!(textarea.mark('a'), None);
+    /// textarea.set_mark('a');
+    /// assert_eq!(textarea.mark('a'), Some((0, 0)));
+    /// ```
+    pub fn mark(&self, name: char) -> Option<(usize, usize)> {
+        self.marks.get(name)
+    }
+
+    /// Remove the mark named `name` and return its position. `None` is returned when the mark does not exist.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["abc"]);
+    /// textarea.set_mark('a');
+    /// assert_eq!(textarea.remove_mark('a'), Some((0, 0)));
+    /// assert_eq!(textarea.mark('a'), None);
+    /// ```
+    pub fn remove_mark(&mut self, name: char) -> Option<(usize, usize)> {
+        self.marks.remove(name)
+    }
+
+    /// Get the names and the positions of all marks ordered by their names. This is useful to list bookmarks. See
+    /// [`TextArea::set_mark`].
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["abc", "def"]);
+    /// textarea.move_cursor(CursorMove::Bottom);
+    /// textarea.set_mark('b');
+    /// textarea.move_cursor(CursorMove::Top);
+    /// textarea.set_mark('a');
+    ///
+    /// let marks: Vec<_> = textarea.marks().collect();
+    /// assert_eq!(marks, [('a', (0, 0)), ('b', (1, 0))]);
+    /// ```
+    pub fn marks(&self) -> impl Iterator<Item = (char, (usize, usize))> + '_ {
+        self.marks.iter()
+    }
+
+    // Display width of the text before the cursor as rendered, where hard tabs advance to the next tab stop
+    fn display_col(&self, (row, col): (usize, usize)) -> usize {
+        self.lines[row].chars().take(col).fold(0, |width, c| {
+            width + cell_width(c, width, self.tab_len, self.mask)
+        })
+    }
+
+    // Display width of the ghost text rendered before the cursor. At the end of the line it is rendered after the
+    // cursor instead
+    fn ghost_width_before_cursor(&self) -> usize {
+        let (row, col) = self.cursor;
+        match &self.ghost_text {
+            Some((text, _)) if col < self.lines[row].chars().count() => text.width(),
+            _ => 0,
+        }
+    }
+
+    // Char index in the line whose display column is the nearest to the desired one without exceeding it
+    fn col_at_display_col(&self, row: usize, desired: usize) -> usize {
+        let mut width = 0;
+        for (i, c) in self.lines[row].chars().enumerate() {
+            width += cell_width(c, width, self.tab_len, self.mask);
+            if width > desired {
+                return i;
+            }
+        }
+        self.lines[row].chars().count()
+    }
+
+    // Move the cursor by `n` display rows of wrapped lines keeping the display column in the row. Returns the new
+    // cursor position and the display column to keep on the next vertical move
+    fn move_display_rows(
+        &self,
+        up: bool,
+        n: usize,
+        wrap: &Wrap,
+    ) -> Option<((usize, usize), usize)> {
+        let (mut row, col) = self.cursor;
+        let (mut index, x) = wrap.position(&self.lines[row], col);
+        let x = match self.desired_col {
+            Some((desired, pos)) if pos == self.cursor => desired,
+            _ => x,
+        };
+        let start = (row, index);
+        for _ in 0..n {
+            if up {
+                if index > 0 {
+                    index -= 1;
+                } else if row > 0 {
+                    row -= 1;
+                    index = wrap.rows(&self.lines[row]).len() - 1;
+                } else {
+                    break;
+                }
+            } else if index + 1 < wrap.rows(&self.lines[row]).len() {
+                index += 1;
+            } else if row + 1 < self.lines.len() {
+                row += 1;
+                index = 0;
+            } else {
+                break;
+            }
+        }
+        if (row, index) == start {
+            return None;
+        }
+        Some(((row, wrap.col(&self.lines[row], index, x)), x))
+    }
+
+    // Move the cursor into the viewport by display rows of wrapped lines for `CursorMove::InViewport`. The cursor is
+    // kept off the top and bottom edges of the viewport by the scroll margin like `cursor_in_viewport`. The display
+    // column is kept as `move_display_rows` does
+    fn cursor_in_wrapped_viewport(&self, wrap: &Wrap) -> Option<((usize, usize), usize)> {
+        let (row, col) = self.cursor;
+        let (index, x) = wrap.position(&self.lines[row], col);
+        let x = match self.desired_col {
+            Some((desired, pos)) if pos == self.cursor => desired,
+            _ => x,
+        };
+        let (_, _, _, height) = self.viewport.rect();
+        let margin = self.scroll_margin_in(height);
+        let top = self.wrapped_viewport_top(wrap);
+        let first = if top == (0, 0) {
+            top
+        } else {
+            wrap.step_rows(&self.lines, top, margin, false)
+        };
+        let bottom = wrap.step_rows(&self.lines, top, (height as usize).saturating_sub(1), false);
+        let last_row = self.lines.len() - 1;
+        let end = (last_row, wrap.rows(&self.lines[last_row]).len() - 1);
+        let last = if bottom == end {
+            bottom
+        } else {
+            wrap.step_rows(&self.lines, bottom, margin, true)
+        };
+        let pos = cmp::min(cmp::max((row, index), first), last);
+        if pos == (row, index) {
+            return None;
+        }
+        Some(((pos.0, wrap.col(&self.lines[pos.0], pos.1, x)), x))
+    }
+
+    // The line and the display row in it at the top of the viewport of wrapped lines. The lines may have been modified
+    // since the last rendering
+    fn wrapped_viewport_top(&self, wrap: &Wrap) -> (usize, usize) {
+        let (row, _) = self.viewport.scroll_top();
+        let row = cmp::min(row, self.lines.len() - 1);
+        let rows_len = wrap.rows(&self.lines[row]).len();
+        (row, cmp::min(self.viewport.wrap_top(), rows_len - 1))
+    }
+
+    fn repeat_find(&self, m: CursorMove, cursor: (usize, usize)) -> Option<(usize, usize)> {
+        let mut find = self.last_find?;
+        if m == CursorMove::RepeatFindReverse {
+            find = find.reversed_find();
+        }
+        // Skip the character next to the cursor not to stop at the same position again
+        let (row, col) = cursor;
+        let from = match find {
+            CursorMove::TillCharForward(_) => (row, col + 1),
+            CursorMove::TillCharBack(_) => (row, col.checked_sub(1)?),
+            _ => cursor,
+        };
+        find.next_cursor(from, &self.lines, &self.viewport)
+    }
+
+    /// Get the last find move made by [`CursorMove::FindCharForward`], [`CursorMove::FindCharBack`],
+    /// [`CursorMove::TillCharForward`], or [`CursorMove::TillCharBack`]. It is repeated by [`CursorMove::RepeatFind`]
+    /// and [`CursorMove::RepeatFindReverse`]. `None` is returned when no find move was made.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["abc"]);
+    /// assert_eq!(textarea.last_find(), None);
+    ///
+    /// textarea.move_cursor(CursorMove::FindCharForward('c'));
+    /// assert_eq!(textarea.last_find(), Some(CursorMove::FindCharForward('c')));
+    ///
+    /// // Repeating the move does not change the last find move
+    /// textarea.move_cursor(CursorMove::RepeatFindReverse);
+    /// assert_eq!(textarea.last_find(), Some(CursorMove::FindCharForward('c')));
+    /// ```
+    pub fn last_find(&self) -> Option<CursorMove> {
+        self.last_find
+    }
+
+    /// Undo the last modification. This method returns if the undo modified text contents or not in the textarea.
+    /// The cursor is moved to the start of the restored or removed text. When the modification was made while
+    /// selecting text, the selection and the cursor before the modification are restored instead. Use
+    /// [`TextArea::set_undo_cursor`] to always restore the cursor before the modification.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["abc def"]);
+    ///
+    /// textarea.delete_next_word();
+    /// assert_eq!(textarea.lines(), [" def"]);
+    /// textarea.undo();
+    /// assert_eq!(textarea.lines(), ["abc def"]);
+    ///
+    /// // Undoing the cut restores the selection
+    /// textarea.start_selection();
+    /// textarea.move_cursor(CursorMove::WordForward);
+    /// textarea.cut();
+    /// textarea.undo();
+    /// assert_eq!(textarea.selection_range(), Some(((0, 0), (0, 4))));
+    /// ```
+    pub fn undo(&mut self) -> bool {
+        if self.history.has_pending_group() {
+            return false;
+        }
+        let edit = self.history.peek_undo();
+        if !self.check_protected_rows(edit.as_ref()) {
+            return false;
+        }
+        let selection = self.history.undo_selection();
+        if let Some(entry) = self.history.peek_undo_entry() {
+            self.marks.undo(entry);
+        }
+        self.signs.apply(edit.as_ref());
+        if let Some(cursor) = self.history.undo(&mut self.lines) {
+            self.clear_virtual_texts();
+            self.auto_pair_opens.clear();
+            self.cancel_selection();
+            #[cfg(feature = "search")]
+            {
+                self.search.current = None;
+            }
+            self.cursor = cursor;
+            if let Some((start, mode)) = selection {
+                self.selection_start = Some(start);
+                self.selection_mode = mode;
+            }
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Redo the last undo change. This method returns if the redo modified text contents or not in the textarea.
+    /// The cursor is moved to the start of the restored or removed text. When the change was made while selecting
+    /// text, the cursor is moved to the position after the change instead. Use [`TextArea::set_undo_cursor`] to always
+    /// restore the cursor after the change.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["abc def"]);
+    ///
+    /// textarea.delete_next_word();
+    /// assert_eq!(textarea.lines(), [" def"]);
+    /// textarea.undo();
+    /// assert_eq!(textarea.lines(), ["abc def"]);
+    /// textarea.redo();
+    /// assert_eq!(textarea.lines(), [" def"]);
+    /// ```
+    pub fn redo(&mut self) -> bool {
+        if self.history.has_pending_group() {
+            return false;
+        }
+        let edit = self.history.peek_redo().cloned();
+        if !self.check_protected_rows(edit.as_ref()) {
+            return false;
+        }
+        self.marks.apply(edit.as_ref());
+        self.signs.apply(edit.as_ref());
+        if let Some(cursor) = self.history.redo(&mut self.lines) {
+            self.clear_virtual_texts();
+            self.auto_pair_opens.clear();
+            self.cancel_selection();
+            #[cfg(feature = "search")]
+            {
+                self.search.current = None;
+            }
+            self.cursor = cursor;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Set if the last change is recorded to apply it again by [`TextArea::repeat_last_edit`]. The default value is
+    /// `false`. Disabling the recording forgets the recorded change.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// assert!(!textarea.edit_recording());
+    /// textarea.set_edit_recording(true);
+    /// assert!(textarea.edit_recording());
+    /// ```
+    pub fn set_edit_recording(&mut self, enabled: bool) {
+        self.history.set_record_changes(enabled);
+    }
+
+    /// Get if the last change is recorded to apply it again by [`TextArea::repeat_last_edit`]. See
+    /// [`TextArea::set_edit_recording`].
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let textarea = TextArea::default();
+    /// assert!(!textarea.edit_recording());
+    /// ```
+    pub fn edit_recording(&self) -> bool {
+        self.history.record_changes()
+    }
+
+    /// Apply the last change again at the cursor position like `.` command of Vim. The recording needs to be enabled
+    /// by [`TextArea::set_edit_recording`]. A change is the modifications recorded as one entry in the edit history, so
+    /// wrap multiple operations with [`TextArea::begin_history_group`] and [`TextArea::end_history_group`] to repeat
+    /// them at once (e.g. all text typed in an insert mode session). Moving the cursor, undo and redo do not replace
+    /// the recorded change.
+    ///
+    /// Inserted text is inserted again and deleted text is replaced with deleting the same number of characters at
+    /// the position relative to the cursor. When the change was made on line-wise selection, it is applied to the
+    /// lines from the cursor line and deleted lines are replaced with deleting the same number of entire lines.
+    /// Positions which no longer fit in the text are clamped. This method returns if some
+    /// text was modified or not in the textarea.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["foo", "bar"]);
+    /// textarea.set_edit_recording(true);
+    ///
+    /// textarea.begin_history_group();
+    /// textarea.delete_next_char();
+    /// textarea.insert_str("xy");
+    /// textarea.end_history_group();
+    /// assert_eq!(textarea.lines(), ["xyoo", "bar"]);
+    ///
+    /// textarea.move_cursor(CursorMove::Jump(1, 1));
+    /// assert!(textarea.repeat_last_edit());
+    /// assert_eq!(textarea.lines(), ["xyoo", "bxyr"]);
+    /// assert_eq!(textarea.cursor(), (1, 3));
+    /// ```
+    pub fn repeat_last_edit(&mut self) -> bool {
+        let change = match self.history.last_change() {
+            Some(change) => change.clone(),
+            None => return false,
+        };
+        if self.needs_guard() {
+            let modified = self.guard(false, |t| t.repeat_last_edit());
+            // Keep the original change since the repeated one may lose the selection mode
+            self.history.set_last_change(Some(change));
+            return modified;
+        }
+        self.cancel_selection();
+        let cursor = self.cursor;
+        let mut modified = false;
+        for (pos, op) in change.ops(cursor) {
+            let (row, col) = self.fit_cursor(pos);
+            self.cursor = (row, col);
+            match op {
+                ChangeOp::Insert(s) => modified |= self.insert_str(s),
+                ChangeOp::Delete(chars) => {
+                    let start = Pos::new(row, col, self.line_offset(row, col));
+                    let end = self.pos_after_chars((row, col), *chars);
+                    if (start.row, start.col) != (end.row, end.col) {
+                        self.remove_range(start, end, false);
+                        modified = true;
+                    }
+                }
+                ChangeOp::DeleteLines(lines) => {
+                    let last = (row + lines).saturating_sub(1).min(self.lines.len() - 1);
+                    modified |= self.delete_lines(row, last, false);
+                }
+            }
+        }
+        self.cursor = self.fit_cursor(change.cursor_after(cursor));
+        modified
+    }
+
+    /// Get the lines affected by the last modification of the text, including the modifications made by
+    /// [`TextArea::undo`] and [`TextArea::redo`]. This is useful to update data associated with lines (e.g.
+    /// diagnostics or bookmarks) only for the affected lines. `None` is returned when the text was never modified.
+    /// See [`EditInfo`] for the details.
+    /// ```
+    /// use tui_textarea::{CursorMove, TextArea};
+    ///
+    /// let mut textarea = TextArea::from(["abc", "def", "ghi"]);
+    /// assert_eq!(textarea.last_edit_info(), None);
+    ///
+    /// // Join the 2nd and 3rd lines
+    /// textarea.move_cursor(CursorMove::Jump(2, 0));
+    /// textarea.delete_char();
+    /// let info = textarea.last_edit_info().unwrap();
+    /// assert_eq!(info.rows(), 1..2);
+    /// assert_eq!(info.lines_delta(), -1);
+    ///
+    /// // Undo splits the line again
+    /// textarea.undo();
+    /// let info = textarea.last_edit_info().unwrap();
+    /// assert_eq!(info.rows(), 1..3);
+    /// assert_eq!(info.lines_delta(), 1);
+    /// ```
+    pub fn last_edit_info(&self) -> Option<EditInfo> {
+        self.history.last_edit().cloned()
+    }
+
+    /// Get the entries of the edit history from the oldest one. This is useful to show the list of past modifications.
+    /// The entries after the current undo point were undone and can be restored by [`TextArea::redo`]. See
+    /// [`HistoryEntry`] for the details of each entry.
+    /// ```
+    /// use tui_textarea::{HistoryEditKind, TextArea};
+    ///
+    /// let mut textarea = TextArea::default();
+    /// textarea.insert_str("hello");
+    /// textarea.delete_char();
+    /// textarea.undo();
+    ///
+    /// let entries: Vec<_> = textarea.history().collect();
+    /// assert_eq!(entries.len(), 2);
+    /// assert_eq!(entries[0].kind(), HistoryEditKind::Insert);
+    /// assert_eq!(entries[0].preview(), "hello");
+    /// assert!(!entries[0].is_undone());
+    /// assert_eq!(entries[1].kind(), HistoryEditKind::Delete);
+    /// assert_eq!(entries[1].position(), (0, 4));
+    /// assert!(entries[1].is_undone());
+    /// ```
+    pub fn history(&self) -> impl Iterator<Item = HistoryEntry> + '_ {
+        self.history.entries()
+    }
+
+    /// Begin a history group. All modifications until the matching [`TextArea::end_history_group`] call are recorded
+    /// as one history entry so that they are undone and redone at once. Groups can be nested. Nested groups are
+    /// flattened into the outermost group. A group with no modification adds nothing to the history. [`TextArea::undo`]
+    /// and [`TextArea::redo`] do nothing while the open group has some modifications. Prefer
+    /// [`TextArea::with_history_group`] to ensure the group is always ended.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// textarea.begin_history_group();
+    /// textarea.insert_str("hello");
+    /// textarea.insert_newline();
+    /// textarea.insert_str("world");
+    /// textarea.end_history_group();
+    /// assert_eq!(textarea.lines(), ["hello", "world"]);
+    ///
+    /// // All modifications in the group are undone at once
+    /// textarea.undo();
+    /// assert_eq!(textarea.lines(), [""]);
+    /// ```
+    pub fn begin_history_group(&mut self) {
+        if !self.history.in_group() {
+            self.history.end_undo_unit();
+        }
+        self.history.begin_group(self.cursor);
+    }
+
+    /// End the history group started by [`TextArea::begin_history_group`]. When no group is open, this method does
+    /// nothing.
+    pub fn end_history_group(&mut self) {
+        if self.history.in_group() {
+            self.history.end_group(self.cursor);
+            if !self.history.in_group() {
+                self.history.end_undo_unit();
+            }
+        }
+    }
+
+    /// Run the closure in a history group. All modifications applied by the closure are recorded as one history entry.
+    /// The group is ended even if the closure panics. See [`TextArea::begin_history_group`] for more details.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["a b c"]);
+    ///
+    /// let deleted = textarea.with_history_group(|t| {
+    ///     let mut deleted = 0;
+    ///     while t.delete_next_word() {
+    ///         deleted += 1;
+    ///     }
+    ///     deleted
+    /// });
+    /// assert_eq!(deleted, 3);
+    /// assert!(textarea.is_empty());
+    ///
+    /// textarea.undo();
+    /// assert_eq!(textarea.lines(), ["a b c"]);
+    /// ```
+    pub fn with_history_group<T>(&mut self, f: impl FnOnce(&mut Self) -> T) -> T {
+        struct Group<'t, 'a>(&'t mut TextArea<'a>);
+
+        impl Drop for Group<'_, '_> {
+            fn drop(&mut self) {
+                self.0.end_history_group();
+            }
+        }
+
+        self.begin_history_group();
+        let group = Group(self);
+        f(group.0)
+    }
+
+    pub(crate) fn line_spans<'b>(
+        &'b self,
+        line: &'b str,
+        row: usize,
+        lnum_len: u8,
+        brackets: &[((usize, usize), Style)],
+    ) -> Line<'b> {
+        self.line_highlighter(line, row, lnum_len, brackets)
+            .into_spans()
+    }
+
+    // Brackets highlighted by the matching bracket style or the unmatched bracket style with their positions. The
+    // bracket under the cursor or just before the cursor is highlighted with its matching bracket
+    pub(crate) fn bracket_highlights(&self) -> Vec<((usize, usize), Style)> {
+        let matched = match self.matching_bracket_style {
+            Some(style) => style,
+            None => return vec![],
+        };
+        let (row, col) = self.cursor;
+        let line = &self.lines[row];
+        let pos = if line.chars().nth(col).is_some_and(is_bracket) {
+            (row, col)
+        } else if col > 0 && line.chars().nth(col - 1).is_some_and(is_bracket) {
+            (row, col - 1)
+        } else {
+            return vec![];
+        };
+        match find_bracket_pair(&self.lines, pos, self.bracket_match_distance) {
+            Some(other) => vec![(pos, matched), (other, matched)],
+            None => self
+                .unmatched_bracket_style
+                .map(|style| (pos, style))
+                .into_iter()
+                .collect(),
+        }
+    }
+
+    pub(crate) fn line_highlighter<'b>(
+        &'b self,
+        line: &'b str,
+        row: usize,
+        lnum_len: u8,
+        brackets: &[((usize, usize), Style)],
+    ) -> LineHighlighter<'b> {
+        let (select_style, cursor_style) = if self.focused {
+            (self.select_style, Some(self.cursor_style))
+        } else {
+            (self.inactive_select_style, self.unfocused_cursor_style)
+        };
+        let mut hl = LineHighlighter::new(
+            line,
+            cursor_style.unwrap_or_default(),
+            self.tab_len,
+            self.mask,
+            select_style,
+        );
+
+        if self.sign_column_visible() {
+            hl.gutter(self.signs.span(row));
+        }
+
+        if let Some(style) = self.line_number_style {
+            match &self.line_number_formatter {
+                Some(f) => hl.gutter(f.format(LineNumberContext {
+                    row,
+                    cursor_row: row == self.cursor.0,
+                    width: lnum_len as usize + 2, // `+ 2` for margins
+                    style,
+                })),
+                None => hl.line_number(row, lnum_len, style),
+            }
+        }
+
+        if row == self.cursor.0 {
+            match (self.cursor_rendering, cursor_style) {
+                (CursorRendering::Cell, Some(_)) => {
+                    hl.cursor_line(self.cursor.1, self.cursor_line_style)
+                }
+                _ => hl.line_style(self.cursor_line_style), // The cursor is hidden
+            }
+        }
+
+        if self.protected_rows.contains(row) {
+            hl.line_style(self.protected_row_style);
+        }
+
+        if let Some(ranges) = self.syntax_ranges(line) {
+            hl.syntax(ranges.into_iter());
+        }
+
+        if let Some(style) = self.trailing_whitespace_style {
+            // Whitespaces being typed at the end of the cursor line are not highlighted not to flicker while typing
+            let typing = row == self.cursor.0 && !self.trailing_whitespace_at_cursor && {
+                let trimmed = line.trim_end_matches([' ', '\t']);
+                self.cursor.1 >= trimmed.chars().count()
+            };
+            if !typing {
+                hl.trailing_whitespace(style);
+            }
+        }
+
+        if let Some(markers) = self.show_whitespace {
+            hl.whitespace_markers(markers);
+        }
+
+        if let (Some((text, style)), true) = (&self.ghost_text, row == self.cursor.0) {
+            hl.ghost_text(self.cursor.1, Span::styled(text.as_str(), *style));
+        }
+
+        if let Some((text, style)) = self.virtual_texts.get(&row) {
+            hl.virtual_text(Span::styled(text.as_str(), *style));
+        }
+
+        for &((r, c), style) in brackets {
+            if r == row {
+                hl.bracket(c, style);
+            }
+        }
+
+        hl.control_chars(self.control_char_style);
+
+        #[cfg(feature = "search")]
+        if let Some(matches) = self.search.row_matches(row, line) {
+            let current = match self.search.current {
+                Some((r, c)) if r == row => {
+                    let offset = line.char_indices().nth(c).map_or(line.len(), |(i, _)| i);
+                    Some((offset, self.search.current_style))
+                }
+                _ => None,
+            };
+            hl.search(matches, self.search.style, current);
+        }
+
+        if let Some((top, bottom, left, right)) = self.block_selection() {
+            if top <= row && row <= bottom {
+                let (start, end) = self.block_row_positions(row, left, right);
+                hl.selection(row, row, start.offset, row, end.offset);
+            }
+        } else if let Some((start, end)) = self.selection_positions() {
+            hl.selection(row, start.row, start.offset, end.row, end.offset);
+        }
+
+        hl
+    }
+
+    // Byte ranges of the line with their syntax highlighting styles, when syntax highlighting is configured
+    fn syntax_ranges(&self, line: &str) -> Option<Vec<(usize, usize, Style)>> {
+        let highlighter = self.syntax_highlighter.as_ref()?;
+        let syntax = highlighter.find_syntax_by_name(self.syntax_name.as_ref()?)?;
+        let theme = highlighter.get_theme(self.theme_name.as_ref()?)?;
+        let mut h = HighlightLines::new(syntax, theme);
+        let ranges = h.highlight_line(line, highlighter.syntax_set()).ok()?;
+        let mut start = 0;
+        let ranges = ranges
+            .into_iter()
+            .map(|(style, text)| {
+                let end = start + text.len();
+                let range = (start, end, syntect_style_to_ratatui(style));
+                start = end;
+                range
+            })
+            .collect();
+        Some(ranges)
+    }
+
+    /// Build a ratatui (or tui-rs) widget to render the current state of the textarea. The widget instance returned
+    /// from this method can be rendered with [`ratatui::Frame::render_widget`].
+    ///
+    /// This method was deprecated at v0.5.3 and is no longer necessary. Instead you can directly pass `&TextArea`
+    /// reference to the `Frame::render_widget` method call.
+    /// ```no_run
+    /// # use ratatui::layout::Rect;
+    /// # use ratatui::Terminal;
+    /// # use ratatui::widgets::Widget as _;
+    /// # use ratatui::backend::CrosstermBackend;
+    /// # use tui_textarea::TextArea;
+    /// #
+    /// # let backend = CrosstermBackend::new(std::io::stdout());
+    /// # let mut term = Terminal::new(backend).unwrap();
+    /// # let textarea = TextArea::default();
+    /// #
+    /// # #[allow(deprecated)]
+    /// # term.draw(|f| {
+    /// #   let rect = Rect {
+    /// #       x: 0,
+    /// #       y: 0,
+    /// #       width: 24,
+    /// #       height: 8,
+    /// #   };
+    /// // v0.5.2 or earlier
+    /// f.render_widget(textarea.widget(), rect);
+    ///
+    /// // v0.5.3 or later
+    /// f.render_widget(&textarea, rect);
+    /// # }).unwrap();
+    /// ```
+    #[deprecated(
+        since = "0.5.3",
+        note = "calling this method is no longer necessary on rendering a textarea. pass &TextArea reference to `Frame::render_widget` method call directly"
+    )]
+    pub fn widget(&'a self) -> impl Widget + 'a {
+        self
+    }
+
+    /// Set the style of textarea. By default, textarea is not styled.
+    /// ```
+    /// use ratatui::style::{Style, Color};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// let style = Style::default().fg(Color::Red);
+    /// textarea.set_style(style);
+    /// assert_eq!(textarea.style(), style);
+    /// ```
+    pub fn set_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    /// Get the current style of textarea.
+    pub fn style(&self) -> Style {
+        self.style
+    }
+
+    /// Set the block of textarea. By default, no block is set.
+    /// ```
+    /// use tui_textarea::TextArea;
+    /// use ratatui::widgets::{Block, Borders};
+    ///
+    /// let mut textarea = TextArea::default();
+    /// let block = Block::default().borders(Borders::ALL).title("Block Title");
+    /// textarea.set_block(block);
+    /// assert!(textarea.block().is_some());
+    /// ```
+    pub fn set_block(&mut self, block: Block<'a>) {
+        self.block = Some(block);
+    }
+
+    /// Remove the block of textarea which was set by [`TextArea::set_block`].
+    /// ```
+    /// use tui_textarea::TextArea;
+    /// use ratatui::widgets::{Block, Borders};
+    ///
+    /// let mut textarea = TextArea::default();
+    /// let block = Block::default().borders(Borders::ALL).title("Block Title");
+    /// textarea.set_block(block);
+    /// textarea.remove_block();
+    /// assert!(textarea.block().is_none());
+    /// ```
+    pub fn remove_block(&mut self) {
+        self.block = None;
+    }
+
+    /// Get the block of textarea if exists.
+    pub fn block<'s>(&'s self) -> Option<&'s Block<'a>> {
+        self.block.as_ref()
+    }
+
+    /// Set the length of tab character. Setting 0 disables tab inputs. Hard tabs in the text are rendered up to the
+    /// next tab stop, which is a multiple of the length, so that text after tabs lines up.
+    /// ```
+    /// use tui_textarea::{TextArea, Input, Key};
+    ///
+    /// let mut textarea = TextArea::default();
+    /// let tab_input = Input { key: Key::Tab, ctrl: false, alt: false, shift: false };
+    ///
+    /// textarea.set_tab_length(8);
+    /// textarea.input(tab_input.clone());
+    /// assert_eq!(textarea.lines(), ["        "]);
+    ///
+    /// textarea.set_tab_length(2);
+    /// textarea.input(tab_input);
+    /// assert_eq!(textarea.lines(), ["          "]);
+    /// ```
+    pub fn set_tab_length(&mut self, len: u8) {
+        self.tab_len = len;
+    }
+
+    /// Get how many spaces are used for representing tab character. The default value is 4.
+    pub fn tab_length(&self) -> u8 {
+        self.tab_len
+    }
+
+    /// Set if a hard tab is used or not for indent. When `true` is set, typing a tab key inserts a hard tab instead of
+    /// spaces. By default, hard tab is disabled.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// textarea.set_hard_tab_indent(true);
+    /// textarea.insert_tab();
+    /// assert_eq!(textarea.lines(), ["\t"]);
+    /// ```
+    pub fn set_hard_tab_indent(&mut self, enabled: bool) {
+        self.hard_tab_indent = enabled;
+    }
+
+    /// Get if a hard tab is used for indent or not.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// assert!(!textarea.hard_tab_indent());
+    /// textarea.set_hard_tab_indent(true);
+    /// assert!(textarea.hard_tab_indent());
+    /// ```
+    pub fn hard_tab_indent(&self) -> bool {
+        self.hard_tab_indent
+    }
+
+    /// Get a string for indent. It consists of spaces by default. When hard tab is enabled, it is a tab character.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// assert_eq!(textarea.indent(), "    ");
+    /// textarea.set_tab_length(2);
+    /// assert_eq!(textarea.indent(), "  ");
+    /// textarea.set_hard_tab_indent(true);
+    /// assert_eq!(textarea.indent(), "\t");
+    /// ```
+    pub fn indent(&self) -> &'static str {
+        if self.hard_tab_indent {
+            "\t"
+        } else {
+            spaces(self.tab_len)
+        }
+    }
+
+    /// Set if [`TextArea::indent_selection`] indents empty lines or not. By default, empty lines are not indented not
+    /// to leave trailing whitespaces.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["a", "", "b"]);
+    /// textarea.select_all();
+    ///
+    /// textarea.set_indent_empty_lines(true);
+    /// textarea.indent_selection();
+    /// assert_eq!(textarea.lines(), ["    a", "    ", "    b"]);
+    /// ```
+    pub fn set_indent_empty_lines(&mut self, enabled: bool) {
+        self.indent_empty_lines = enabled;
+    }
+
+    /// Get if [`TextArea::indent_selection`] indents empty lines or not.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// assert!(!textarea.indent_empty_lines());
+    /// textarea.set_indent_empty_lines(true);
+    /// assert!(textarea.indent_empty_lines());
+    /// ```
+    pub fn indent_empty_lines(&self) -> bool {
+        self.indent_empty_lines
+    }
+
+    /// Set if [`TextArea::insert_newline`] copies the indentation of the cursor line to the new line. Only the
+    /// indentation before the cursor is copied as is, so tabs and spaces in it are kept. Whitespaces right after the
+    /// cursor are removed so that the moved text starts after the copied indentation. This is disabled by default.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["\t  if x {"]);
+    /// textarea.move_cursor(CursorMove::End);
+    ///
+    /// textarea.set_auto_indent(true);
+    /// textarea.insert_newline();
+    /// assert_eq!(textarea.lines(), ["\t  if x {", "\t  "]);
+    /// assert_eq!(textarea.cursor(), (1, 3));
+    /// ```
+    pub fn set_auto_indent(&mut self, enabled: bool) {
+        self.auto_indent = enabled;
+    }
+
+    /// Get if [`TextArea::insert_newline`] copies the indentation of the cursor line to the new line.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// assert!(!textarea.auto_indent());
+    /// textarea.set_auto_indent(true);
+    /// assert!(textarea.auto_indent());
+    /// ```
+    pub fn auto_indent(&self) -> bool {
+        self.auto_indent
+    }
+
+    /// Set if `\r\n` and `\r` in inserted text are converted into line breaks. When enabled, text given to
+    /// [`TextArea::insert_str`], [`TextArea::set_text`] and [`TextArea::set_yank_text`] (so also pasted by
+    /// [`TextArea::paste`]) never adds carriage returns to the lines. Disabling this only splits the text at `\n` and
+    /// keeps carriage returns in the lines as they are. This is enabled by default.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// textarea.insert_str("a\r\nb\rc");
+    /// assert_eq!(textarea.lines(), ["a", "b", "c"]);
+    ///
+    /// let mut textarea = TextArea::default();
+    /// textarea.set_normalize_newlines(false);
+    /// textarea.insert_str("a\r\nb\rc");
+    /// assert_eq!(textarea.lines(), ["a\r", "b\rc"]);
+    /// ```
+    pub fn set_normalize_newlines(&mut self, enabled: bool) {
+        self.normalize_newlines = enabled;
+    }
+
+    /// Get if `\r\n` and `\r` in inserted text are converted into line breaks. See
+    /// [`TextArea::set_normalize_newlines`].
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let textarea = TextArea::default();
+    /// assert!(textarea.normalize_newlines());
+    /// ```
+    pub fn normalize_newlines(&self) -> bool {
+        self.normalize_newlines
+    }
+
+    /// Set if [`TextArea::insert_str_block`] pads lines shorter than the left column of the block with spaces. When
+    /// disabled, which is the default, such lines are skipped like Vim.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["foo", "", "bar"]);
+    /// textarea.set_block_insert_padding(true);
+    ///
+    /// textarea.move_cursor(CursorMove::End);
+    /// textarea.start_block_selection();
+    /// textarea.move_cursor(CursorMove::Bottom);
+    /// textarea.insert_str_block(";");
+    /// assert_eq!(textarea.lines(), ["foo;", "   ;", "bar;"]);
+    /// ```
+    pub fn set_block_insert_padding(&mut self, enabled: bool) {
+        self.block_insert_padding = enabled;
+    }
+
+    /// Get if [`TextArea::insert_str_block`] pads lines shorter than the left column of the block with spaces.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// assert!(!textarea.block_insert_padding());
+    /// textarea.set_block_insert_padding(true);
+    /// assert!(textarea.block_insert_padding());
+    /// ```
+    pub fn block_insert_padding(&self) -> bool {
+        self.block_insert_padding
+    }
+
+    /// Set if brackets and quotes are automatically closed. When enabled, typing an opening character by
+    /// [`TextArea::insert_char`] (or [`TextArea::input`]) also inserts the closing character and puts the cursor between
+    /// them. Typing a closing character just before the same character moves the cursor over it instead of inserting
+    /// it, and [`TextArea::delete_char`] between an empty pair inserted this way deletes both characters. Pairs typed
+    /// character by character or restored by undo and redo are deleted one character at a time. A quote whose opening
+    /// and closing characters are the same is not paired after a word character. The pairs can be changed by
+    /// [`TextArea::set_auto_pair_chars`]. This is disabled by default.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// textarea.set_auto_pairs(true);
+    ///
+    /// textarea.insert_char('(');
+    /// assert_eq!(textarea.lines(), ["()"]);
+    /// assert_eq!(textarea.cursor(), (0, 1));
+    ///
+    /// textarea.insert_char(')');
+    /// assert_eq!(textarea.lines(), ["()"]);
+    /// assert_eq!(textarea.cursor(), (0, 2));
+    /// ```
+    pub fn set_auto_pairs(&mut self, enabled: bool) {
+        self.auto_pairs = enabled;
+    }
+
+    /// Get if brackets and quotes are automatically closed.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// assert!(!textarea.auto_pairs());
+    /// textarea.set_auto_pairs(true);
+    /// assert!(textarea.auto_pairs());
+    /// ```
+    pub fn auto_pairs(&self) -> bool {
+        self.auto_pairs
+    }
+
+    /// Set the pairs of opening and closing characters closed by [`TextArea::set_auto_pairs`]. The default pairs are
+    /// `()`, `[]`, `{}`, `""`, `''` and ` `` `.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// textarea.set_auto_pairs(true);
+    /// textarea.set_auto_pair_chars(vec![('<', '>')]);
+    ///
+    /// textarea.insert_char('<');
+    /// textarea.insert_char('(');
+    /// assert_eq!(textarea.lines(), ["<(>"]);
+    /// ```
+    pub fn set_auto_pair_chars(&mut self, pairs: Vec<(char, char)>) {
+        self.auto_pair_chars = pairs;
+    }
+
+    /// Get the pairs of opening and closing characters closed by [`TextArea::set_auto_pairs`].
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let textarea = TextArea::default();
+    /// assert_eq!(textarea.auto_pair_chars()[0], ('(', ')'));
+    /// ```
+    pub fn auto_pair_chars(&self) -> &[(char, char)] {
+        &self.auto_pair_chars
+    }
+
+    /// Set the abbreviations expanded while typing, like `:iabbrev` of Vim. When a word boundary character (a
+    /// whitespace, a punctuation or a newline) is typed by [`TextArea::insert_char`], [`TextArea::insert_newline`] or
+    /// [`TextArea::input`], the word just before the cursor is replaced with the expansion if the word exactly matches
+    /// a key of the map. The expansion and the typed character are recorded as one entry in the edit history, so one
+    /// [`TextArea::undo`] call restores the typed word. Expansions can contain newlines. No abbreviation is set by
+    /// default.
+    /// ```
+    /// use std::collections::HashMap;
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// let mut abbrevs = HashMap::new();
+    /// abbrevs.insert("teh".to_string(), "the".to_string());
+    /// textarea.set_abbreviations(abbrevs);
+    ///
+    /// for c in "teh cat".chars() {
+    ///     textarea.insert_char(c);
+    /// }
+    /// textarea.insert_char('.');
+    /// assert_eq!(textarea.lines(), ["the cat."]);
+    /// ```
+    pub fn set_abbreviations(&mut self, abbreviations: HashMap<String, String>) {
+        self.abbreviations = abbreviations;
+    }
+
+    /// Get the abbreviations set by [`TextArea::set_abbreviations`].
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let textarea = TextArea::default();
+    /// assert!(textarea.abbreviations().is_empty());
+    /// ```
+    pub fn abbreviations(&self) -> &HashMap<String, String> {
+        &self.abbreviations
+    }
+
+    /// Indent every line touched by the selection by one indent unit returned by [`TextArea::indent`]. When no text is
+    /// selected, the cursor line is indented. Empty lines are skipped unless [`TextArea::set_indent_empty_lines`]
+    /// enables them. The selection keeps covering the same text. Pressing Tab while selecting text calls this method
+    /// in [`TextArea::input`].
+    ///
+    /// This method returns if some text was modified or not in the textarea.
+    ///
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["a", "", "b", "c"]);
+    ///
+    /// textarea.start_selection();
+    /// textarea.move_cursor(CursorMove::Jump(2, 1));
+    /// textarea.indent_selection();
+    /// assert_eq!(textarea.lines(), ["    a", "", "    b", "c"]);
+    /// assert_eq!(textarea.selection_range(), Some(((0, 4), (2, 5))));
+    /// ```
+    pub fn indent_selection(&mut self) -> bool {
+        if self.needs_guard() {
+            return self.guard(false, |t| t.indent_selection());
+        }
+        self.shift_selected_lines(true)
+    }
+
+    /// Dedent every line touched by the selection by one indent unit. One leading hard tab or at most tab length
+    /// spaces are removed from each line. Lines are never dedented past the column 0. When no text is selected, the
+    /// cursor line is dedented. The selection keeps covering the same text. Pressing Shift+Tab calls this method in
+    /// [`TextArea::input`].
+    ///
+    /// This method returns if some text was modified or not in the textarea.
+    ///
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["      a", "\tb", "  c", "d"]);
+    ///
+    /// textarea.start_selection();
+    /// textarea.move_cursor(CursorMove::Bottom);
+    /// textarea.dedent_selection();
+    /// assert_eq!(textarea.lines(), ["  a", "b", "c", "d"]);
+    /// ```
+    pub fn dedent_selection(&mut self) -> bool {
+        if self.needs_guard() {
+            return self.guard(false, |t| t.dedent_selection());
+        }
+        self.shift_selected_lines(false)
+    }
+
+    // Rows touched by the selection, or the cursor row when no text is selected
+    fn selected_rows(&self) -> Range<usize> {
+        if let Some((first, last)) = self.line_selection_rows() {
+            let last = cmp::min(last, self.lines.len() - 1);
+            return cmp::min(first, last)..last + 1;
+        }
+        match self.selection_range() {
+            Some((start, end)) => start.0..end.0 + 1,
+            None => self.cursor.0..self.cursor.0 + 1,
+        }
+    }
+
+    fn shift_selected_lines(&mut self, indent: bool) -> bool {
+        let mut rows = self.selected_rows();
+        // A selection ending at the head of a non-empty line does not shift the line, like the end of a line-wise
+        // selection
+        if let Some((start, end)) = self.selection_range() {
+            if self.selection_mode == SelectionMode::Char
+                && end.1 == 0
+                && end.0 > start.0
+                && !self.lines[end.0].is_empty()
+            {
+                rows.end = end.0;
+            }
+        }
+        let (mut anchor, mut cursor) = (self.selection_start, self.cursor);
+        let unit = self.indent();
+        let mut modified = false;
+
+        for row in rows {
+            let line = &self.lines[row];
+            let (added, removed) = if indent {
+                if unit.is_empty() || line.is_empty() && !self.indent_empty_lines {
+                    continue;
+                }
+                self.cursor = (row, 0);
+                self.insert_piece(unit.to_string());
+                (unit.chars().count(), 0)
+            } else {
+                let width = if line.starts_with('\t') {
+                    1
+                } else {
+                    let tab_len = self.tab_len as usize;
+                    line.chars().take(tab_len).take_while(|c| *c == ' ').count()
+                };
+                if width == 0 {
+                    continue;
+                }
+                self.remove_range(Pos::new(row, 0, 0), Pos::new(row, width, width), false);
+                (0, width)
+            };
+            // Keep the cursor and the selection anchor on the same character
+            let shift = |(r, c): (usize, usize)| {
+                if r == row {
+                    (r, c + added - c.min(removed))
+                } else {
+                    (r, c)
+                }
+            };
+            anchor = anchor.map(shift);
+            cursor = shift(cursor);
+            modified = true;
+        }
+
+        self.selection_start = anchor;
+        self.cursor = cursor;
+        modified
+    }
+
+    /// Remove spaces and tabs at the end of all lines. The cursor stays at the same position, or moves to the end of
+    /// the line when it was in the removed whitespaces. All the removals are recorded as one entry in the edit history.
+    /// This method returns the number of modified lines.
+    ///
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["fn f() {  ", "\t", "}"]);
+    ///
+    /// assert_eq!(textarea.trim_trailing_whitespace(), 2);
+    /// assert_eq!(textarea.lines(), ["fn f() {", "", "}"]);
+    ///
+    /// textarea.undo();
+    /// assert_eq!(textarea.lines(), ["fn f() {  ", "\t", "}"]);
+    /// ```
+    pub fn trim_trailing_whitespace(&mut self) -> usize {
+        if self.needs_guard() {
+            return self.guard(0, |t| t.trim_trailing_whitespace());
+        }
+        let (mut cursor, mut anchor) = (self.cursor, self.selection_start);
+        let mut count = 0;
+        for row in 0..self.lines.len() {
+            let line = &self.lines[row];
+            let trimmed = line.trim_end_matches([' ', '\t']);
+            if trimmed.len() == line.len() {
+                continue;
+            }
+            let start = Pos::new(row, trimmed.chars().count(), trimmed.len());
+            let end = Pos::new(row, line.chars().count(), line.len());
+            self.remove_range(start, end, false);
+            for (r, c) in iter::once(&mut cursor).chain(anchor.as_mut()) {
+                if *r == row {
+                    *c = (*c).min(start.col);
+                }
+            }
+            count += 1;
+        }
+        self.cursor = cursor;
+        self.selection_start = anchor;
+        count
+    }
+
+    /// Sort the lines touched by the selection in the `order`. When no text is selected, all lines are sorted. Lines
+    /// are compared as described in [`SortOrder`] and the sort is stable. When `unique` is `true`, duplicate lines are
+    /// removed after sorting. The selection keeps covering the sorted lines.
+    ///
+    /// This method returns if some text was modified or not in the textarea.
+    ///
+    /// ```
+    /// use tui_textarea::{TextArea, SortOrder};
+    ///
+    /// let mut textarea = TextArea::from(["c", "a", "b", "a"]);
+    ///
+    /// textarea.sort_lines(SortOrder::Ascending, false);
+    /// assert_eq!(textarea.lines(), ["a", "a", "b", "c"]);
+    ///
+    /// textarea.sort_lines(SortOrder::Descending, true);
+    /// assert_eq!(textarea.lines(), ["c", "b", "a"]);
+    /// ```
+    pub fn sort_lines(&mut self, order: SortOrder, unique: bool) -> bool {
+        if self.needs_guard() {
+            return self.guard(false, |t| t.sort_lines(order, unique));
+        }
+        let rows = if self.selection_start.is_some() {
+            self.selected_rows()
+        } else {
+            0..self.lines.len()
+        };
+        let mut lines = self.lines[rows.clone()].to_vec();
+        order.sort(&mut lines, unique);
+        if lines[..] == self.lines[rows.clone()] {
+            return false;
+        }
+
+        let (cursor, anchor) = (self.cursor, self.selection_start);
+        let last = &self.lines[rows.end - 1];
+        let start = Pos::new(rows.start, 0, 0);
+        let end = Pos::new(rows.end - 1, last.chars().count(), last.len());
+        self.remove_range(start, end, false);
+        let last_row = rows.start + lines.len() - 1;
+        if lines.len() == 1 {
+            self.insert_piece(lines.remove(0));
+        } else {
+            self.insert_chunk(lines);
+        }
+
+        // Lines may be removed by `unique`
+        let fit = |t: &Self, (row, col): (usize, usize)| t.fit_cursor((row.min(last_row), col));
+        self.selection_start = anchor.map(|pos| fit(self, pos));
+        self.cursor = fit(self, cursor);
+        true
+    }
+
+    /// Convert the case of the selected text. When no text is selected, the character under the cursor is converted
+    /// and the cursor moves forward, like '~' of Vim. Characters whose case conversion has a different length such as
+    /// 'ß' to "SS" are supported. The selection keeps covering the converted text.
+    ///
+    /// This method returns if some text was modified or not in the textarea.
+    ///
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove, Case};
+    ///
+    /// let mut textarea = TextArea::from(["hello world"]);
+    ///
+    /// textarea.start_selection();
+    /// textarea.move_cursor(CursorMove::WordForward);
+    /// textarea.convert_selection_case(Case::Upper);
+    /// assert_eq!(textarea.lines(), ["HELLO world"]);
+    ///
+    /// textarea.cancel_selection();
+    /// textarea.convert_selection_case(Case::Toggle);
+    /// assert_eq!(textarea.lines(), ["HELLO World"]);
+    /// assert_eq!(textarea.cursor(), (0, 7));
+    /// ```
+    pub fn convert_selection_case(&mut self, case: Case) -> bool {
+        if self.needs_guard() {
+            return self.guard(false, |t| t.convert_selection_case(case));
+        }
+
+        // Row, start column, and end column (exclusive) of each part of the selection
+        let parts: Vec<(usize, usize, usize)> =
+            if let Some((top, bottom, left, right)) = self.block_selection() {
+                (top..=bottom).map(|row| (row, left, right)).collect()
+            } else if let Some((first, last)) = self.line_selection_rows() {
+                (first..=last).map(|row| (row, 0, usize::MAX)).collect()
+            } else if let Some((start, end)) = self.selection_range() {
+                (start.0..=end.0)
+                    .map(|row| {
+                        let left = if row == start.0 { start.1 } else { 0 };
+                        let right = if row == end.0 { end.1 } else { usize::MAX };
+                        (row, left, right)
+                    })
+                    .collect()
+            } else {
+                let (row, col) = self.cursor;
+                if col >= self.lines[row].chars().count() {
+                    return false;
+                }
+                vec![(row, col, col + 1)]
+            };
+
+        let (mut anchor, mut cursor) = (self.selection_start, self.cursor);
+        let mut modified = false;
+        for (row, left, right) in parts {
+            let (start, end) = self.block_row_positions(row, left, right);
+            let text = &self.lines[row][start.offset..end.offset];
+            let converted = case.convert(text);
+            let (old_len, new_len) = (end.col - start.col, converted.chars().count());
+            if anchor.is_none() {
+                cursor.1 = start.col + new_len; // Move forward like '~' of Vim
+            }
+            if converted == text {
+                continue;
+            }
+            self.remove_range(start, end, false);
+            self.insert_piece(converted);
+            // Keep the cursor and the selection anchor on the same character
+            let shift = |(r, c): (usize, usize)| {
+                if r == row && c >= end.col {
+                    (r, c + new_len - old_len)
+                } else {
+                    (r, c)
+                }
+            };
+            if anchor.is_some() {
+                anchor = anchor.map(shift);
+                cursor = shift(cursor);
+            }
+            modified = true;
+        }
+
+        self.selection_start = anchor;
+        self.cursor = cursor;
+        modified
+    }
+
+    /// Set the prefix of line comments toggled by [`TextArea::toggle_comment`] such as `"// "` or `"# "`. An empty
+    /// prefix disables commenting. It is empty by default.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["let x = 1;"]);
+    ///
+    /// textarea.set_comment_prefix("// ");
+    /// textarea.toggle_comment();
+    /// assert_eq!(textarea.lines(), ["// let x = 1;"]);
+    /// ```
+    pub fn set_comment_prefix(&mut self, prefix: &str) {
+        self.comment_prefix = prefix.to_string();
+    }
+
+    /// Get the prefix of line comments set by [`TextArea::set_comment_prefix`].
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// assert_eq!(textarea.comment_prefix(), "");
+    /// textarea.set_comment_prefix("# ");
+    /// assert_eq!(textarea.comment_prefix(), "# ");
+    /// ```
+    pub fn comment_prefix(&self) -> &str {
+        &self.comment_prefix
+    }
+
+    /// Comment or uncomment every line touched by the selection with the prefix set by
+    /// [`TextArea::set_comment_prefix`]. When no text is selected, the cursor line is toggled. When all non-blank lines
+    /// already start with the prefix after their indentation, the prefix is removed. The prefix without its trailing
+    /// whitespaces is also accepted on removing, so `//foo` is uncommented with the prefix `"// "`. Otherwise, the
+    /// prefix is inserted to all non-blank lines at their common indentation. Blank lines are not modified. The
+    /// selection keeps covering the same text.
+    ///
+    /// This method returns if some text was modified or not in the textarea. Nothing happens when the prefix is empty.
+    ///
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["if x {", "    y();", "", "}"]);
+    /// textarea.set_comment_prefix("// ");
+    ///
+    /// textarea.select_all();
+    /// textarea.toggle_comment();
+    /// assert_eq!(textarea.lines(), ["// if x {", "//     y();", "", "// }"]);
+    ///
+    /// textarea.toggle_comment();
+    /// assert_eq!(textarea.lines(), ["if x {", "    y();", "", "}"]);
+    /// ```
+    pub fn toggle_comment(&mut self) -> bool {
+        if self.needs_guard() {
+            return self.guard(false, |t| t.toggle_comment());
+        }
+        let prefix = self.comment_prefix.clone();
+        if prefix.is_empty() {
+            return false;
+        }
+        let short_prefix = prefix.trim_end();
+
+        // Non-blank rows with the number of characters of their indentation
+        let rows: Vec<(usize, usize)> = self
+            .selected_rows()
+            .filter_map(|row| {
+                let line = &self.lines[row];
+                let indent = line.chars().take_while(|c| c.is_whitespace()).count();
+                (indent < line.chars().count()).then_some((row, indent))
+            })
+            .collect();
+        if rows.is_empty() {
+            return false;
+        }
+        let commented = !short_prefix.is_empty()
+            && rows.iter().all(|&(row, indent)| {
+                let line = &self.lines[row];
+                line[self.line_offset(row, indent)..].starts_with(short_prefix)
+            });
+        let common_indent = rows.iter().map(|(_, indent)| *indent).min().unwrap_or(0);
+
+        let (mut anchor, mut cursor) = (self.selection_start, self.cursor);
+        for (row, indent) in rows {
+            let (col, added, removed) = if commented {
+                let offset = self.line_offset(row, indent);
+                let line = &self.lines[row];
+                let len = if line[offset..].starts_with(prefix.as_str()) {
+                    prefix.len()
+                } else {
+                    short_prefix.len()
+                };
+                let chars = line[offset..offset + len].chars().count();
+                let start = Pos::new(row, indent, offset);
+                let end = Pos::new(row, indent + chars, offset + len);
+                self.remove_range(start, end, false);
+                (indent, 0, chars)
+            } else {
+                self.cursor = (row, common_indent);
+                self.insert_piece(prefix.clone());
+                (common_indent, prefix.chars().count(), 0)
+            };
+            // Keep the cursor and the selection anchor on the same character
+            let shift = |(r, c): (usize, usize)| {
+                if r == row && c >= col {
+                    (r, c + added - (c - col).min(removed))
+                } else {
+                    (r, c)
+                }
+            };
+            anchor = anchor.map(shift);
+            cursor = shift(cursor);
+        }
+
+        self.selection_start = anchor;
+        self.cursor = cursor;
+        true
+    }
+
+    /// Set list continuation on inserting a newline. When enabled, a newline in a list item such as `- item` or
+    /// `1. item` starts the next item with the same indentation and marker. Numbers are incremented and checkboxes are
+    /// unchecked. A newline in an empty nested list item outdents it by one indent level, and a newline in an empty
+    /// top-level list item removes its marker instead. Each of them is a single undo entry.
+    /// Setting `None` disables list continuation. It is disabled by default.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove, ListContinuation};
+    ///
+    /// let mut textarea = TextArea::from(["- [x] done"]);
+    /// textarea.set_list_continuation(Some(ListContinuation::default()));
+    /// textarea.move_cursor(CursorMove::End);
+    ///
+    /// textarea.insert_newline();
+    /// assert_eq!(textarea.lines(), ["- [x] done", "- [ ] "]);
+    ///
+    /// // Newline in the empty nested item outdents it
+    /// textarea.move_cursor(CursorMove::Head);
+    /// textarea.insert_str("    ");
+    /// textarea.move_cursor(CursorMove::End);
+    /// textarea.insert_newline();
+    /// assert_eq!(textarea.lines(), ["- [x] done", "- [ ] "]);
+    ///
+    /// // Newline in the empty top-level item ends the list
+    /// textarea.insert_newline();
+    /// assert_eq!(textarea.lines(), ["- [x] done", ""]);
+    /// ```
+    pub fn set_list_continuation(&mut self, list: Option<ListContinuation>) {
+        self.list_continuation = list;
+    }
+
+    /// Get the list continuation configuration set by [`TextArea::set_list_continuation`].
+    /// ```
+    /// use tui_textarea::{TextArea, ListContinuation};
+    ///
+    /// let mut textarea = TextArea::default();
+    /// assert!(textarea.list_continuation().is_none());
+    /// textarea.set_list_continuation(Some(ListContinuation::default()));
+    /// assert!(textarea.list_continuation().is_some());
+    /// ```
+    pub fn list_continuation(&self) -> Option<&ListContinuation> {
+        self.list_continuation.as_ref()
+    }
+
+    /// Set rows which cannot be edited. Each row is 0-base and ranges may overlap. The cursor can still move into the
+    /// protected rows and their text can be selected and copied. Any edit which modifies a protected row is rejected
+    /// without touching the text: edit methods such as [`TextArea::insert_str`] or [`TextArea::input`] return `false`
+    /// (or do nothing) and [`TextArea::protected_row_error`] returns the error. This includes [`TextArea::undo`] and
+    /// [`TextArea::redo`] which would modify a protected row.
+    ///
+    /// The protected rows move along with the text when rows are inserted or removed above them. Setting an empty
+    /// [`Vec`] removes the protection.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["// DO NOT EDIT", "body"]);
+    /// textarea.set_protected_rows(vec![0..1]);
+    ///
+    /// // Editing the header is rejected
+    /// assert!(!textarea.insert_str("x"));
+    /// assert_eq!(textarea.lines(), ["// DO NOT EDIT", "body"]);
+    /// assert_eq!(textarea.protected_row_error().unwrap().row(), 0);
+    ///
+    /// // Editing the other rows is allowed
+    /// textarea.move_cursor(CursorMove::Down);
+    /// assert!(textarea.insert_str("x"));
+    /// assert_eq!(textarea.lines(), ["// DO NOT EDIT", "xbody"]);
+    /// assert_eq!(textarea.protected_row_error(), None);
+    /// ```
+    pub fn set_protected_rows(&mut self, rows: Vec<Range<usize>>) {
+        self.protected_rows = ProtectedRows::new(rows);
+        self.protected_row_error = None;
+    }
+
+    /// Get the ranges of protected rows set by [`TextArea::set_protected_rows`]. The ranges are shifted when rows are
+    /// inserted or removed above them.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["a", "b", "c"]);
+    /// textarea.set_protected_rows(vec![2..3]);
+    ///
+    /// textarea.insert_newline();
+    /// assert_eq!(textarea.protected_rows(), [3..4]);
+    /// ```
+    pub fn protected_rows(&self) -> &[Range<usize>] {
+        self.protected_rows.ranges()
+    }
+
+    /// Check if the row is protected by [`TextArea::set_protected_rows`]. The row is 0-base.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["a", "b", "c"]);
+    /// textarea.set_protected_rows(vec![0..2]);
+    ///
+    /// assert!(textarea.is_row_protected(1));
+    /// assert!(!textarea.is_row_protected(2));
+    /// ```
+    pub fn is_row_protected(&self, row: usize) -> bool {
+        self.protected_rows.contains(row)
+    }
+
+    /// Get the error of the last edit when it was rejected since it modified a protected row. `None` is returned when
+    /// the last edit was not rejected. See [`TextArea::set_protected_rows`].
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["header"]);
+    /// textarea.set_protected_rows(vec![0..1]);
+    ///
+    /// textarea.insert_char('x');
+    /// let err = textarea.protected_row_error().unwrap();
+    /// assert_eq!(err.to_string(), "row 1 is protected and cannot be edited");
+    /// ```
+    pub fn protected_row_error(&self) -> Option<ProtectedRowError> {
+        self.protected_row_error
+    }
+
+    /// Set the style of protected rows set by [`TextArea::set_protected_rows`]. The default value is the empty style.
+    /// Setting a distinct background color makes the boundary of protected rows clear.
+    /// ```
+    /// use ratatui::style::{Style, Color};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// textarea.set_protected_row_style(Style::default().bg(Color::DarkGray));
+    /// assert_eq!(textarea.protected_row_style(), Style::default().bg(Color::DarkGray));
+    /// ```
+    pub fn set_protected_row_style(&mut self, style: Style) {
+        self.protected_row_style = style;
+    }
+
+    /// Get the style of protected rows. See [`TextArea::set_protected_row_style`].
+    /// ```
+    /// use ratatui::style::Style;
+    /// use tui_textarea::TextArea;
+    ///
+    /// let textarea = TextArea::default();
+    ///
+    /// assert_eq!(textarea.protected_row_style(), Style::default());
+    /// ```
+    pub fn protected_row_style(&self) -> Style {
+        self.protected_row_style
+    }
+
+    /// Set how many modifications are remembered for undo/redo. Setting 0 disables undo/redo.
+    pub fn set_max_histories(&mut self, max: usize) {
+        let modified = self.history.is_modified();
+        let coalescing = self.history.coalescing();
+        let max_bytes = self.history.max_bytes();
+        let (changes, record_changes) = (self.history.changes(), self.history.record_changes());
+        self.history = History::new(max);
+        self.history.set_coalescing(coalescing);
+        self.history.set_max_bytes(max_bytes);
+        self.history.set_changes(changes);
+        self.history.set_record_changes(record_changes);
+        if modified {
+            self.history.forget_saved();
+        }
+    }
+
+    /// Get how many modifications are remembered for undo/redo. The default value is 50.
+    pub fn max_histories(&self) -> usize {
+        self.history.max_items()
+    }
+
+    /// Set the max size in bytes of the text stored in the edit history. When the total size of the text inserted or
+    /// deleted by the remembered modifications exceeds the limit, the oldest modifications are forgotten until the
+    /// total size fits in the limit. The last modification is always remembered even if it is larger than the limit.
+    /// The size of the modifications in a history group is counted together. The default value is `usize::MAX`, which
+    /// means no limit.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// textarea.set_max_history_bytes(8);
+    ///
+    /// textarea.insert_str("hello");
+    /// textarea.insert_str("world");
+    /// assert_eq!(textarea.history_memory_usage(), 5);
+    ///
+    /// // Only the last modification is remembered
+    /// assert!(textarea.undo());
+    /// assert_eq!(textarea.lines(), ["hello"]);
+    /// assert!(!textarea.undo());
+    /// ```
+    pub fn set_max_history_bytes(&mut self, max: usize) {
+        self.history.set_max_bytes(max);
+    }
+
+    /// Get the max size in bytes of the text stored in the edit history. The default value is `usize::MAX`. See
+    /// [`TextArea::set_max_history_bytes`] for more details.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// assert_eq!(textarea.max_history_bytes(), usize::MAX);
+    ///
+    /// textarea.set_max_history_bytes(1024);
+    /// assert_eq!(textarea.max_history_bytes(), 1024);
+    /// ```
+    pub fn max_history_bytes(&self) -> usize {
+        self.history.max_bytes()
+    }
+
+    /// Get the total size in bytes of the text stored in the edit history for undo and redo.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// assert_eq!(textarea.history_memory_usage(), 0);
+    ///
+    /// textarea.insert_str("hello");
+    /// textarea.delete_char();
+    /// assert_eq!(textarea.history_memory_usage(), 6);
+    /// ```
+    pub fn history_memory_usage(&self) -> usize {
+        self.history.bytes()
+    }
+
+    /// Set how consecutive single-character insertions and deletions are merged into one undo unit. See
+    /// [`Coalescing`] for the policies. The default policy is [`Coalescing::PerChar`].
+    /// ```
+    /// use tui_textarea::{Coalescing, TextArea};
+    ///
+    /// let mut textarea = TextArea::default();
+    /// textarea.set_history_coalescing(Coalescing::PerWord);
+    ///
+    /// for c in "hello world".chars() {
+    ///     textarea.insert_char(c);
+    /// }
+    ///
+    /// // Typed characters are undone word by word
+    /// textarea.undo();
+    /// assert_eq!(textarea.lines(), ["hello "]);
+    /// textarea.undo();
+    /// assert_eq!(textarea.lines(), [""]);
+    /// ```
+    pub fn set_history_coalescing(&mut self, coalescing: Coalescing) {
+        self.history.set_coalescing(coalescing);
+    }
+
+    /// Get the policy to merge consecutive single-character edits into one undo unit. The default value is
+    /// [`Coalescing::PerChar`].
+    /// ```
+    /// use tui_textarea::{Coalescing, TextArea};
+    ///
+    /// let mut textarea = TextArea::default();
+    /// assert_eq!(textarea.history_coalescing(), Coalescing::PerChar);
+    ///
+    /// textarea.set_history_coalescing(Coalescing::Manual);
+    /// assert_eq!(textarea.history_coalescing(), Coalescing::Manual);
+    /// ```
+    pub fn history_coalescing(&self) -> Coalescing {
+        self.history.coalescing()
+    }
+
+    /// Set where the cursor is placed after [`TextArea::undo`] and [`TextArea::redo`]. See [`UndoCursor`] for the
+    /// options. The default value is [`UndoCursor::EditStart`].
+    /// ```
+    /// use tui_textarea::{CursorMove, TextArea, UndoCursor};
+    ///
+    /// let mut textarea = TextArea::from(["abc def"]);
+    /// textarea.set_undo_cursor(UndoCursor::Restore);
+    ///
+    /// textarea.move_cursor(CursorMove::End);
+    /// textarea.delete_word();
+    /// assert_eq!(textarea.lines(), ["abc "]);
+    ///
+    /// textarea.undo();
+    /// assert_eq!(textarea.cursor(), (0, 7));
+    /// textarea.redo();
+    /// assert_eq!(textarea.cursor(), (0, 4));
+    /// ```
+    pub fn set_undo_cursor(&mut self, undo_cursor: UndoCursor) {
+        self.history.set_undo_cursor(undo_cursor);
+    }
+
+    /// Get where the cursor is placed after [`TextArea::undo`] and [`TextArea::redo`]. The default value is
+    /// [`UndoCursor::EditStart`].
+    /// ```
+    /// use tui_textarea::{TextArea, UndoCursor};
+    ///
+    /// let textarea = TextArea::default();
+    /// assert_eq!(textarea.undo_cursor(), UndoCursor::EditStart);
+    /// ```
+    pub fn undo_cursor(&self) -> UndoCursor {
+        self.history.undo_cursor()
+    }
+
+    /// Return if the text was modified since the last [`TextArea::mark_saved`] call, or since the textarea was created
+    /// when it was never called. Undoing all modifications since the saved state makes the textarea unmodified again.
+    /// Changes of the yanked text or the cursor position are not modifications.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["hello"]);
+    /// assert!(!textarea.is_modified());
+    ///
+    /// textarea.insert_char('x');
+    /// assert!(textarea.is_modified());
+    ///
+    /// textarea.mark_saved();
+    /// assert!(!textarea.is_modified());
+    ///
+    /// textarea.insert_char('y');
+    /// assert!(textarea.is_modified());
+    ///
+    /// // Undo back to the saved state
+    /// textarea.undo();
+    /// assert!(!textarea.is_modified());
+    /// ```
+    pub fn is_modified(&self) -> bool {
+        self.history.is_modified()
+    }
+
+    /// Mark the current text as saved. After calling this method, [`TextArea::is_modified`] returns `false` until the
+    /// text is modified again. When the saved state is no longer reachable by undo or redo (e.g. the history entries
+    /// were removed due to [`TextArea::max_histories`]), the textarea stays modified.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// textarea.insert_str("hello");
+    /// textarea.mark_saved();
+    /// textarea.undo();
+    /// assert!(textarea.is_modified());
+    ///
+    /// // Redo back to the saved state
+    /// textarea.redo();
+    /// assert!(!textarea.is_modified());
+    /// ```
+    pub fn mark_saved(&mut self) {
+        self.history.mark_saved();
+    }
+
+    /// Set the style of line at cursor. By default, the cursor line is styled with underline. To stop styling the
+    /// cursor line, set the default style.
+    /// ```
+    /// use ratatui::style::{Style, Color};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// let style = Style::default().fg(Color::Red);
+    /// textarea.set_cursor_line_style(style);
+    /// assert_eq!(textarea.cursor_line_style(), style);
+    ///
+    /// // Disable cursor line style
+    /// textarea.set_cursor_line_style(Style::default());
+    /// ```
+    pub fn set_cursor_line_style(&mut self, style: Style) {
+        self.cursor_line_style = style;
+    }
+
+    /// Get the style of cursor line. By default it is styled with underline.
+    pub fn cursor_line_style(&self) -> Style {
+        self.cursor_line_style
+    }
+
+    /// Set if the cursor line style set by [`TextArea::set_cursor_line_style`] fills the whole row of the textarea
+    /// including the gutter and the empty space after the end of the line, like `cursorline` option of Vim. The styles
+    /// of line numbers and text on the row are merged with the cursor line style. When lines are wrapped, all display
+    /// rows of the cursor line are filled.
+    /// ```
+    /// use ratatui::buffer::Buffer;
+    /// use ratatui::layout::Rect;
+    /// use ratatui::style::{Color, Style};
+    /// use ratatui::widgets::Widget as _;
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["abc"]);
+    /// textarea.set_cursor_line_style(Style::default().bg(Color::DarkGray));
+    /// textarea.set_line_number_style(Style::default().fg(Color::Yellow));
+    /// textarea.set_cursor_line_fill(true);
+    ///
+    /// let r = Rect { x: 0, y: 0, width: 10, height: 1 };
+    /// let mut buf = Buffer::empty(r);
+    /// textarea.render(r, &mut buf);
+    ///
+    /// // The line number has both styles
+    /// assert_eq!(buf[(1, 0)].fg, Color::Yellow);
+    /// assert_eq!(buf[(1, 0)].bg, Color::DarkGray);
+    /// // The space at the end of the row is filled
+    /// assert_eq!(buf[(9, 0)].bg, Color::DarkGray);
+    /// ```
+    pub fn set_cursor_line_fill(&mut self, enabled: bool) {
+        self.cursor_line_fill = enabled;
+    }
+
+    /// Get if the cursor line style fills the whole row. The default value is `false`.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let textarea = TextArea::default();
+    /// assert!(!textarea.cursor_line_fill());
+    /// ```
+    pub fn cursor_line_fill(&self) -> bool {
+        self.cursor_line_fill
+    }
+
+    /// Set the style of line number. By setting the style with this method, line numbers are drawn in textarea, meant
+    /// that line numbers are disabled by default. If you want to show line numbers but don't want to style them, set
+    /// the default style.
+    /// ```
+    /// use ratatui::style::{Style, Color};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// // Show line numbers in dark gray background
+    /// let style = Style::default().bg(Color::DarkGray);
+    /// textarea.set_line_number_style(style);
+    /// assert_eq!(textarea.line_number_style(), Some(style));
+    /// ```
+    pub fn set_line_number_style(&mut self, style: Style) {
+        self.line_number_style = Some(style);
+    }
+
+    /// Remove the style of line number which was set by [`TextArea::set_line_number_style`]. After calling this
+    /// method, Line numbers will no longer be shown.
+    /// ```
+    /// use ratatui::style::{Style, Color};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// textarea.set_line_number_style(Style::default().bg(Color::DarkGray));
+    /// textarea.remove_line_number();
+    /// assert_eq!(textarea.line_number_style(), None);
+    /// ```
+    pub fn remove_line_number(&mut self) {
+        self.line_number_style = None;
+    }
+
+    /// Get the style of line number if set.
+    pub fn line_number_style(&self) -> Option<Style> {
+        self.line_number_style
+    }
+
+    /// Set a function to build the text of line numbers. The function is called for each rendered line with a
+    /// [`LineNumberContext`] and the returned span is rendered as the line number. The span is padded with spaces or
+    /// truncated to [`LineNumberContext::width`] so that text is aligned even if the formatter returns spans of
+    /// different widths. Line numbers are shown only while the style is set by [`TextArea::set_line_number_style`].
+    /// ```
+    /// use ratatui::buffer::Buffer;
+    /// use ratatui::layout::Rect;
+    /// use ratatui::style::Style;
+    /// use ratatui::text::Span;
+    /// use ratatui::widgets::Widget as _;
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["hello", "world"]);
+    /// textarea.set_line_number_style(Style::default());
+    /// textarea.set_line_number_formatter(|ctx| {
+    ///     let mark = if ctx.cursor_row { '>' } else { ' ' };
+    ///     Span::styled(format!("{}{}", mark, ctx.row + 1), ctx.style)
+    /// });
+    ///
+    /// let r = Rect { x: 0, y: 0, width: 8, height: 2 };
+    /// let mut buf = Buffer::empty(r);
+    /// textarea.render(r, &mut buf);
+    /// assert_eq!(buf[(0, 0)].symbol(), ">");
+    /// assert_eq!(buf[(3, 1)].symbol(), "w");
+    /// ```
+    pub fn set_line_number_formatter<F>(&mut self, f: F)
+    where
+        F: Fn(LineNumberContext) -> Span<'static> + Send + Sync + 'static,
+    {
+        self.line_number_formatter = Some(LineNumberFormatter::new(f));
+    }
+
+    /// Remove the line number formatter set by [`TextArea::set_line_number_formatter`]. After calling this method,
+    /// line numbers are rendered in the default format.
+    /// ```
+    /// use ratatui::text::Span;
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// textarea.set_line_number_formatter(|ctx| Span::raw(ctx.row.to_string()));
+    /// textarea.remove_line_number_formatter();
+    /// ```
+    pub fn remove_line_number_formatter(&mut self) {
+        self.line_number_formatter = None;
+    }
+
+    /// Set the sign on the row, or remove the sign from the row with `None`. Signs are rendered in the sign column on
+    /// the left of line numbers. The sign column is 2 cells wide and is shown while at least one sign is set, or
+    /// always when it is enabled by [`TextArea::set_sign_column`]. A sign moves along with its line when lines are
+    /// inserted or deleted above it, and it is removed when its line is deleted.
+    /// ```
+    /// use ratatui::style::{Style, Color};
+    /// use tui_textarea::{TextArea, Sign};
+    ///
+    /// let mut textarea = TextArea::from(["aaa", "bbb"]);
+    ///
+    /// let sign = Sign::new("●", Style::default().fg(Color::Red));
+    /// textarea.set_sign(1, Some(sign.clone()));
+    /// assert_eq!(textarea.sign(1), Some(&sign));
+    ///
+    /// // The sign follows the line
+    /// textarea.insert_newline();
+    /// assert_eq!(textarea.sign(1), None);
+    /// assert_eq!(textarea.sign(2), Some(&sign));
+    ///
+    /// textarea.set_sign(2, None);
+    /// assert_eq!(textarea.sign(2), None);
+    /// ```
+    pub fn set_sign(&mut self, row: usize, sign: Option<Sign>) {
+        self.signs.set(row, sign);
+    }
+
+    /// Get the sign on the row set by [`TextArea::set_sign`].
+    /// ```
+    /// use ratatui::style::Style;
+    /// use tui_textarea::{TextArea, Sign};
+    ///
+    /// let mut textarea = TextArea::from(["aaa"]);
+    /// assert_eq!(textarea.sign(0), None);
+    ///
+    /// textarea.set_sign(0, Some(Sign::new("E", Style::default())));
+    /// assert_eq!(textarea.sign(0).unwrap().symbol, "E");
+    /// ```
+    pub fn sign(&self, row: usize) -> Option<&Sign> {
+        self.signs.get(row)
+    }
+
+    /// Remove all signs set by [`TextArea::set_sign`].
+    /// ```
+    /// use ratatui::style::Style;
+    /// use tui_textarea::{TextArea, Sign};
+    ///
+    /// let mut textarea = TextArea::from(["aaa", "bbb"]);
+    /// textarea.set_sign(0, Some(Sign::new("E", Style::default())));
+    /// textarea.set_sign(1, Some(Sign::new("W", Style::default())));
+    ///
+    /// textarea.clear_signs();
+    /// assert_eq!(textarea.sign(0), None);
+    /// assert_eq!(textarea.sign(1), None);
+    /// ```
+    pub fn clear_signs(&mut self) {
+        self.signs.clear();
+    }
+
+    /// Set if the sign column is always shown. When disabled, the sign column is shown only while some sign is set
+    /// by [`TextArea::set_sign`]. Showing it always avoids shifting the text when signs are set and removed.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// textarea.set_sign_column(true);
+    /// assert!(textarea.sign_column());
+    /// ```
+    pub fn set_sign_column(&mut self, enabled: bool) {
+        self.sign_column = enabled;
+    }
+
+    /// Get if the sign column is always shown. The default value is `false`.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let textarea = TextArea::default();
+    /// assert!(!textarea.sign_column());
+    /// ```
+    pub fn sign_column(&self) -> bool {
+        self.sign_column
+    }
+
+    fn sign_column_visible(&self) -> bool {
+        self.sign_column || !self.signs.is_empty()
+    }
+
+    // Width of the columns rendered on the left of the text, which are the sign column and line numbers
+    pub(crate) fn gutter_width(&self) -> usize {
+        let signs = if self.sign_column_visible() {
+            SIGN_WIDTH
+        } else {
+            0
+        };
+        let lnum = match self.line_number_style {
+            Some(_) => num_digits(self.lines.len()) as usize + 2, // `+ 2` for margins
+            None => 0,
+        };
+        signs + lnum
+    }
+
+    /// Set the style of trailing spaces and tabs at the end of lines, which are removed by
+    /// [`TextArea::trim_trailing_whitespace`]. Note that trailing whitespaces are not highlighted by default.
+    /// ```
+    /// use ratatui::style::{Style, Color};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// let style = Style::default().bg(Color::Red);
+    /// textarea.set_trailing_whitespace_style(style);
+    /// assert_eq!(textarea.trailing_whitespace_style(), Some(style));
+    /// ```
+    pub fn set_trailing_whitespace_style(&mut self, style: Style) {
+        self.trailing_whitespace_style = Some(style);
+    }
+
+    /// Remove the style of trailing whitespaces which was set by [`TextArea::set_trailing_whitespace_style`]. After
+    /// calling this method, trailing whitespaces will no longer be highlighted.
+    /// ```
+    /// use ratatui::style::{Style, Color};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// textarea.set_trailing_whitespace_style(Style::default().bg(Color::Red));
+    /// textarea.remove_trailing_whitespace_style();
+    /// assert_eq!(textarea.trailing_whitespace_style(), None);
+    /// ```
+    pub fn remove_trailing_whitespace_style(&mut self) {
+        self.trailing_whitespace_style = None;
+    }
+
+    /// Get the style of trailing whitespaces if set.
+    pub fn trailing_whitespace_style(&self) -> Option<Style> {
+        self.trailing_whitespace_style
+    }
+
+    /// Set if trailing whitespaces are highlighted on the cursor line while the cursor is in or at the end of them.
+    /// They are not highlighted by default so that whitespaces typed at the end of a line do not flicker.
+    /// ```
+    /// use ratatui::buffer::Buffer;
+    /// use ratatui::layout::Rect;
+    /// use ratatui::style::{Color, Style};
+    /// use ratatui::widgets::Widget as _;
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["a  "]);
+    /// textarea.set_trailing_whitespace_style(Style::default().bg(Color::Red));
+    /// textarea.move_cursor(CursorMove::End);
+    ///
+    /// let r = Rect { x: 0, y: 0, width: 5, height: 1 };
+    /// let mut buf = Buffer::empty(r);
+    /// textarea.render(r, &mut buf);
+    /// assert_eq!(buf[(1, 0)].bg, Color::Reset);
+    ///
+    /// textarea.set_trailing_whitespace_at_cursor(true);
+    /// textarea.render(r, &mut buf);
+    /// assert_eq!(buf[(1, 0)].bg, Color::Red);
+    /// ```
+    pub fn set_trailing_whitespace_at_cursor(&mut self, enabled: bool) {
+        self.trailing_whitespace_at_cursor = enabled;
+    }
+
+    /// Get if trailing whitespaces are highlighted on the cursor line while the cursor is in or at the end of them.
+    /// The default value is `false`.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let textarea = TextArea::default();
+    /// assert!(!textarea.trailing_whitespace_at_cursor());
+    /// ```
+    pub fn trailing_whitespace_at_cursor(&self) -> bool {
+        self.trailing_whitespace_at_cursor
+    }
+
+    /// Set the style of control characters. Control characters are rendered in caret notation like `^[` for ESC, and
+    /// C1 control characters are rendered with their code like `<9b>` so that they do not break the terminal. The
+    /// cursor moves over them as they are rendered, but they are still edited as single characters.
+    /// ```
+    /// use ratatui::buffer::Buffer;
+    /// use ratatui::layout::Rect;
+    /// use ratatui::style::{Color, Style};
+    /// use ratatui::widgets::Widget as _;
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["a\x1bb"]);
+    /// textarea.set_control_char_style(Style::default().fg(Color::Red));
+    ///
+    /// let r = Rect { x: 0, y: 0, width: 5, height: 1 };
+    /// let mut buf = Buffer::empty(r);
+    /// textarea.render(r, &mut buf);
+    /// assert_eq!(buf[(1, 0)].symbol(), "^");
+    /// assert_eq!(buf[(2, 0)].symbol(), "[");
+    /// assert_eq!(buf[(1, 0)].fg, Color::Red);
+    /// ```
+    pub fn set_control_char_style(&mut self, style: Style) {
+        self.control_char_style = style;
+    }
+
+    /// Get the style of control characters. The default style is dark gray foreground.
+    /// ```
+    /// use ratatui::style::{Color, Style};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let textarea = TextArea::default();
+    /// assert_eq!(textarea.control_char_style(), Style::default().fg(Color::DarkGray));
+    /// ```
+    pub fn control_char_style(&self) -> Style {
+        self.control_char_style
+    }
+
+    /// Set the style to highlight the bracket under the cursor or just before the cursor and its matching bracket.
+    /// `()`, `[]`, and `{}` are supported and the matching bracket is searched in the same way as
+    /// [`CursorMove::MatchingBracket`]. The highlight is updated whenever the textarea is rendered. Matching brackets
+    /// are not highlighted by default.
+    /// ```
+    /// use ratatui::buffer::Buffer;
+    /// use ratatui::layout::Rect;
+    /// use ratatui::style::{Color, Style};
+    /// use ratatui::widgets::Widget as _;
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["(a [b])"]);
+    /// textarea.set_matching_bracket_style(Style::default().bg(Color::Yellow));
+    /// textarea.move_cursor(CursorMove::Jump(0, 6));
+    ///
+    /// let r = Rect { x: 0, y: 0, width: 8, height: 1 };
+    /// let mut buf = Buffer::empty(r);
+    /// textarea.render(r, &mut buf);
+    /// assert_eq!(buf[(0, 0)].bg, Color::Yellow);
+    /// assert_eq!(buf[(3, 0)].bg, Color::Reset);
+    /// ```
+    pub fn set_matching_bracket_style(&mut self, style: Style) {
+        self.matching_bracket_style = Some(style);
+    }
+
+    /// Remove the style of matching brackets which was set by [`TextArea::set_matching_bracket_style`]. After calling
+    /// this method, matching brackets will no longer be highlighted.
+    /// ```
+    /// use ratatui::style::{Style, Color};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// textarea.set_matching_bracket_style(Style::default().bg(Color::Yellow));
+    /// textarea.remove_matching_bracket_style();
+    /// assert_eq!(textarea.matching_bracket_style(), None);
+    /// ```
+    pub fn remove_matching_bracket_style(&mut self) {
+        self.matching_bracket_style = None;
+    }
+
+    /// Get the style of matching brackets if set.
+    pub fn matching_bracket_style(&self) -> Option<Style> {
+        self.matching_bracket_style
+    }
+
+    /// Set the style of the bracket under the cursor or just before the cursor when no matching bracket is found. It
+    /// is only used while the matching bracket style is set by [`TextArea::set_matching_bracket_style`].
+    /// ```
+    /// use ratatui::buffer::Buffer;
+    /// use ratatui::layout::Rect;
+    /// use ratatui::style::{Color, Style};
+    /// use ratatui::widgets::Widget as _;
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["(a"]);
+    /// textarea.set_matching_bracket_style(Style::default().bg(Color::Yellow));
+    /// textarea.set_unmatched_bracket_style(Style::default().bg(Color::Red));
+    /// textarea.move_cursor(CursorMove::End);
+    ///
+    /// let r = Rect { x: 0, y: 0, width: 4, height: 1 };
+    /// let mut buf = Buffer::empty(r);
+    /// textarea.render(r, &mut buf);
+    /// assert_eq!(buf[(0, 0)].bg, Color::Reset);
+    ///
+    /// textarea.move_cursor(CursorMove::Back);
+    /// textarea.render(r, &mut buf);
+    /// assert_eq!(buf[(0, 0)].bg, Color::Red);
+    /// ```
+    pub fn set_unmatched_bracket_style(&mut self, style: Style) {
+        self.unmatched_bracket_style = Some(style);
+    }
+
+    /// Remove the style of unmatched brackets which was set by [`TextArea::set_unmatched_bracket_style`].
+    /// ```
+    /// use ratatui::style::{Style, Color};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// textarea.set_unmatched_bracket_style(Style::default().bg(Color::Red));
+    /// textarea.remove_unmatched_bracket_style();
+    /// assert_eq!(textarea.unmatched_bracket_style(), None);
+    /// ```
+    pub fn remove_unmatched_bracket_style(&mut self) {
+        self.unmatched_bracket_style = None;
+    }
+
+    /// Get the style of unmatched brackets if set.
+    pub fn unmatched_bracket_style(&self) -> Option<Style> {
+        self.unmatched_bracket_style
+    }
+
+    /// Set the maximum number of lines searched from the cursor line to find the matching bracket for highlighting.
+    /// Brackets matching beyond the distance are treated as unmatched. This keeps rendering fast in a large buffer.
+    /// [`CursorMove::MatchingBracket`] is not limited by this value.
+    /// ```
+    /// use ratatui::style::{Style, Color};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// textarea.set_bracket_match_distance(100);
+    /// assert_eq!(textarea.bracket_match_distance(), 100);
+    /// ```
+    pub fn set_bracket_match_distance(&mut self, lines: usize) {
+        self.bracket_match_distance = lines;
+    }
+
+    /// Get the maximum number of lines searched to find the matching bracket for highlighting. The default value is
+    /// 1000.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let textarea = TextArea::default();
+    /// assert_eq!(textarea.bracket_match_distance(), 1000);
+    /// ```
+    pub fn bracket_match_distance(&self) -> usize {
+        self.bracket_match_distance
+    }
+
+    /// Render tabs, spaces and the ends of lines with the markers like `list` option of Vim. Only the rendering is
+    /// changed, so the text of the textarea and the cursor positions are not affected. The markers are rendered in
+    /// the style of [`WhitespaceRender`], and selection, search matches, and the cursor are highlighted over them.
+    /// Whitespaces are not marked while the text is masked by [`TextArea::set_mask_char`].
+    /// ```
+    /// use ratatui::buffer::Buffer;
+    /// use ratatui::layout::Rect;
+    /// use ratatui::style::{Color, Style};
+    /// use ratatui::widgets::Widget as _;
+    /// use tui_textarea::{TextArea, WhitespaceRender};
+    ///
+    /// let mut textarea = TextArea::from(["\ta b"]);
+    /// textarea.set_show_whitespace(WhitespaceRender {
+    ///     tab: Some('→'),
+    ///     space: Some('·'),
+    ///     eol: Some('$'),
+    ///     style: Style::default().fg(Color::DarkGray),
+    /// });
+    ///
+    /// let r = Rect { x: 0, y: 0, width: 8, height: 1 };
+    /// let mut buf = Buffer::empty(r);
+    /// textarea.render(r, &mut buf);
+    ///
+    /// let rendered: String = (0..8).map(|x| buf[(x, 0)].symbol()).collect();
+    /// assert_eq!(rendered, "→   a·b$");
+    /// assert_eq!(textarea.lines(), ["\ta b"]);
+    /// ```
+    pub fn set_show_whitespace(&mut self, render: WhitespaceRender) {
+        self.show_whitespace = Some(render);
+    }
+
+    /// Stop rendering the whitespace markers set by [`TextArea::set_show_whitespace`].
+    /// ```
+    /// use tui_textarea::{TextArea, WhitespaceRender};
+    ///
+    /// let mut textarea = TextArea::default();
+    /// textarea.set_show_whitespace(WhitespaceRender { eol: Some('$'), ..Default::default() });
+    /// textarea.hide_whitespace();
+    /// assert_eq!(textarea.show_whitespace(), None);
+    /// ```
+    pub fn hide_whitespace(&mut self) {
+        self.show_whitespace = None;
+    }
+
+    /// Get the whitespace markers if set.
+    pub fn show_whitespace(&self) -> Option<WhitespaceRender> {
+        self.show_whitespace
+    }
+
+    /// Set the placeholder text. The text is set in the textarea when no text is input. Setting a non-empty string `""`
+    /// enables the placeholder. The default value is an empty string so the placeholder is disabled by default.
+    /// To customize the text style, see [`TextArea::set_placeholder_style`].
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// assert_eq!(textarea.placeholder_text(), "");
+    /// assert!(textarea.placeholder_style().is_none());
+    ///
+    /// textarea.set_placeholder_text("Hello");
+    /// assert_eq!(textarea.placeholder_text(), "Hello");
+    /// assert!(textarea.placeholder_style().is_some());
+    /// ```
+    pub fn set_placeholder_text(&mut self, placeholder: impl Into<String>) {
+        self.placeholder = placeholder.into();
+    }
+
+    /// Set the style of the placeholder text. The default style is a dark gray text.
+    /// ```
+    /// use ratatui::style::{Style, Color};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// assert_eq!(textarea.placeholder_style(), None); // When the placeholder is disabled
+    ///
+    /// textarea.set_placeholder_text("Enter your message"); // Enable placeholder by setting non-empty text
+    ///
+    /// let style = Style::default().bg(Color::Blue);
+    /// textarea.set_placeholder_style(style);
+    /// assert_eq!(textarea.placeholder_style(), Some(style));
+    /// ```
+    pub fn set_placeholder_style(&mut self, style: Style) {
+        self.placeholder_style = style;
+    }
+
+    /// Get the placeholder text. An empty string means the placeholder is disabled. The default value is an empty string.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let textarea = TextArea::default();
+    /// assert_eq!(textarea.placeholder_text(), "");
+    /// ```
+    pub fn placeholder_text(&self) -> &'_ str {
+        self.placeholder.as_str()
+    }
+
+    /// Get the placeholder style. When the placeholder text is empty, it returns `None` since the placeholder is disabled.
+    /// The default style is a dark gray text.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// assert_eq!(textarea.placeholder_style(), None);
+    ///
+    /// textarea.set_placeholder_text("hello");
+    /// assert!(textarea.placeholder_style().is_some());
+    /// ```
+    pub fn placeholder_style(&self) -> Option<Style> {
+        if self.placeholder.is_empty() {
+            None
+        } else {
+            Some(self.placeholder_style)
+        }
+    }
+
+    /// Specify a character masking the text. All characters in the textarea will be replaced by this character.
+    /// This API is useful for making a kind of credentials form such as a password input.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// textarea.set_mask_char('*');
+    /// assert_eq!(textarea.mask_char(), Some('*'));
+    /// textarea.set_mask_char('●');
+    /// assert_eq!(textarea.mask_char(), Some('●'));
+    /// ```
+    pub fn set_mask_char(&mut self, mask: char) {
+        self.mask = Some(mask);
+    }
+
+    /// Clear the masking character previously set by [`TextArea::set_mask_char`].
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// textarea.set_mask_char('*');
+    /// assert_eq!(textarea.mask_char(), Some('*'));
+    /// textarea.clear_mask_char();
+    /// assert_eq!(textarea.mask_char(), None);
+    /// ```
+    pub fn clear_mask_char(&mut self) {
+        self.mask = None;
+    }
+
+    /// Get the character to mask text. When no character is set, `None` is returned.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// assert_eq!(textarea.mask_char(), None);
+    /// textarea.set_mask_char('*');
+    /// assert_eq!(textarea.mask_char(), Some('*'));
+    /// ```
+    pub fn mask_char(&self) -> Option<char> {
+        self.mask
+    }
+
+    /// Set the style of cursor. By default, a cursor is rendered in the reversed color. Setting the same style as
+    /// cursor line hides a cursor.
+    /// ```
+    /// use ratatui::style::{Style, Color};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// let style = Style::default().bg(Color::Red);
+    /// textarea.set_cursor_style(style);
+    /// assert_eq!(textarea.cursor_style(), style);
+    /// ```
+    pub fn set_cursor_style(&mut self, style: Style) {
+        self.cursor_style = style;
+    }
+
+    /// Get the style of cursor.
+    pub fn cursor_style(&self) -> Style {
+        self.cursor_style
+    }
+
+    /// Set how the cursor is rendered. With [`CursorRendering::Hardware`], the textarea does not paint the cell at the
+    /// cursor. Put the terminal cursor at the position returned from [`TextArea::screen_cursor_position`] instead.
+    /// ```
+    /// use ratatui::buffer::Buffer;
+    /// use ratatui::layout::Rect;
+    /// use ratatui::style::Modifier;
+    /// use ratatui::widgets::Widget as _;
+    /// use tui_textarea::{TextArea, CursorRendering};
+    ///
+    /// let mut textarea = TextArea::from(["abc"]);
+    /// textarea.set_cursor_rendering(CursorRendering::Hardware);
+    /// assert_eq!(textarea.cursor_rendering(), CursorRendering::Hardware);
+    ///
+    /// let r = Rect { x: 0, y: 0, width: 5, height: 1 };
+    /// let mut buf = Buffer::empty(r);
+    /// textarea.render(r, &mut buf);
+    /// assert!(!buf[(0, 0)].modifier.contains(Modifier::REVERSED));
+    /// ```
+    pub fn set_cursor_rendering(&mut self, rendering: CursorRendering) {
+        self.cursor_rendering = rendering;
+    }
+
+    /// Get how the cursor is rendered. The default value is [`CursorRendering::Cell`].
+    /// ```
+    /// use tui_textarea::{TextArea, CursorRendering};
+    ///
+    /// let textarea = TextArea::default();
+    /// assert_eq!(textarea.cursor_rendering(), CursorRendering::Cell);
+    /// ```
+    pub fn cursor_rendering(&self) -> CursorRendering {
+        self.cursor_rendering
+    }
+
+    /// Get slice of line texts. This method borrows the content, but not moves. Note that the returned slice will
+    /// never be empty because an empty text means a slice containing one empty line. This is correct since any text
+    /// file must end with a newline.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// assert_eq!(textarea.lines(), [""]);
+    ///
+    /// textarea.insert_char('a');
+    /// assert_eq!(textarea.lines(), ["a"]);
+    ///
+    /// textarea.insert_newline();
+    /// assert_eq!(textarea.lines(), ["a", ""]);
+    ///
+    /// textarea.insert_char('b');
+    /// assert_eq!(textarea.lines(), ["a", "b"]);
+    /// ```
+    pub fn lines(&'a self) -> &'a [String] {
+        &self.lines
+    }
+
+    /// Convert [`TextArea`] instance into line texts.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// textarea.insert_char('a');
+    /// textarea.insert_newline();
+    /// textarea.insert_char('b');
+    ///
+    /// assert_eq!(textarea.into_lines(), ["a", "b"]);
+    /// ```
+    pub fn into_lines(self) -> Vec<String> {
+        self.lines
+    }
+
+    /// Replace the whole text with the lines. Unlike creating a new [`TextArea`] instance, all the settings, the yank
+    /// buffer and the edit history are kept. The replacement is recorded as one entry in the edit history so
+    /// [`TextArea::undo`] restores the previous text. The cursor stays at the same position clamped to the new text and
+    /// the text selection is canceled. Each line must not contain a newline.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["hello", "world"]);
+    /// textarea.move_cursor(CursorMove::Bottom);
+    /// textarea.move_cursor(CursorMove::End);
+    ///
+    /// textarea.set_lines(vec!["foo".to_string()]);
+    /// assert_eq!(textarea.lines(), ["foo"]);
+    /// assert_eq!(textarea.cursor(), (0, 3));
+    ///
+    /// textarea.undo();
+    /// assert_eq!(textarea.lines(), ["hello", "world"]);
+    /// ```
+    pub fn set_lines(&mut self, mut lines: Vec<String>) {
+        if self.needs_guard() {
+            return self.guard((), |t| t.set_lines(lines));
+        }
+        if lines.is_empty() {
+            lines.push(String::new());
+        }
+        let cursor = self.cursor;
+        self.cancel_selection();
+
+        let last = self.lines.len() - 1;
+        let end = Pos::new(
+            last,
+            self.lines[last].chars().count(),
+            self.lines[last].len(),
+        );
+        if end.row > 0 || end.offset > 0 {
+            self.remove_range(Pos::new(0, 0, 0), end, false);
+        }
+        self.cursor = (0, 0);
+        if lines.len() == 1 {
+            self.insert_piece(lines.remove(0));
+        } else {
+            self.insert_chunk(lines);
+        }
+        self.cursor = self.fit_cursor(cursor);
+    }
+
+    /// Replace the whole text with the string in the same way as [`TextArea::set_lines`]. Both `\n` and `\r\n` are
+    /// recognized as newlines.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// textarea.set_yank_text("yanked");
+    ///
+    /// textarea.set_text("hello\r\nworld");
+    /// assert_eq!(textarea.lines(), ["hello", "world"]);
+    /// assert_eq!(textarea.yank_text(), "yanked");
+    /// ```
+    pub fn set_text(&mut self, text: &str) {
+        let lines = split_lines(text, self.normalize_newlines);
+        self.set_lines(lines);
+    }
+
+    /// Iterate over the text content as string slices without allocating. Line texts and line endings `"\n"` are
+    /// yielded alternately. No line ending is yielded after the last line. Concatenating all chunks results in the
+    /// same string as joining [`TextArea::lines`] with `\n`.
+    ///
+    /// This is useful for streaming the content to a writer or feeding it to an incremental hasher.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let textarea = TextArea::from(["hello", "", "world"]);
+    ///
+    /// let chunks: Vec<_> = textarea.chunks().collect();
+    /// assert_eq!(chunks, ["hello", "\n", "", "\n", "world"]);
+    /// assert_eq!(chunks.concat(), textarea.lines().join("\n"));
+    /// ```
+    pub fn chunks(&self) -> impl Iterator<Item = &'_ str> + '_ {
+        let last = self.lines.len() - 1;
+        self.lines.iter().enumerate().flat_map(move |(i, line)| {
+            let eol = if i < last { Some("\n") } else { None };
+            iter::once(line.as_str()).chain(eol)
+        })
+    }
+
+    /// Get the length of the text content in bytes. The length includes newlines between lines, which means that it
+    /// is equal to the total length of the chunks yielded by [`TextArea::chunks`]. The length is calculated by
+    /// visiting all lines on the first call after the text is modified, and it is cached until the next modification.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let textarea = TextArea::from(["abc", "🐶"]);
+    /// assert_eq!(textarea.byte_len(), 3 + 1 + 4);
+    /// assert_eq!(TextArea::default().byte_len(), 0);
+    /// ```
+    pub fn byte_len(&self) -> usize {
+        self.byte_len.get(self.history.changes(), || {
+            let newlines = self.lines.len() - 1;
+            self.lines.iter().map(String::len).sum::<usize>() + newlines
+        })
+    }
+
+    /// Write the text content to the writer chunk by chunk. See [`TextArea::chunks`] for the chunks to be written.
+    /// Since this method calls `write_all` multiple times, wrapping the writer with [`io::BufWriter`] is recommended
+    /// when the writer is not buffered (e.g. [`std::fs::File`]).
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let textarea = TextArea::from(["hello", "world"]);
+    ///
+    /// let mut buf = vec![];
+    /// textarea.write_to(&mut buf).unwrap();
+    /// assert_eq!(buf, b"hello\nworld");
+    /// ```
+    pub fn write_to<W: io::Write>(&self, mut w: W) -> io::Result<()> {
+        for chunk in self.chunks() {
+            w.write_all(chunk.as_bytes())?;
+        }
+        Ok(())
+    }
+
+    /// Build a [`Text`] from the lines of the textarea styled by `styler`, for example to render a preview pane of the
+    /// textarea contents. `styler` is called with the row and the text of each line and returns the styled line. Tabs
+    /// in the returned spans are expanded and masking is applied in the same way as rendering the textarea itself, so
+    /// columns of the two panes are aligned.
+    ///
+    /// ```
+    /// use ratatui::style::{Style, Modifier};
+    /// use ratatui::text::{Line, Span};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let textarea = TextArea::from(["# Title", "a\tb"]);
+    ///
+    /// let text = textarea.to_styled_text(|_, line| {
+    ///     if line.starts_with('#') {
+    ///         Line::from(Span::styled(line, Style::default().add_modifier(Modifier::BOLD)))
+    ///     } else {
+    ///         Line::from(line)
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(text.lines[0].spans[0].style, Style::default().add_modifier(Modifier::BOLD));
+    /// assert_eq!(text.lines[1].spans[0].content, "a   b"); // The tab length is 4 by default
+    /// ```
+    pub fn to_styled_text<'b, F>(&'b self, styler: F) -> Text<'b>
+    where
+        F: Fn(usize, &'b str) -> Line<'b>,
+    {
+        let lines: Vec<_> = self
+            .lines
+            .iter()
+            .enumerate()
+            .map(|(row, line)| {
+                let mut line = styler(row, line.as_str());
+                let mut builder = DisplayTextBuilder::new(self.tab_len, self.mask);
+                for span in line.spans.iter_mut() {
+                    span.content = match mem::take(&mut span.content) {
+                        Cow::Borrowed(s) => builder.build(s),
+                        Cow::Owned(s) => Cow::Owned(builder.build(&s).into_owned()),
+                    };
+                }
+                line
+            })
+            .collect();
+        Text::from(lines)
+    }
+
+    /// Get the current cursor position. 0-base character-wise (row, col) cursor position.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// assert_eq!(textarea.cursor(), (0, 0));
+    ///
+    /// textarea.insert_char('a');
+    /// textarea.insert_newline();
+    /// textarea.insert_char('b');
+    ///
+    /// assert_eq!(textarea.cursor(), (1, 1));
+    /// ```
+    pub fn cursor(&self) -> (usize, usize) {
+        self.cursor
+    }
+
+    /// Get the current selection range as a pair of the start position and the end position. The range is bounded
+    /// inclusively below and exclusively above. The positions are 0-base character-wise (row, col) values.
+    /// The first element of the pair is always smaller than the second one even when it is ahead of the cursor.
+    /// When no text is selected, this method returns `None`. Unlike [`TextArea::copy`], this method does not cancel
+    /// the ongoing selection. On line-wise selection started by [`TextArea::start_line_selection`], the range starts
+    /// at the head of the first line and ends at the head of the line after the last line (or the end of the last
+    /// line when it is the last line of the text). On block selection started by [`TextArea::start_block_selection`],
+    /// the range is the top-left corner and the bottom-right corner of the selected rectangle.
+    /// ```
+    /// use tui_textarea::TextArea;
+    /// use tui_textarea::CursorMove;
+    ///
+    /// let mut textarea = TextArea::from([
+    ///     "aaa",
+    ///     "bbb",
+    ///     "ccc",
+    /// ]);
+    ///
+    /// // It returns `None` when the text selection is not ongoing
+    /// assert_eq!(textarea.selection_range(), None);
+    ///
+    /// textarea.start_selection();
+    /// assert_eq!(textarea.selection_range(), Some(((0, 0), (0, 0))));
+    ///
+    /// textarea.move_cursor(CursorMove::Forward);
+    /// assert_eq!(textarea.selection_range(), Some(((0, 0), (0, 1))));
+    ///
+    /// textarea.move_cursor(CursorMove::Down);
+    /// assert_eq!(textarea.selection_range(), Some(((0, 0), (1, 1))));
+    ///
+    /// // Start selection at (1, 1)
+    /// textarea.cancel_selection();
+    /// textarea.start_selection();
+    ///
+    /// // The first element of the pair is always smaller than the second one
+    /// textarea.move_cursor(CursorMove::Back);
+    /// assert_eq!(textarea.selection_range(), Some(((1, 0), (1, 1))));
+    /// ```
+    pub fn selection_range(&self) -> Option<((usize, usize), (usize, usize))> {
+        if let Some((top, bottom, left, right)) = self.block_selection() {
+            return Some(((top, left), (bottom, right)));
+        }
+        if self.selection_mode == SelectionMode::Line {
+            return self
+                .selection_positions()
+                .map(|(s, e)| ((s.row, s.col), (e.row, e.col)));
+        }
+        self.selection_start.map(|pos| {
+            if pos > self.cursor {
+                (self.cursor, pos)
+            } else {
+                (pos, self.cursor)
+            }
+        })
+    }
+
+    /// Return if the character at the 0-base character-wise (row, col) position is selected or not. When text
+    /// selection is not ongoing, this method returns `false`. On line-wise selection, all columns of the selected
+    /// lines are selected.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["abc", "def"]);
+    /// assert!(!textarea.selection_contains(0, 0));
+    ///
+    /// textarea.move_cursor(CursorMove::Forward);
+    /// textarea.start_selection();
+    /// textarea.move_cursor(CursorMove::Down);
+    ///
+    /// assert!(!textarea.selection_contains(0, 0));
+    /// assert!(textarea.selection_contains(0, 1));
+    /// assert!(textarea.selection_contains(1, 0));
+    /// assert!(!textarea.selection_contains(1, 1)); // The cursor position is not included
+    /// ```
+    pub fn selection_contains(&self, row: usize, col: usize) -> bool {
+        if let Some((top, bottom, left, right)) = self.block_selection() {
+            return top <= row && row <= bottom && left <= col && col < right;
+        }
+        if let Some((first, last)) = self.line_selection_rows() {
+            return first <= row && row <= last;
+        }
+        match self.selection_range() {
+            Some((start, end)) => start <= (row, col) && (row, col) < end,
+            None => false,
+        }
+    }
+
+    /// Set text alignment. When [`Alignment::Center`] or [`Alignment::Right`] is set, line number is automatically
+    /// disabled because those alignments don't work well with line numbers.
+    /// ```
+    /// use ratatui::layout::Alignment;
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// textarea.set_alignment(Alignment::Center);
+    /// assert_eq!(textarea.alignment(), Alignment::Center);
+    /// ```
+    pub fn set_alignment(&mut self, alignment: Alignment) {
+        if let Alignment::Center | Alignment::Right = alignment {
+            self.line_number_style = None;
+        }
+        self.alignment = alignment;
+    }
+
+    /// Get current text alignment. The default alignment is [`Alignment::Left`].
+    /// ```
+    /// use ratatui::layout::Alignment;
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// assert_eq!(textarea.alignment(), Alignment::Left);
+    /// ```
+    pub fn alignment(&self) -> Alignment {
+        self.alignment
+    }
+
+    /// Enable or disable soft wrapping of lines. When enabled, lines longer than the width of the textarea are
+    /// wrapped into multiple display rows at word boundaries instead of scrolling horizontally. A word longer than
+    /// the width is broken at the width. The line number is rendered only on the first display row of each line.
+    /// Wrapping only changes how the text is rendered and the lines of the textarea are not modified.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// textarea.set_wrap(true);
+    /// assert!(textarea.wrap());
+    /// ```
+    pub fn set_wrap(&mut self, enabled: bool) {
+        self.wrap = enabled;
+    }
+
+    /// Get if lines are soft wrapped on rendering. The default value is `false`.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let textarea = TextArea::default();
+    /// assert!(!textarea.wrap());
+    /// ```
+    pub fn wrap(&self) -> bool {
+        self.wrap
+    }
+
+    /// Set if [`CursorMove::Up`] and [`CursorMove::Down`] move the cursor by display rows while lines are soft
+    /// wrapped with [`TextArea::set_wrap`]. When disabled, they move the cursor by lines as usual. Moves by display
+    /// rows are available after the textarea is rendered once since the width of the rows is unknown until then.
+    /// ```
+    /// use ratatui::buffer::Buffer;
+    /// use ratatui::layout::Rect;
+    /// use ratatui::widgets::Widget as _;
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["hello world", "foo"]);
+    /// textarea.set_wrap(true);
+    ///
+    /// let r = Rect { x: 0, y: 0, width: 8, height: 4 };
+    /// textarea.render(r, &mut Buffer::empty(r));
+    ///
+    /// // "world" is rendered on the second display row of the first line
+    /// textarea.move_cursor(CursorMove::Down);
+    /// assert_eq!(textarea.cursor(), (0, 6));
+    ///
+    /// textarea.set_move_by_display_rows(false);
+    /// textarea.move_cursor(CursorMove::End);
+    /// textarea.move_cursor(CursorMove::Down);
+    /// assert_eq!(textarea.cursor(), (1, 3));
     /// ```
-    #[deprecated(
-        since = "0.5.3",
-        note = "calling this method is no longer necessary on rendering a textarea. pass &TextArea reference to `Frame::render_widget` method call directly"
-    )]
-    pub fn widget(&'a self) -> impl Widget + 'a {
-        self
+    pub fn set_move_by_display_rows(&mut self, enabled: bool) {
+        self.move_by_display_rows = enabled;
     }
 
-    /// Set the style of textarea. By default, textarea is not styled.
+    /// Get if the cursor moves by display rows on vertical moves while lines are soft wrapped. The default value is
+    /// `true`.
     /// ```
-    /// use ratatui::style::{Style, Color};
     /// use tui_textarea::TextArea;
     ///
-    /// let mut textarea = TextArea::default();
-    /// let style = Style::default().fg(Color::Red);
-    /// textarea.set_style(style);
-    /// assert_eq!(textarea.style(), style);
+    /// let textarea = TextArea::default();
+    /// assert!(textarea.move_by_display_rows());
     /// ```
-    pub fn set_style(&mut self, style: Style) {
-        self.style = style;
+    pub fn move_by_display_rows(&self) -> bool {
+        self.move_by_display_rows
     }
 
-    /// Get the current style of textarea.
-    pub fn style(&self) -> Style {
-        self.style
+    /// Set the indicator rendered at the start of continuation rows of wrapped lines, such as `"↪ "`, or remove it with
+    /// `None`. The indicator is not a part of the text, so the cursor is never put on it. See [`TextArea::set_wrap`].
+    /// ```
+    /// use ratatui::buffer::Buffer;
+    /// use ratatui::layout::Rect;
+    /// use ratatui::style::{Color, Style};
+    /// use ratatui::widgets::Widget as _;
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["abcdefgh"]);
+    /// textarea.set_wrap(true);
+    /// textarea.set_wrap_indicator(Some(("> ".to_string(), Style::default().fg(Color::DarkGray))));
+    ///
+    /// let r = Rect { x: 0, y: 0, width: 5, height: 2 };
+    /// let mut buf = Buffer::empty(r);
+    /// textarea.render(r, &mut buf);
+    /// assert_eq!(buf[(0, 1)].symbol(), ">");
+    /// assert_eq!(buf[(2, 1)].symbol(), "f");
+    /// ```
+    pub fn set_wrap_indicator(&mut self, indicator: Option<(String, Style)>) {
+        self.wrap_indicator = indicator;
     }
 
-    /// Set the block of textarea. By default, no block is set.
+    /// Get the indicator rendered at the start of continuation rows of wrapped lines and its style.
     /// ```
+    /// use ratatui::style::Style;
     /// use tui_textarea::TextArea;
-    /// use ratatui::widgets::{Block, Borders};
     ///
     /// let mut textarea = TextArea::default();
-    /// let block = Block::default().borders(Borders::ALL).title("Block Title");
-    /// textarea.set_block(block);
-    /// assert!(textarea.block().is_some());
+    /// assert_eq!(textarea.wrap_indicator(), None);
+    /// textarea.set_wrap_indicator(Some(("↪ ".to_string(), Style::default())));
+    /// assert_eq!(textarea.wrap_indicator(), Some(("↪ ", Style::default())));
     /// ```
-    pub fn set_block(&mut self, block: Block<'a>) {
-        self.block = Some(block);
+    pub fn wrap_indicator(&self) -> Option<(&str, Style)> {
+        self.wrap_indicator
+            .as_ref()
+            .map(|(s, st)| (s.as_str(), *st))
     }
 
-    /// Remove the block of textarea which was set by [`TextArea::set_block`].
+    /// Set if continuation rows of wrapped lines are indented as deep as the leading whitespaces of the lines, like
+    /// `breakindent` option of Vim. The indent is rendered before the wrap indicator set by
+    /// [`TextArea::set_wrap_indicator`]. The indent is not applied when no space would be left for the text.
     /// ```
+    /// use ratatui::buffer::Buffer;
+    /// use ratatui::layout::Rect;
+    /// use ratatui::widgets::Widget as _;
     /// use tui_textarea::TextArea;
-    /// use ratatui::widgets::{Block, Borders};
     ///
-    /// let mut textarea = TextArea::default();
-    /// let block = Block::default().borders(Borders::ALL).title("Block Title");
-    /// textarea.set_block(block);
-    /// textarea.remove_block();
-    /// assert!(textarea.block().is_none());
+    /// let mut textarea = TextArea::from(["  abc def"]);
+    /// textarea.set_wrap(true);
+    /// textarea.set_wrap_hanging_indent(true);
+    ///
+    /// let r = Rect { x: 0, y: 0, width: 7, height: 2 };
+    /// let mut buf = Buffer::empty(r);
+    /// textarea.render(r, &mut buf);
+    /// assert_eq!(buf[(2, 1)].symbol(), "d");
     /// ```
-    pub fn remove_block(&mut self) {
-        self.block = None;
+    pub fn set_wrap_hanging_indent(&mut self, enabled: bool) {
+        self.wrap_hanging_indent = enabled;
     }
 
-    /// Get the block of textarea if exists.
-    pub fn block<'s>(&'s self) -> Option<&'s Block<'a>> {
-        self.block.as_ref()
+    /// Get if continuation rows of wrapped lines are indented. The default value is `false`.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let textarea = TextArea::default();
+    /// assert!(!textarea.wrap_hanging_indent());
+    /// ```
+    pub fn wrap_hanging_indent(&self) -> bool {
+        self.wrap_hanging_indent
+    }
+
+    // How lines are wrapped into display rows which are `width` cells wide
+    pub(crate) fn wrap_layout(&self, width: usize) -> Wrap {
+        let indicator = self.wrap_indicator.as_ref().map_or(0, |(s, _)| s.width());
+        Wrap::new(width, self.tab_len, self.mask).continuation(indicator, self.wrap_hanging_indent)
+    }
+
+    // Spans put at the start of continuation rows of the wrapped line
+    pub(crate) fn wrap_prefix(&self, line: &str, wrap: &Wrap) -> Vec<Span<'_>> {
+        let (indent, indicator) = wrap.prefix(line);
+        let mut spans = vec![];
+        if indent > 0 {
+            spans.push(Span::raw(" ".repeat(indent)));
+        }
+        if let (Some((s, style)), true) = (&self.wrap_indicator, indicator > 0) {
+            spans.push(Span::styled(s.as_str(), *style));
+        }
+        spans
     }
 
-    /// Set the length of tab character. Setting 0 disables tab inputs.
+    /// Set characters to indicate that a line continues beyond the left or the right edge of the textarea. The left
+    /// indicator is rendered in the first column of a row whose line is scrolled out on the left, and the right one
+    /// is rendered in the last column of a row whose line is longer than the textarea. `None` disables the
+    /// indicator on the side. The indicators are not rendered over the cursor, and not rendered when lines are soft
+    /// wrapped or the text is not aligned to the left.
     /// ```
-    /// use tui_textarea::{TextArea, Input, Key};
-    ///
-    /// let mut textarea = TextArea::default();
-    /// let tab_input = Input { key: Key::Tab, ctrl: false, alt: false, shift: false };
+    /// use ratatui::buffer::Buffer;
+    /// use ratatui::layout::Rect;
+    /// use ratatui::widgets::Widget as _;
+    /// use tui_textarea::TextArea;
     ///
-    /// textarea.set_tab_length(8);
-    /// textarea.input(tab_input.clone());
-    /// assert_eq!(textarea.lines(), ["        "]);
+    /// let mut textarea = TextArea::from(["hello, world", "foo"]);
+    /// textarea.set_overflow_indicators(Some('‹'), Some('›'));
+    /// assert_eq!(textarea.overflow_indicators(), (Some('‹'), Some('›')));
     ///
-    /// textarea.set_tab_length(2);
-    /// textarea.input(tab_input);
-    /// assert_eq!(textarea.lines(), ["          "]);
+    /// let r = Rect { x: 0, y: 0, width: 8, height: 2 };
+    /// let mut buf = Buffer::empty(r);
+    /// textarea.render(r, &mut buf);
+    /// assert_eq!(buf[(7, 0)].symbol(), "›");
+    /// assert_eq!(buf[(7, 1)].symbol(), " ");
     /// ```
-    pub fn set_tab_length(&mut self, len: u8) {
-        self.tab_len = len;
+    pub fn set_overflow_indicators(&mut self, left: Option<char>, right: Option<char>) {
+        self.overflow_indicators = (left, right);
     }
 
-    /// Get how many spaces are used for representing tab character. The default value is 4.
-    pub fn tab_length(&self) -> u8 {
-        self.tab_len
+    /// Get the characters to indicate that a line continues beyond the left and right edges of the textarea. Both
+    /// are `None` by default.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let textarea = TextArea::default();
+    /// assert_eq!(textarea.overflow_indicators(), (None, None));
+    /// ```
+    pub fn overflow_indicators(&self) -> (Option<char>, Option<char>) {
+        self.overflow_indicators
     }
 
-    /// Set if a hard tab is used or not for indent. When `true` is set, typing a tab key inserts a hard tab instead of
-    /// spaces. By default, hard tab is disabled.
+    /// Set the style of the indicators set by [`TextArea::set_overflow_indicators`]. The style is patched on the
+    /// style of the cell.
     /// ```
+    /// use ratatui::style::{Style, Color};
     /// use tui_textarea::TextArea;
     ///
     /// let mut textarea = TextArea::default();
-    ///
-    /// textarea.set_hard_tab_indent(true);
-    /// textarea.insert_tab();
-    /// assert_eq!(textarea.lines(), ["\t"]);
+    /// let style = Style::default().fg(Color::DarkGray);
+    /// textarea.set_overflow_indicator_style(style);
+    /// assert_eq!(textarea.overflow_indicator_style(), style);
     /// ```
-    pub fn set_hard_tab_indent(&mut self, enabled: bool) {
-        self.hard_tab_indent = enabled;
+    pub fn set_overflow_indicator_style(&mut self, style: Style) {
+        self.overflow_indicator_style = style;
     }
 
-    /// Get if a hard tab is used for indent or not.
+    /// Get the style of the overflow indicators. The default value is [`Style::default`].
     /// ```
+    /// use ratatui::style::Style;
     /// use tui_textarea::TextArea;
     ///
-    /// let mut textarea = TextArea::default();
-    ///
-    /// assert!(!textarea.hard_tab_indent());
-    /// textarea.set_hard_tab_indent(true);
-    /// assert!(textarea.hard_tab_indent());
+    /// let textarea = TextArea::default();
+    /// assert_eq!(textarea.overflow_indicator_style(), Style::default());
     /// ```
-    pub fn hard_tab_indent(&self) -> bool {
-        self.hard_tab_indent
+    pub fn overflow_indicator_style(&self) -> Style {
+        self.overflow_indicator_style
     }
 
-    /// Get a string for indent. It consists of spaces by default. When hard tab is enabled, it is a tab character.
+    /// Set the display column painted in the style down the whole viewport like `colorcolumn` of Vim, or remove the
+    /// color columns with `None`. The column is 0-base and counted in display columns from the start of the text, so
+    /// tabs are counted as their rendered width and the gutter for line numbers is not included. For example, `80`
+    /// paints the column next to the 80th column. The style is applied over the style of the textarea and under the
+    /// styles of the text such as selection and cursor.
     /// ```
+    /// use ratatui::buffer::Buffer;
+    /// use ratatui::layout::Rect;
+    /// use ratatui::style::{Color, Style};
+    /// use ratatui::widgets::Widget as _;
     /// use tui_textarea::TextArea;
     ///
-    /// let mut textarea = TextArea::default();
+    /// let mut textarea = TextArea::from(["\tabc"]);
+    /// textarea.set_color_column(Some((5, Style::default().bg(Color::DarkGray))));
     ///
-    /// assert_eq!(textarea.indent(), "    ");
-    /// textarea.set_tab_length(2);
-    /// assert_eq!(textarea.indent(), "  ");
-    /// textarea.set_hard_tab_indent(true);
-    /// assert_eq!(textarea.indent(), "\t");
+    /// let r = Rect { x: 0, y: 0, width: 8, height: 2 };
+    /// let mut buf = Buffer::empty(r);
+    /// textarea.render(r, &mut buf);
+    ///
+    /// // The tab is rendered as 4 spaces so 'b' is at the column 5
+    /// assert_eq!(buf[(5, 0)].symbol(), "b");
+    /// assert_eq!(buf[(5, 0)].bg, Color::DarkGray);
+    /// assert_eq!(buf[(5, 1)].bg, Color::DarkGray);
     /// ```
-    pub fn indent(&self) -> &'static str {
-        if self.hard_tab_indent {
-            "\t"
-        } else {
-            spaces(self.tab_len)
-        }
+    pub fn set_color_column(&mut self, column: Option<(u16, Style)>) {
+        self.color_columns = column.into_iter().collect();
     }
 
-    /// Set how many modifications are remembered for undo/redo. Setting 0 disables undo/redo.
-    pub fn set_max_histories(&mut self, max: usize) {
-        self.history = History::new(max);
+    /// Set multiple color columns with their styles. See [`TextArea::set_color_column`] for more details. An empty
+    /// slice removes all color columns.
+    /// ```
+    /// use ratatui::style::{Color, Style};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// let columns = [
+    ///     (80, Style::default().bg(Color::DarkGray)),
+    ///     (120, Style::default().bg(Color::Red)),
+    /// ];
+    /// textarea.set_color_columns(&columns);
+    /// assert_eq!(textarea.color_columns(), &columns);
+    /// ```
+    pub fn set_color_columns(&mut self, columns: &[(u16, Style)]) {
+        self.color_columns = columns.to_vec();
     }
 
-    /// Get how many modifications are remembered for undo/redo. The default value is 50.
-    pub fn max_histories(&self) -> usize {
-        self.history.max_items()
+    /// Get the color columns and their styles. No color column is set by default.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let textarea = TextArea::default();
+    /// assert!(textarea.color_columns().is_empty());
+    /// ```
+    pub fn color_columns(&self) -> &[(u16, Style)] {
+        &self.color_columns
     }
 
-    /// Set the style of line at cursor. By default, the cursor line is styled with underline. To stop styling the
-    /// cursor line, set the default style.
+    /// Set the ghost text rendered at the cursor position, such as an inline completion, or remove it with `None`. The
+    /// ghost text is only rendered and is not a part of the text, so it does not affect the cursor movements or the
+    /// yanked text. The ghost text is rendered before the character at the cursor, where accepting it inserts the text,
+    /// and the cursor moves after the ghost text on screen. When the cursor is at the end of the line, the ghost text is
+    /// rendered after the cursor. It is removed on the next edit of the text.
     /// ```
-    /// use ratatui::style::{Style, Color};
-    /// use tui_textarea::TextArea;
+    /// use ratatui::buffer::Buffer;
+    /// use ratatui::layout::Rect;
+    /// use ratatui::style::{Color, Style};
+    /// use ratatui::widgets::Widget as _;
+    /// use tui_textarea::{TextArea, CursorMove};
     ///
-    /// let mut textarea = TextArea::default();
+    /// let mut textarea = TextArea::from(["fn ma"]);
+    /// textarea.move_cursor(CursorMove::End);
+    /// textarea.set_ghost_text(Some(("in()".to_string(), Style::default().fg(Color::DarkGray))));
     ///
-    /// let style = Style::default().fg(Color::Red);
-    /// textarea.set_cursor_line_style(style);
-    /// assert_eq!(textarea.cursor_line_style(), style);
+    /// let r = Rect { x: 0, y: 0, width: 12, height: 1 };
+    /// let mut buf = Buffer::empty(r);
+    /// textarea.render(r, &mut buf);
+    /// assert_eq!(buf[(6, 0)].symbol(), "i");
+    /// assert_eq!(buf[(6, 0)].fg, Color::DarkGray);
+    /// assert_eq!(textarea.lines(), ["fn ma"]);
     ///
-    /// // Disable cursor line style
-    /// textarea.set_cursor_line_style(Style::default());
+    /// textarea.insert_char('i');
+    /// assert_eq!(textarea.ghost_text(), None);
     /// ```
-    pub fn set_cursor_line_style(&mut self, style: Style) {
-        self.cursor_line_style = style;
+    pub fn set_ghost_text(&mut self, text: Option<(String, Style)>) {
+        self.ghost_text = text;
     }
 
-    /// Get the style of cursor line. By default it is styled with underline.
-    pub fn cursor_line_style(&self) -> Style {
-        self.cursor_line_style
+    /// Get the ghost text set by [`TextArea::set_ghost_text`] and its style.
+    /// ```
+    /// use ratatui::style::Style;
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// assert_eq!(textarea.ghost_text(), None);
+    /// textarea.set_ghost_text(Some(("abc".to_string(), Style::default())));
+    /// assert_eq!(textarea.ghost_text(), Some(("abc", Style::default())));
+    /// ```
+    pub fn ghost_text(&self) -> Option<(&str, Style)> {
+        self.ghost_text.as_ref().map(|(t, s)| (t.as_str(), *s))
     }
 
-    /// Set the style of line number. By setting the style with this method, line numbers are drawn in textarea, meant
-    /// that line numbers are disabled by default. If you want to show line numbers but don't want to style them, set
-    /// the default style.
+    /// Set the virtual text rendered after the end of the line at the row, such as a diagnostic message or a type
+    /// hint, or remove it with `None`. Like [`TextArea::set_ghost_text`], the virtual text is not a part of the text and
+    /// it is removed on the next edit of the text.
     /// ```
-    /// use ratatui::style::{Style, Color};
+    /// use ratatui::buffer::Buffer;
+    /// use ratatui::layout::Rect;
+    /// use ratatui::style::{Color, Style};
+    /// use ratatui::widgets::Widget as _;
     /// use tui_textarea::TextArea;
     ///
-    /// let mut textarea = TextArea::default();
+    /// let mut textarea = TextArea::from(["let x = 1;", "x"]);
+    /// textarea.set_virtual_text(1, Some((" // i32".to_string(), Style::default().fg(Color::DarkGray))));
     ///
-    /// // Show line numbers in dark gray background
-    /// let style = Style::default().bg(Color::DarkGray);
-    /// textarea.set_line_number_style(style);
-    /// assert_eq!(textarea.line_number_style(), Some(style));
+    /// let r = Rect { x: 0, y: 0, width: 12, height: 2 };
+    /// let mut buf = Buffer::empty(r);
+    /// textarea.render(r, &mut buf);
+    /// assert_eq!(buf[(2, 1)].symbol(), "/");
+    /// assert_eq!(buf[(2, 1)].fg, Color::DarkGray);
     /// ```
-    pub fn set_line_number_style(&mut self, style: Style) {
-        self.line_number_style = Some(style);
+    pub fn set_virtual_text(&mut self, row: usize, text: Option<(String, Style)>) {
+        match text {
+            Some(text) => self.virtual_texts.insert(row, text),
+            None => self.virtual_texts.remove(&row),
+        };
     }
 
-    /// Remove the style of line number which was set by [`TextArea::set_line_number_style`]. After calling this
-    /// method, Line numbers will no longer be shown.
+    /// Get the virtual text at the row set by [`TextArea::set_virtual_text`] and its style.
     /// ```
-    /// use ratatui::style::{Style, Color};
+    /// use ratatui::style::Style;
     /// use tui_textarea::TextArea;
     ///
-    /// let mut textarea = TextArea::default();
-    ///
-    /// textarea.set_line_number_style(Style::default().bg(Color::DarkGray));
-    /// textarea.remove_line_number();
-    /// assert_eq!(textarea.line_number_style(), None);
+    /// let mut textarea = TextArea::from(["a", "b"]);
+    /// textarea.set_virtual_text(1, Some(("abc".to_string(), Style::default())));
+    /// assert_eq!(textarea.virtual_text(0), None);
+    /// assert_eq!(textarea.virtual_text(1), Some(("abc", Style::default())));
     /// ```
-    pub fn remove_line_number(&mut self) {
-        self.line_number_style = None;
+    pub fn virtual_text(&self, row: usize) -> Option<(&str, Style)> {
+        self.virtual_texts.get(&row).map(|(t, s)| (t.as_str(), *s))
     }
 
-    /// Get the style of line number if set.
-    pub fn line_number_style(&self) -> Option<Style> {
-        self.line_number_style
+    // Ghost text and virtual texts are only valid for the text when they were set
+    fn clear_virtual_texts(&mut self) {
+        self.ghost_text = None;
+        self.virtual_texts.clear();
     }
 
-    /// Set the placeholder text. The text is set in the textarea when no text is input. Setting a non-empty string `""`
-    /// enables the placeholder. The default value is an empty string so the placeholder is disabled by default.
-    /// To customize the text style, see [`TextArea::set_placeholder_style`].
+    /// Set the character rendered in the first column of each row of the viewport beyond the end of the buffer, like
+    /// `~` of Vim, or remove it with `None`. It makes it possible to distinguish rows after the last line from empty
+    /// lines. The gutter is kept blank on the rows.
     /// ```
+    /// use ratatui::buffer::Buffer;
+    /// use ratatui::layout::Rect;
+    /// use ratatui::style::{Color, Style};
+    /// use ratatui::widgets::Widget as _;
     /// use tui_textarea::TextArea;
     ///
-    /// let mut textarea = TextArea::default();
-    /// assert_eq!(textarea.placeholder_text(), "");
-    /// assert!(textarea.placeholder_style().is_none());
+    /// let mut textarea = TextArea::from(["abc", ""]);
+    /// textarea.set_end_of_buffer_char(Some(('~', Style::default().fg(Color::Blue))));
     ///
-    /// textarea.set_placeholder_text("Hello");
-    /// assert_eq!(textarea.placeholder_text(), "Hello");
-    /// assert!(textarea.placeholder_style().is_some());
+    /// let r = Rect { x: 0, y: 0, width: 4, height: 3 };
+    /// let mut buf = Buffer::empty(r);
+    /// textarea.render(r, &mut buf);
+    ///
+    /// // The second row is an empty line and the third row is beyond the end
+    /// assert_eq!(buf[(0, 1)].symbol(), " ");
+    /// assert_eq!(buf[(0, 2)].symbol(), "~");
+    /// assert_eq!(buf[(0, 2)].fg, Color::Blue);
     /// ```
-    pub fn set_placeholder_text(&mut self, placeholder: impl Into<String>) {
-        self.placeholder = placeholder.into();
+    pub fn set_end_of_buffer_char(&mut self, c: Option<(char, Style)>) {
+        self.end_of_buffer_char = c;
     }
 
-    /// Set the style of the placeholder text. The default style is a dark gray text.
+    /// Get the character rendered on rows beyond the end of the buffer and its style. It is not set by default.
     /// ```
-    /// use ratatui::style::{Style, Color};
+    /// use ratatui::style::Style;
     /// use tui_textarea::TextArea;
     ///
     /// let mut textarea = TextArea::default();
-    /// assert_eq!(textarea.placeholder_style(), None); // When the placeholder is disabled
-    ///
-    /// textarea.set_placeholder_text("Enter your message"); // Enable placeholder by setting non-empty text
-    ///
-    /// let style = Style::default().bg(Color::Blue);
-    /// textarea.set_placeholder_style(style);
-    /// assert_eq!(textarea.placeholder_style(), Some(style));
+    /// assert_eq!(textarea.end_of_buffer_char(), None);
+    /// textarea.set_end_of_buffer_char(Some(('~', Style::default())));
+    /// assert_eq!(textarea.end_of_buffer_char(), Some(('~', Style::default())));
     /// ```
-    pub fn set_placeholder_style(&mut self, style: Style) {
-        self.placeholder_style = style;
+    pub fn end_of_buffer_char(&self) -> Option<(char, Style)> {
+        self.end_of_buffer_char
     }
 
-    /// Get the placeholder text. An empty string means the placeholder is disabled. The default value is an empty string.
+    /// Check if the textarea has a empty content.
     /// ```
     /// use tui_textarea::TextArea;
     ///
     /// let textarea = TextArea::default();
-    /// assert_eq!(textarea.placeholder_text(), "");
+    /// assert!(textarea.is_empty());
+    ///
+    /// let textarea = TextArea::from(["hello"]);
+    /// assert!(!textarea.is_empty());
     /// ```
-    pub fn placeholder_text(&self) -> &'_ str {
-        self.placeholder.as_str()
+    pub fn is_empty(&self) -> bool {
+        self.lines == [""]
     }
 
-    /// Get the placeholder style. When the placeholder text is empty, it returns `None` since the placeholder is disabled.
-    /// The default style is a dark gray text.
+    /// Get the yanked text. Text is automatically yanked when deleting strings by [`TextArea::delete_line_by_head`],
+    /// [`TextArea::delete_line_by_end`], [`TextArea::delete_word`], [`TextArea::delete_next_word`],
+    /// [`TextArea::delete_str`], [`TextArea::copy`], and [`TextArea::cut`]. When multiple lines were yanked, they are
+    /// always joined with `\n`.
     /// ```
     /// use tui_textarea::TextArea;
     ///
-    /// let mut textarea = TextArea::default();
-    /// assert_eq!(textarea.placeholder_style(), None);
+    /// let mut textarea = TextArea::from(["abc"]);
+    /// textarea.delete_next_word();
+    /// assert_eq!(textarea.yank_text(), "abc");
     ///
-    /// textarea.set_placeholder_text("hello");
-    /// assert!(textarea.placeholder_style().is_some());
+    /// // Multiple lines are joined with \n
+    /// let mut textarea = TextArea::from(["abc", "def"]);
+    /// textarea.delete_str(5);
+    /// assert_eq!(textarea.yank_text(), "abc\nd");
     /// ```
-    pub fn placeholder_style(&self) -> Option<Style> {
-        if self.placeholder.is_empty() {
-            None
-        } else {
-            Some(self.placeholder_style)
-        }
+    pub fn yank_text(&self) -> String {
+        self.yank.to_string()
     }
 
-    /// Specify a character masking the text. All characters in the textarea will be replaced by this character.
-    /// This API is useful for making a kind of credentials form such as a password input.
+    /// Set a yanked text. The text can be inserted by [`TextArea::paste`]. Newlines are recognized in the same way as
+    /// [`TextArea::insert_str`].
     /// ```
     /// use tui_textarea::TextArea;
     ///
     /// let mut textarea = TextArea::default();
     ///
-    /// textarea.set_mask_char('*');
-    /// assert_eq!(textarea.mask_char(), Some('*'));
-    /// textarea.set_mask_char('●');
-    /// assert_eq!(textarea.mask_char(), Some('●'));
+    /// textarea.set_yank_text("hello\nworld");
+    /// textarea.paste();
+    /// assert_eq!(textarea.lines(), ["hello", "world"]);
     /// ```
-    pub fn set_mask_char(&mut self, mask: char) {
-        self.mask = Some(mask);
+    pub fn set_yank_text(&mut self, text: impl Into<String>) {
+        self.set_yank(YankText::from_text(&text.into(), self.normalize_newlines));
+    }
+
+    /// Get the kind of the yanked text. Text yanked from a line-wise selection is [`YankKind::Line`] and text yanked
+    /// from a block selection is [`YankKind::Block`]. Other yanked text including the text set by
+    /// [`TextArea::set_yank_text`] is [`YankKind::Char`].
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove, YankKind};
+    ///
+    /// let mut textarea = TextArea::from(["abc", "def"]);
+    /// assert_eq!(textarea.yank_kind(), YankKind::Char);
+    ///
+    /// textarea.start_line_selection();
+    /// textarea.copy();
+    /// assert_eq!(textarea.yank_kind(), YankKind::Line);
+    ///
+    /// // Multi-line character-wise text is not line-wise
+    /// textarea.start_selection();
+    /// textarea.move_cursor(CursorMove::Down);
+    /// textarea.copy();
+    /// assert_eq!(textarea.yank_text(), "abc\n");
+    /// assert_eq!(textarea.yank_kind(), YankKind::Char);
+    /// ```
+    pub fn yank_kind(&self) -> YankKind {
+        self.yank.kind()
+    }
+
+    fn set_yank(&mut self, yank: YankText) {
+        self.yank_history.push(&yank);
+        self.yank = yank;
     }
 
-    /// Clear the masking character previously set by [`TextArea::set_mask_char`].
+    /// Set how many yanked texts are remembered for [`TextArea::paste_previous_yank`]. Setting 0 disables the yank
+    /// history. The yank history is disabled by default. When the history has more texts than `size`, the oldest
+    /// texts are removed.
     /// ```
     /// use tui_textarea::TextArea;
     ///
     /// let mut textarea = TextArea::default();
     ///
-    /// textarea.set_mask_char('*');
-    /// assert_eq!(textarea.mask_char(), Some('*'));
-    /// textarea.clear_mask_char();
-    /// assert_eq!(textarea.mask_char(), None);
+    /// textarea.set_yank_history_size(2);
+    /// assert_eq!(textarea.yank_history_size(), 2);
+    /// textarea.set_yank_text("a");
+    /// textarea.set_yank_text("b");
+    /// textarea.set_yank_text("c");
+    /// assert_eq!(textarea.yank_history(), ["c", "b"]);
     /// ```
-    pub fn clear_mask_char(&mut self) {
-        self.mask = None;
+    pub fn set_yank_history_size(&mut self, size: usize) {
+        self.yank_history.max_len = size;
+        self.yank_history.entries.truncate(size);
+        if self.yank_history.index >= size {
+            self.yank_history.index = 0;
+        }
     }
 
-    /// Get the character to mask text. When no character is set, `None` is returned.
+    /// Get how many yanked texts are remembered. See [`TextArea::set_yank_history_size`].
     /// ```
     /// use tui_textarea::TextArea;
     ///
-    /// let mut textarea = TextArea::default();
-    ///
-    /// assert_eq!(textarea.mask_char(), None);
-    /// textarea.set_mask_char('*');
-    /// assert_eq!(textarea.mask_char(), Some('*'));
+    /// let textarea = TextArea::default();
+    /// assert_eq!(textarea.yank_history_size(), 0);
     /// ```
-    pub fn mask_char(&self) -> Option<char> {
-        self.mask
+    pub fn yank_history_size(&self) -> usize {
+        self.yank_history.max_len
     }
 
-    /// Set the style of cursor. By default, a cursor is rendered in the reversed color. Setting the same style as
-    /// cursor line hides a cursor.
+    /// Get the yanked texts remembered in the yank history. The newest text comes first. Texts are added to the
+    /// history when they are yanked in the same way as [`TextArea::yank_text`] is updated.
     /// ```
-    /// use ratatui::style::{Style, Color};
-    /// use tui_textarea::TextArea;
+    /// use tui_textarea::{TextArea, CursorMove};
     ///
-    /// let mut textarea = TextArea::default();
+    /// let mut textarea = TextArea::from(["aaa bbb"]);
+    /// textarea.set_yank_history_size(10);
     ///
-    /// let style = Style::default().bg(Color::Red);
-    /// textarea.set_cursor_style(style);
-    /// assert_eq!(textarea.cursor_style(), style);
+    /// textarea.delete_next_word();
+    /// textarea.delete_next_word();
+    /// assert_eq!(textarea.yank_history(), [" bbb", "aaa"]);
     /// ```
-    pub fn set_cursor_style(&mut self, style: Style) {
-        self.cursor_style = style;
-    }
-
-    /// Get the style of cursor.
-    pub fn cursor_style(&self) -> Style {
-        self.cursor_style
+    pub fn yank_history(&self) -> Vec<String> {
+        self.yank_history
+            .entries
+            .iter()
+            .map(|y| y.to_string())
+            .collect()
     }
 
-    /// Get slice of line texts. This method borrows the content, but not moves. Note that the returned slice will
-    /// never be empty because an empty text means a slice containing one empty line. This is correct since any text
-    /// file must end with a newline.
+    /// Set a regular expression pattern for text search. Setting an empty string stops the text search.
+    /// When a valid pattern is set, all matches will be highlighted in the textarea. Note that the cursor does not
+    /// move. To move the cursor, use [`TextArea::search_forward`] and [`TextArea::search_back`].
+    ///
+    /// Grammar of regular expression follows [regex crate](https://docs.rs/regex/latest/regex). Patterns don't match
+    /// to newlines so match passes across no newline.
+    ///
+    /// When the pattern is invalid, the search pattern will not be updated and an error will be returned.
+    ///
+    /// The pattern is not recorded in the search history by this method so that updating the pattern on every
+    /// keystroke in a search prompt does not fill the history. Use [`TextArea::commit_search_pattern`] to record it.
+    ///
     /// ```
     /// use tui_textarea::TextArea;
     ///
-    /// let mut textarea = TextArea::default();
-    /// assert_eq!(textarea.lines(), [""]);
+    /// let mut textarea = TextArea::from(["hello, world", "goodbye, world"]);
     ///
-    /// textarea.insert_char('a');
-    /// assert_eq!(textarea.lines(), ["a"]);
+    /// // Search "world"
+    /// textarea.set_search_pattern("world").unwrap();
     ///
-    /// textarea.insert_newline();
-    /// assert_eq!(textarea.lines(), ["a", ""]);
+    /// assert_eq!(textarea.cursor(), (0, 0));
+    /// textarea.search_forward(false);
+    /// assert_eq!(textarea.cursor(), (0, 7));
+    /// textarea.search_forward(false);
+    /// assert_eq!(textarea.cursor(), (1, 9));
     ///
-    /// textarea.insert_char('b');
-    /// assert_eq!(textarea.lines(), ["a", "b"]);
+    /// // Stop the text search
+    /// textarea.set_search_pattern("");
+    ///
+    /// // Invalid search pattern
+    /// assert!(textarea.set_search_pattern("(hello").is_err());
     /// ```
-    pub fn lines(&'a self) -> &'a [String] {
-        &self.lines
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn set_search_pattern(&mut self, query: impl AsRef<str>) -> Result<(), regex::Error> {
+        self.search.set_pattern(query.as_ref())
     }
 
-    /// Convert [`TextArea`] instance into line texts.
+    /// Record the current search pattern set by [`TextArea::set_search_pattern`] in the search history. Call this when
+    /// the user commits the query, for example by pressing Enter in a search prompt. Nothing is recorded when no search
+    /// pattern is set.
+    ///
     /// ```
     /// use tui_textarea::TextArea;
     ///
     /// let mut textarea = TextArea::default();
     ///
-    /// textarea.insert_char('a');
-    /// textarea.insert_newline();
-    /// textarea.insert_char('b');
+    /// // Patterns updated while typing the query are not recorded
+    /// textarea.set_search_pattern("f").unwrap();
+    /// textarea.set_search_pattern("fo").unwrap();
+    /// textarea.set_search_pattern("foo").unwrap();
+    /// assert!(textarea.search_history().entries().is_empty());
     ///
-    /// assert_eq!(textarea.into_lines(), ["a", "b"]);
+    /// textarea.commit_search_pattern();
+    /// assert_eq!(textarea.search_history().entries(), ["foo"]);
     /// ```
-    pub fn into_lines(self) -> Vec<String> {
-        self.lines
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn commit_search_pattern(&mut self) {
+        if let Some(pat) = &self.search.pat {
+            self.search_history.push(pat.as_str());
+        }
     }
 
-    /// Get the current cursor position. 0-base character-wise (row, col) cursor position.
+    /// Get the history of search queries recorded by [`TextArea::commit_search_pattern`]. See [`SearchHistory`] for
+    /// more details. The history remembers 50 queries by default.
+    ///
     /// ```
     /// use tui_textarea::TextArea;
     ///
     /// let mut textarea = TextArea::default();
-    /// assert_eq!(textarea.cursor(), (0, 0));
     ///
-    /// textarea.insert_char('a');
-    /// textarea.insert_newline();
-    /// textarea.insert_char('b');
+    /// textarea.set_search_pattern("foo").unwrap();
+    /// textarea.commit_search_pattern();
+    /// textarea.set_search_pattern("(").unwrap_err(); // Invalid patterns are not set
+    /// textarea.commit_search_pattern(); // The same query as the newest entry is not recorded again
+    /// textarea.set_search_pattern("bar").unwrap();
+    /// textarea.commit_search_pattern();
+    /// textarea.set_search_pattern("").unwrap();
+    /// textarea.commit_search_pattern(); // Nothing is recorded after stopping the search
     ///
-    /// assert_eq!(textarea.cursor(), (1, 1));
+    /// assert_eq!(textarea.search_history().entries(), ["foo", "bar"]);
     /// ```
-    pub fn cursor(&self) -> (usize, usize) {
-        self.cursor
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn search_history(&self) -> &SearchHistory {
+        &self.search_history
     }
 
-    /// Get the current selection range as a pair of the start position and the end position. The range is bounded
-    /// inclusively below and exclusively above. The positions are 0-base character-wise (row, col) values.
-    /// The first element of the pair is always smaller than the second one even when it is ahead of the cursor.
-    /// When no text is selected, this method returns `None`.
+    /// Get the mutable history of search queries. This is useful to change the maximum length of the history with
+    /// [`SearchHistory::set_max_len`] or to restore queries saved in the previous session.
+    ///
     /// ```
     /// use tui_textarea::TextArea;
-    /// use tui_textarea::CursorMove;
     ///
-    /// let mut textarea = TextArea::from([
-    ///     "aaa",
-    ///     "bbb",
-    ///     "ccc",
-    /// ]);
+    /// let mut textarea = TextArea::default();
     ///
-    /// // It returns `None` when the text selection is not ongoing
-    /// assert_eq!(textarea.selection_range(), None);
+    /// textarea.search_history_mut().set_max_len(1);
+    /// for query in ["foo", "bar"] {
+    ///     textarea.set_search_pattern(query).unwrap();
+    ///     textarea.commit_search_pattern();
+    /// }
     ///
-    /// textarea.start_selection();
-    /// assert_eq!(textarea.selection_range(), Some(((0, 0), (0, 0))));
+    /// assert_eq!(textarea.search_history().entries(), ["bar"]);
+    /// ```
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn search_history_mut(&mut self) -> &mut SearchHistory {
+        &mut self.search_history
+    }
+
+    /// Recall the search query older than the one last recalled from the search history. This is useful to implement
+    /// recalling queries with the up arrow key in a search prompt. See [`SearchHistory::recall_previous`].
     ///
-    /// textarea.move_cursor(CursorMove::Forward);
-    /// assert_eq!(textarea.selection_range(), Some(((0, 0), (0, 1))));
+    /// ```
+    /// use tui_textarea::TextArea;
     ///
-    /// textarea.move_cursor(CursorMove::Down);
-    /// assert_eq!(textarea.selection_range(), Some(((0, 0), (1, 1))));
+    /// let mut textarea = TextArea::default();
     ///
-    /// // Start selection at (1, 1)
-    /// textarea.cancel_selection();
-    /// textarea.start_selection();
+    /// for query in ["foo", "bar"] {
+    ///     textarea.set_search_pattern(query).unwrap();
+    ///     textarea.commit_search_pattern();
+    /// }
     ///
-    /// // The first element of the pair is always smaller than the second one
-    /// textarea.move_cursor(CursorMove::Back);
-    /// assert_eq!(textarea.selection_range(), Some(((1, 0), (1, 1))));
+    /// assert_eq!(textarea.recall_previous_search(), Some("bar"));
+    /// assert_eq!(textarea.recall_previous_search(), Some("foo"));
+    /// assert_eq!(textarea.recall_next_search(), Some("bar"));
+    /// assert_eq!(textarea.recall_next_search(), None);
     /// ```
-    pub fn selection_range(&self) -> Option<((usize, usize), (usize, usize))> {
-        self.selection_start.map(|pos| {
-            if pos > self.cursor {
-                (self.cursor, pos)
-            } else {
-                (pos, self.cursor)
-            }
-        })
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn recall_previous_search(&mut self) -> Option<&str> {
+        self.search_history.recall_previous()
     }
 
-    /// Set text alignment. When [`Alignment::Center`] or [`Alignment::Right`] is set, line number is automatically
-    /// disabled because those alignments don't work well with line numbers.
+    /// Recall the search query newer than the one last recalled from the search history. This is useful to implement
+    /// recalling queries with the down arrow key in a search prompt. See [`SearchHistory::recall_next`].
+    ///
     /// ```
-    /// use ratatui::layout::Alignment;
     /// use tui_textarea::TextArea;
     ///
     /// let mut textarea = TextArea::default();
     ///
-    /// textarea.set_alignment(Alignment::Center);
-    /// assert_eq!(textarea.alignment(), Alignment::Center);
+    /// textarea.set_search_pattern("foo").unwrap();
+    /// textarea.commit_search_pattern();
+    /// assert_eq!(textarea.recall_next_search(), None);
+    /// assert_eq!(textarea.recall_previous_search(), Some("foo"));
+    /// assert_eq!(textarea.recall_next_search(), None);
     /// ```
-    pub fn set_alignment(&mut self, alignment: Alignment) {
-        if let Alignment::Center | Alignment::Right = alignment {
-            self.line_number_style = None;
-        }
-        self.alignment = alignment;
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn recall_next_search(&mut self) -> Option<&str> {
+        self.search_history.recall_next()
     }
 
-    /// Get current text alignment. The default alignment is [`Alignment::Left`].
+    /// Set case sensitivity of text search. See [`SearchCase`] for the options. The default is
+    /// [`SearchCase::Sensitive`]. When a search pattern is already set, it is recompiled with the new option
+    /// immediately.
+    ///
     /// ```
-    /// use ratatui::layout::Alignment;
-    /// use tui_textarea::TextArea;
+    /// use tui_textarea::{TextArea, SearchCase};
     ///
-    /// let mut textarea = TextArea::default();
+    /// let mut textarea = TextArea::from(["Hello, hello"]);
     ///
-    /// assert_eq!(textarea.alignment(), Alignment::Left);
+    /// textarea.set_search_pattern("hello").unwrap();
+    /// assert_eq!(textarea.search_matches().count(), 1);
+    ///
+    /// textarea.set_search_case(SearchCase::Insensitive);
+    /// assert_eq!(textarea.search_matches().count(), 2);
+    ///
+    /// // Smart case matches case-sensitively only when the pattern contains some uppercase letter
+    /// textarea.set_search_case(SearchCase::Smart);
+    /// assert_eq!(textarea.search_matches().count(), 2);
+    /// textarea.set_search_pattern("Hello").unwrap();
+    /// assert_eq!(textarea.search_matches().count(), 1);
     /// ```
-    pub fn alignment(&self) -> Alignment {
-        self.alignment
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn set_search_case(&mut self, case: SearchCase) {
+        self.search.set_case(case);
     }
 
-    /// Check if the textarea has a empty content.
+    /// Get case sensitivity of text search set by [`TextArea::set_search_case`].
+    ///
     /// ```
-    /// use tui_textarea::TextArea;
+    /// use tui_textarea::{TextArea, SearchCase};
     ///
-    /// let textarea = TextArea::default();
-    /// assert!(textarea.is_empty());
+    /// let mut textarea = TextArea::default();
     ///
-    /// let textarea = TextArea::from(["hello"]);
-    /// assert!(!textarea.is_empty());
+    /// assert_eq!(textarea.search_case(), SearchCase::Sensitive);
+    /// textarea.set_search_case(SearchCase::Smart);
+    /// assert_eq!(textarea.search_case(), SearchCase::Smart);
     /// ```
-    pub fn is_empty(&self) -> bool {
-        self.lines == [""]
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn search_case(&self) -> SearchCase {
+        self.search.case()
     }
 
-    /// Get the yanked text. Text is automatically yanked when deleting strings by [`TextArea::delete_line_by_head`],
-    /// [`TextArea::delete_line_by_end`], [`TextArea::delete_word`], [`TextArea::delete_next_word`],
-    /// [`TextArea::delete_str`], [`TextArea::copy`], and [`TextArea::cut`]. When multiple lines were yanked, they are
-    /// always joined with `\n`.
+    /// Set whether text search only matches whole words. Word boundaries are required only on the sides of a match
+    /// which are word characters, so a pattern such as `->` still matches in `a->b`. This option is applied to
+    /// highlighting, [`TextArea::search_matches`], [`TextArea::search_forward`], [`TextArea::search_back`], and
+    /// replacements, and it can be combined with [`TextArea::set_search_case`]. It is disabled by default.
+    ///
     /// ```
     /// use tui_textarea::TextArea;
     ///
-    /// let mut textarea = TextArea::from(["abc"]);
-    /// textarea.delete_next_word();
-    /// assert_eq!(textarea.yank_text(), "abc");
+    /// let mut textarea = TextArea::from(["for i in input {}"]);
     ///
-    /// // Multiple lines are joined with \n
-    /// let mut textarea = TextArea::from(["abc", "def"]);
-    /// textarea.delete_str(5);
-    /// assert_eq!(textarea.yank_text(), "abc\nd");
+    /// textarea.set_search_pattern("in").unwrap();
+    /// assert_eq!(textarea.search_matches().count(), 2);
+    ///
+    /// textarea.set_search_whole_word(true);
+    /// assert_eq!(textarea.search_matches().collect::<Vec<_>>(), [((0, 6), (0, 8))]);
     /// ```
-    pub fn yank_text(&self) -> String {
-        self.yank.to_string()
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn set_search_whole_word(&mut self, enabled: bool) {
+        self.search.set_whole_word(enabled);
     }
 
-    /// Set a yanked text. The text can be inserted by [`TextArea::paste`]. `\n` and `\r\n` are recognized as newline
-    /// but `\r` isn't.
+    /// Get whether text search only matches whole words. See [`TextArea::set_search_whole_word`].
+    ///
     /// ```
     /// use tui_textarea::TextArea;
     ///
     /// let mut textarea = TextArea::default();
     ///
-    /// textarea.set_yank_text("hello\nworld");
-    /// textarea.paste();
-    /// assert_eq!(textarea.lines(), ["hello", "world"]);
+    /// assert!(!textarea.search_whole_word());
+    /// textarea.set_search_whole_word(true);
+    /// assert!(textarea.search_whole_word());
     /// ```
-    pub fn set_yank_text(&mut self, text: impl Into<String>) {
-        // `str::lines` is not available since it strips a newline at end
-        let lines: Vec<_> = text
-            .into()
-            .split('\n')
-            .map(|s| s.strip_suffix('\r').unwrap_or(s).to_string())
-            .collect();
-        self.yank = lines.into();
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn search_whole_word(&self) -> bool {
+        self.search.whole_word()
     }
 
-    /// Set a regular expression pattern for text search. Setting an empty string stops the text search.
-    /// When a valid pattern is set, all matches will be highlighted in the textarea. Note that the cursor does not
-    /// move. To move the cursor, use [`TextArea::search_forward`] and [`TextArea::search_back`].
+    /// Set the range of text searched by text search. With [`SearchScope::Selection`], highlighting,
+    /// [`TextArea::search_matches`], [`TextArea::search_forward`], [`TextArea::search_back`], and replacements only
+    /// consider matches inside the text selected at the time of this call. The region is kept while the cursor moves to
+    /// matches and moves together with the text when the text is edited. When nothing is selected, or when the
+    /// selection is canceled or a new selection is started, the scope reverts to [`SearchScope::Buffer`].
     ///
-    /// Grammar of regular expression follows [regex crate](https://docs.rs/regex/latest/regex). Patterns don't match
-    /// to newlines so match passes across no newline.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove, SearchScope};
     ///
-    /// When the pattern is invalid, the search pattern will not be updated and an error will be returned.
+    /// let mut textarea = TextArea::from(["foo foo", "foo foo"]);
     ///
-    /// ```
-    /// use tui_textarea::TextArea;
+    /// textarea.move_cursor(CursorMove::Jump(0, 4));
+    /// textarea.start_selection();
+    /// textarea.move_cursor(CursorMove::Jump(1, 3));
+    /// textarea.set_search_scope(SearchScope::Selection);
     ///
-    /// let mut textarea = TextArea::from(["hello, world", "goodbye, world"]);
+    /// textarea.set_search_pattern("foo").unwrap();
+    /// assert_eq!(textarea.search_matches().collect::<Vec<_>>(), [((0, 4), (0, 7)), ((1, 0), (1, 3))]);
     ///
-    /// // Search "world"
-    /// textarea.set_search_pattern("world").unwrap();
+    /// assert_eq!(textarea.replace_all("x"), 2);
+    /// assert_eq!(textarea.lines(), ["foo x", "x foo"]);
     ///
-    /// assert_eq!(textarea.cursor(), (0, 0));
-    /// textarea.search_forward(false);
-    /// assert_eq!(textarea.cursor(), (0, 7));
-    /// textarea.search_forward(false);
-    /// assert_eq!(textarea.cursor(), (1, 9));
+    /// textarea.cancel_selection();
+    /// assert_eq!(textarea.search_scope(), SearchScope::Buffer);
+    /// ```
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn set_search_scope(&mut self, scope: SearchScope) {
+        let region = match scope {
+            SearchScope::Buffer => None,
+            SearchScope::Selection => self.selection_positions(),
+        };
+        self.search.set_region(region);
+    }
+
+    /// Get the range of text searched by text search. See [`TextArea::set_search_scope`].
     ///
-    /// // Stop the text search
-    /// textarea.set_search_pattern("");
+    /// ```
+    /// use tui_textarea::{TextArea, SearchScope};
     ///
-    /// // Invalid search pattern
-    /// assert!(textarea.set_search_pattern("(hello").is_err());
+    /// let mut textarea = TextArea::from(["abc"]);
+    ///
+    /// // The scope is the entire buffer when nothing is selected
+    /// textarea.set_search_scope(SearchScope::Selection);
+    /// assert_eq!(textarea.search_scope(), SearchScope::Buffer);
+    ///
+    /// textarea.select_all();
+    /// textarea.set_search_scope(SearchScope::Selection);
+    /// assert_eq!(textarea.search_scope(), SearchScope::Selection);
     /// ```
     #[cfg(feature = "search")]
     #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
-    pub fn set_search_pattern(&mut self, query: impl AsRef<str>) -> Result<(), regex::Error> {
-        self.search.set_pattern(query.as_ref())
+    pub fn search_scope(&self) -> SearchScope {
+        if self.search.region().is_some() {
+            SearchScope::Selection
+        } else {
+            SearchScope::Buffer
+        }
     }
 
     /// Get a regular expression which was set by [`TextArea::set_search_pattern`]. When no text search is ongoing, this
@@ -2414,8 +8085,34 @@ impl<'a> TextArea<'a> {
     /// ```
     #[cfg(feature = "search")]
     #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
-    pub fn search_pattern(&self) -> Option<&regex::Regex> {
-        self.search.pat.as_ref()
+    pub fn search_pattern(&self) -> Option<&regex::Regex> {
+        self.search.pat.as_ref()
+    }
+
+    /// Iterate all matches of the pattern set by [`TextArea::set_search_pattern`] in the text buffer. Each item is a pair
+    /// of the start and end positions of a match as `(row, col)`. The end position is exclusive. Empty matches are
+    /// skipped. When no text search is ongoing, the iterator yields nothing.
+    ///
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["hello, world", "goodbye, world"]);
+    ///
+    /// textarea.set_search_pattern("wo?rld").unwrap();
+    /// let matches: Vec<_> = textarea.search_matches().collect();
+    /// assert_eq!(matches, [((0, 7), (0, 12)), ((1, 9), (1, 14))]);
+    ///
+    /// // Count the matches
+    /// assert_eq!(textarea.search_matches().count(), 2);
+    ///
+    /// // Stop the text search
+    /// textarea.set_search_pattern("").unwrap();
+    /// assert_eq!(textarea.search_matches().count(), 0);
+    /// ```
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn search_matches(&self) -> impl Iterator<Item = ((usize, usize), (usize, usize))> + '_ {
+        self.search.positions(&self.lines)
     }
 
     /// Search the pattern set by [`TextArea::set_search_pattern`] forward and move the cursor to the next match
@@ -2458,11 +8155,52 @@ impl<'a> TextArea<'a> {
     #[cfg(feature = "search")]
     #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
     pub fn search_forward(&mut self, match_cursor: bool) -> bool {
-        if let Some(cursor) = self.search.forward(&self.lines, self.cursor, match_cursor) {
-            self.cursor = cursor;
-            true
-        } else {
-            false
+        self.search_forward_ex(match_cursor, true).is_found()
+    }
+
+    /// Search the pattern set by [`TextArea::set_search_pattern`] forward and move the cursor to the next match
+    /// position like [`TextArea::search_forward`]. The search wraps around a text buffer only when `wrap` is `true`. It
+    /// returns [`SearchResult::Found`] with `wrapped: true` when the match was found after wrapping around. This is
+    /// useful to show a message like "search hit BOTTOM, continuing at TOP".
+    ///
+    /// ```
+    /// use tui_textarea::{TextArea, SearchResult};
+    ///
+    /// let mut textarea = TextArea::from(["hello", "helloo", "hellooo"]);
+    ///
+    /// textarea.set_search_pattern("hello+").unwrap();
+    ///
+    /// assert_eq!(textarea.search_forward_ex(false, true), SearchResult::Found { wrapped: false });
+    /// assert_eq!(textarea.cursor(), (1, 0));
+    /// assert_eq!(textarea.search_forward_ex(false, true), SearchResult::Found { wrapped: false });
+    /// assert_eq!(textarea.cursor(), (2, 0));
+    ///
+    /// // Search reached the bottom of the buffer and continued from the top
+    /// assert_eq!(textarea.search_forward_ex(false, true), SearchResult::Found { wrapped: true });
+    /// assert_eq!(textarea.cursor(), (0, 0));
+    ///
+    /// // Search does not wrap around when `wrap` is `false`
+    /// textarea.move_cursor(tui_textarea::CursorMove::Bottom);
+    /// assert_eq!(textarea.search_forward_ex(false, false), SearchResult::NotFound);
+    /// assert_eq!(textarea.cursor(), (2, 0));
+    /// ```
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn search_forward_ex(&mut self, match_cursor: bool, wrap: bool) -> SearchResult {
+        match self
+            .search
+            .forward(&self.lines, self.cursor, match_cursor, wrap)
+        {
+            Some((cursor, wrapped)) => {
+                self.record_jump(self.cursor, cursor);
+                self.cursor = cursor;
+                self.search.current = Some(cursor);
+                SearchResult::Found { wrapped }
+            }
+            None => {
+                self.search.current = None;
+                SearchResult::NotFound
+            }
         }
     }
 
@@ -2502,11 +8240,47 @@ impl<'a> TextArea<'a> {
     #[cfg(feature = "search")]
     #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
     pub fn search_back(&mut self, match_cursor: bool) -> bool {
-        if let Some(cursor) = self.search.back(&self.lines, self.cursor, match_cursor) {
-            self.cursor = cursor;
-            true
-        } else {
-            false
+        self.search_back_ex(match_cursor, true).is_found()
+    }
+
+    /// Search the pattern set by [`TextArea::set_search_pattern`] backward and move the cursor to the next match
+    /// position like [`TextArea::search_back`]. The search wraps around a text buffer only when `wrap` is `true`. It
+    /// returns [`SearchResult::Found`] with `wrapped: true` when the match was found after wrapping around.
+    ///
+    /// ```
+    /// use tui_textarea::{TextArea, SearchResult};
+    ///
+    /// let mut textarea = TextArea::from(["hello", "helloo", "hellooo"]);
+    ///
+    /// textarea.set_search_pattern("hello+").unwrap();
+    ///
+    /// // Search does not wrap around when `wrap` is `false`
+    /// assert_eq!(textarea.search_back_ex(false, false), SearchResult::NotFound);
+    /// assert_eq!(textarea.cursor(), (0, 0));
+    ///
+    /// // Search reached the top of the buffer and continued from the bottom
+    /// assert_eq!(textarea.search_back_ex(false, true), SearchResult::Found { wrapped: true });
+    /// assert_eq!(textarea.cursor(), (2, 0));
+    /// assert_eq!(textarea.search_back_ex(false, true), SearchResult::Found { wrapped: false });
+    /// assert_eq!(textarea.cursor(), (1, 0));
+    /// ```
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn search_back_ex(&mut self, match_cursor: bool, wrap: bool) -> SearchResult {
+        match self
+            .search
+            .back(&self.lines, self.cursor, match_cursor, wrap)
+        {
+            Some((cursor, wrapped)) => {
+                self.record_jump(self.cursor, cursor);
+                self.cursor = cursor;
+                self.search.current = Some(cursor);
+                SearchResult::Found { wrapped }
+            }
+            None => {
+                self.search.current = None;
+                SearchResult::NotFound
+            }
         }
     }
 
@@ -2545,9 +8319,292 @@ impl<'a> TextArea<'a> {
         self.search.style = style;
     }
 
+    /// Set the text style of the current match of text search, which is the match the cursor last moved to with
+    /// [`TextArea::search_forward`] or [`TextArea::search_back`]. The style is patched on the style set by
+    /// [`TextArea::set_search_style`] so that the current match is distinguished from other matches. The current match
+    /// is cleared when the search pattern or its options change. The default style is the empty style, which means the
+    /// current match looks the same as other matches.
+    ///
+    /// ```
+    /// use ratatui::style::{Style, Color};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// let yellow_bg = Style::default().bg(Color::Yellow);
+    /// textarea.set_current_search_match_style(yellow_bg);
+    ///
+    /// assert_eq!(textarea.current_search_match_style(), yellow_bg);
+    /// ```
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn set_current_search_match_style(&mut self, style: Style) {
+        self.search.current_style = style;
+    }
+
+    /// Get the text style of the current match of text search. See [`TextArea::set_current_search_match_style`].
+    ///
+    /// ```
+    /// use ratatui::style::Style;
+    /// use tui_textarea::TextArea;
+    ///
+    /// let textarea = TextArea::default();
+    ///
+    /// assert_eq!(textarea.current_search_match_style(), Style::default());
+    /// ```
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn current_search_match_style(&self) -> Style {
+        self.search.current_style
+    }
+
+    /// Get the start position of the current match of text search as `(row, col)`, which is the match the cursor last
+    /// moved to with [`TextArea::search_forward`] or [`TextArea::search_back`]. `None` is returned when the cursor has
+    /// not moved to any match since the search pattern was set or since the text was last modified.
+    ///
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["foo", "foo"]);
+    ///
+    /// textarea.set_search_pattern("foo").unwrap();
+    /// assert_eq!(textarea.current_search_match(), None);
+    ///
+    /// textarea.search_forward(false);
+    /// assert_eq!(textarea.current_search_match(), Some((1, 0)));
+    ///
+    /// // Changing the pattern clears the current match
+    /// textarea.set_search_pattern("fo+").unwrap();
+    /// assert_eq!(textarea.current_search_match(), None);
+    /// ```
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn current_search_match(&self) -> Option<(usize, usize)> {
+        self.search.current
+    }
+
+    /// Replace the next match of the pattern set by [`TextArea::set_search_pattern`] with `replacement`. The match at the
+    /// cursor position is also a candidate, and text search wraps around the text buffer like
+    /// [`TextArea::search_forward`]. `replacement` can refer to capture groups of the match such as `$1` or `${name}`.
+    /// See [`regex::Captures::expand`] for the syntax. The cursor moves to the end of the replaced text. This method
+    /// returns `true` when some match was replaced. The replacement is a single undo entry.
+    ///
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["foo=1, bar=2"]);
+    ///
+    /// textarea.set_search_pattern(r"(\w+)=(\d)").unwrap();
+    /// assert!(textarea.replace_next("$2=$1"));
+    /// assert_eq!(textarea.lines(), ["1=foo, bar=2"]);
+    /// assert_eq!(textarea.cursor(), (0, 5));
+    ///
+    /// assert!(textarea.replace_next("$2=$1"));
+    /// assert_eq!(textarea.lines(), ["1=foo, 2=bar"]);
+    ///
+    /// textarea.undo();
+    /// assert_eq!(textarea.lines(), ["1=foo, bar=2"]);
+    /// ```
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn replace_next(&mut self, replacement: &str) -> bool {
+        if self.needs_guard() {
+            return self.guard(false, |t| t.replace_next(replacement));
+        }
+        let ((row, col), _) = match self.search.forward(&self.lines, self.cursor, true, true) {
+            Some(pos) => pos,
+            None => return false,
+        };
+        let (start, end, text) = {
+            let pat = match &self.search.pat {
+                Some(pat) => pat,
+                None => return false,
+            };
+            let line = &self.lines[row];
+            let offset = line
+                .char_indices()
+                .nth(col)
+                .map(|(i, _)| i)
+                .unwrap_or(line.len());
+            let caps = match pat.captures_at(line, offset) {
+                Some(caps) => caps,
+                None => return false,
+            };
+            let m = caps.get(0).unwrap();
+            let mut text = String::new();
+            caps.expand(replacement, &mut text);
+            let end_col = col + line[m.start()..m.end()].chars().count();
+            (
+                Pos::new(row, col, m.start()),
+                Pos::new(row, end_col, m.end()),
+                text,
+            )
+        };
+
+        self.history.begin_group(self.cursor);
+        self.replace_range(start, end, &text);
+        self.search.current = None;
+        self.history.end_group(self.cursor);
+        true
+    }
+
+    /// Replace all matches of the pattern set by [`TextArea::set_search_pattern`] with `replacement`. `replacement` can
+    /// refer to capture groups in the same way as [`TextArea::replace_next`]. The cursor moves to the end of the last
+    /// replaced text. This method returns the number of replaced matches. All the replacements are a single undo entry.
+    ///
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["let a = 1;", "let b = a;"]);
+    ///
+    /// textarea.set_search_pattern(r"\ba\b").unwrap();
+    /// assert_eq!(textarea.replace_all("alpha"), 2);
+    /// assert_eq!(textarea.lines(), ["let alpha = 1;", "let b = alpha;"]);
+    /// assert_eq!(textarea.cursor(), (1, 13));
+    ///
+    /// // Undo reverts all the replacements at once
+    /// textarea.undo();
+    /// assert_eq!(textarea.lines(), ["let a = 1;", "let b = a;"]);
+    /// ```
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn replace_all(&mut self, replacement: &str) -> usize {
+        if self.needs_guard() {
+            return self.guard(0, |t| t.replace_all(replacement));
+        }
+        let pat = match &self.search.pat {
+            Some(pat) => pat,
+            None => return 0,
+        };
+
+        let mut replaces = vec![];
+        for (row, line) in self.lines.iter().enumerate() {
+            let matches = self.search.find_iter(pat, line);
+            for m in matches.filter(|m| self.search.in_region(row, m.start(), m.end())) {
+                let caps = pat.captures_at(line, m.start()).unwrap();
+                let mut text = String::new();
+                caps.expand(replacement, &mut text);
+                let col = line[..m.start()].chars().count();
+                let end_col = col + line[m.start()..m.end()].chars().count();
+                replaces.push((
+                    Pos::new(row, col, m.start()),
+                    Pos::new(row, end_col, m.end()),
+                    text,
+                ));
+            }
+        }
+
+        let count = replaces.len();
+        if count == 0 {
+            return 0;
+        }
+
+        // Replace from the end of the buffer so that positions of remaining matches are not affected
+        let region = self.search.region();
+        let mut cursor = None;
+        let mut selection = region.map(|(s, e)| ((s.row, s.col), (e.row, e.col)));
+        self.history.begin_group(self.cursor);
+        for (start, end, text) in replaces.into_iter().rev() {
+            self.replace_range(start, end, &text);
+            let replaced_end = self.cursor;
+            cursor = Some(match cursor {
+                Some(pos) => shift_pos(pos, start, end, replaced_end),
+                None => replaced_end,
+            });
+            selection = selection.map(|(s, e)| {
+                let shift = |pos| shift_pos(pos, start, end, replaced_end);
+                (shift(s), shift(e))
+            });
+        }
+        self.cursor = cursor.unwrap();
+
+        // Keep selecting the replaced region
+        if let Some((start, end)) = selection {
+            self.selection_start = Some(start);
+            self.cursor = end;
+        }
+        self.search.current = None;
+        self.history.end_group(self.cursor);
+        count
+    }
+
+    // Replace the text between `start` and `end` with `text` and move the cursor to the end of the inserted text. The
+    // selection anchor is kept on the same text.
+    #[cfg(feature = "search")]
+    fn replace_range(&mut self, start: Pos, end: Pos, text: &str) {
+        let anchor = self.selection_start.take();
+        if start.row != end.row || start.offset != end.offset {
+            self.remove_range(start, end, false);
+        } else {
+            self.cursor = (start.row, start.col);
+        }
+        self.insert_str(text);
+        let replaced_end = self.cursor;
+        self.selection_start = anchor.map(|pos| shift_pos(pos, start, end, replaced_end));
+    }
+
+    /// Dump the internal state of the textarea in a compact single line: the cursor, the selection, the viewport
+    /// (`row,col` of the top-left corner and `width`x`height` of the last render), the number of lines, the number of
+    /// undoable/redoable/maximum history entries, and the active settings. This is intended to be attached to bug
+    /// reports. The format is not stable and may change in future versions.
+    ///
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["hello", "world"]);
+    /// textarea.move_cursor(CursorMove::Down);
+    /// textarea.start_selection();
+    /// textarea.insert_char('!');
+    ///
+    /// let state = textarea.debug_state();
+    /// assert!(state.starts_with("cursor=(1,1) selection=None viewport=0,0 0x0 lines=2 history=1/0/50 tab=4 "));
+    /// ```
+    pub fn debug_state(&self) -> String {
+        let selection = match self.selection_range() {
+            Some(((sr, sc), (er, ec))) => format!("({},{})-({},{})", sr, sc, er, ec),
+            None => "None".to_string(),
+        };
+        let (row, col, width, height) = self.viewport.rect();
+        #[allow(unused_mut)]
+        let mut state = format!(
+            "cursor=({},{}) selection={} viewport={},{} {}x{} lines={} history={}/{}/{} tab={} hard_tab={} mask={:?} lnum={} align={:?} list={} syntax={:?} theme={:?}",
+            self.cursor.0,
+            self.cursor.1,
+            selection,
+            row,
+            col,
+            width,
+            height,
+            self.lines.len(),
+            self.history.undo_len(),
+            self.history.redo_len(),
+            self.history.max_items(),
+            self.tab_len,
+            self.hard_tab_indent,
+            self.mask,
+            self.line_number_style.is_some(),
+            self.alignment,
+            self.list_continuation.is_some(),
+            self.syntax_name,
+            self.theme_name,
+        );
+        #[cfg(feature = "search")]
+        {
+            let pat = self.search.pat.as_ref().map(|p| p.as_str());
+            state.push_str(&format!(
+                " search={:?} case={:?} whole_word={}",
+                pat,
+                self.search.case(),
+                self.search.whole_word(),
+            ));
+        }
+        state
+    }
+
     /// Scroll the textarea. See [`Scrolling`] for the argument.
     /// The cursor will not move until it goes out the viewport. When the cursor position is outside the viewport after scroll,
     /// the cursor position will be adjusted to stay in the viewport using the same logic as [`CursorMove::InViewport`].
+    /// When lines are wrapped with [`TextArea::set_wrap`], rows are counted as display rows of the wrapped lines.
     ///
     /// ```
     /// # use ratatui::buffer::Buffer;
@@ -2588,31 +8645,376 @@ impl<'a> TextArea<'a> {
     }
 
     fn scroll_with_shift(&mut self, scrolling: Scrolling, shift: bool) {
+        trace!(?scrolling, shift, "scroll");
         if shift && self.selection_start.is_none() {
             self.selection_start = Some(self.cursor);
         }
-        scrolling.scroll(&mut self.viewport);
+        if let Some(offset) = scrolling.cursor_offset(&self.viewport) {
+            self.scroll_cursor_to(offset);
+            return;
+        }
+        match self.viewport.wrap_width() {
+            // Wrapped lines are scrolled by display rows. All columns are visible
+            Some(width) if self.wrap => {
+                let (rows, _) = scrolling.delta(&self.viewport);
+                let wrap = self.wrap_layout(width);
+                let top = self.wrapped_viewport_top(&wrap);
+                let n = rows.unsigned_abs() as usize;
+                let (top_row, top_offset) = wrap.step_rows(&self.lines, top, n, rows < 0);
+                self.viewport.set_scroll_top(top_row, 0);
+                self.viewport.set_wrap_top(top_offset);
+            }
+            _ => scrolling.scroll(&mut self.viewport),
+        }
         self.move_cursor_with_shift(CursorMove::InViewport, shift);
     }
 
-    /// Expands tabs in a line to spaces according to tab_len.
-    fn expand_tabs(&self, line: &str) -> String {
-        if self.tab_len == 0 || !line.contains('\t') {
-            return line.to_string();
+    // Scroll the viewport so that the cursor is rendered at the display row `offset` in the viewport. The top of the
+    // viewport stops at the first line. The offset is kept within the scroll margin. Display rows of wrapped lines are
+    // counted when lines were wrapped on the last rendering
+    fn scroll_cursor_to(&mut self, offset: usize) {
+        let (_, _, _, height) = self.viewport.rect();
+        let margin = self.scroll_margin_in(height);
+        let last = (height as usize).saturating_sub(1);
+        let offset = cmp::max(cmp::min(offset, last - margin), margin);
+        let (row, col) = self.cursor;
+        let (top_row, top_offset) = match self.viewport.wrap_width() {
+            Some(width) => {
+                let wrap = self.wrap_layout(width);
+                let (mut r, mut i) = (row, wrap.position(&self.lines[row], col).0);
+                // Do not scroll beyond the end of the text. Count the display rows below the cursor up to the height
+                let mut below = wrap.rows(&self.lines[row]).len() - 1 - i;
+                for line in &self.lines[row + 1..] {
+                    if below >= last {
+                        break;
+                    }
+                    below += wrap.rows(line).len();
+                }
+                let offset = cmp::max(offset, last.saturating_sub(below));
+                for _ in 0..offset {
+                    if i > 0 {
+                        i -= 1;
+                    } else if r > 0 {
+                        r -= 1;
+                        i = wrap.rows(&self.lines[r]).len() - 1;
+                    } else {
+                        break;
+                    }
+                }
+                (r, i)
+            }
+            None => {
+                let below = self.lines.len() - 1 - row;
+                let offset = cmp::max(offset, last.saturating_sub(below));
+                (row.saturating_sub(offset), 0)
+            }
+        };
+        let (_, top_col) = self.viewport.scroll_top();
+        self.viewport.set_scroll_top(top_row, top_col);
+        self.viewport.set_wrap_top(top_offset);
+    }
+
+    /// Get the top row and the left column of the viewport as of the last rendering. When lines are wrapped with
+    /// [`TextArea::set_wrap`], the row is the line at the top of the viewport.
+    /// ```
+    /// use ratatui::buffer::Buffer;
+    /// use ratatui::layout::Rect;
+    /// use ratatui::widgets::Widget as _;
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from((0..20).map(|i| i.to_string()));
+    /// assert_eq!(textarea.viewport_offset(), (0, 0));
+    ///
+    /// textarea.move_cursor(CursorMove::Bottom);
+    /// let r = Rect { x: 0, y: 0, width: 10, height: 5 };
+    /// textarea.render(r, &mut Buffer::empty(r));
+    /// assert_eq!(textarea.viewport_offset(), (15, 0));
+    /// ```
+    pub fn viewport_offset(&self) -> (usize, usize) {
+        self.viewport.scroll_top()
+    }
+
+    /// Set the top row and the left column of the viewport which are applied on the next rendering. The row is
+    /// clamped to the last line. This is useful to save and restore the position of the view. When the textarea was
+    /// already rendered and the cursor goes out of the new viewport, the cursor is moved into the viewport in the same
+    /// way as [`TextArea::scroll`]. Otherwise the viewport is scrolled to the cursor on the next rendering.
+    /// ```
+    /// use ratatui::buffer::Buffer;
+    /// use ratatui::layout::Rect;
+    /// use ratatui::widgets::Widget as _;
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from((0..20).map(|i| i.to_string()));
+    /// let r = Rect { x: 0, y: 0, width: 10, height: 5 };
+    /// textarea.render(r, &mut Buffer::empty(r));
+    ///
+    /// // Scroll to the middle of the text
+    /// textarea.set_viewport_offset(10, 0);
+    /// assert_eq!(textarea.cursor(), (10, 0));
+    /// textarea.render(r, &mut Buffer::empty(r));
+    /// assert_eq!(textarea.viewport_offset(), (10, 0));
+    ///
+    /// // Restore the view before the first rendering
+    /// let mut restored = TextArea::from((0..20).map(|i| i.to_string()));
+    /// restored.move_cursor(CursorMove::Jump(12, 0));
+    /// restored.set_viewport_offset(10, 0);
+    /// restored.render(r, &mut Buffer::empty(r));
+    /// assert_eq!(restored.viewport_offset(), (10, 0));
+    /// assert_eq!(restored.cursor(), (12, 0));
+    /// ```
+    pub fn set_viewport_offset(&mut self, row: usize, col: usize) {
+        let row = cmp::min(row, self.lines.len() - 1);
+        self.viewport.set_scroll_top(row, col);
+        let (_, _, _, height) = self.viewport.rect();
+        if height > 0 {
+            self.move_cursor_with_shift(CursorMove::InViewport, self.selection_start.is_some());
         }
-        let mut expanded = String::with_capacity(line.len() + self.tab_len as usize * 4); // Preallocate a bit
-        let mut width = 0;
-        for ch in line.chars() {
-            if ch == '\t' {
-                let spaces_to_add = self.tab_len as usize - (width % self.tab_len as usize);
-                expanded.push_str(&spaces(spaces_to_add as u8));
-                width += spaces_to_add;
-            } else {
-                expanded.push(ch);
-                width += ch.width().unwrap_or(0);
+    }
+
+    /// Set the number of rows kept visible above and below the cursor, like the `scrolloff` option of Vim. The
+    /// viewport is scrolled before the cursor reaches its top or bottom edge, and scrolling with [`TextArea::scroll`]
+    /// keeps the cursor off the edges. The margin is not kept at the start and the end of the text. When the viewport
+    /// is too short for the margin on both sides, the cursor is kept at the middle of the viewport. The default value
+    /// is 0.
+    /// ```
+    /// use ratatui::buffer::Buffer;
+    /// use ratatui::layout::Rect;
+    /// use ratatui::widgets::Widget as _;
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from((0..20).map(|i| i.to_string()));
+    /// textarea.set_scroll_margin(2);
+    /// assert_eq!(textarea.scroll_margin(), 2);
+    ///
+    /// // The viewport is scrolled when the cursor is 2 rows above the bottom
+    /// textarea.move_cursor(CursorMove::Jump(5, 0));
+    /// let r = Rect { x: 0, y: 0, width: 10, height: 5 };
+    /// textarea.render(r, &mut Buffer::empty(r));
+    /// assert_eq!(textarea.viewport_offset(), (3, 0));
+    /// ```
+    pub fn set_scroll_margin(&mut self, rows: u16) {
+        self.scroll_margin = rows;
+    }
+
+    /// Get the number of rows kept visible above and below the cursor set by [`TextArea::set_scroll_margin`].
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let textarea = TextArea::default();
+    /// assert_eq!(textarea.scroll_margin(), 0);
+    /// ```
+    pub fn scroll_margin(&self) -> u16 {
+        self.scroll_margin
+    }
+
+    // The scroll margin applied to a viewport of the height. It is reduced so that the cursor can stay in the middle
+    // of a short viewport
+    pub(crate) fn scroll_margin_in(&self, height: u16) -> usize {
+        cmp::min(self.scroll_margin, height.saturating_sub(1) / 2) as usize
+    }
+
+    /// Get the scroll state of the viewport on the last rendering as a tuple of the top row, the number of rows in
+    /// the viewport, and the total number of rows. The number of rows in the viewport is the height of the area
+    /// inside the block borders. When lines are soft wrapped with [`TextArea::set_wrap`], the rows are counted in
+    /// display rows. This is useful to render a scrollbar next to the textarea. All values except for the total are
+    /// zero until the textarea is rendered once.
+    /// ```
+    /// use ratatui::buffer::Buffer;
+    /// use ratatui::layout::Rect;
+    /// use ratatui::widgets::{Block, Widget as _};
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from((0..20).map(|i| i.to_string()));
+    /// textarea.set_block(Block::bordered());
+    /// textarea.move_cursor(CursorMove::Bottom);
+    ///
+    /// // The borders take 2 rows of the area
+    /// let r = Rect { x: 0, y: 0, width: 10, height: 7 };
+    /// textarea.render(r, &mut Buffer::empty(r));
+    /// assert_eq!(textarea.scroll_state(), (15, 5, 20));
+    /// ```
+    pub fn scroll_state(&self) -> (usize, usize, usize) {
+        let (top, _, _, height) = self.viewport.rect();
+        let height = height as usize;
+        match self.viewport.wrap_width() {
+            Some(width) => {
+                let wrap = self.wrap_layout(width);
+                let top = top.min(self.lines.len() - 1);
+                let rows = |line: &String| wrap.rows(line).len();
+                let above: usize = self.lines[..top].iter().map(rows).sum();
+                let total = above + self.lines[top..].iter().map(rows).sum::<usize>();
+                (above + self.viewport.wrap_top(), height, total)
+            }
+            None => (top, height, self.lines.len()),
+        }
+    }
+
+    /// Build a [`ratatui::widgets::ScrollbarState`] from [`TextArea::scroll_state`] to render a vertical
+    /// [`ratatui::widgets::Scrollbar`] for the textarea.
+    /// ```
+    /// use ratatui::buffer::Buffer;
+    /// use ratatui::layout::Rect;
+    /// use ratatui::widgets::{Scrollbar, ScrollbarOrientation, StatefulWidget as _, Widget as _};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let textarea = TextArea::from((0..20).map(|i| i.to_string()));
+    ///
+    /// let r = Rect { x: 0, y: 0, width: 10, height: 5 };
+    /// let mut buf = Buffer::empty(r);
+    /// textarea.render(r, &mut buf);
+    ///
+    /// let mut state = textarea.scrollbar_state();
+    /// Scrollbar::new(ScrollbarOrientation::VerticalRight).render(r, &mut buf, &mut state);
+    /// ```
+    #[cfg(feature = "ratatui")]
+    pub fn scrollbar_state(&self) -> ratatui::widgets::ScrollbarState {
+        let (top, height, total) = self.scroll_state();
+        ratatui::widgets::ScrollbarState::new(total)
+            .position(top)
+            .viewport_content_length(height)
+    }
+
+    /// Get the position of the cursor on the screen as `(x, y)` when the textarea was rendered in the area on the last
+    /// rendering. `None` is returned when the cursor is scrolled out of the viewport. The ghost text rendered before
+    /// the cursor is taken into account. This is useful to put the terminal cursor with
+    /// [`ratatui::Frame::set_cursor_position`] along with [`CursorRendering::Hardware`].
+    /// ```
+    /// use ratatui::buffer::Buffer;
+    /// use ratatui::layout::Rect;
+    /// use ratatui::widgets::Widget as _;
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["abc", "def"]);
+    /// textarea.move_cursor(CursorMove::Jump(1, 2));
+    ///
+    /// let r = Rect { x: 3, y: 2, width: 10, height: 5 };
+    /// textarea.render(r, &mut Buffer::empty(r));
+    /// assert_eq!(textarea.screen_cursor_position(r), Some((5, 3)));
+    /// ```
+    pub fn screen_cursor_position(&self, area: Rect) -> Option<(u16, u16)> {
+        let inner = self.block.as_ref().map_or(area, |b| b.inner(area));
+        let (top_row, top_col, width, height) = self.viewport.rect();
+        let (row, col) = self.cursor;
+        let gutter = self.gutter_width();
+
+        let (y, x) = match self.viewport.wrap_width() {
+            Some(wrap_width) => {
+                let wrap = self.wrap_layout(wrap_width);
+                if row < top_row {
+                    return None;
+                }
+                let above: usize = self.lines[top_row..row]
+                    .iter()
+                    .map(|line| wrap.rows(line).len())
+                    .sum();
+                let (index, x) = wrap.position(&self.lines[row], col);
+                let y = (above + index).checked_sub(self.viewport.wrap_top())?;
+                // The ghost text stays at the end of the previous row when the cursor starts a continuation row
+                let ghost = if index > 0 && wrap.rows(&self.lines[row])[index].0 == col {
+                    0
+                } else {
+                    self.ghost_width_before_cursor()
+                };
+                (y, gutter + x + ghost)
+            }
+            None => {
+                let y = row.checked_sub(top_row)?;
+                let x = gutter + self.display_col(self.cursor) + self.ghost_width_before_cursor();
+                let x = x.checked_sub(top_col)?;
+                let line = &self.lines[row];
+                let line_width = self
+                    .line_spans(line, row, num_digits(self.lines.len()), &[])
+                    .width();
+                let offset = match self.alignment {
+                    Alignment::Left => 0,
+                    Alignment::Center => (width as usize).saturating_sub(line_width) / 2,
+                    Alignment::Right => (width as usize).saturating_sub(line_width),
+                };
+                (y, offset + x)
+            }
+        };
+
+        if y >= height as usize || x >= width as usize {
+            return None;
+        }
+        Some((inner.x + x as u16, inner.y + y as u16))
+    }
+
+    /// Build the styled lines in the range of rows as they are rendered, including line numbers, syntax highlighting,
+    /// selection, search matches, and the cursor. The range is clamped to the number of lines. Lines are not wrapped
+    /// and the style of the textarea set by [`TextArea::set_style`] is not included since it is applied to the whole
+    /// area. This is useful to embed the content of the textarea in another widget or to test highlighting.
+    /// ```
+    /// use ratatui::style::{Color, Style};
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["abc", "def", "ghi"]);
+    /// textarea.set_selection_style(Style::default().bg(Color::Blue));
+    /// textarea.move_cursor(CursorMove::Down);
+    /// textarea.start_selection();
+    /// textarea.move_cursor(CursorMove::Forward);
+    ///
+    /// let lines = textarea.styled_lines(1..5);
+    /// assert_eq!(lines.len(), 2);
+    /// assert_eq!(lines[0].spans[0].content, "d");
+    /// assert_eq!(lines[0].spans[0].style.bg, Some(Color::Blue));
+    /// ```
+    pub fn styled_lines(&self, rows: Range<usize>) -> Vec<Line<'_>> {
+        let len = self.lines.len();
+        let (start, end) = (cmp::min(rows.start, len), cmp::min(rows.end, len));
+        let lnum_len = num_digits(len);
+        let brackets = self.bracket_highlights();
+        (start..cmp::max(start, end))
+            .map(|row| self.line_spans(&self.lines[row], row, lnum_len, &brackets))
+            .collect()
+    }
+
+    /// Render the textarea in an area of the size and get the rendered text as a string. Rows are separated with
+    /// `\n` and each row has exactly `width` columns, so trailing spaces are kept. This is useful for snapshot tests of
+    /// applications using the textarea.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["hello world"]);
+    /// textarea.set_wrap(true);
+    /// assert_eq!(textarea.render_to_string(8, 3), "hello   \nworld   \n        ");
+    /// ```
+    #[cfg(feature = "ratatui")]
+    pub fn render_to_string(&self, width: u16, height: u16) -> String {
+        let buf = self.render_to_styled(width, height);
+        let mut s = String::with_capacity((width as usize + 1) * height as usize);
+        for y in 0..height {
+            if y > 0 {
+                s.push('\n');
+            }
+            let mut x = 0;
+            while x < width {
+                let symbol = buf[(x, y)].symbol();
+                s.push_str(symbol);
+                x += cmp::max(symbol.width(), 1) as u16; // The cells covered by a wide character are skipped
             }
         }
-        expanded
+        s
+    }
+
+    /// Render the textarea in an area of the size at the origin and get the buffer which has the symbol and the style
+    /// of each cell. This is useful for snapshot tests which check styles.
+    /// ```
+    /// use ratatui::style::Modifier;
+    /// use tui_textarea::TextArea;
+    ///
+    /// let textarea = TextArea::from(["abc"]);
+    /// let buf = textarea.render_to_styled(5, 1);
+    /// assert_eq!(buf[(0, 0)].symbol(), "a");
+    /// // The cursor is rendered at the first character
+    /// assert!(buf[(0, 0)].modifier.contains(Modifier::REVERSED));
+    /// ```
+    #[cfg(feature = "ratatui")]
+    pub fn render_to_styled(&self, width: u16, height: u16) -> ratatui::buffer::Buffer {
+        let area = Rect::new(0, 0, width, height);
+        let mut buf = ratatui::buffer::Buffer::empty(area);
+        self.render(area, &mut buf);
+        buf
     }
 
     // --- Syntax Highlighting Methods --- ADD THIS BLOCK BACK ---
@@ -2648,7 +9050,26 @@ impl<'a> TextArea<'a> {
     }
 
     // --- End Syntax Highlighting Methods ---
+}
+
+// Typing this character completes the word before the cursor
+fn is_word_boundary(c: char) -> bool {
+    c.is_whitespace() || c.is_ascii_punctuation()
+}
 
+// Map a position across an edit which replaced the text between `start` and `end` with a text ending at `new_end`.
+// Positions inside the replaced text move to its start.
+fn shift_pos(pos: (usize, usize), start: Pos, end: Pos, new_end: (usize, usize)) -> (usize, usize) {
+    let (row, col) = pos;
+    if pos <= (start.row, start.col) {
+        pos
+    } else if pos < (end.row, end.col) {
+        (start.row, start.col)
+    } else if row == end.row {
+        (new_end.0, new_end.1 + col - end.col)
+    } else {
+        (row + new_end.0 - end.row, col)
+    }
 }
 
 #[cfg(test)]
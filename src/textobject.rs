@@ -0,0 +1,389 @@
+#[cfg(feature = "arbitrary")]
+use arbitrary::Arbitrary;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Text object around the cursor like Vim's text objects. See [`crate::TextArea::text_object_range`].
+///
+/// This type is marked as `#[non_exhaustive]` since more variations may be supported in the future.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum TextObject {
+    /// The word under the cursor, or the whitespaces under the cursor. This is similar to 'iw' of Vim. Word
+    /// boundaries are the same as [`crate::CursorMove::WordForward`].
+    InnerWord,
+    /// The word under the cursor with the whitespaces after it. When no whitespace follows the word, the whitespaces
+    /// before it are included instead. When the cursor is on whitespaces, the whitespaces and the word after them are
+    /// the text object. This is similar to 'aw' of Vim.
+    AWord,
+    /// The text between the quotes around the cursor in the cursor line. When the cursor is before the first quote,
+    /// the first quoted text after the cursor is the text object. Quotes escaped with `\` are ignored. This is
+    /// similar to 'i"' of Vim.
+    InnerQuote(char),
+    /// The quoted text around the cursor including the quotes and the whitespaces after the closing quote. This is
+    /// similar to 'a"' of Vim.
+    AQuote(char),
+    /// The text between the pair of opening and closing characters around the cursor, such as `InnerPair('(', ')')`.
+    /// The pair can be across lines and nested pairs are skipped. When the opening character is at the end of its
+    /// line and the closing character is preceded only by whitespaces, the lines between them are the text object.
+    /// This is similar to 'i(' of Vim.
+    InnerPair(char, char),
+    /// The text of the pair around the cursor including the opening and closing characters. This is similar to 'a('
+    /// of Vim.
+    APair(char, char),
+    /// The paragraph around the cursor. A paragraph is a chunk of non-blank lines, the same as
+    /// [`crate::CursorMove::ParagraphForward`]. When the cursor is on a blank line, the consecutive blank lines are the
+    /// text object. The range covers entire lines including the line break so that deleting it removes the lines. This
+    /// is similar to 'ip' of Vim.
+    InnerParagraph,
+    /// The paragraph around the cursor with the blank lines after it. When no blank line follows the paragraph, the
+    /// blank lines before it are included instead. When the cursor is on a blank line, the blank lines and the
+    /// paragraph after them are the text object. This is similar to 'ap' of Vim.
+    AParagraph,
+}
+
+type Pos = (usize, usize);
+
+impl TextObject {
+    // Range of the text object around the cursor. The end position is exclusive
+    pub(crate) fn range(self, lines: &[String], cursor: Pos) -> Option<(Pos, Pos)> {
+        match self {
+            TextObject::InnerWord => inner_word(lines, cursor),
+            TextObject::AWord => a_word(lines, cursor),
+            TextObject::InnerQuote(q) => {
+                let (open, close) = quotes(&lines[cursor.0], cursor.1, q)?;
+                Some(((cursor.0, open + 1), (cursor.0, close)))
+            }
+            TextObject::AQuote(q) => {
+                let line = &lines[cursor.0];
+                let (open, close) = quotes(line, cursor.1, q)?;
+                let end = skip_spaces(line.chars().skip(close + 1), close + 1);
+                Some(((cursor.0, open), (cursor.0, end)))
+            }
+            TextObject::InnerPair(open, close) => {
+                let ((sr, sc), (er, ec)) = pair(lines, cursor, open, close)?;
+                let start_at_eol = sc + 1 == lines[sr].chars().count();
+                let end_after_spaces = lines[er].chars().take(ec).all(char::is_whitespace);
+                if start_at_eol && end_after_spaces && sr + 1 < er {
+                    let last = er - 1;
+                    Some(((sr + 1, 0), (last, lines[last].chars().count())))
+                } else {
+                    Some(((sr, sc + 1), (er, ec)))
+                }
+            }
+            TextObject::APair(open, close) => {
+                let (start, (er, ec)) = pair(lines, cursor, open, close)?;
+                Some((start, (er, ec + 1)))
+            }
+            TextObject::InnerParagraph => {
+                let (start, end) = inner_paragraph(lines, cursor.0);
+                Some(line_range(lines, start, end))
+            }
+            TextObject::AParagraph => {
+                let (start, end) = a_paragraph(lines, cursor.0);
+                Some(line_range(lines, start, end))
+            }
+        }
+    }
+}
+
+fn is_space(c: char) -> bool {
+    c == ' ' || c == '\t'
+}
+
+// Return the column after the whitespaces at the start of `chars`. `col` is the column of the first character
+fn skip_spaces(chars: impl Iterator<Item = char>, col: usize) -> usize {
+    col + chars.take_while(|&c| is_space(c)).count()
+}
+
+fn inner_word(lines: &[String], (row, col): Pos) -> Option<(Pos, Pos)> {
+    let chars: Vec<char> = lines[row].chars().collect();
+    if !is_space(*chars.get(col)?) {
+        let (start, end) = crate::word::find_word_at(&lines[row], col)?;
+        return Some(((row, start), (row, end)));
+    }
+    let start = chars[..col]
+        .iter()
+        .rposition(|&c| !is_space(c))
+        .map_or(0, |i| i + 1);
+    let end = skip_spaces(chars[col..].iter().copied(), col);
+    Some(((row, start), (row, end)))
+}
+
+fn a_word(lines: &[String], cursor: Pos) -> Option<(Pos, Pos)> {
+    let ((row, start), (_, end)) = inner_word(lines, cursor)?;
+    let chars: Vec<char> = lines[row].chars().collect();
+    if is_space(chars[start]) {
+        // Whitespaces followed by a word
+        if end == chars.len() {
+            return Some(((row, start), (row, end)));
+        }
+        let ((_, _), (_, word_end)) = inner_word(lines, (row, end))?;
+        return Some(((row, start), (row, word_end)));
+    }
+    let after = skip_spaces(chars[end..].iter().copied(), end);
+    if after > end {
+        return Some(((row, start), (row, after)));
+    }
+    let before = chars[..start]
+        .iter()
+        .rposition(|&c| !is_space(c))
+        .map_or(0, |i| i + 1);
+    Some(((row, before), (row, end)))
+}
+
+// Columns of the opening and closing quotes around the column or after the column in the line
+fn quotes(line: &str, col: usize, quote: char) -> Option<(usize, usize)> {
+    let mut positions = vec![];
+    let mut escaped = false;
+    for (i, c) in line.chars().enumerate() {
+        if c == quote && !escaped {
+            positions.push(i);
+        }
+        escaped = c == '\\' && !escaped;
+    }
+    positions
+        .chunks_exact(2)
+        .map(|p| (p[0], p[1]))
+        .find(|&(_, close)| col <= close)
+}
+
+// Positions of the opening and closing characters of the innermost pair around the cursor
+fn pair(lines: &[String], (row, col): Pos, open: char, close: char) -> Option<(Pos, Pos)> {
+    let chars_of = |r: usize| lines[r].chars().collect::<Vec<_>>();
+
+    // Find the unmatched opening character before the cursor. The character under the cursor can be a part of the pair
+    let mut depth = 0usize;
+    let mut start = None;
+    let cursor_char = lines[row].chars().nth(col);
+    'back: for r in (0..=row).rev() {
+        let chars = chars_of(r);
+        let last = if r == row {
+            match cursor_char {
+                Some(c) if c == close && open != close => col.checked_sub(1),
+                _ => Some(col.min(chars.len().saturating_sub(1))),
+            }
+        } else {
+            chars.len().checked_sub(1)
+        };
+        let last = match last {
+            Some(last) if !chars.is_empty() => last,
+            _ => continue,
+        };
+        for c in (0..=last).rev() {
+            if chars[c] == close {
+                depth += 1;
+            } else if chars[c] == open {
+                if depth == 0 {
+                    start = Some((r, c));
+                    break 'back;
+                }
+                depth -= 1;
+            }
+        }
+    }
+    let (sr, sc) = start?;
+
+    // Find the closing character matching the opening character
+    let mut depth = 0usize;
+    for r in sr..lines.len() {
+        let chars = chars_of(r);
+        let first = if r == sr { sc + 1 } else { 0 };
+        for c in first..chars.len() {
+            if chars[c] == open {
+                depth += 1;
+            } else if chars[c] == close {
+                if depth == 0 {
+                    return Some(((sr, sc), (r, c)));
+                }
+                depth -= 1;
+            }
+        }
+    }
+    None
+}
+
+fn is_blank(line: &str) -> bool {
+    line.chars().all(char::is_whitespace)
+}
+
+// Rows of the consecutive lines which are all blank or all non-blank around the row. The end row is inclusive
+fn inner_paragraph(lines: &[String], row: usize) -> (usize, usize) {
+    let blank = is_blank(&lines[row]);
+    let start = (0..row)
+        .rev()
+        .find(|&r| is_blank(&lines[r]) != blank)
+        .map_or(0, |r| r + 1);
+    let end = (row + 1..lines.len())
+        .find(|&r| is_blank(&lines[r]) != blank)
+        .map_or(lines.len() - 1, |r| r - 1);
+    (start, end)
+}
+
+fn a_paragraph(lines: &[String], row: usize) -> (usize, usize) {
+    let (start, end) = inner_paragraph(lines, row);
+    if end + 1 < lines.len() {
+        // The blank lines after the paragraph, or the paragraph after the blank lines
+        let (_, next_end) = inner_paragraph(lines, end + 1);
+        return (start, next_end);
+    }
+    if is_blank(&lines[row]) || start == 0 {
+        return (start, end);
+    }
+    // No blank line follows the last paragraph. Include the blank lines before it
+    let (prev_start, _) = inner_paragraph(lines, start - 1);
+    (prev_start, end)
+}
+
+// Range of the entire lines from `start` row to `end` row. The line break after the last line is included. When the
+// last line is the end of the text, the line break before the first line is included instead
+fn line_range(lines: &[String], start: usize, end: usize) -> (Pos, Pos) {
+    if end + 1 < lines.len() {
+        ((start, 0), (end + 1, 0))
+    } else if start > 0 {
+        let prev = start - 1;
+        (
+            (prev, lines[prev].chars().count()),
+            (end, lines[end].chars().count()),
+        )
+    } else {
+        ((0, 0), (end, lines[end].chars().count()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(s: &[&str]) -> Vec<String> {
+        s.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn word_objects() {
+        let l = lines(&["foo  bar.baz", ""]);
+        for (obj, cursor, want) in [
+            (TextObject::InnerWord, (0, 1), Some(((0, 0), (0, 3)))),
+            (TextObject::InnerWord, (0, 3), Some(((0, 3), (0, 5)))),
+            (TextObject::InnerWord, (0, 8), Some(((0, 8), (0, 9)))),
+            (TextObject::InnerWord, (0, 12), None),
+            (TextObject::InnerWord, (1, 0), None),
+            (TextObject::AWord, (0, 0), Some(((0, 0), (0, 5)))),
+            (TextObject::AWord, (0, 4), Some(((0, 3), (0, 8)))),
+            (TextObject::AWord, (0, 10), Some(((0, 9), (0, 12)))),
+        ] {
+            assert_eq!(obj.range(&l, cursor), want, "{:?} {:?}", obj, cursor);
+        }
+    }
+
+    #[test]
+    fn quote_objects() {
+        let l = lines(&[r#"a "b \" c" "d"  e"#]);
+        for (obj, cursor, want) in [
+            (TextObject::InnerQuote('"'), (0, 0), Some(((0, 3), (0, 9)))),
+            (TextObject::InnerQuote('"'), (0, 2), Some(((0, 3), (0, 9)))),
+            (TextObject::InnerQuote('"'), (0, 6), Some(((0, 3), (0, 9)))),
+            (TextObject::InnerQuote('"'), (0, 9), Some(((0, 3), (0, 9)))),
+            (
+                TextObject::InnerQuote('"'),
+                (0, 10),
+                Some(((0, 12), (0, 13))),
+            ),
+            (TextObject::InnerQuote('"'), (0, 15), None),
+            (TextObject::InnerQuote('\''), (0, 0), None),
+            (TextObject::AQuote('"'), (0, 3), Some(((0, 2), (0, 11)))),
+            (TextObject::AQuote('"'), (0, 12), Some(((0, 11), (0, 16)))),
+        ] {
+            assert_eq!(obj.range(&l, cursor), want, "{:?} {:?}", obj, cursor);
+        }
+    }
+
+    #[test]
+    fn pair_objects() {
+        let l = lines(&["f(a, (b), c)", "g {", "    x", "}"]);
+        for (obj, cursor, want) in [
+            (
+                TextObject::InnerPair('(', ')'),
+                (0, 2),
+                Some(((0, 2), (0, 11))),
+            ),
+            (
+                TextObject::InnerPair('(', ')'),
+                (0, 1),
+                Some(((0, 2), (0, 11))),
+            ),
+            (
+                TextObject::InnerPair('(', ')'),
+                (0, 11),
+                Some(((0, 2), (0, 11))),
+            ),
+            (
+                TextObject::InnerPair('(', ')'),
+                (0, 6),
+                Some(((0, 6), (0, 7))),
+            ),
+            (
+                TextObject::InnerPair('(', ')'),
+                (0, 8),
+                Some(((0, 2), (0, 11))),
+            ),
+            (TextObject::InnerPair('(', ')'), (0, 0), None),
+            (TextObject::APair('(', ')'), (0, 9), Some(((0, 1), (0, 12)))),
+            (TextObject::APair('(', ')'), (0, 5), Some(((0, 5), (0, 8)))),
+            // Across lines
+            (
+                TextObject::InnerPair('{', '}'),
+                (2, 4),
+                Some(((2, 0), (2, 5))),
+            ),
+            (
+                TextObject::InnerPair('{', '}'),
+                (3, 0),
+                Some(((2, 0), (2, 5))),
+            ),
+            (TextObject::APair('{', '}'), (2, 0), Some(((1, 2), (3, 1)))),
+            (TextObject::InnerPair('{', '}'), (0, 3), None),
+        ] {
+            assert_eq!(obj.range(&l, cursor), want, "{:?} {:?}", obj, cursor);
+        }
+
+        let l = lines(&["{", "}"]);
+        let want = Some(((0, 1), (1, 0)));
+        assert_eq!(TextObject::InnerPair('{', '}').range(&l, (1, 0)), want);
+    }
+
+    #[test]
+    fn paragraph_objects() {
+        let l = lines(&["a", "b", "", " ", "c", "", "d", "e"]);
+        for (obj, row, want) in [
+            (TextObject::InnerParagraph, 0, ((0, 0), (2, 0))),
+            (TextObject::InnerParagraph, 1, ((0, 0), (2, 0))),
+            (TextObject::InnerParagraph, 3, ((2, 0), (4, 0))),
+            (TextObject::InnerParagraph, 4, ((4, 0), (5, 0))),
+            (TextObject::InnerParagraph, 7, ((5, 0), (7, 1))),
+            (TextObject::AParagraph, 0, ((0, 0), (4, 0))),
+            (TextObject::AParagraph, 2, ((2, 0), (5, 0))),
+            (TextObject::AParagraph, 5, ((4, 1), (7, 1))),
+            // No blank line follows the last paragraph
+            (TextObject::AParagraph, 6, ((4, 1), (7, 1))),
+        ] {
+            let have = obj.range(&l, (row, 0));
+            assert_eq!(have, Some(want), "{:?} {:?}", obj, row);
+        }
+
+        let l = lines(&["a", "b"]);
+        for obj in [TextObject::InnerParagraph, TextObject::AParagraph] {
+            assert_eq!(obj.range(&l, (1, 0)), Some(((0, 0), (1, 1))));
+        }
+        let l = lines(&["", "", "a"]);
+        let have = TextObject::InnerParagraph.range(&l, (0, 0));
+        assert_eq!(have, Some(((0, 0), (2, 0))));
+        let have = TextObject::AParagraph.range(&l, (1, 0));
+        assert_eq!(have, Some(((0, 0), (2, 1))));
+        let l = lines(&["a", "", ""]);
+        let have = TextObject::AParagraph.range(&l, (2, 0));
+        assert_eq!(have, Some(((0, 1), (2, 0))));
+    }
+}
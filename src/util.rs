@@ -1,3 +1,5 @@
+use unicode_width::UnicodeWidthChar as _;
+
 pub fn spaces(size: u8) -> &'static str {
     const SPACES: &str = "                                                                                                                                                                                                                                                                ";
     &SPACES[..size as usize]
@@ -7,6 +9,40 @@ pub fn num_digits(i: usize) -> u8 {
     f64::log10(i as f64) as u8 + 1
 }
 
+// Text rendered in place of a control character so that it does not garble the terminal. C0 control characters and
+// DEL are rendered in caret notation like `^[`, and C1 control characters are rendered with their code like `<9b>`.
+// Tab is not included since it is expanded to spaces
+pub fn control_char_text(c: char) -> Option<String> {
+    match c as u32 {
+        0x09 => None,
+        n @ 0x00..=0x1f => Some(format!("^{}", (n as u8 + 0x40) as char)),
+        0x7f => Some("^?".to_string()),
+        n @ 0x80..=0x9f => Some(format!("<{:02x}>", n)),
+        _ => None,
+    }
+}
+
+// Width of a character rendered in the textarea. Tab is counted as 0 since its width depends on its position
+pub fn char_width(c: char) -> usize {
+    match c as u32 {
+        0x09 => 0,
+        0x00..=0x1f | 0x7f => 2,
+        0x80..=0x9f => 4,
+        _ => c.width().unwrap_or(0),
+    }
+}
+
+// Width of a character rendered at the display column `x` of a line. A hard tab advances to the next tab stop and
+// every character takes the width of the mask character when the text is masked
+pub fn cell_width(c: char, x: usize, tab_len: u8, mask: Option<char>) -> usize {
+    let tab_len = tab_len as usize;
+    match mask {
+        Some(m) => m.width().unwrap_or(0),
+        None if c == '\t' && tab_len > 0 => tab_len - x % tab_len,
+        None => char_width(c),
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct Pos {
     pub row: usize,
@@ -19,3 +55,27 @@ impl Pos {
         Self { row, col, offset }
     }
 }
+
+// Split the text into lines at `\n`. When `normalize` is `true`, `\r\n` and `\r` are also recognized as newline so
+// that no line contains a carriage return
+pub fn split_lines(text: &str, normalize: bool) -> Vec<String> {
+    if !normalize {
+        return text.split('\n').map(String::from).collect();
+    }
+    let mut lines = vec![];
+    let mut start = 0;
+    let mut chars = text.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        if c != '\n' && c != '\r' {
+            continue;
+        }
+        lines.push(text[start..i].to_string());
+        start = i + 1;
+        if c == '\r' && matches!(chars.peek(), Some((_, '\n'))) {
+            chars.next();
+            start += 1;
+        }
+    }
+    lines.push(text[start..].to_string());
+    lines
+}
@@ -1,17 +1,24 @@
+use crate::highlight::DisplayTextBuilder;
 use crate::ratatui::buffer::Buffer;
-use crate::ratatui::layout::Rect;
+use crate::ratatui::layout::{Alignment, Rect};
+use crate::ratatui::style::Style;
 use crate::ratatui::text::{Span, Text};
 use crate::ratatui::widgets::{Paragraph, Widget};
 use crate::textarea::TextArea;
 use crate::util::num_digits;
+use crate::wrap::Wrap;
 #[cfg(feature = "ratatui")]
 use ratatui::text::Line;
 use std::cmp;
-use std::sync::atomic::{AtomicU64, Ordering};
+use std::iter;
+use std::ops::Range;
+use std::sync::atomic::{AtomicU16, AtomicUsize, Ordering};
 #[cfg(feature = "tuirs")]
 use tui::text::Spans as Line;
+use unicode_width::UnicodeWidthChar as _;
+use unicode_width::UnicodeWidthStr as _;
 
-// &mut 'a (u16, u16, u16, u16) is not available since `render` method takes immutable reference of TextArea
+// &mut 'a (usize, usize, u16, u16) is not available since `render` method takes immutable reference of TextArea
 // instance. In the case, the TextArea instance cannot be accessed from any other objects since it is mutablly
 // borrowed.
 //
@@ -19,35 +26,60 @@ use tui::text::Spans as Line;
 // point we stick with using `ratatui::Frame::render_widget` because it is simpler API. Users don't need to
 // manage states of textarea instances separately.
 // https://docs.rs/ratatui/latest/ratatui/terminal/struct.Frame.html#method.render_stateful_widget
+//
+// The top-left position is kept in `usize` so that a viewport beyond the 65535th line is not truncated. The wrap
+// fields keep the state of soft wrapping on the last rendering: the width of display rows (0 when lines are not
+// wrapped), the last line in the viewport and the display row of the top line where the viewport starts.
 #[derive(Default, Debug)]
-pub struct Viewport(AtomicU64);
+pub struct Viewport {
+    row: AtomicUsize,
+    col: AtomicUsize,
+    width: AtomicU16,
+    height: AtomicU16,
+    wrap_width: AtomicU16,
+    row_bottom: AtomicUsize,
+    top_offset: AtomicUsize,
+}
 
 impl Clone for Viewport {
     fn clone(&self) -> Self {
-        let u = self.0.load(Ordering::Relaxed);
-        Viewport(AtomicU64::new(u))
+        let usize = |a: &AtomicUsize| AtomicUsize::new(a.load(Ordering::Relaxed));
+        let u16 = |a: &AtomicU16| AtomicU16::new(a.load(Ordering::Relaxed));
+        Viewport {
+            row: usize(&self.row),
+            col: usize(&self.col),
+            width: u16(&self.width),
+            height: u16(&self.height),
+            wrap_width: u16(&self.wrap_width),
+            row_bottom: usize(&self.row_bottom),
+            top_offset: usize(&self.top_offset),
+        }
     }
 }
 
 impl Viewport {
-    pub fn scroll_top(&self) -> (u16, u16) {
-        let u = self.0.load(Ordering::Relaxed);
-        ((u >> 16) as u16, u as u16)
+    pub fn scroll_top(&self) -> (usize, usize) {
+        let row = self.row.load(Ordering::Relaxed);
+        let col = self.col.load(Ordering::Relaxed);
+        (row, col)
     }
 
-    pub fn rect(&self) -> (u16, u16, u16, u16) {
-        let u = self.0.load(Ordering::Relaxed);
-        let width = (u >> 48) as u16;
-        let height = (u >> 32) as u16;
-        let row = (u >> 16) as u16;
-        let col = u as u16;
+    pub fn rect(&self) -> (usize, usize, u16, u16) {
+        let (row, col) = self.scroll_top();
+        let width = self.width.load(Ordering::Relaxed);
+        let height = self.height.load(Ordering::Relaxed);
         (row, col, width, height)
     }
 
-    pub fn position(&self) -> (u16, u16, u16, u16) {
+    pub fn position(&self) -> (usize, usize, usize, usize) {
         let (row_top, col_top, width, height) = self.rect();
-        let row_bottom = row_top.saturating_add(height).saturating_sub(1);
-        let col_bottom = col_top.saturating_add(width).saturating_sub(1);
+        let (wrap_width, row_bottom, _) = self.wrap_state();
+        if wrap_width > 0 {
+            // All columns are visible when lines are wrapped
+            return (row_top, 0, cmp::max(row_top, row_bottom), usize::MAX);
+        }
+        let row_bottom = row_top.saturating_add(height as usize).saturating_sub(1);
+        let col_bottom = col_top.saturating_add(width as usize).saturating_sub(1);
 
         (
             row_top,
@@ -57,35 +89,132 @@ impl Viewport {
         )
     }
 
-    fn store(&self, row: u16, col: u16, width: u16, height: u16) {
-        // Pack four u16 values into one u64 value
-        let u =
-            ((width as u64) << 48) | ((height as u64) << 32) | ((row as u64) << 16) | col as u64;
-        self.0.store(u, Ordering::Relaxed);
+    fn store(&self, row: usize, col: usize, width: u16, height: u16) {
+        self.row.store(row, Ordering::Relaxed);
+        self.col.store(col, Ordering::Relaxed);
+        self.width.store(width, Ordering::Relaxed);
+        self.height.store(height, Ordering::Relaxed);
+    }
+
+    fn wrap_state(&self) -> (u16, usize, usize) {
+        let width = self.wrap_width.load(Ordering::Relaxed);
+        let row_bottom = self.row_bottom.load(Ordering::Relaxed);
+        let top_offset = self.top_offset.load(Ordering::Relaxed);
+        (width, row_bottom, top_offset)
+    }
+
+    fn store_wrap(&self, width: u16, row_bottom: usize, top_offset: usize) {
+        self.wrap_width.store(width, Ordering::Relaxed);
+        self.row_bottom.store(row_bottom, Ordering::Relaxed);
+        self.top_offset.store(top_offset, Ordering::Relaxed);
+    }
+
+    // Width of display rows of wrapped lines on the last rendering. `None` when lines were not wrapped
+    pub fn wrap_width(&self) -> Option<usize> {
+        let (width, _, _) = self.wrap_state();
+        (width > 0).then_some(width as usize)
+    }
+
+    // Display row of the top line where the viewport starts on the last rendering of wrapped lines
+    pub fn wrap_top(&self) -> usize {
+        let (_, _, top_offset) = self.wrap_state();
+        top_offset
+    }
+
+    // Set the top-left position of the viewport. The display row in the top line is reset when lines are wrapped
+    pub fn set_scroll_top(&mut self, row: usize, col: usize) {
+        *self.row.get_mut() = row;
+        *self.col.get_mut() = col;
+        *self.top_offset.get_mut() = 0;
+    }
+
+    // Set the display row in the top line where the viewport starts when lines are wrapped
+    pub fn set_wrap_top(&mut self, offset: usize) {
+        *self.top_offset.get_mut() = offset;
     }
 
     pub fn scroll(&mut self, rows: i16, cols: i16) {
-        fn apply_scroll(pos: u16, delta: i16) -> u16 {
+        fn apply_scroll(pos: usize, delta: i16) -> usize {
             if delta >= 0 {
-                pos.saturating_add(delta as u16)
+                pos.saturating_add(delta as usize)
             } else {
-                pos.saturating_sub(-delta as u16)
+                pos.saturating_sub(delta.unsigned_abs() as usize)
             }
         }
 
-        let u = self.0.get_mut();
-        let row = apply_scroll((*u >> 16) as u16, rows);
-        let col = apply_scroll(*u as u16, cols);
-        *u = (*u & 0xffff_ffff_0000_0000) | ((row as u64) << 16) | (col as u64);
+        let row = apply_scroll(*self.row.get_mut(), rows);
+        let col = apply_scroll(*self.col.get_mut(), cols);
+        *self.row.get_mut() = row;
+        *self.col.get_mut() = col;
     }
 }
 
+// Put the indicator in the cell. A double-width character on the left cell is cleared since the indicator would be
+// hidden under the second half of it
+fn put_indicator(buf: &mut Buffer, area: Rect, x: u16, y: u16, c: char, style: Style) {
+    if x > area.left() && buf[(x - 1, y)].symbol().width() > 1 {
+        buf[(x - 1, y)].set_char(' ');
+    }
+    buf[(x, y)].set_char(c).set_style(style);
+}
+
+// The next top position of the viewport which keeps `before` rows above the cursor and `after` rows below it visible
+// Drop the first `cols` cells of each line in the text. The cells of a double-width character split by the left edge
+// are replaced with spaces so that the following characters stay in their columns
+fn skip_cells(text: Text<'_>, cols: usize) -> Text<'_> {
+    let lines = text
+        .lines
+        .into_iter()
+        .map(|line| {
+            let mut skip = cols;
+            let mut spans = Vec::with_capacity(line.spans.len());
+            for span in line.spans {
+                if skip == 0 {
+                    spans.push(span);
+                    continue;
+                }
+                let width = span.width();
+                if width <= skip {
+                    skip -= width;
+                    continue;
+                }
+                let mut content = String::with_capacity(span.content.len());
+                let mut dropped = false;
+                for c in span.content.chars() {
+                    let w = c.width().unwrap_or(0);
+                    // Zero-width characters are dropped with the character they are combined with
+                    if skip == 0 && !(dropped && w == 0) {
+                        content.push(c);
+                        dropped = false;
+                    } else if w <= skip {
+                        skip -= w;
+                        dropped = true;
+                    } else {
+                        content.extend(iter::repeat(' ').take(w - skip));
+                        skip = 0;
+                        dropped = true;
+                    }
+                }
+                spans.push(Span::styled(content, span.style));
+            }
+            Line { spans, ..line }
+        })
+        .collect();
+    Text { lines, ..text }
+}
+
 #[inline]
-fn next_scroll_top(prev_top: u16, cursor: u16, len: u16) -> u16 {
-    if cursor < prev_top {
-        cursor
-    } else if prev_top + len <= cursor {
-        cursor + 1 - len
+fn next_scroll_top(
+    prev_top: usize,
+    cursor: usize,
+    len: usize,
+    before: usize,
+    after: usize,
+) -> usize {
+    if cursor < prev_top + before {
+        cursor.saturating_sub(before)
+    } else if prev_top + len <= cursor + after {
+        (cursor + after + 1).saturating_sub(len)
     } else {
         prev_top
     }
@@ -93,14 +222,187 @@ fn next_scroll_top(prev_top: u16, cursor: u16, len: u16) -> u16 {
 
 impl<'a> TextArea<'a> {
     fn text_widget(&'a self, top_row: usize, height: usize) -> Text<'a> {
-        let lines_len = self.lines().len();
-        let lnum_len = num_digits(lines_len);
-        let bottom_row = cmp::min(top_row + height, lines_len);
-        let mut lines = Vec::with_capacity(bottom_row - top_row);
-        for (i, line) in self.lines()[top_row..bottom_row].iter().enumerate() {
-            lines.push(self.line_spans(line.as_str(), top_row + i, lnum_len));
+        Text::from(self.styled_lines(top_row..top_row + height))
+    }
+
+    // Lines wrapped into display rows from the top position which is a pair of a line and a display row in the line.
+    // The top position is decided so that the display row of the cursor is in the viewport. Returns the text, the top
+    // position and the last line in the viewport
+    fn wrapped_text_widget(
+        &'a self,
+        prev_top: (usize, usize),
+        height: usize,
+        wrap: &Wrap,
+    ) -> (Text<'a>, (usize, usize), usize) {
+        let lines = self.lines();
+        let rows_len = |row: usize| wrap.rows(&lines[row]).len();
+
+        // The lines may have been modified since the last rendering
+        let top_row = cmp::min(prev_top.0, lines.len() - 1);
+        let prev_top = (top_row, cmp::min(prev_top.1, rows_len(top_row) - 1));
+
+        // Move the position by display rows. It stops at the start and the end of the text
+        let up = |(mut r, mut i): (usize, usize), n: usize| {
+            for _ in 0..n {
+                if i > 0 {
+                    i -= 1;
+                } else if r > 0 {
+                    r -= 1;
+                    i = rows_len(r) - 1;
+                } else {
+                    break;
+                }
+            }
+            (r, i)
+        };
+        let down = |(mut r, mut i): (usize, usize), n: usize| {
+            for _ in 0..n {
+                if i + 1 < rows_len(r) {
+                    i += 1;
+                } else if r + 1 < lines.len() {
+                    r += 1;
+                    i = 0;
+                } else {
+                    break;
+                }
+            }
+            (r, i)
+        };
+
+        let (row, col) = self.cursor();
+        let cursor = (row, wrap.position(&lines[row], col).0);
+        let margin = self.scroll_margin_in(height as u16);
+        let above = up(cursor, margin);
+        let top = if above < prev_top {
+            above
+        } else {
+            // The topmost position where the margin below the cursor is still in the viewport
+            let top = up(down(cursor, margin), height.saturating_sub(1));
+            cmp::max(top, prev_top)
+        };
+
+        let lnum_len = num_digits(lines.len());
+        let brackets = self.bracket_highlights();
+        let mut text = Vec::with_capacity(height);
+        let (mut row, mut skip) = top;
+        while text.len() < height && row < lines.len() {
+            let line = lines[row].as_str();
+            let xs: Vec<_> = wrap.rows(line).into_iter().map(|(_, x)| x).collect();
+            let rows = self
+                .line_highlighter(line, row, lnum_len, &brackets)
+                .into_wrapped_lines(&xs, &self.wrap_prefix(line, wrap));
+            let take = height - text.len();
+            text.extend(rows.into_iter().skip(skip).take(take));
+            skip = 0;
+            row += 1;
+        }
+        let bottom = cmp::max(top.0, row.saturating_sub(1));
+        (Text::from(text), top, bottom)
+    }
+
+    // Paint the cursor line style across the whole width of the area including the gutter. The spans of the line are
+    // rendered over it so that their styles are merged with the cursor line style
+    fn render_cursor_line_fill(&self, area: Rect, rows: Range<usize>, buf: &mut Buffer) {
+        let end = cmp::min(rows.end, area.height as usize);
+        if rows.start >= end {
+            return;
+        }
+        let row = Rect {
+            y: area.y + rows.start as u16,
+            height: (end - rows.start) as u16,
+            ..area
+        };
+        buf.set_style(row, self.cursor_line_style());
+    }
+
+    // Paint the color columns down the area. The columns are counted from the start of the text excluding the gutter
+    fn render_color_columns(&self, area: Rect, top_col: usize, buf: &mut Buffer) {
+        let gutter = self.gutter_width();
+        for &(col, style) in self.color_columns() {
+            let x = (gutter + col as usize).checked_sub(top_col);
+            if let Some(x) = x.filter(|&x| x < area.width as usize) {
+                let column = Rect {
+                    x: area.x + x as u16,
+                    width: 1,
+                    ..area
+                };
+                buf.set_style(column, style);
+            }
+        }
+    }
+
+    // Render the end-of-buffer character on rows of the area from `rows`, which are beyond the end of the buffer. The
+    // character is put in the first column of the text on the right of the gutter
+    fn render_end_of_buffer(&self, area: Rect, rows: u16, top_col: usize, buf: &mut Buffer) {
+        let (c, style) = match self.end_of_buffer_char() {
+            Some(eob) => eob,
+            None => return,
+        };
+        let x = self.gutter_width().saturating_sub(top_col);
+        if x >= area.width as usize {
+            return;
+        }
+        for y in area.top() + rows..area.bottom() {
+            buf[(area.x + x as u16, y)].set_char(c).set_style(style);
+        }
+    }
+
+    // Render the overflow indicators on rows in the area whose lines continue beyond the left or right edge
+    fn render_overflow_indicators(
+        &self,
+        area: Rect,
+        top_row: usize,
+        top_col: usize,
+        buf: &mut Buffer,
+    ) {
+        let (left, right) = self.overflow_indicators();
+        if left.is_none() && right.is_none() || area.width == 0 {
+            return;
+        }
+        let width = |s: &str| {
+            DisplayTextBuilder::new(self.tab_length(), self.mask_char())
+                .build(s)
+                .width()
+        };
+        let lnum = self.gutter_width();
+        let last_x = area.width as usize - 1;
+        let (cursor_row, cursor_col) = self.cursor();
+        let lines = self.lines().iter().enumerate().skip(top_row);
+        for ((row, line), y) in lines.zip(area.top()..area.bottom()) {
+            let line_width = width(line);
+            // Columns in the area covered by the cursor
+            let cursor = (row == cursor_row).then(|| {
+                let mut offsets = line.char_indices().map(|(i, _)| i).skip(cursor_col);
+                let start = offsets.next().unwrap_or(line.len());
+                let end = offsets.next().unwrap_or(line.len());
+                // A tab under the cursor is as wide as the cells to the next tab stop
+                let x = width(&line[..start]);
+                let w = cmp::max(width(&line[..end]) - x, 1) as isize;
+                let x = (lnum + x) as isize - top_col as isize;
+                (x, x + w)
+            });
+            let covered =
+                |x: usize| cursor.is_some_and(|(s, e)| s <= x as isize && (x as isize) < e);
+
+            if let Some(c) = left {
+                if line_width > 0 && top_col > lnum && !covered(0) {
+                    put_indicator(
+                        buf,
+                        area,
+                        area.left(),
+                        y,
+                        c,
+                        self.overflow_indicator_style(),
+                    );
+                }
+            }
+            if let Some(c) = right {
+                if lnum + line_width > top_col + area.width as usize && !covered(last_x) {
+                    let x = area.left() + last_x as u16;
+                    put_indicator(buf, area, x, y, c, self.overflow_indicator_style());
+                }
+            }
         }
-        Text::from(lines)
     }
 
     fn placeholder_widget(&'a self) -> Text<'a> {
@@ -109,64 +411,132 @@ impl<'a> TextArea<'a> {
         Text::from(Line::from(vec![cursor, text]))
     }
 
-    fn scroll_top_row(&self, prev_top: u16, height: u16) -> u16 {
-        next_scroll_top(prev_top, self.cursor().0 as u16, height)
+    fn scroll_top_row(&self, prev_top: usize, height: u16) -> usize {
+        let row = self.cursor().0;
+        // The margin is not kept beyond the start and the end of the text
+        let margin = self.scroll_margin_in(height);
+        let before = cmp::min(margin, row);
+        let after = cmp::min(margin, self.lines().len() - 1 - row);
+        next_scroll_top(prev_top, row, height as _, before, after)
     }
 
-    fn scroll_top_col(&self, prev_top: u16, width: u16) -> u16 {
-        let mut cursor = self.cursor().1 as u16;
-        // Adjust the cursor position due to the width of line number.
-        if self.line_number_style().is_some() {
-            let lnum = num_digits(self.lines().len()) as u16 + 2; // `+ 2` for margins
-            if cursor <= lnum {
+    fn scroll_top_col(&self, prev_top: usize, width: u16) -> usize {
+        // The display column and the width of the cursor cell. Both cells of a double-width character must be visible
+        let (row, col) = self.cursor();
+        let line = &self.lines()[row];
+        let mut offsets = line.char_indices().map(|(i, _)| i).skip(col);
+        let start = offsets.next().unwrap_or(line.len());
+        let end = offsets.next().unwrap_or(line.len());
+        let cells = |s: &str| {
+            DisplayTextBuilder::new(self.tab_length(), self.mask_char())
+                .build(s)
+                .width()
+        };
+        let x = cells(&line[..start]);
+        let w = cmp::max(cells(&line[..end]) - x, 1);
+
+        let mut cursor = x;
+        // Adjust the cursor position due to the width of signs and line number.
+        let gutter = self.gutter_width();
+        if gutter > 0 {
+            if cursor <= gutter {
                 cursor *= 2; // Smoothly slide the line number into the screen on scrolling left
             } else {
-                cursor += lnum; // The cursor position is shifted by the line number part
+                cursor += gutter; // The cursor position is shifted by the line number part
             };
         }
-        next_scroll_top(prev_top, cursor, width)
+        next_scroll_top(prev_top, cursor, width as _, 0, w - 1)
     }
 }
 
 impl Widget for &TextArea<'_> {
     fn render(self, area: Rect, buf: &mut Buffer) {
-        let Rect { width, height, .. } = if let Some(b) = self.block() {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::trace_span!("render", ?area).entered();
+        // The inner area is computed exactly once. Scrolling, the viewport and the text (including all of its
+        // highlights) are laid out against this same rect so they cannot drift from each other.
+        let inner_area = if let Some(b) = self.block() {
             b.inner(area)
         } else {
             area
         };
+        let Rect { width, height, .. } = inner_area;
 
         let (top_row, top_col) = self.viewport.scroll_top();
-        let top_row = self.scroll_top_row(top_row, height);
-        let top_col = self.scroll_top_col(top_col, width);
+        let (text, top_row, top_col, cursor_rows) = if self.wrap() {
+            let wrap_width = width.saturating_sub(self.gutter_width() as u16);
+            let wrap = self.wrap_layout(wrap_width as _);
+            let (_, _, top_offset) = self.viewport.wrap_state();
+            let (text, (top_row, top_offset), bottom) =
+                self.wrapped_text_widget((top_row, top_offset), height as _, &wrap);
+            self.viewport.store_wrap(wrap_width, bottom, top_offset);
+            // Display rows of the cursor line in the viewport
+            let (row, lines) = (self.cursor().0, self.lines());
+            let above: usize = lines[top_row..row].iter().map(|l| wrap.rows(l).len()).sum();
+            let end = above + wrap.rows(&lines[row]).len() - top_offset;
+            let cursor_rows = above.saturating_sub(top_offset)..end;
+            (text, top_row, 0, cursor_rows) // Wrapped lines are never scrolled horizontally
+        } else {
+            let top_row = self.scroll_top_row(top_row, height);
+            let top_col = self.scroll_top_col(top_col, width);
+            self.viewport.store_wrap(0, 0, 0);
+            let row = self.cursor().0 - top_row;
+            (
+                self.text_widget(top_row, height as _),
+                top_row,
+                top_col,
+                row..row + 1,
+            )
+        };
 
-        let (text, style) = if !self.placeholder.is_empty() && self.is_empty() {
+        let rows = cmp::min(text.height(), height as usize) as u16;
+        let placeholder = !self.placeholder.is_empty() && self.is_empty();
+        let (text, style) = if placeholder {
             (self.placeholder_widget(), self.placeholder_style)
         } else {
-            (self.text_widget(top_row as _, height as _), self.style())
+            (text, self.style())
+        };
+
+        // Lines are scrolled horizontally by cutting their cells so that a double-width character on the left edge is
+        // not shifted into the viewport. Aligned lines are scrolled by the paragraph since the cells depend on the
+        // alignment
+        let left = self.alignment() == Alignment::Left;
+        let text = if top_col != 0 && left {
+            skip_cells(text, top_col)
+        } else {
+            text
         };
 
         // To get fine control over the text color and the surrrounding block they have to be rendered separately
         // see https://github.com/ratatui/ratatui/issues/144
-        let mut text_area = area;
-        let mut inner = Paragraph::new(text)
-            .style(style)
-            .alignment(self.alignment());
+        let mut inner = Paragraph::new(text).alignment(self.alignment());
         if let Some(b) = self.block() {
-            text_area = b.inner(area);
             // ratatui does not need `clone()` call because `Block` implements `WidgetRef` and `&T` implements `Widget`
             // where `T: WidgetRef`. So `b.render` internally calls `b.render_ref` and it doesn't move out `self`.
             #[cfg(feature = "tuirs")]
             let b = b.clone();
             b.render(area, buf)
         }
-        if top_col != 0 {
-            inner = inner.scroll((0, top_col));
+        if top_col != 0 && !left {
+            inner = inner.scroll((0, cmp::min(top_col, u16::MAX as usize) as u16));
         }
 
         // Store scroll top position for rendering on the next tick
         self.viewport.store(top_row, top_col, width, height);
+        trace!(top_row, top_col, width, height, "viewport stored");
+
+        // The base style is applied before the color columns so that the columns are rendered over the base style and
+        // under the text styles
+        buf.set_style(inner_area, style);
+        if self.cursor_line_fill() && !placeholder {
+            self.render_cursor_line_fill(inner_area, cursor_rows, buf);
+        }
+        self.render_color_columns(inner_area, top_col, buf);
+        inner.render(inner_area, buf);
+        self.render_end_of_buffer(inner_area, rows, top_col, buf);
 
-        inner.render(text_area, buf);
+        if !self.wrap() && self.alignment() == Alignment::Left {
+            self.render_overflow_indicators(inner_area, top_row, top_col, buf);
+        }
     }
 }
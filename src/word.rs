@@ -15,13 +15,30 @@ impl CharKind {
             Self::Other
         }
     }
+
+    // Kind of the character in WORD (a sequence of non-whitespace characters like Vim's `W`)
+    fn new_big(c: char) -> Self {
+        if c.is_whitespace() {
+            Self::Space
+        } else {
+            Self::Other
+        }
+    }
 }
 
 pub fn find_word_start_forward(line: &str, start_col: usize) -> Option<usize> {
+    word_start_forward(line, start_col, CharKind::new)
+}
+
+pub fn find_big_word_start_forward(line: &str, start_col: usize) -> Option<usize> {
+    word_start_forward(line, start_col, CharKind::new_big)
+}
+
+fn word_start_forward(line: &str, start_col: usize, kind: fn(char) -> CharKind) -> Option<usize> {
     let mut it = line.chars().enumerate().skip(start_col);
-    let mut prev = CharKind::new(it.next()?.1);
+    let mut prev = kind(it.next()?.1);
     for (col, c) in it {
-        let cur = CharKind::new(c);
+        let cur = kind(c);
         if cur != CharKind::Space && prev != cur {
             return Some(col);
         }
@@ -31,10 +48,22 @@ pub fn find_word_start_forward(line: &str, start_col: usize) -> Option<usize> {
 }
 
 pub fn find_word_exclusive_end_forward(line: &str, start_col: usize) -> Option<usize> {
+    word_exclusive_end_forward(line, start_col, CharKind::new)
+}
+
+pub fn find_big_word_exclusive_end_forward(line: &str, start_col: usize) -> Option<usize> {
+    word_exclusive_end_forward(line, start_col, CharKind::new_big)
+}
+
+fn word_exclusive_end_forward(
+    line: &str,
+    start_col: usize,
+    kind: fn(char) -> CharKind,
+) -> Option<usize> {
     let mut it = line.chars().enumerate().skip(start_col);
-    let mut prev = CharKind::new(it.next()?.1);
+    let mut prev = kind(it.next()?.1);
     for (col, c) in it {
-        let cur = CharKind::new(c);
+        let cur = kind(c);
         if prev != CharKind::Space && prev != cur {
             return Some(col);
         }
@@ -44,11 +73,23 @@ pub fn find_word_exclusive_end_forward(line: &str, start_col: usize) -> Option<u
 }
 
 pub fn find_word_inclusive_end_forward(line: &str, start_col: usize) -> Option<usize> {
+    word_inclusive_end_forward(line, start_col, CharKind::new)
+}
+
+pub fn find_big_word_inclusive_end_forward(line: &str, start_col: usize) -> Option<usize> {
+    word_inclusive_end_forward(line, start_col, CharKind::new_big)
+}
+
+fn word_inclusive_end_forward(
+    line: &str,
+    start_col: usize,
+    kind: fn(char) -> CharKind,
+) -> Option<usize> {
     let mut it = line.chars().enumerate().skip(start_col);
     let (mut last_col, c) = it.next()?;
-    let mut prev = CharKind::new(c);
+    let mut prev = kind(c);
     for (col, c) in it {
-        let cur = CharKind::new(c);
+        let cur = kind(c);
         if prev != CharKind::Space && cur != prev {
             return Some(col.saturating_sub(1));
         }
@@ -62,20 +103,67 @@ pub fn find_word_inclusive_end_forward(line: &str, start_col: usize) -> Option<u
     }
 }
 
+// Column of the last character of the word ending before the column
+pub fn find_word_inclusive_end_backward(line: &str, start_col: usize) -> Option<usize> {
+    word_inclusive_end_backward(line, start_col, CharKind::new)
+}
+
+pub fn find_big_word_inclusive_end_backward(line: &str, start_col: usize) -> Option<usize> {
+    word_inclusive_end_backward(line, start_col, CharKind::new_big)
+}
+
+fn word_inclusive_end_backward(
+    line: &str,
+    start_col: usize,
+    kind: fn(char) -> CharKind,
+) -> Option<usize> {
+    let kinds: Vec<_> = line.chars().map(kind).collect();
+    let end = start_col.min(kinds.len());
+    (0..end)
+        .rev()
+        .find(|&i| kinds[i] != CharKind::Space && kinds.get(i + 1) != Some(&kinds[i]))
+}
+
 pub fn find_word_start_backward(line: &str, start_col: usize) -> Option<usize> {
+    word_start_backward(line, start_col, CharKind::new)
+}
+
+pub fn find_big_word_start_backward(line: &str, start_col: usize) -> Option<usize> {
+    word_start_backward(line, start_col, CharKind::new_big)
+}
+
+fn word_start_backward(line: &str, start_col: usize, kind: fn(char) -> CharKind) -> Option<usize> {
     let idx = line
         .char_indices()
         .nth(start_col)
         .map(|(i, _)| i)
         .unwrap_or(line.len());
     let mut it = line[..idx].chars().rev().enumerate();
-    let mut cur = CharKind::new(it.next()?.1);
+    let mut cur = kind(it.next()?.1);
     for (i, c) in it {
-        let next = CharKind::new(c);
+        let next = kind(c);
         if cur != CharKind::Space && next != cur {
             return Some(start_col - i);
         }
         cur = next;
     }
-    (cur != CharKind::Space).then(|| 0)
+    (cur != CharKind::Space).then_some(0)
+}
+
+// Range of columns of the word containing the character at the column. The end of the range is exclusive
+pub fn find_word_at(line: &str, col: usize) -> Option<(usize, usize)> {
+    let kinds: Vec<_> = line.chars().map(CharKind::new).collect();
+    let kind = *kinds.get(col)?;
+    if kind == CharKind::Space {
+        return None;
+    }
+    let start = kinds[..col]
+        .iter()
+        .rposition(|k| *k != kind)
+        .map_or(0, |i| i + 1);
+    let end = kinds[col..]
+        .iter()
+        .position(|k| *k != kind)
+        .map_or(kinds.len(), |i| col + i);
+    Some((start, end))
 }
@@ -0,0 +1,259 @@
+use crate::util::cell_width;
+use std::iter;
+
+// How lines are wrapped into display rows. `width` is the number of cells of a display row excluding the line
+// number. Lines are not wrapped when it is zero. Continuation rows start with the hanging indent and the wrap
+// indicator which is `indicator` cells wide
+#[derive(Clone, Copy, Debug)]
+pub struct Wrap {
+    width: usize,
+    tab_len: u8,
+    mask: Option<char>,
+    indicator: usize,
+    hanging_indent: bool,
+}
+
+impl Wrap {
+    pub fn new(width: usize, tab_len: u8, mask: Option<char>) -> Self {
+        Self {
+            width,
+            tab_len,
+            mask,
+            indicator: 0,
+            hanging_indent: false,
+        }
+    }
+
+    pub fn continuation(mut self, indicator: usize, hanging_indent: bool) -> Self {
+        self.indicator = indicator;
+        self.hanging_indent = hanging_indent;
+        self
+    }
+
+    // Widths of the hanging indent and the wrap indicator put at the start of continuation rows of the line. They are
+    // dropped when no cell would be left for the text in the row
+    pub fn prefix(&self, line: &str) -> (usize, usize) {
+        let indent = if self.hanging_indent {
+            self.cells(line)
+                .take_while(|&(c, _)| c == ' ' || c == '\t')
+                .map(|(_, w)| w)
+                .sum()
+        } else {
+            0
+        };
+        if indent + self.indicator < self.width {
+            (indent, self.indicator)
+        } else if self.indicator < self.width {
+            (0, self.indicator)
+        } else {
+            (0, 0)
+        }
+    }
+
+    fn prefix_width(&self, line: &str) -> usize {
+        let (indent, indicator) = self.prefix(line);
+        indent + indicator
+    }
+
+    // Characters of the line as rendered with their display widths. Tabs are expanded and the mask is applied in
+    // the same way as `DisplayTextBuilder`
+    fn cells<'a>(&self, line: &'a str) -> impl Iterator<Item = (char, usize)> + 'a {
+        let (tab_len, mask) = (self.tab_len, self.mask);
+        let mut x = 0;
+        line.chars().map(move |c| {
+            let w = cell_width(c, x, tab_len, mask);
+            x += w;
+            (mask.unwrap_or(c), w)
+        })
+    }
+
+    // Start positions of the display rows of the line as pairs of the char index and the display column in the line.
+    // A line is broken after the last whitespace which fits in the row, or at the row width when a word is longer
+    // than a row. One more cell is reserved at the end of the line for the cursor so a line exactly as wide as a row
+    // takes one more row
+    pub fn rows(&self, line: &str) -> Vec<(usize, usize)> {
+        let mut rows = vec![(0, 0)];
+        if self.width == 0 {
+            return rows;
+        }
+        let (mut x, mut row_x) = (0, 0);
+        let mut width = self.width;
+        let mut brk = None;
+        let end = iter::once(('\n', 1));
+        for (i, (c, w)) in self.cells(line).chain(end).enumerate() {
+            while x + w - row_x > width && x > row_x {
+                let (col, bx) = match brk {
+                    Some((col, bx)) if bx > row_x => (col, bx),
+                    _ => (i, x),
+                };
+                rows.push((col, bx));
+                row_x = bx;
+                width = self.width - self.prefix_width(line);
+                brk = None;
+            }
+            x += w;
+            if c != '\n' && c.is_whitespace() {
+                brk = Some((i + 1, x));
+            }
+        }
+        rows
+    }
+
+    // The display row index and the display column in the row where the character at `col` is rendered. The column
+    // includes the prefix of a continuation row
+    pub fn position(&self, line: &str, col: usize) -> (usize, usize) {
+        let rows = self.rows(line);
+        let index = rows.iter().rposition(|&(c, _)| c <= col).unwrap_or(0);
+        let x: usize = self.cells(line).take(col).map(|(_, w)| w).sum();
+        let prefix = if index > 0 {
+            self.prefix_width(line)
+        } else {
+            0
+        };
+        (index, x - rows[index].1 + prefix)
+    }
+
+    // The char index in the display row `index` whose display column in the row is the nearest to `x` without
+    // exceeding it. The position never goes past the end of the row. A column in the prefix of a continuation row
+    // points to the start of the row
+    pub fn col(&self, line: &str, index: usize, x: usize) -> usize {
+        let rows = self.rows(line);
+        let x = if index > 0 {
+            x.saturating_sub(self.prefix_width(line))
+        } else {
+            x
+        };
+        let (start, row_x) = rows[index];
+        let end = match rows.get(index + 1) {
+            Some(&(next, _)) => next - 1,
+            None => line.chars().count(),
+        };
+        let mut cx = 0;
+        for (i, (_, w)) in self.cells(line).enumerate() {
+            if i >= start && (i >= end || cx - row_x + w > x) {
+                return i;
+            }
+            cx += w;
+        }
+        end
+    }
+
+    // Move the position of the display row `(row, index)` in the lines by `n` display rows. It stops at the start and
+    // the end of the lines
+    pub fn step_rows(
+        &self,
+        lines: &[String],
+        pos: (usize, usize),
+        n: usize,
+        up: bool,
+    ) -> (usize, usize) {
+        let (mut row, mut index) = pos;
+        for _ in 0..n {
+            if up {
+                if index > 0 {
+                    index -= 1;
+                } else if row > 0 {
+                    row -= 1;
+                    index = self.rows(&lines[row]).len() - 1;
+                } else {
+                    break;
+                }
+            } else if index + 1 < self.rows(&lines[row]).len() {
+                index += 1;
+            } else if row + 1 < lines.len() {
+                row += 1;
+                index = 0;
+            } else {
+                break;
+            }
+        }
+        (row, index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rows() {
+        for (line, width, want) in [
+            ("", 4, vec![(0, 0)]),
+            ("abc", 4, vec![(0, 0)]),
+            ("abcd", 4, vec![(0, 0), (4, 4)]),
+            ("abcdefghij", 4, vec![(0, 0), (4, 4), (8, 8)]),
+            ("ab cd ef", 6, vec![(0, 0), (6, 6)]),
+            ("hello world", 8, vec![(0, 0), (6, 6)]),
+            ("a\tb", 4, vec![(0, 0), (2, 4)]),
+            ("あいう", 4, vec![(0, 0), (2, 4)]),
+            ("abc", 0, vec![(0, 0)]),
+        ] {
+            let wrap = Wrap::new(width, 4, None);
+            assert_eq!(wrap.rows(line), want, "{:?} width={}", line, width);
+        }
+
+        // Masked text is broken at the row width since whitespaces are hidden
+        let wrap = Wrap::new(4, 4, Some('*'));
+        assert_eq!(wrap.rows("ab cd"), vec![(0, 0), (4, 4)]);
+    }
+
+    #[test]
+    fn position_and_col() {
+        let wrap = Wrap::new(6, 4, None);
+        let line = "hello world foo";
+        assert_eq!(wrap.rows(line), vec![(0, 0), (6, 6), (12, 12)]);
+        for (col, want) in [
+            (0, (0, 0)),
+            (5, (0, 5)),
+            (6, (1, 0)),
+            (8, (1, 2)),
+            (15, (2, 3)),
+        ] {
+            assert_eq!(wrap.position(line, col), want, "col={}", col);
+        }
+        for (index, x, want) in [
+            (0, 0, 0),
+            (0, 3, 3),
+            (0, 9, 5),
+            (1, 2, 8),
+            (1, 9, 11),
+            (2, 9, 15),
+        ] {
+            assert_eq!(wrap.col(line, index, x), want, "index={} x={}", index, x);
+        }
+    }
+
+    #[test]
+    fn continuation_prefix() {
+        // The indicator takes 2 cells and the hanging indent takes 2 cells on continuation rows
+        let wrap = Wrap::new(8, 4, None).continuation(2, true);
+        let line = "  abc def ghi";
+        assert_eq!(wrap.prefix(line), (2, 2));
+        assert_eq!(wrap.rows(line), vec![(0, 0), (6, 6), (10, 10)]);
+        assert_eq!(wrap.position(line, 7), (1, 5));
+        assert_eq!(wrap.col(line, 1, 5), 7);
+        assert_eq!(wrap.col(line, 1, 0), 6);
+
+        // The hanging indent is dropped when no cell is left for the text
+        let wrap = Wrap::new(4, 4, None).continuation(2, true);
+        assert_eq!(wrap.prefix("  abcdef"), (0, 2));
+        let wrap = Wrap::new(2, 4, None).continuation(2, true);
+        assert_eq!(wrap.prefix("  abcdef"), (0, 0));
+    }
+
+    #[test]
+    fn step_rows() {
+        let wrap = Wrap::new(4, 4, None);
+        let lines = ["abcdefgh".to_string(), "".to_string(), "ijkl".to_string()];
+        for (pos, n, up, want) in [
+            ((0, 0), 1, false, (0, 1)),
+            ((0, 1), 2, false, (1, 0)),
+            ((1, 0), 9, false, (2, 1)),
+            ((2, 0), 2, true, (0, 2)),
+            ((0, 1), 9, true, (0, 0)),
+        ] {
+            let got = wrap.step_rows(&lines, pos, n, up);
+            assert_eq!(got, want, "pos={:?} n={} up={}", pos, n, up);
+        }
+    }
+}
@@ -0,0 +1,21 @@
+#[cfg(feature = "arbitrary")]
+use arbitrary::Arbitrary;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Kind of the yanked text. See [`crate::TextArea::yank_kind`].
+///
+/// This type is marked as `#[non_exhaustive]` since more variations may be supported in the future.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum YankKind {
+    /// Character-wise text. [`crate::TextArea::paste`] inserts the text at the cursor position.
+    Char,
+    /// Line-wise text yanked from a line-wise selection. [`crate::TextArea::paste`] inserts the text as complete
+    /// lines.
+    Line,
+    /// Rectangular text yanked from a block selection. [`crate::TextArea::paste`] inserts the text as a block.
+    Block,
+}
@@ -1,6 +1,6 @@
 use tui_textarea::{CursorMove, TextArea};
 
-const BOTTOM_RIGHT: CursorMove = CursorMove::Jump(u16::MAX, u16::MAX);
+const BOTTOM_RIGHT: CursorMove = CursorMove::Jump(usize::MAX, usize::MAX);
 
 #[test]
 fn empty_textarea() {
@@ -13,16 +13,29 @@ fn empty_textarea() {
         Up,
         Down,
         Head,
+        FirstNonWhitespace,
         End,
         Top,
         Bottom,
         WordForward,
         WordEnd,
         WordBack,
+        WordEndBack,
+        WORDForward,
+        WORDEnd,
+        WORDBack,
+        WORDEndBack,
         ParagraphForward,
         ParagraphBack,
+        MatchingBracket,
+        FindCharForward('a'),
+        FindCharBack('a'),
+        TillCharForward('a'),
+        TillCharBack('a'),
+        RepeatFind,
+        RepeatFindReverse,
         Jump(0, 0),
-        Jump(u16::MAX, u16::MAX),
+        Jump(usize::MAX, usize::MAX),
     ] {
         t.move_cursor(m);
         assert_eq!(t.cursor(), (0, 0), "{:?}", m);
@@ -117,7 +130,7 @@ fn up() {
         for col in 0..=3 {
             let mut row = 2;
 
-            t.move_cursor(CursorMove::Jump(2, col as u16));
+            t.move_cursor(CursorMove::Jump(2, col));
             assert_eq!(t.cursor(), (row, col), "{:?}", t.lines());
 
             while row > 0 {
@@ -151,7 +164,7 @@ fn down() {
         for col in 0..=3 {
             let mut row = 0;
 
-            t.move_cursor(CursorMove::Jump(0, col as u16));
+            t.move_cursor(CursorMove::Jump(0, col));
             assert_eq!(t.cursor(), (row, col), "{:?}", t.lines());
 
             while row < 2 {
@@ -176,6 +189,56 @@ fn down_trim() {
     }
 }
 
+#[test]
+fn sticky_column() {
+    use CursorMove::*;
+
+    let mut t = TextArea::from(["a long line", "x", "another long line"]);
+    t.move_cursor(Jump(0, 7));
+
+    for (m, expected) in [(Down, (1, 1)), (Down, (2, 7)), (Up, (1, 1)), (Up, (0, 7))] {
+        t.move_cursor(m);
+        assert_eq!(t.cursor(), expected, "{:?}", m);
+    }
+
+    // Horizontal move resets the desired column
+    t.move_cursor(Down);
+    t.move_cursor(Back);
+    t.move_cursor(Down);
+    assert_eq!(t.cursor(), (2, 0));
+
+    // Edit resets the desired column
+    t.move_cursor(Jump(0, 7));
+    t.move_cursor(Down);
+    t.insert_char('y');
+    t.move_cursor(Down);
+    assert_eq!(t.cursor(), (2, 2));
+
+    // Paragraph moves keep the desired column
+    let mut t = TextArea::from(["abc def", "", "ghi jkl"]);
+    t.move_cursor(Jump(0, 5));
+    t.move_cursor(ParagraphForward);
+    assert_eq!(t.cursor(), (1, 0));
+    t.move_cursor(Down);
+    assert_eq!(t.cursor(), (2, 5));
+
+    // Desired column is kept in display width
+    let mut t = TextArea::from(["\tabc", "", "12345678", "あいうえお"]);
+    t.set_tab_length(4);
+    t.move_cursor(Jump(0, 2));
+    for (m, expected) in [
+        (Down, (1, 0)),
+        (Down, (2, 5)),
+        (Down, (3, 2)),
+        (Up, (2, 5)),
+        (Up, (1, 0)),
+        (Up, (0, 2)),
+    ] {
+        t.move_cursor(m);
+        assert_eq!(t.cursor(), expected, "{:?}", m);
+    }
+}
+
 #[test]
 fn head() {
     for text in [["efg", "h", ""], ["あいう", "👪", ""]] {
@@ -183,7 +246,7 @@ fn head() {
         for row in 0..t.lines().len() {
             let len = t.lines()[row].len();
             for col in [0, len / 2, len] {
-                t.move_cursor(CursorMove::Jump(row as u16, col as u16));
+                t.move_cursor(CursorMove::Jump(row, col));
                 t.move_cursor(CursorMove::Head);
                 assert_eq!(t.cursor(), (row, 0), "{:?}", t.lines());
             }
@@ -191,6 +254,24 @@ fn head() {
     }
 }
 
+#[test]
+fn first_non_whitespace() {
+    for (line, col, want) in [
+        ("abc", 2, 0),
+        ("  abc", 0, 2),
+        ("  abc", 4, 2),
+        ("\t a", 3, 2),
+        ("   ", 0, 3),
+        ("", 0, 0),
+        ("　あ", 0, 1),
+    ] {
+        let mut t = TextArea::from([line]);
+        t.move_cursor(CursorMove::Jump(0, col));
+        t.move_cursor(CursorMove::FirstNonWhitespace);
+        assert_eq!(t.cursor(), (0, want), "{:?} {}", line, col);
+    }
+}
+
 #[test]
 fn end() {
     for text in [["efg", "h", ""], ["あいう", "👪", ""]] {
@@ -203,7 +284,7 @@ fn end() {
                 _ => unreachable!(),
             };
             for col in [0, len / 2, len] {
-                t.move_cursor(CursorMove::Jump(row as u16, col as u16));
+                t.move_cursor(CursorMove::Jump(row, col));
                 t.move_cursor(CursorMove::End);
                 assert_eq!(t.cursor(), (row, len), "{:?}", t.lines());
             }
@@ -220,7 +301,7 @@ fn top() {
             for col in 0..=3 {
                 t.move_cursor(CursorMove::Jump(row, col));
                 t.move_cursor(CursorMove::Top);
-                assert_eq!(t.cursor(), (0, col as usize), "{:?}", t.lines());
+                assert_eq!(t.cursor(), (0, col), "{:?}", t.lines());
             }
         }
     }
@@ -235,7 +316,7 @@ fn top_trim() {
         &["", "犬"][..],
     ] {
         let mut t: TextArea = lines.iter().cloned().collect();
-        t.move_cursor(CursorMove::Jump(u16::MAX, u16::MAX));
+        t.move_cursor(CursorMove::Jump(usize::MAX, usize::MAX));
         t.move_cursor(CursorMove::Top);
         let col = t.lines()[0].chars().count();
         assert_eq!(t.cursor(), (0, col), "{:?}", t.lines());
@@ -251,7 +332,7 @@ fn bottom() {
             for col in 0..=3 {
                 t.move_cursor(CursorMove::Jump(row, col));
                 t.move_cursor(CursorMove::Bottom);
-                assert_eq!(t.cursor(), (2, col as usize), "{:?}", t.lines());
+                assert_eq!(t.cursor(), (2, col), "{:?}", t.lines());
             }
         }
     }
@@ -266,7 +347,7 @@ fn bottom_trim() {
         &["犬", ""][..],
     ] {
         let mut t: TextArea = lines.iter().cloned().collect();
-        t.move_cursor(CursorMove::Jump(0, u16::MAX));
+        t.move_cursor(CursorMove::Jump(0, usize::MAX));
         t.move_cursor(CursorMove::Bottom);
         let col = t.lines().last().unwrap().chars().count();
         assert_eq!(t.cursor(), (t.lines().len() - 1, col), "{:?}", t.lines());
@@ -326,3 +407,464 @@ fn word_end() {
         }
     }
 }
+
+#[test]
+fn word_end_back() {
+    for (lines, positions) in [
+        (
+            &[
+                "aaa !!! bbb", // Consecutive punctuations are a word
+            ][..],
+            &[(0, 10), (0, 6), (0, 2), (0, 0)][..],
+        ),
+        (
+            &[
+                "aaa!!!bbb", // Word boundaries without spaces
+            ][..],
+            &[(0, 8), (0, 5), (0, 2), (0, 0)][..],
+        ),
+        (
+            &[
+                "aaa", "", "", "bbb", // Go across multiple empty lines
+            ][..],
+            &[(3, 2), (0, 2)][..],
+        ),
+        (
+            &[
+                "aaa", "   ", "   ", "bbb", // Go across multiple blank lines
+            ][..],
+            &[(3, 2), (0, 2)][..],
+        ),
+        (
+            &[
+                "aaa   ",
+                "   bbb   ", // Ignore the spaces at the head and the end of line
+            ][..],
+            &[(1, 5), (0, 2), (0, 0)][..],
+        ),
+        (
+            &[
+                "!aa", "b", // Accept the end of line and the head of line
+            ][..],
+            &[(1, 0), (0, 2), (0, 0)][..],
+        ),
+    ] {
+        let mut t: TextArea = lines.iter().cloned().collect();
+        t.move_cursor(BOTTOM_RIGHT);
+        for pos in positions {
+            t.move_cursor(CursorMove::WordEndBack);
+            assert_eq!(t.cursor(), *pos, "{:?}", t.lines());
+        }
+    }
+
+    // Cursor in the middle of word or at the end of word
+    for col in [4, 5, 6] {
+        let mut t = TextArea::from(["aaa bbb"]);
+        t.move_cursor(CursorMove::Jump(0, col));
+        t.move_cursor(CursorMove::WordEndBack);
+        assert_eq!(t.cursor(), (0, 2), "col={}", col);
+    }
+}
+
+#[test]
+fn big_word_end_back() {
+    let mut t = TextArea::from(["foo.bar ()", "", "  baz!  "]);
+    t.move_cursor(BOTTOM_RIGHT);
+    for pos in [(2, 5), (0, 9), (0, 6), (0, 0)] {
+        t.move_cursor(CursorMove::WORDEndBack);
+        assert_eq!(t.cursor(), pos);
+    }
+}
+
+#[test]
+fn big_word_forward() {
+    for (lines, positions) in [
+        (
+            &[
+                "foo.bar().baz qux", // Consecutive punctuations are a part of WORD
+            ][..],
+            &[(0, 14), (0, 17)][..],
+        ),
+        (
+            &[
+                "  foo.bar  ",
+                "baz", // Spaces at the head and the end of line
+            ][..],
+            &[(0, 2), (1, 0), (1, 3)][..],
+        ),
+        (
+            &[
+                "a", "", "b", // Stop at empty lines
+            ][..],
+            &[(1, 0), (2, 0), (2, 1)][..],
+        ),
+    ] {
+        let mut t: TextArea = lines.iter().cloned().collect();
+        for pos in positions {
+            t.move_cursor(CursorMove::WORDForward);
+            assert_eq!(t.cursor(), *pos, "{:?}", t.lines());
+        }
+    }
+}
+
+#[test]
+fn big_word_end() {
+    for (lines, positions) in [
+        (
+            &[
+                "foo.bar().baz qux", // Consecutive punctuations are a part of WORD
+            ][..],
+            &[(0, 12), (0, 16)][..],
+        ),
+        (
+            &[
+                "foo ",
+                "",
+                "  (bar)!!", // Go across empty lines and spaces at the head of line
+            ][..],
+            &[(0, 2), (2, 8)][..],
+        ),
+    ] {
+        let mut t: TextArea = lines.iter().cloned().collect();
+        for pos in positions {
+            t.move_cursor(CursorMove::WORDEnd);
+            assert_eq!(t.cursor(), *pos, "{:?}", t.lines());
+        }
+    }
+}
+
+#[test]
+fn big_word_back() {
+    for (lines, positions) in [
+        (
+            &[
+                "foo.bar().baz qux", // Consecutive punctuations are a part of WORD
+            ][..],
+            &[(0, 14), (0, 0)][..],
+        ),
+        (
+            &[
+                "foo.bar",
+                "  baz.qux  ", // Spaces at the head and the end of line
+            ][..],
+            &[(1, 2), (0, 7), (0, 0)][..],
+        ),
+    ] {
+        let mut t: TextArea = lines.iter().cloned().collect();
+        t.move_cursor(BOTTOM_RIGHT);
+        for pos in positions {
+            t.move_cursor(CursorMove::WORDBack);
+            assert_eq!(t.cursor(), *pos, "{:?}", t.lines());
+        }
+    }
+}
+
+#[test]
+fn paragraph_forward() {
+    for (lines, positions) in [
+        (
+            &[
+                "aaa", "", "bbb", "ccc", "", "ddd", // Stop at blank lines
+            ][..],
+            &[(1, 0), (4, 0), (5, 3), (5, 3)][..],
+        ),
+        (
+            &[
+                "aaa", "", "  ", "\t", "bbb", "", // Consecutive blank lines are one boundary
+            ][..],
+            &[(1, 0), (5, 0), (5, 0)][..],
+        ),
+        (
+            &[
+                "", "", "aaa", "bbb", // Skip blank lines at the cursor
+            ][..],
+            &[(3, 3)][..],
+        ),
+    ] {
+        let mut t: TextArea = lines.iter().cloned().collect();
+        for pos in positions {
+            t.move_cursor(CursorMove::ParagraphForward);
+            assert_eq!(t.cursor(), *pos, "{:?}", t.lines());
+        }
+    }
+}
+
+#[test]
+fn paragraph_back() {
+    for (lines, positions) in [
+        (
+            &[
+                "aaa", "", "bbb", "ccc", "", "ddd", // Stop at blank lines
+            ][..],
+            &[(4, 0), (1, 0), (0, 0), (0, 0)][..],
+        ),
+        (
+            &[
+                "", "aaa", "", "  ", "\t", "bbb", // Consecutive blank lines are one boundary
+            ][..],
+            &[(4, 0), (0, 0)][..],
+        ),
+        (
+            &[
+                "aaa", "bbb", "", "", // Skip blank lines at the cursor
+            ][..],
+            &[(0, 0)][..],
+        ),
+    ] {
+        let mut t: TextArea = lines.iter().cloned().collect();
+        t.move_cursor(BOTTOM_RIGHT);
+        for pos in positions {
+            t.move_cursor(CursorMove::ParagraphBack);
+            assert_eq!(t.cursor(), *pos, "{:?}", t.lines());
+        }
+    }
+}
+
+#[test]
+fn sentence_forward_back() {
+    for (lines, positions) in [
+        (
+            &[
+                "Hi! This is a test.",
+                "(Really.) It works?",
+                "Yes", // Sentence without punctuation ends at blank line
+                "",
+                "End. Done",
+            ][..],
+            &[(0, 4), (1, 0), (1, 10), (2, 0), (4, 0), (4, 5)][..],
+        ),
+        (
+            &[
+                "x.y z. w", // Punctuation not followed by whitespace does not end a sentence
+            ][..],
+            &[(0, 7)][..],
+        ),
+    ] {
+        let mut t: TextArea = lines.iter().cloned().collect();
+        for pos in positions {
+            t.move_cursor(CursorMove::SentenceForward);
+            assert_eq!(t.cursor(), *pos, "{:?}", t.lines());
+        }
+        // Stop at the end of the last line
+        let end = (lines.len() - 1, lines[lines.len() - 1].chars().count());
+        for _ in 0..2 {
+            t.move_cursor(CursorMove::SentenceForward);
+            assert_eq!(t.cursor(), end, "{:?}", t.lines());
+        }
+
+        for pos in positions.iter().rev() {
+            t.move_cursor(CursorMove::SentenceBack);
+            assert_eq!(t.cursor(), *pos, "{:?}", t.lines());
+        }
+        // Stop at the head of the first line
+        for _ in 0..2 {
+            t.move_cursor(CursorMove::SentenceBack);
+            assert_eq!(t.cursor(), (0, 0), "{:?}", t.lines());
+        }
+    }
+}
+
+#[test]
+fn matching_bracket() {
+    for (lines, start, want) in [
+        (&["(a)"][..], (0, 0), (0, 2)),
+        (&["(a)"][..], (0, 2), (0, 0)),
+        // Nested brackets
+        (&["((a) [b] {c})"][..], (0, 0), (0, 12)),
+        (&["((a) [b] {c})"][..], (0, 12), (0, 0)),
+        (&["([a(b)c])"][..], (0, 1), (0, 7)),
+        (&["{(})"][..], (0, 0), (0, 2)),
+        // Search the first bracket after the cursor
+        (&["foo(bar)"][..], (0, 0), (0, 7)),
+        (&["a) (b)"][..], (0, 2), (0, 5)),
+        // Across lines
+        (&["fn f() {", "    (x)", "}"][..], (0, 7), (2, 0)),
+        (&["fn f() {", "    (x)", "}"][..], (2, 0), (0, 7)),
+        (&["🐶[", "🐱", "]🐭"][..], (0, 0), (2, 0)),
+        (&["🐶[", "🐱", "]🐭"][..], (2, 0), (0, 1)),
+        // No matching bracket
+        (&["(a", "b"][..], (0, 0), (0, 0)),
+        (&["a)"][..], (0, 0), (0, 0)),
+        (&["a) (b)"][..], (0, 0), (0, 0)),
+        (&["abc"][..], (0, 1), (0, 1)),
+        (&["(a) b"][..], (0, 3), (0, 3)),
+    ] {
+        let mut t: TextArea = lines.iter().cloned().collect();
+        t.move_cursor(CursorMove::Jump(start.0, start.1));
+        t.move_cursor(CursorMove::MatchingBracket);
+        assert_eq!(t.cursor(), want, "{:?} {:?}", lines, start);
+    }
+}
+
+#[test]
+fn find_char() {
+    use CursorMove::*;
+
+    for (line, col, m, want) in [
+        ("a,b,c", 0, FindCharForward(','), 1),
+        ("a,b,c", 1, FindCharForward(','), 3), // Not accept the character under the cursor
+        ("a,b,c", 3, FindCharForward(','), 3), // Not found
+        ("a,b,c", 5, FindCharForward(','), 5),
+        ("a,b,c", 5, FindCharBack(','), 3),
+        ("a,b,c", 3, FindCharBack(','), 1),
+        ("a,b,c", 1, FindCharBack(','), 1),
+        ("a,b,c", 0, TillCharForward('c'), 3),
+        ("a,b,c", 0, TillCharForward(','), 0), // Character next to the cursor
+        ("a,b,c", 0, TillCharForward('x'), 0),
+        ("a,b,c", 5, TillCharBack('a'), 1),
+        ("a,b,c", 4, TillCharBack(','), 4), // Character before the cursor
+        ("a,b,c", 0, TillCharBack('a'), 0),
+        // Multi-byte characters
+        ("🐶あ🐱あ", 0, FindCharForward('あ'), 1),
+        ("🐶あ🐱あ", 1, FindCharForward('あ'), 3),
+        ("🐶あ🐱あ", 4, FindCharBack('🐶'), 0),
+        ("🐶あ🐱あ", 0, TillCharForward('🐱'), 1),
+        ("🐶あ🐱あ", 4, TillCharBack('🐱'), 3),
+    ] {
+        let mut t = TextArea::from([line]);
+        t.move_cursor(CursorMove::Jump(0, col));
+        t.move_cursor(m);
+        assert_eq!(t.cursor(), (0, want), "{:?} {} {:?}", line, col, m);
+    }
+
+    // Only the current line is searched
+    let mut t = TextArea::from(["a", "b"]);
+    t.move_cursor(FindCharForward('b'));
+    assert_eq!(t.cursor(), (0, 0));
+}
+
+#[test]
+fn repeat_find() {
+    use CursorMove::*;
+
+    let mut t = TextArea::from(["a,b,c,d", "e,f"]);
+    assert_eq!(t.last_find(), None);
+    t.move_cursor(RepeatFind);
+    assert_eq!(t.cursor(), (0, 0));
+
+    t.move_cursor(FindCharForward(','));
+    assert_eq!(t.cursor(), (0, 1));
+    t.move_cursor(RepeatFind);
+    assert_eq!(t.cursor(), (0, 3));
+    t.move_cursor(RepeatFindReverse);
+    assert_eq!(t.cursor(), (0, 1));
+    assert_eq!(t.last_find(), Some(FindCharForward(',')));
+
+    // Repeating till moves skips the character next to the cursor
+    t.move_cursor(TillCharForward(','));
+    assert_eq!(t.cursor(), (0, 2));
+    t.move_cursor(RepeatFind);
+    assert_eq!(t.cursor(), (0, 4));
+    t.move_cursor(RepeatFindReverse);
+    assert_eq!(t.cursor(), (0, 2));
+    t.move_cursor(RepeatFindReverse);
+    assert_eq!(t.cursor(), (0, 2));
+
+    // The last find move is kept across lines
+    t.move_cursor(Down);
+    t.move_cursor(Head);
+    t.move_cursor(RepeatFind);
+    assert_eq!(t.cursor(), (1, 0));
+    t.move_cursor(FindCharBack(','));
+    t.move_cursor(End);
+    t.move_cursor(RepeatFind);
+    assert_eq!(t.cursor(), (1, 1));
+    t.move_cursor(RepeatFindReverse);
+    assert_eq!(t.cursor(), (1, 1));
+}
+
+#[test]
+fn jump_beyond_u16() {
+    let mut t: TextArea = (0..70000).map(|i| format!("{:05}", i)).collect();
+
+    // Rows beyond `u16::MAX` can be addressed
+    t.move_cursor(CursorMove::Jump(69999, 3));
+    assert_eq!(t.cursor(), (69999, 3));
+    t.move_cursor(CursorMove::Jump(65535, 3));
+    assert_eq!(t.cursor(), (65535, 3));
+
+    // Position is clamped to the text
+    for (row, col, want) in [
+        (70000, 0, (69999, 0)),
+        (0, 6, (0, 5)),
+        (usize::MAX, usize::MAX, (69999, 5)),
+    ] {
+        t.move_cursor(CursorMove::Jump(row, col));
+        assert_eq!(t.cursor(), want, "{:?}", (row, col));
+    }
+
+    // Select all text beyond `u16::MAX` rows
+    t.select_all();
+    assert_eq!(t.selection_range(), Some(((0, 0), (69999, 5))));
+}
+
+#[test]
+fn move_cursor_n_same_as_repeat() {
+    use CursorMove::*;
+
+    // Simple xorshift PRNG to generate random buffers and counts without any dependency
+    let mut seed = 0x2545f4914f6cdd1du64;
+    let mut rand = move |n: usize| {
+        seed ^= seed << 13;
+        seed ^= seed >> 7;
+        seed ^= seed << 17;
+        (seed % n as u64) as usize
+    };
+
+    let pieces = [
+        "abc", "d", " ", "  ", "\t", ".", "? ", "(", ")", "あい", "🐶",
+    ];
+    let moves = [
+        Forward,
+        Back,
+        Up,
+        Down,
+        Head,
+        End,
+        Top,
+        Bottom,
+        WordForward,
+        WordEnd,
+        WordBack,
+        WordEndBack,
+        WORDForward,
+        WORDEnd,
+        WORDBack,
+        ParagraphForward,
+        ParagraphBack,
+        SentenceForward,
+        SentenceBack,
+        FindCharForward('d'),
+        TillCharBack('a'),
+        RepeatFind,
+        RepeatFindReverse,
+        MatchingBracket,
+        FirstNonWhitespace,
+    ];
+
+    for _ in 0..200 {
+        let lines: Vec<String> = (0..rand(8) + 1)
+            .map(|_| (0..rand(6)).map(|_| pieces[rand(pieces.len())]).collect())
+            .collect();
+        let mut once = TextArea::new(lines.clone());
+        let mut repeat = TextArea::new(lines);
+
+        for _ in 0..10 {
+            let m = moves[rand(moves.len())];
+            let n = rand(12);
+            once.move_cursor_n(m, n);
+            for _ in 0..n {
+                repeat.move_cursor(m);
+            }
+            assert_eq!(
+                once.cursor(),
+                repeat.cursor(),
+                "{:?} x {} in {:?}",
+                m,
+                n,
+                once.lines(),
+            );
+            assert_eq!(once.jumps(), repeat.jumps());
+        }
+    }
+}
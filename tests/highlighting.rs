@@ -0,0 +1,50 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use syntect::parsing::SyntaxSet;
+use tui_textarea::SyntaxHighlighter;
+
+#[test]
+fn test_syntax_set_loaded_lazily() {
+    static LOADS: AtomicUsize = AtomicUsize::new(0);
+    fn loader() -> SyntaxSet {
+        LOADS.fetch_add(1, Ordering::SeqCst);
+        SyntaxSet::load_defaults_newlines()
+    }
+
+    let h = SyntaxHighlighter::with_syntax_set_loader(loader);
+    let cloned = h.clone();
+    assert!(!h.is_syntax_set_loaded());
+    assert_eq!(LOADS.load(Ordering::SeqCst), 0);
+
+    assert!(h.find_syntax_by_extension("rs").is_some());
+    assert!(h.is_syntax_set_loaded());
+    assert!(cloned.is_syntax_set_loaded());
+    assert!(cloned.find_syntax_by_name("Rust").is_some());
+    assert_eq!(LOADS.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_syntax_set_from_sets_is_loaded() {
+    let h = SyntaxHighlighter::from_sets(SyntaxSet::load_defaults_newlines(), Default::default());
+    assert!(h.is_syntax_set_loaded());
+    assert!(h.find_syntax_by_extension("py").is_some());
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_syntax_set_field_is_loaded_on_access() {
+    let h = SyntaxHighlighter::new();
+    assert!(!h.syntax_set.is_loaded());
+    let syntax_set: &SyntaxSet = &h.syntax_set;
+    assert!(syntax_set.find_syntax_by_name("Rust").is_some());
+    assert!(h.is_syntax_set_loaded());
+}
+
+#[cfg(feature = "extra-syntaxes")]
+#[test]
+fn test_extra_syntaxes() {
+    let h = SyntaxHighlighter::new();
+    for ext in ["toml", "ts", "zig"] {
+        assert!(h.find_syntax_by_extension(ext).is_some(), "{}", ext);
+    }
+    assert!(h.find_syntax_by_name("Dockerfile").is_some());
+}
@@ -1,6 +1,6 @@
 #![cfg(feature = "search")]
 
-use tui_textarea::{CursorMove, TextArea};
+use tui_textarea::{CursorMove, SearchCase, SearchResult, SearchScope, TextArea};
 
 #[test]
 fn search_lines_forward() {
@@ -48,6 +48,109 @@ fn search_lines_backward() {
     }
 }
 
+#[test]
+fn search_forward_wrapped() {
+    let mut textarea = TextArea::from(["foo", "bar", "foo"]);
+    textarea.move_cursor(CursorMove::Jump(1, 0));
+    textarea.set_search_pattern("foo").unwrap();
+
+    let found = |wrapped| SearchResult::Found { wrapped };
+    let expected = [
+        (found(false), (2, 0)),
+        (found(true), (0, 0)),
+        (found(false), (2, 0)),
+    ];
+    for (i, (result, pos)) in expected.into_iter().enumerate() {
+        assert_eq!(textarea.search_forward_ex(false, true), result, "{i}");
+        assert_eq!(textarea.cursor(), pos, "{i}");
+    }
+
+    // Without wrap, search stops at the bottom of buffer
+    assert_eq!(
+        textarea.search_forward_ex(false, false),
+        SearchResult::NotFound
+    );
+    assert_eq!(textarea.cursor(), (2, 0));
+
+    // Match at cursor is returned without wrapping
+    assert_eq!(textarea.search_forward_ex(true, false), found(false));
+    assert_eq!(textarea.cursor(), (2, 0));
+
+    textarea.set_search_pattern("baz").unwrap();
+    assert_eq!(
+        textarea.search_forward_ex(false, true),
+        SearchResult::NotFound
+    );
+}
+
+#[test]
+fn search_back_wrapped() {
+    let mut textarea = TextArea::from(["foo", "bar", "foo"]);
+    textarea.move_cursor(CursorMove::Jump(1, 0));
+    textarea.set_search_pattern("foo").unwrap();
+
+    let found = |wrapped| SearchResult::Found { wrapped };
+    let expected = [
+        (found(false), (0, 0)),
+        (found(true), (2, 0)),
+        (found(false), (0, 0)),
+    ];
+    for (i, (result, pos)) in expected.into_iter().enumerate() {
+        assert_eq!(textarea.search_back_ex(false, true), result, "{i}");
+        assert_eq!(textarea.cursor(), pos, "{i}");
+    }
+
+    assert_eq!(
+        textarea.search_back_ex(false, false),
+        SearchResult::NotFound
+    );
+    assert_eq!(textarea.cursor(), (0, 0));
+}
+
+#[test]
+fn search_repeat_advances_from_match_at_cursor() {
+    // Repeated searches ignoring the match at cursor must move to the next match, even for overlapping matches
+    let mut textarea = TextArea::from(["aaa a"]);
+    textarea.set_search_pattern("aa").unwrap();
+    assert_eq!(
+        textarea.search_forward_ex(true, true),
+        SearchResult::Found { wrapped: false },
+    );
+    assert_eq!(textarea.cursor(), (0, 0));
+    for (pos, wrapped) in [((0, 1), false), ((0, 0), true)] {
+        assert_eq!(
+            textarea.search_forward_ex(false, true),
+            SearchResult::Found { wrapped },
+        );
+        assert_eq!(textarea.cursor(), pos);
+    }
+
+    // Only match is at cursor
+    let mut textarea = TextArea::from(["foo"]);
+    textarea.set_search_pattern("foo").unwrap();
+    assert_eq!(
+        textarea.search_forward_ex(false, true),
+        SearchResult::Found { wrapped: true },
+    );
+    assert_eq!(textarea.cursor(), (0, 0));
+    assert_eq!(
+        textarea.search_forward_ex(false, false),
+        SearchResult::NotFound
+    );
+    assert_eq!(
+        textarea.search_back_ex(false, false),
+        SearchResult::NotFound
+    );
+
+    // Searching backward from the end of line finds the match before cursor
+    textarea.move_cursor(CursorMove::End);
+    assert_eq!(
+        textarea.search_back_ex(true, false),
+        SearchResult::Found { wrapped: false },
+    );
+    assert_eq!(textarea.cursor(), (0, 0));
+}
+
 #[test]
 fn search_forward_within_line() {
     let mut textarea = TextArea::from(["foo fo foo fooo"]);
@@ -130,3 +233,434 @@ fn set_search_pattern() {
     assert!(!textarea.search_back(true));
     assert!(!textarea.search_back(false));
 }
+
+#[test]
+fn search_matches() {
+    #[rustfmt::skip]
+    let mut textarea = TextArea::from([
+        "fooo foo",
+        "",
+        "あいfoo fo",
+    ]);
+
+    assert_eq!(textarea.search_matches().count(), 0);
+
+    textarea.set_search_pattern("fo+").unwrap();
+    let matches: Vec<_> = textarea.search_matches().collect();
+    assert_eq!(
+        matches,
+        [
+            ((0, 0), (0, 4)),
+            ((0, 5), (0, 8)),
+            ((2, 2), (2, 5)),
+            ((2, 6), (2, 8)),
+        ],
+    );
+
+    // Every match start is reachable by searching forward
+    textarea.move_cursor(CursorMove::Jump(0, 0));
+    for &(start, _) in &matches[1..] {
+        assert!(textarea.search_forward(false));
+        assert_eq!(textarea.cursor(), start);
+    }
+
+    // Empty matches are not reported
+    textarea.set_search_pattern("x*").unwrap();
+    assert_eq!(textarea.search_matches().count(), 0);
+
+    textarea.set_search_pattern("").unwrap();
+    assert_eq!(textarea.search_matches().count(), 0);
+}
+
+#[test]
+fn replace_next() {
+    let mut textarea = TextArea::from(["foo fooo", "bar", "fo"]);
+
+    assert!(!textarea.replace_next("x"));
+
+    textarea.set_search_pattern("fo+").unwrap();
+    for (after, cursor, start) in [
+        (&["x fooo", "bar", "fo"][..], (0, 1), (0, 0)),
+        (&["x x", "bar", "fo"][..], (0, 3), (0, 2)),
+        (&["x x", "bar", "x"][..], (2, 1), (2, 0)),
+    ] {
+        // Cursor is adjusted after the length of the match has changed
+        let before = textarea.lines().to_vec();
+        assert!(textarea.replace_next("x"), "{after:?}");
+        assert_eq!(textarea.lines(), after);
+        assert_eq!(textarea.cursor(), cursor, "{after:?}");
+
+        // Each replacement is a single undo entry
+        assert!(textarea.undo());
+        assert_eq!(textarea.lines(), before);
+        assert_eq!(textarea.cursor(), start, "{after:?}");
+        assert!(textarea.redo());
+        assert_eq!(textarea.lines(), after);
+        assert_eq!(textarea.cursor(), start, "{after:?}");
+        textarea.move_cursor(CursorMove::Jump(cursor.0, cursor.1));
+    }
+    assert!(!textarea.replace_next("x"));
+}
+
+#[test]
+fn replace_next_wrap() {
+    let mut textarea = TextArea::from(["foo", "bar"]);
+    textarea.set_search_pattern("foo").unwrap();
+    textarea.move_cursor(CursorMove::Bottom);
+    assert!(textarea.replace_next("baz"));
+    assert_eq!(textarea.lines(), ["baz", "bar"]);
+    assert_eq!(textarea.cursor(), (0, 3));
+}
+
+#[test]
+fn replace_captures() {
+    let mut textarea = TextArea::from(["key: value", "name: tui"]);
+    textarea.set_search_pattern(r"(?P<k>\w+): (\w+)").unwrap();
+
+    assert!(textarea.replace_next("$2 = ${k}"));
+    assert_eq!(textarea.lines(), ["value = key", "name: tui"]);
+
+    assert_eq!(textarea.replace_all("[$2]"), 1);
+    assert_eq!(textarea.lines(), ["value = key", "[tui]"]);
+
+    // Replacement can insert newlines
+    textarea.set_search_pattern(r"(\w+) = (\w+)").unwrap();
+    assert_eq!(textarea.replace_all("$1\n$2"), 1);
+    assert_eq!(textarea.lines(), ["value", "key", "[tui]"]);
+    assert_eq!(textarea.cursor(), (1, 3));
+}
+
+#[test]
+fn replace_all() {
+    let mut textarea = TextArea::from(["a aa", "", "aaa a", "b"]);
+    textarea.move_cursor(CursorMove::Bottom);
+
+    assert_eq!(textarea.replace_all("x"), 0);
+
+    textarea.set_search_pattern("a+").unwrap();
+    assert_eq!(textarea.replace_all("bcd"), 4);
+    assert_eq!(textarea.lines(), ["bcd bcd", "", "bcd bcd", "b"]);
+    assert_eq!(textarea.cursor(), (2, 7));
+    assert_eq!(textarea.search_matches().count(), 0);
+
+    // All replacements are undone at once
+    assert!(textarea.undo());
+    assert_eq!(textarea.lines(), ["a aa", "", "aaa a", "b"]);
+    assert_eq!(textarea.cursor(), (0, 0));
+    assert!(textarea.redo());
+    assert_eq!(textarea.lines(), ["bcd bcd", "", "bcd bcd", "b"]);
+    assert_eq!(textarea.cursor(), (0, 0));
+    assert!(textarea.undo());
+    assert!(!textarea.undo());
+
+    // Shrink text
+    textarea.set_search_pattern("a+").unwrap();
+    assert_eq!(textarea.replace_all(""), 4);
+    assert_eq!(textarea.lines(), [" ", "", " ", "b"]);
+    assert_eq!(textarea.cursor(), (2, 1));
+}
+
+#[test]
+fn replace_keeps_selection() {
+    let mut textarea = TextArea::from(["foo bar foo baz"]);
+    textarea.set_search_pattern("foo").unwrap();
+
+    // Select "bar foo b"
+    textarea.move_cursor(CursorMove::Jump(0, 4));
+    textarea.start_selection();
+    textarea.move_cursor(CursorMove::Jump(0, 13));
+
+    assert_eq!(textarea.replace_all("x"), 2);
+    assert_eq!(textarea.lines(), ["x bar x baz"]);
+    // The anchor stays at the start of "bar" and the cursor is after the last replacement
+    assert_eq!(textarea.selection_range(), Some(((0, 2), (0, 7))));
+}
+
+#[test]
+fn search_case() {
+    let mut textarea = TextArea::from(["foo Foo FOO", "fOo"]);
+
+    for (case, pattern, want) in [
+        (SearchCase::Sensitive, "foo", &[(0, 0)][..]),
+        (SearchCase::Sensitive, "Foo", &[(0, 4)][..]),
+        (
+            SearchCase::Insensitive,
+            "foo",
+            &[(0, 0), (0, 4), (0, 8), (1, 0)][..],
+        ),
+        (
+            SearchCase::Insensitive,
+            "FOO",
+            &[(0, 0), (0, 4), (0, 8), (1, 0)][..],
+        ),
+        (
+            SearchCase::Smart,
+            "foo",
+            &[(0, 0), (0, 4), (0, 8), (1, 0)][..],
+        ),
+        (SearchCase::Smart, "Foo", &[(0, 4)][..]),
+        (SearchCase::Smart, "fOo", &[(1, 0)][..]),
+        // Escape sequences don't make the search case-sensitive
+        (
+            SearchCase::Smart,
+            r"f\So",
+            &[(0, 0), (0, 4), (0, 8), (1, 0)][..],
+        ),
+    ] {
+        textarea.set_search_case(case);
+        textarea.set_search_pattern(pattern).unwrap();
+        let have: Vec<_> = textarea.search_matches().map(|(s, _)| s).collect();
+        assert_eq!(have, want, "{case:?} {pattern:?}");
+
+        // Search moves the cursor along with matches under the case option
+        textarea.move_cursor(CursorMove::Jump(0, 0));
+        let mut have = vec![];
+        for _ in 0..want.len() {
+            assert!(textarea.search_forward(true));
+            have.push(textarea.cursor());
+            textarea.move_cursor(CursorMove::Forward);
+        }
+        have.sort();
+        have.dedup();
+        assert_eq!(have, want, "{case:?} {pattern:?}");
+    }
+}
+
+#[test]
+fn search_case_recompiles_pattern() {
+    let mut textarea = TextArea::from(["foo Foo"]);
+    textarea.set_search_pattern("foo").unwrap();
+    assert_eq!(textarea.search_matches().count(), 1);
+
+    textarea.set_search_case(SearchCase::Insensitive);
+    assert_eq!(textarea.search_case(), SearchCase::Insensitive);
+    assert_eq!(textarea.search_matches().count(), 2);
+    assert_eq!(textarea.search_pattern().unwrap().as_str(), "foo");
+
+    textarea.set_search_case(SearchCase::Sensitive);
+    assert_eq!(textarea.search_matches().count(), 1);
+
+    // Clearing the pattern is not affected by the case option
+    textarea.set_search_pattern("").unwrap();
+    textarea.set_search_case(SearchCase::Smart);
+    assert!(textarea.search_pattern().is_none());
+}
+
+#[test]
+fn search_whole_word() {
+    #[rustfmt::skip]
+    let mut textarea = TextArea::from([
+        "in print(in_x, x.in) in",
+        "a->b ->c d-> e->",
+        "ünïn in_",
+        "xa-b",
+    ]);
+    textarea.set_search_whole_word(true);
+
+    for (pattern, want) in [
+        ("in", &[(0, 0), (0, 17), (0, 21)][..]),
+        // Boundaries are not required on the sides of non-word characters
+        ("->", &[(1, 1), (1, 5), (1, 10), (1, 14)][..]),
+        ("->c", &[(1, 5)][..]),
+        (r"\w->", &[(1, 0), (1, 9), (1, 13)][..]),
+        ("in_", &[(2, 5)][..]),
+        // Non-ASCII word characters
+        ("n", &[][..]),
+        ("ünïn", &[(2, 0)][..]),
+        // A candidate overlapping a rejected match is still found
+        ("a-b|b", &[(1, 3), (3, 3)][..]),
+    ] {
+        textarea.set_search_pattern(pattern).unwrap();
+        let have: Vec<_> = textarea.search_matches().map(|(s, _)| s).collect();
+        assert_eq!(have, want, "{pattern:?}");
+
+        // Forward and backward search stop only at whole word matches
+        textarea.move_cursor(CursorMove::Jump(0, 0));
+        if want.is_empty() {
+            assert!(!textarea.search_forward(true), "{pattern:?}");
+            continue;
+        }
+        for (i, &pos) in want.iter().enumerate() {
+            assert!(textarea.search_forward(i == 0), "{pattern:?}");
+            assert_eq!(textarea.cursor(), pos, "{pattern:?}");
+        }
+        for &pos in want.iter().rev().skip(1) {
+            assert!(textarea.search_back(false), "{pattern:?}");
+            assert_eq!(textarea.cursor(), pos, "{pattern:?}");
+        }
+    }
+}
+
+#[test]
+fn search_whole_word_with_case() {
+    let mut textarea = TextArea::from(["In in IN inside"]);
+    textarea.set_search_pattern("in").unwrap();
+    textarea.set_search_whole_word(true);
+    assert_eq!(textarea.search_matches().count(), 1);
+
+    textarea.set_search_case(SearchCase::Insensitive);
+    let have: Vec<_> = textarea.search_matches().map(|(s, _)| s).collect();
+    assert_eq!(have, [(0, 0), (0, 3), (0, 6)]);
+
+    assert_eq!(textarea.replace_all("on"), 3);
+    assert_eq!(textarea.lines(), ["on on on inside"]);
+
+    textarea.set_search_whole_word(false);
+    assert_eq!(textarea.search_matches().count(), 1);
+}
+
+fn select(textarea: &mut TextArea<'_>, start: (usize, usize), end: (usize, usize)) {
+    textarea.move_cursor(CursorMove::Jump(start.0, start.1));
+    textarea.start_selection();
+    textarea.move_cursor(CursorMove::Jump(end.0, end.1));
+}
+
+#[test]
+fn search_scope_selection() {
+    let mut textarea = TextArea::from(["foo foo", "foo", "foo foo"]);
+    textarea.set_search_pattern("foo").unwrap();
+    assert_eq!(textarea.search_scope(), SearchScope::Buffer);
+    assert_eq!(textarea.search_matches().count(), 5);
+
+    // Matches partially inside the selection are excluded
+    select(&mut textarea, (0, 2), (2, 5));
+    textarea.set_search_scope(SearchScope::Selection);
+    assert_eq!(textarea.search_scope(), SearchScope::Selection);
+    let matches: Vec<_> = textarea.search_matches().collect();
+    assert_eq!(
+        matches,
+        [((0, 4), (0, 7)), ((1, 0), (1, 3)), ((2, 0), (2, 3))]
+    );
+
+    // Search moves only to matches in the region and wraps around within it
+    let found = |wrapped| SearchResult::Found { wrapped };
+    textarea.move_cursor(CursorMove::Jump(0, 0));
+    for (result, pos) in [
+        (found(false), (0, 4)),
+        (found(false), (1, 0)),
+        (found(false), (2, 0)),
+        (found(true), (0, 4)),
+    ] {
+        assert_eq!(textarea.search_forward_ex(false, true), result);
+        assert_eq!(textarea.cursor(), pos);
+    }
+    assert_eq!(
+        textarea.search_back_ex(false, false),
+        SearchResult::NotFound
+    );
+    assert_eq!(textarea.search_back_ex(false, true), found(true));
+    assert_eq!(textarea.cursor(), (2, 0));
+    assert_eq!(textarea.search_back_ex(true, true), found(false));
+    assert_eq!(textarea.cursor(), (2, 0));
+
+    // Scope reverts to the entire buffer when selection is canceled
+    assert_eq!(textarea.search_scope(), SearchScope::Selection);
+    textarea.cancel_selection();
+    assert_eq!(textarea.search_scope(), SearchScope::Buffer);
+    assert_eq!(textarea.search_matches().count(), 5);
+
+    // Scope is the entire buffer when nothing is selected
+    textarea.set_search_scope(SearchScope::Selection);
+    assert_eq!(textarea.search_scope(), SearchScope::Buffer);
+
+    // Starting a new selection also reverts the scope
+    select(&mut textarea, (0, 0), (0, 3));
+    textarea.set_search_scope(SearchScope::Selection);
+    textarea.start_selection();
+    assert_eq!(textarea.search_scope(), SearchScope::Buffer);
+}
+
+#[test]
+fn current_match_cleared_on_edit() {
+    let mut textarea = TextArea::from(["foo", "foo"]);
+    textarea.set_search_pattern("foo").unwrap();
+    assert!(textarea.search_forward(false));
+    assert_eq!(textarea.current_search_match(), Some((1, 0)));
+
+    // Moving the cursor keeps the current match
+    textarea.move_cursor(CursorMove::End);
+    assert_eq!(textarea.current_search_match(), Some((1, 0)));
+
+    // Text at the current match is no longer known to match after edits
+    textarea.insert_str("x");
+    assert_eq!(textarea.current_search_match(), None);
+
+    textarea.move_cursor(CursorMove::Top);
+    assert!(textarea.search_forward(false));
+    assert_eq!(textarea.current_search_match(), Some((1, 0)));
+    assert!(textarea.undo());
+    assert_eq!(textarea.current_search_match(), None);
+}
+
+#[test]
+fn search_scope_follows_edits() {
+    let mut textarea = TextArea::from(["foo foo", "foo", "foo foo"]);
+    textarea.set_search_pattern("foo").unwrap();
+    select(&mut textarea, (0, 2), (2, 5));
+    textarea.set_search_scope(SearchScope::Selection);
+
+    // Indenting the selected lines shifts the region together with the text
+    textarea.indent_selection();
+    assert_eq!(textarea.lines(), ["    foo foo", "    foo", "    foo foo"]);
+    assert_eq!(textarea.search_scope(), SearchScope::Selection);
+    let matches: Vec<_> = textarea.search_matches().collect();
+    assert_eq!(
+        matches,
+        [((0, 8), (0, 11)), ((1, 4), (1, 7)), ((2, 4), (2, 7))]
+    );
+
+    // Next replacement is searched in the moved region
+    textarea.move_cursor(CursorMove::Jump(0, 0));
+    assert!(textarea.replace_next("x"));
+    assert_eq!(textarea.lines(), ["    foo x", "    foo", "    foo foo"]);
+}
+
+#[test]
+fn replace_all_in_selection_scope() {
+    let mut textarea = TextArea::from(["a a", "a", "a a"]);
+    textarea.set_search_pattern("a").unwrap();
+    select(&mut textarea, (0, 2), (2, 1));
+    textarea.set_search_scope(SearchScope::Selection);
+
+    assert_eq!(textarea.replace_all("bbb"), 3);
+    assert_eq!(textarea.lines(), ["a bbb", "bbb", "bbb a"]);
+
+    // Selection spans the resized region
+    assert_eq!(textarea.selection_range(), Some(((0, 2), (2, 3))));
+    assert_eq!(textarea.search_scope(), SearchScope::Selection);
+    let matches: Vec<_> = textarea.search_matches().collect();
+    assert_eq!(matches, []);
+
+    // Replacement can shrink the region
+    textarea.set_search_pattern("b+").unwrap();
+    assert_eq!(textarea.replace_all(""), 3);
+    assert_eq!(textarea.lines(), ["a ", "", " a"]);
+    assert_eq!(textarea.selection_range(), Some(((0, 2), (2, 0))));
+
+    // All replacements in the region are undone at once
+    assert!(textarea.undo());
+    assert_eq!(textarea.lines(), ["a bbb", "bbb", "bbb a"]);
+    assert_eq!(textarea.selection_range(), Some(((0, 2), (2, 3))));
+}
+
+#[test]
+fn search_history() {
+    let mut textarea = TextArea::default();
+    for query in ["fo+", "fo+", "(", "", "bar", "fo+"] {
+        let _ = textarea.set_search_pattern(query);
+        textarea.commit_search_pattern();
+    }
+    assert_eq!(textarea.search_history().entries(), ["fo+", "bar", "fo+"]);
+
+    // Setting a pattern without committing it is not recorded
+    textarea.set_search_pattern("baz").unwrap();
+    assert_eq!(textarea.search_history().entries(), ["fo+", "bar", "fo+"]);
+
+    assert_eq!(textarea.recall_previous_search(), Some("fo+"));
+    assert_eq!(textarea.recall_previous_search(), Some("bar"));
+    assert_eq!(textarea.recall_next_search(), Some("fo+"));
+    assert_eq!(textarea.recall_next_search(), None);
+
+    textarea.search_history_mut().set_max_len(2);
+    assert_eq!(textarea.search_history().entries(), ["bar", "fo+"]);
+}
@@ -1,6 +1,12 @@
 use std::cmp;
 use std::fmt::Debug;
-use tui_textarea::{CursorMove, TextArea};
+use std::ops::ControlFlow;
+use std::time::Duration;
+use tui_textarea::{
+    Case, Coalescing, CursorMove, HistoryEditKind, HistoryEntry, Input, Key, ListContinuation,
+    ListMarker, SelectionMode, SelectionUnit, SortOrder, TextArea, TextObject, UndoCursor,
+    YankKind,
+};
 
 fn assert_undo_redo<T: Debug>(
     before_pos: (usize, usize),
@@ -11,13 +17,42 @@ fn assert_undo_redo<T: Debug>(
 ) {
     let after_pos = t.cursor();
     let modified = before_buf != after_buf;
+    // Both undo and redo place the cursor at the start of the edited region unless the cursor positions are restored
+    let (undo_pos, redo_pos) = if modified && t.undo_cursor() == UndoCursor::EditStart {
+        let start = cmp::min(before_pos, after_pos);
+        (start, start)
+    } else {
+        (before_pos, after_pos)
+    };
     assert_eq!(t.cursor(), after_pos, "pos before undo: {context:?}");
     assert_eq!(t.undo(), modified, "undo modification: {context:?}");
     assert_eq!(t.lines(), before_buf, "buf after undo: {context:?}");
-    assert_eq!(t.cursor(), before_pos, "pos after undo: {context:?}");
+    assert_eq!(t.cursor(), undo_pos, "pos after undo: {context:?}");
     assert_eq!(t.redo(), modified, "redo modification: {context:?}");
     assert_eq!(t.lines(), after_buf, "buf after redo: {context:?}");
+    assert_eq!(t.cursor(), redo_pos, "pos after redo: {context:?}");
+}
+
+// Same as `assert_undo_redo` but for the edit made while selecting text. Undo restores the selection and the cursor
+// before the edit, and redo places the cursor after the edit
+fn assert_undo_redo_selection<T: Debug>(
+    selection: ((usize, usize), (usize, usize)),
+    before_pos: (usize, usize),
+    before_buf: &[&str],
+    after_buf: &[&str],
+    t: &mut TextArea<'_>,
+    context: T,
+) {
+    let after_pos = t.cursor();
+    assert!(t.undo(), "undo modification: {context:?}");
+    assert_eq!(t.lines(), before_buf, "buf after undo: {context:?}");
+    assert_eq!(t.cursor(), before_pos, "pos after undo: {context:?}");
+    let range = t.selection_range();
+    assert_eq!(range, Some(selection), "selection after undo: {context:?}");
+    assert!(t.redo(), "redo modification: {context:?}");
+    assert_eq!(t.lines(), after_buf, "buf after redo: {context:?}");
     assert_eq!(t.cursor(), after_pos, "pos after redo: {context:?}");
+    assert!(!t.is_selecting(), "selection after redo: {context:?}");
 }
 
 fn assert_no_undo_redo<T: Debug>(t: &mut TextArea<'_>, context: T) {
@@ -50,7 +85,7 @@ fn test_insert_soft_tab() {
         t.move_cursor(CursorMove::Jump(0, col));
         assert!(t.insert_tab(), "{test:?}");
         assert_eq!(t.lines(), [expected], "{test:?}");
-        assert_eq!(t.cursor(), (0, col as usize + width), "{test:?}");
+        assert_eq!(t.cursor(), (0, col + width), "{test:?}");
         assert_undo_redo((0, col as _), &[input], &[expected], &mut t, test);
     }
 }
@@ -88,11 +123,7 @@ fn test_insert_char() {
         t.move_cursor(CursorMove::Jump(0, col));
         t.insert_char(ch);
         assert_eq!(t.lines(), want, "{test:?}");
-        let pos = if ch == '\n' {
-            (1, 0)
-        } else {
-            (0, col as usize + 1)
-        };
+        let pos = if ch == '\n' { (1, 0) } else { (0, col + 1) };
         assert_eq!(t.cursor(), pos, "{test:?}");
         assert_undo_redo((0, col as _), &["ab"], want, &mut t, test);
     }
@@ -102,7 +133,7 @@ fn test_insert_char() {
 fn test_insert_str_one_line() {
     for i in 0..="ab".len() {
         let mut t = TextArea::from(["ab"]);
-        t.move_cursor(CursorMove::Jump(0, i as u16));
+        t.move_cursor(CursorMove::Jump(0, i));
         assert!(t.insert_str("x"), "{i}");
 
         let mut want = "ab".to_string();
@@ -947,9 +978,9 @@ fn test_copy_single_line() {
         for j in i.."abc".len() {
             let mut t = TextArea::from(["abc"]);
 
-            t.move_cursor(CursorMove::Jump(0, i as u16));
+            t.move_cursor(CursorMove::Jump(0, i));
             t.start_selection();
-            t.move_cursor(CursorMove::Jump(0, j as u16));
+            t.move_cursor(CursorMove::Jump(0, j));
             t.copy();
 
             assert_eq!(t.yank_text(), &"abc"[i..j], "from {i} to {j}");
@@ -966,9 +997,9 @@ fn test_cut_single_line() {
         for j in i + 1.."abc".len() {
             let mut t = TextArea::from(["abc"]);
 
-            t.move_cursor(CursorMove::Jump(0, i as u16));
+            t.move_cursor(CursorMove::Jump(0, i));
             t.start_selection();
-            t.move_cursor(CursorMove::Jump(0, j as u16));
+            t.move_cursor(CursorMove::Jump(0, j));
             t.cut();
 
             assert_eq!(t.yank_text(), &"abc"[i..j], "from {i} to {j}");
@@ -978,7 +1009,8 @@ fn test_cut_single_line() {
             let after = after.as_str();
             assert_eq!(t.lines(), [after], "from {i} to {j}");
             assert_eq!(t.cursor(), (0, i));
-            assert_undo_redo((0, j), &["abc"], &[after], &mut t, (i, j));
+            let selection = ((0, i), (0, j));
+            assert_undo_redo_selection(selection, (0, j), &["abc"], &[after], &mut t, (i, j));
 
             t.paste();
             assert_eq!(t.lines(), ["abc"], "from {i} to {j}");
@@ -1280,7 +1312,8 @@ fn test_copy_cut_paste_multi_lines() {
             assert_eq!(t.cursor(), (srow, scol), "{test:?}");
             assert_eq!(t.yank_text(), yanked, "{test:?}");
             assert_eq!(t.lines(), after_cut, "{test:?}");
-            assert_undo_redo((erow, ecol), init_text, after_cut, &mut t, test);
+            let (sel, pos) = (((srow, scol), (erow, ecol)), (erow, ecol));
+            assert_undo_redo_selection(sel, pos, init_text, after_cut, &mut t, test);
 
             t.paste();
             assert_eq!(t.lines(), init_text, "{test:?}");
@@ -1311,7 +1344,8 @@ fn test_copy_cut_paste_multi_lines() {
             assert_eq!(t.cursor(), (srow, scol), "{test:?}");
             assert_eq!(t.yank_text(), yanked, "{test:?}");
             assert_eq!(t.lines(), after_cut, "{test:?}");
-            assert_undo_redo((erow, ecol), init_text, after_cut, &mut t, test);
+            let (sel, pos) = (((srow, scol), (erow, ecol)), (srow, scol));
+            assert_undo_redo_selection(sel, pos, init_text, after_cut, &mut t, test);
 
             t.paste();
             assert_eq!(t.lines(), init_text, "{test:?}");
@@ -1338,6 +1372,8 @@ fn test_delete_selection_on_delete_operations() {
         test_case!(delete_line_by_head()),
         test_case!(delete_word()),
         test_case!(delete_next_word()),
+        test_case!(delete_big_word()),
+        test_case!(delete_next_big_word()),
         test_case!(delete_str(3)),
     ];
 
@@ -1352,7 +1388,8 @@ fn test_delete_selection_on_delete_operations() {
         assert_eq!(t.lines(), ["af"], "{n}");
         assert_eq!(t.cursor(), (0, 1), "{n}");
 
-        assert_undo_redo((2, 1), &["ab", "cd", "ef"], &["af"], &mut t, n);
+        let selection = ((0, 1), (2, 1));
+        assert_undo_redo_selection(selection, (2, 1), &["ab", "cd", "ef"], &["af"], &mut t, n);
     }
 }
 
@@ -1505,7 +1542,383 @@ fn test_select_all() {
     t.cut();
     assert_eq!(t.lines(), [""]);
     assert_eq!(t.yank_text(), "aaa\nbbb\nccc");
-    assert_undo_redo((2, 3), &["aaa", "bbb", "ccc"], &[""], &mut t, "");
+    let selection = ((0, 0), (2, 3));
+    assert_undo_redo_selection(selection, (2, 3), &["aaa", "bbb", "ccc"], &[""], &mut t, "");
+
+    let mut t = TextArea::from(["aaa", "bbb", "ccc"]);
+    t.move_cursor(CursorMove::Jump(1, 1));
+    t.select_all();
+    assert_eq!(t.selection_range(), Some(((0, 0), (2, 3))));
+    t.copy();
+    assert!(!t.is_selecting());
+    assert_eq!(t.yank_text(), "aaa\nbbb\nccc");
+    assert_eq!(t.cursor(), (2, 3));
+}
+
+#[test]
+fn test_select_all_empty() {
+    let mut t = TextArea::default();
+    t.set_yank_text("foo");
+    t.select_all();
+    assert!(t.is_selecting());
+    assert_eq!(t.selection_range(), Some(((0, 0), (0, 0))));
+    t.copy();
+    assert_eq!(t.yank_text(), "foo");
+    t.select_all();
+    assert!(!t.cut());
+    assert!(!t.is_selecting());
+    assert_eq!(t.lines(), [""]);
+    assert_eq!(t.yank_text(), "foo");
+}
+
+#[test]
+fn test_set_selection_range() {
+    let mut t = TextArea::from(["abc", "def", "ghi"]);
+
+    t.set_selection_range((0, 1), (2, 2));
+    assert!(t.is_selecting());
+    assert_eq!(t.cursor(), (2, 2));
+    assert_eq!(t.selection_range(), Some(((0, 1), (2, 2))));
+    t.copy();
+    assert_eq!(t.yank_text(), "bc\ndef\ngh");
+
+    // Backward selection
+    t.set_selection_range((1, 3), (0, 2));
+    assert_eq!(t.cursor(), (0, 2));
+    assert_eq!(t.selection_range(), Some(((0, 2), (1, 3))));
+
+    // Positions are clamped
+    t.set_selection_range((1, 10), (10, 10));
+    assert_eq!(t.cursor(), (2, 3));
+    assert_eq!(t.selection_range(), Some(((1, 3), (2, 3))));
+    assert!(t.cut());
+    assert_eq!(t.lines(), ["abc", "def"]);
+    assert_eq!(t.yank_text(), "\nghi");
+    assert_eq!(t.cursor(), (1, 3));
+
+    // Typing replaces the selection
+    t.set_selection_range((0, 1), (1, 2));
+    t.insert_char('x');
+    assert_eq!(t.lines(), ["axf"]);
+    assert_eq!(t.cursor(), (0, 2));
+    assert!(!t.is_selecting());
+}
+
+#[test]
+fn test_line_selection() {
+    let mut t = TextArea::from(["aaa", "bbb", "ccc", "ddd"]);
+    t.move_cursor(CursorMove::Jump(2, 1));
+    t.start_line_selection();
+    assert_eq!(t.selection_mode(), Some(SelectionMode::Line));
+    t.move_cursor(CursorMove::Up);
+    assert_eq!(t.selection_range(), Some(((1, 0), (3, 0))));
+    // Moving the cursor horizontally does not change the selected lines
+    t.move_cursor(CursorMove::End);
+    t.move_cursor(CursorMove::Head);
+    assert_eq!(t.selection_range(), Some(((1, 0), (3, 0))));
+
+    t.copy();
+    assert!(!t.is_selecting());
+    assert_eq!(t.selection_mode(), None);
+    assert_eq!(t.yank_text(), "bbb\nccc\n");
+    assert_eq!(t.lines(), ["aaa", "bbb", "ccc", "ddd"]);
+
+    // Paste inserts the lines before the cursor line regardless of the cursor column
+    t.move_cursor(CursorMove::Jump(0, 2));
+    assert!(t.paste());
+    assert_eq!(t.lines(), ["bbb", "ccc", "aaa", "bbb", "ccc", "ddd"]);
+    assert_eq!(t.cursor(), (0, 0));
+}
+
+#[test]
+fn test_line_selection_cut() {
+    let mut t = TextArea::from(["aaa", "bbb", "ccc"]);
+    t.move_cursor(CursorMove::Jump(1, 2));
+    t.start_line_selection();
+    assert!(t.cut());
+    assert_eq!(t.lines(), ["aaa", "ccc"]);
+    assert_eq!(t.cursor(), (1, 0));
+    assert_eq!(t.yank_text(), "bbb\n");
+    let (before, after) = (&["aaa", "bbb", "ccc"], &["aaa", "ccc"]);
+    assert_undo_redo_selection(((1, 0), (2, 0)), (1, 2), before, after, &mut t, "");
+    assert_eq!(t.selection_mode(), None);
+    assert!(t.undo());
+    assert_eq!(t.selection_mode(), Some(SelectionMode::Line));
+
+    // The newline before the lines is deleted when the last line is selected
+    let mut t = TextArea::from(["aaa", "bbb", "ccc"]);
+    t.move_cursor(CursorMove::Jump(2, 1));
+    t.start_line_selection();
+    t.move_cursor(CursorMove::Up);
+    assert_eq!(t.selection_range(), Some(((1, 0), (2, 3))));
+    assert!(t.cut());
+    assert_eq!(t.lines(), ["aaa"]);
+    assert_eq!(t.cursor(), (0, 0));
+    assert_eq!(t.yank_text(), "bbb\nccc\n");
+
+    assert!(t.paste());
+    assert_eq!(t.lines(), ["bbb", "ccc", "aaa"]);
+
+    // Cut all lines
+    let mut t = TextArea::from(["aaa", "bbb"]);
+    t.start_line_selection();
+    t.move_cursor(CursorMove::Down);
+    assert!(t.cut());
+    assert_eq!(t.lines(), [""]);
+    assert_eq!(t.cursor(), (0, 0));
+    assert_eq!(t.yank_text(), "aaa\nbbb\n");
+
+    // Cutting the single empty line yanks it but does not modify the text
+    t.start_line_selection();
+    assert!(!t.cut());
+    assert_eq!(t.lines(), [""]);
+    assert_eq!(t.yank_text(), "\n");
+}
+
+#[test]
+fn test_line_selection_paste() {
+    // Pasting line-wise yank over line-wise selection replaces the selected lines
+    let mut t = TextArea::from(["aaa", "bbb", "ccc"]);
+    t.start_line_selection();
+    t.copy();
+    t.move_cursor(CursorMove::Down);
+    t.start_line_selection();
+    t.move_cursor(CursorMove::Down);
+    assert!(t.paste());
+    assert_eq!(t.lines(), ["aaa", "aaa"]);
+    assert_eq!(t.cursor(), (1, 0));
+    assert!(!t.is_selecting());
+
+    // Replacing the last line appends the lines after the remaining lines
+    let mut t = TextArea::from(["aaa", "bbb", "ccc"]);
+    t.start_line_selection();
+    t.copy();
+    t.move_cursor(CursorMove::Bottom);
+    t.start_line_selection();
+    assert!(t.paste());
+    assert_eq!(t.lines(), ["aaa", "bbb", "aaa"]);
+    assert_eq!(t.cursor(), (2, 0));
+
+    // Typing over line-wise selection replaces the selected lines
+    let mut t = TextArea::from(["aaa", "bbb", "ccc"]);
+    t.start_line_selection();
+    t.move_cursor(CursorMove::Down);
+    t.insert_str("x");
+    assert_eq!(t.lines(), ["xccc"]);
+}
+
+#[test]
+fn test_block_selection() {
+    let mut t = TextArea::from(["abcde", "fg", "hijkl"]);
+    t.move_cursor(CursorMove::Jump(2, 4));
+    t.start_block_selection();
+    assert_eq!(t.selection_mode(), Some(SelectionMode::Block));
+    t.move_cursor(CursorMove::Jump(0, 1));
+    assert_eq!(t.selection_range(), Some(((0, 1), (2, 4))));
+
+    // Short lines are treated as padded with nothing
+    t.copy();
+    assert!(!t.is_selecting());
+    assert_eq!(t.yank_text(), "bcd\ng\nijk");
+    assert_eq!(t.lines(), ["abcde", "fg", "hijkl"]);
+
+    t.move_cursor(CursorMove::Jump(0, 1));
+    t.start_block_selection();
+    t.move_cursor(CursorMove::Jump(2, 4));
+    assert!(t.cut());
+    assert_eq!(t.lines(), ["ae", "f", "hl"]);
+    assert_eq!(t.cursor(), (0, 1));
+    assert_eq!(t.yank_text(), "bcd\ng\nijk");
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["abcde", "fg", "hijkl"]);
+    assert!(t.redo());
+    assert_eq!(t.lines(), ["ae", "f", "hl"]);
+
+    // Cutting a block containing nothing does not modify the text
+    t.move_cursor(CursorMove::Jump(1, 1));
+    t.start_block_selection();
+    t.move_cursor(CursorMove::Jump(2, 1));
+    assert!(!t.cut());
+    assert_eq!(t.lines(), ["ae", "f", "hl"]);
+}
+
+#[test]
+fn test_block_paste() {
+    let mut t = TextArea::from(["ab", "c", "de"]);
+    t.start_block_selection();
+    t.move_cursor(CursorMove::Jump(1, 1));
+    t.copy();
+    assert_eq!(t.yank_text(), "a\nc");
+
+    // Short lines are padded with spaces and lines are added after the last line
+    t.move_cursor(CursorMove::Jump(2, 2));
+    assert!(t.paste());
+    assert_eq!(t.lines(), ["ab", "c", "dea", "  c"]);
+    assert_eq!(t.cursor(), (2, 2));
+
+    // Paste is undone as one edit
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["ab", "c", "de"]);
+    assert!(!t.undo());
+
+    t.move_cursor(CursorMove::Jump(0, 2));
+    assert!(t.paste());
+    assert_eq!(t.lines(), ["aba", "c c", "de"]);
+    assert_eq!(t.cursor(), (0, 2));
+}
+
+#[test]
+fn test_selected_text() {
+    let mut t = TextArea::from(["abc", "def", "ghi"]);
+    t.set_yank_text("yank");
+    assert_eq!(t.selected_text(), None);
+
+    t.move_cursor(CursorMove::Jump(0, 1));
+    t.start_selection();
+    assert_eq!(t.selected_text().as_deref(), Some(""));
+    t.move_cursor(CursorMove::Jump(0, 3));
+    assert_eq!(t.selected_text().as_deref(), Some("bc"));
+    t.move_cursor(CursorMove::Jump(2, 1));
+    assert_eq!(t.selected_text().as_deref(), Some("bc\ndef\ng"));
+
+    // Backward selection
+    t.move_cursor(CursorMove::Jump(2, 2));
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(1, 1));
+    assert_eq!(t.selected_text().as_deref(), Some("ef\ngh"));
+
+    t.start_line_selection();
+    t.move_cursor(CursorMove::Up);
+    assert_eq!(t.selected_text().as_deref(), Some("abc\ndef\n"));
+
+    t.start_block_selection();
+    t.move_cursor(CursorMove::Jump(2, 2));
+    assert_eq!(t.selected_text().as_deref(), Some("b\ne\nh"));
+
+    assert!(t.is_selecting());
+    assert_eq!(t.yank_text(), "yank");
+    t.cancel_selection();
+    assert_eq!(t.selected_text(), None);
+}
+
+#[test]
+fn test_swap_selection_ends() {
+    let mut t = TextArea::from(["abc", "def", "ghi"]);
+    t.swap_selection_ends();
+    assert_eq!(t.cursor(), (0, 0));
+    assert!(!t.is_selecting());
+
+    t.move_cursor(CursorMove::Jump(1, 2));
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(0, 1));
+    t.swap_selection_ends();
+    assert_eq!(t.cursor(), (1, 2));
+    assert_eq!(t.selection_range(), Some(((0, 1), (1, 2))));
+    t.move_cursor(CursorMove::Down);
+    assert_eq!(t.selection_range(), Some(((0, 1), (2, 2))));
+    t.copy();
+    assert_eq!(t.yank_text(), "bc\ndef\ngh");
+
+    // Block selection jumps to the diagonally opposite corner
+    t.move_cursor(CursorMove::Jump(0, 0));
+    t.start_block_selection();
+    t.move_cursor(CursorMove::Jump(1, 2));
+    t.swap_selection_ends();
+    assert_eq!(t.cursor(), (0, 0));
+    assert_eq!(t.selection_mode(), Some(SelectionMode::Block));
+    assert_eq!(t.selection_range(), Some(((0, 0), (1, 2))));
+}
+
+#[test]
+fn test_expand_selection() {
+    let mut t = TextArea::from(["foo.bar baz", "qux", "", "", "end"]);
+
+    // Word after the cursor is selected. Punctuations are separate words like word motions
+    t.move_cursor(CursorMove::Jump(0, 4));
+    assert!(t.expand_selection(SelectionUnit::Word));
+    assert_eq!(t.selection_range(), Some(((0, 4), (0, 7))));
+    assert_eq!(t.cursor(), (0, 7));
+    assert!(!t.expand_selection(SelectionUnit::Word));
+
+    assert!(t.expand_selection(SelectionUnit::Line));
+    assert_eq!(t.selection_range(), Some(((0, 0), (0, 11))));
+    assert!(t.expand_selection(SelectionUnit::Paragraph));
+    assert_eq!(t.selection_range(), Some(((0, 0), (1, 3))));
+    assert!(!t.expand_selection(SelectionUnit::Paragraph));
+    assert!(t.expand_selection(SelectionUnit::Buffer));
+    assert_eq!(t.selection_range(), Some(((0, 0), (4, 3))));
+    assert!(!t.expand_selection(SelectionUnit::Buffer));
+
+    assert!(t.shrink_selection());
+    assert_eq!(t.selection_range(), Some(((0, 0), (1, 3))));
+    assert!(t.shrink_selection());
+    assert!(t.shrink_selection());
+    assert_eq!(t.selection_range(), Some(((0, 4), (0, 7))));
+    assert!(t.shrink_selection());
+    assert_eq!(t.selection_range(), None);
+    assert_eq!(t.cursor(), (0, 4));
+    assert!(!t.shrink_selection());
+
+    // Word before the cursor when the cursor is at the end of word
+    t.move_cursor(CursorMove::Jump(1, 3));
+    assert!(t.expand_selection(SelectionUnit::Word));
+    assert_eq!(t.selection_range(), Some(((1, 0), (1, 3))));
+
+    // Blank lines are one paragraph
+    t.cancel_selection();
+    t.move_cursor(CursorMove::Jump(2, 0));
+    assert!(!t.expand_selection(SelectionUnit::Word));
+    assert!(!t.is_selecting());
+    assert!(t.expand_selection(SelectionUnit::Paragraph));
+    assert_eq!(t.selection_range(), Some(((2, 0), (3, 0))));
+}
+
+#[test]
+fn test_expand_selection_words() {
+    let mut t = TextArea::from(["aaa bbb ccc"]);
+    t.move_cursor(CursorMove::Jump(0, 1));
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(0, 5));
+    assert!(t.expand_selection(SelectionUnit::Word));
+    assert_eq!(t.selection_range(), Some(((0, 0), (0, 7))));
+
+    // Moving the cursor after the expansion discards the expansion history
+    t.move_cursor(CursorMove::Forward);
+    assert!(!t.shrink_selection());
+    assert_eq!(t.selection_range(), Some(((0, 0), (0, 8))));
+}
+
+#[test]
+fn test_selection_contains() {
+    let mut t = TextArea::from(["abcd", "ef", "ghij"]);
+    t.move_cursor(CursorMove::Jump(2, 1));
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(0, 2));
+    let selected = |t: &TextArea, row| {
+        (0..5)
+            .filter(|c| t.selection_contains(row, *c))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(selected(&t, 0), [2, 3, 4]);
+    assert_eq!(selected(&t, 1), [0, 1, 2, 3, 4]);
+    assert_eq!(selected(&t, 2), [0]);
+    assert!(!t.selection_contains(3, 0));
+
+    t.start_line_selection();
+    t.move_cursor(CursorMove::Down);
+    assert_eq!(selected(&t, 0), [0, 1, 2, 3, 4]);
+    assert_eq!(selected(&t, 1), [0, 1, 2, 3, 4]);
+    assert!(selected(&t, 2).is_empty());
+
+    t.move_cursor(CursorMove::Jump(0, 1));
+    t.start_block_selection();
+    t.move_cursor(CursorMove::Jump(2, 3));
+    assert_eq!(selected(&t, 0), [1, 2]);
+    assert_eq!(selected(&t, 1), [1, 2]);
+    assert_eq!(selected(&t, 2), [1, 2]);
+
+    t.cancel_selection();
+    assert!(selected(&t, 1).is_empty());
 }
 
 #[test]
@@ -1529,6 +1942,288 @@ fn test_paste_while_selection() {
     assert!(!t.is_selecting());
 }
 
+#[test]
+fn test_copy_cut_append() {
+    let mut t = TextArea::from(["abc", "def", "ghi"]);
+
+    // Appending to the empty yank is the same as copy
+    t.start_selection();
+    t.move_cursor(CursorMove::Forward);
+    t.copy_append();
+    assert_eq!(t.yank_text(), "a");
+    assert_eq!(t.yank_kind(), YankKind::Char);
+    assert!(!t.is_selecting());
+
+    // Nothing is selected
+    t.copy_append();
+    assert!(!t.cut_append());
+    assert_eq!(t.yank_text(), "a");
+
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(1, 1));
+    t.copy_append();
+    assert_eq!(t.yank_text(), "abc\nd");
+    assert_eq!(t.yank_kind(), YankKind::Char);
+
+    t.move_cursor(CursorMove::Jump(2, 0));
+    t.start_line_selection();
+    assert!(t.cut_append());
+    assert_eq!(t.lines(), ["abc", "def"]);
+    assert_eq!(t.yank_text(), "abc\nd\nghi\n");
+    assert_eq!(t.yank_kind(), YankKind::Line);
+
+    // Character-wise text appended to line-wise text is put on its own line
+    t.move_cursor(CursorMove::Jump(0, 0));
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(0, 2));
+    assert!(t.cut_append());
+    assert_eq!(t.lines(), ["c", "def"]);
+    assert_eq!(t.yank_text(), "abc\nd\nghi\nab\n");
+    assert_eq!(t.yank_kind(), YankKind::Line);
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["abc", "def"]);
+
+    // Block-wise texts
+    let mut t = TextArea::from(["abc", "def"]);
+    t.start_block_selection();
+    t.move_cursor(CursorMove::Jump(1, 1));
+    t.copy();
+    t.move_cursor(CursorMove::Jump(0, 2));
+    t.start_block_selection();
+    t.move_cursor(CursorMove::Jump(1, 3));
+    t.copy_append();
+    assert_eq!(t.yank_text(), "a\nd\nc\nf");
+    assert_eq!(t.yank_kind(), YankKind::Block);
+}
+
+#[test]
+#[allow(clippy::single_range_in_vec_init)]
+fn test_registers() {
+    let mut t = TextArea::from(["abc", "def", "ghi"]);
+    t.set_yank_text("yank");
+
+    // Registers are separated from the yank buffer and from each other
+    t.start_selection();
+    t.move_cursor(CursorMove::Forward);
+    t.copy_to_register('a');
+    t.move_cursor(CursorMove::Jump(1, 0));
+    t.start_line_selection();
+    t.copy_to_register('b');
+    assert_eq!(t.register_text('a').as_deref(), Some("a"));
+    assert_eq!(t.register_text('b').as_deref(), Some("def\n"));
+    assert_eq!(t.register_text('c'), None);
+    assert_eq!(t.yank_text(), "yank");
+    assert!(!t.is_selecting());
+
+    // Nothing is selected
+    t.copy_to_register('a');
+    t.copy_append_to_register('a');
+    assert!(!t.cut_to_register('a'));
+    assert!(!t.cut_append_to_register('a'));
+    assert_eq!(t.register_text('a').as_deref(), Some("a"));
+
+    // Line-wise text appended to character-wise text makes the register line-wise
+    t.move_cursor(CursorMove::Jump(2, 0));
+    t.start_line_selection();
+    t.copy_append_to_register('a');
+    assert_eq!(t.register_text('a').as_deref(), Some("a\nghi\n"));
+
+    t.move_cursor(CursorMove::Jump(0, 1));
+    t.start_selection();
+    t.move_cursor(CursorMove::End);
+    assert!(t.cut_append_to_register('b'));
+    assert_eq!(t.lines(), ["a", "def", "ghi"]);
+    assert_eq!(t.register_text('b').as_deref(), Some("def\nbc\n"));
+
+    // Pasting from a register keeps the yank buffer. Line-wise text is pasted before the cursor line
+    t.move_cursor(CursorMove::Jump(0, 1));
+    assert!(t.paste_from_register('b'));
+    assert_eq!(t.lines(), ["def", "bc", "a", "def", "ghi"]);
+    assert!(!t.paste_from_register('c'));
+    assert_eq!(t.yank_text(), "yank");
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["a", "def", "ghi"]);
+
+    t.move_cursor(CursorMove::Jump(2, 0));
+    t.start_selection();
+    t.move_cursor(CursorMove::End);
+    assert!(t.cut_to_register('c'));
+    assert_eq!(t.lines(), ["a", "def", ""]);
+    assert_eq!(t.register_text('c').as_deref(), Some("ghi"));
+
+    // Registers are restored when the cut is rejected by protected rows
+    t.set_protected_rows(vec![1..2]);
+    t.move_cursor(CursorMove::Jump(1, 0));
+    t.start_selection();
+    t.move_cursor(CursorMove::End);
+    assert!(!t.cut_append_to_register('c'));
+    assert_eq!(t.register_text('c').as_deref(), Some("ghi"));
+    assert!(!t.cut_to_register('d'));
+    assert_eq!(t.register_text('d'), None);
+}
+
+#[test]
+fn test_paste_linewise() {
+    let mut t = TextArea::from(["ab", "cd"]);
+    assert!(!t.paste_linewise_below());
+    assert!(!t.paste_linewise_above());
+    assert_eq!(t.yank_kind(), YankKind::Char);
+
+    t.start_line_selection();
+    t.copy();
+    assert_eq!(t.yank_kind(), YankKind::Line);
+    t.move_cursor(CursorMove::Jump(1, 1));
+    assert!(t.paste_linewise_below());
+    assert_eq!(t.lines(), ["ab", "cd", "ab"]);
+    assert_eq!(t.cursor(), (2, 0));
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["ab", "cd"]);
+    assert!(t.redo());
+    assert_eq!(t.lines(), ["ab", "cd", "ab"]);
+
+    // Character-wise text is also pasted as lines
+    t.set_yank_text("x\ny\n");
+    assert_eq!(t.yank_kind(), YankKind::Char);
+    t.move_cursor(CursorMove::Jump(1, 1));
+    assert!(t.paste_linewise_above());
+    assert_eq!(t.lines(), ["ab", "x", "y", "cd", "ab"]);
+    assert_eq!(t.cursor(), (1, 0));
+
+    // Block-wise text
+    t.start_block_selection();
+    t.move_cursor(CursorMove::Jump(2, 1));
+    t.copy();
+    assert_eq!(t.yank_kind(), YankKind::Block);
+    t.move_cursor(CursorMove::Jump(0, 0));
+    assert!(t.paste_linewise_above());
+    assert_eq!(t.lines(), ["x", "y", "ab", "x", "y", "cd", "ab"]);
+
+    // The selection is cancelled
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(0, 1));
+    assert!(t.paste_linewise_below());
+    assert!(!t.is_selecting());
+    assert_eq!(t.lines()[..3], ["x", "x", "y"]);
+    assert!(t.undo());
+    assert_eq!(t.lines()[..3], ["x", "y", "ab"]);
+    assert_eq!(t.selection_range(), Some(((0, 0), (0, 1))));
+}
+
+#[test]
+fn test_paste_n() {
+    let mut t = TextArea::from(["ab", "cd"]);
+    t.set_yank_text("x");
+    assert!(!t.paste_n(0));
+    assert_eq!(t.lines(), ["ab", "cd"]);
+
+    t.move_cursor(CursorMove::Forward);
+    assert!(t.paste_n(3));
+    assert_eq!(t.lines(), ["axxxb", "cd"]);
+    assert_eq!(t.cursor(), (0, 4));
+    assert_eq!(t.yank_text(), "x");
+    assert_undo_redo((0, 1), &["ab", "cd"], &["axxxb", "cd"], &mut t, "");
+
+    // Multi-line text
+    let mut t = TextArea::from(["ab"]);
+    t.set_yank_text("x\ny");
+    t.move_cursor(CursorMove::Forward);
+    assert!(t.paste_n(2));
+    assert_eq!(t.lines(), ["ax", "yx", "yb"]);
+    assert_eq!(t.cursor(), (2, 1));
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["ab"]);
+    assert!(!t.undo());
+
+    // Line-wise text
+    let mut t = TextArea::from(["ab", "cd"]);
+    t.start_line_selection();
+    t.copy();
+    t.move_cursor(CursorMove::Down);
+    assert!(t.paste_n(2));
+    assert_eq!(t.lines(), ["ab", "ab", "ab", "cd"]);
+    assert_eq!(t.cursor(), (1, 0));
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["ab", "cd"]);
+
+    // Block-wise text is repeated side by side
+    let mut t = TextArea::from(["abc", "d", "efg"]);
+    t.move_cursor(CursorMove::Forward);
+    t.start_block_selection();
+    t.move_cursor(CursorMove::Jump(2, 3));
+    t.copy();
+    assert_eq!(t.yank_text(), "bc\n\nfg");
+    t.move_cursor(CursorMove::Jump(0, 0));
+    assert!(t.paste_n(2));
+    assert_eq!(t.lines(), ["bcbcabc", "  d", "fgfgefg"]);
+    assert_eq!(t.cursor(), (0, 0));
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["abc", "d", "efg"]);
+}
+
+#[test]
+fn test_paste_previous_yank() {
+    let mut t = TextArea::from(["a b c", "d"]);
+    assert!(t.yank_history().is_empty());
+    t.set_yank_history_size(2);
+
+    t.delete_next_word();
+    t.delete_next_word();
+    t.delete_next_word();
+    assert_eq!(t.lines(), ["", "d"]);
+    assert_eq!(t.yank_history(), [" c", " b"]);
+
+    // Not right after paste
+    assert!(!t.paste_previous_yank());
+
+    t.move_cursor(CursorMove::Down);
+    assert!(t.paste());
+    assert_eq!(t.lines(), ["", " cd"]);
+    assert!(t.paste_previous_yank());
+    assert_eq!(t.lines(), ["", " bd"]);
+    assert_eq!(t.cursor(), (1, 2));
+    assert_eq!(t.yank_text(), " b");
+    assert!(t.paste_previous_yank());
+    assert_eq!(t.lines(), ["", " cd"]);
+    assert!(t.paste_previous_yank());
+    assert_eq!(t.lines(), ["", " bd"]);
+
+    // The rotated yank is pasted next
+    t.move_cursor(CursorMove::End);
+    assert!(!t.paste_previous_yank());
+    assert!(t.paste());
+    assert_eq!(t.lines(), ["", " bd b"]);
+
+    // Undo reverts the pasted text at once
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["", " bd"]);
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["", "d"]);
+
+    // Any edit after the paste stops cycling
+    assert!(t.paste());
+    t.insert_char('x');
+    assert!(!t.paste_previous_yank());
+
+    // Multi-line text replaces the selection
+    let mut t = TextArea::from(["ab", "cd"]);
+    t.set_yank_history_size(10);
+    t.set_yank_text("x\ny");
+    t.set_yank_text("z");
+    t.start_selection();
+    t.move_cursor(CursorMove::Forward);
+    assert!(t.paste());
+    assert_eq!(t.lines(), ["zb", "cd"]);
+    assert!(t.paste_previous_yank());
+    assert_eq!(t.lines(), ["x", "yb", "cd"]);
+    assert_eq!(t.cursor(), (1, 1));
+
+    // Shrinking the history drops old texts
+    t.set_yank_history_size(1);
+    assert_eq!(t.yank_history(), ["z"]);
+    assert!(t.paste());
+    assert!(!t.paste_previous_yank());
+}
+
 #[test]
 fn test_selection_range() {
     #[rustfmt::skip]
@@ -1552,6 +2247,12 @@ fn test_selection_range() {
         t.move_cursor(CursorMove::Jump(x as _, y as _));
 
         t.start_selection();
+        let range = t.selection_range();
+        assert_eq!(
+            range,
+            Some((from, from)),
+            "selection from {from:?} to {to:?}"
+        );
 
         let (x, y) = to;
         t.move_cursor(CursorMove::Jump(x as _, y as _));
@@ -1559,6 +2260,9 @@ fn test_selection_range() {
         let have = t.selection_range().unwrap();
         let want = if from <= to { (from, to) } else { (to, from) };
         assert_eq!(have, want, "selection from {from:?} to {to:?}");
+        // Getting the range does not cancel the selection
+        assert!(t.is_selecting(), "selection from {from:?} to {to:?}");
+        assert_eq!(t.cursor(), to, "selection from {from:?} to {to:?}");
 
         t.cancel_selection();
         let range = t.selection_range();
@@ -1669,3 +2373,2313 @@ fn test_delete_next_word() {
     t.test((1, 0), (1, 0, &["word  ことば 🐶", ""], " x"));
     t.test((1, 2), (1, 2, t.0, ""));
 }
+
+#[test]
+fn test_delete_big_word() {
+    let t = DeleteTester(&["cd  foo/bar 🐶!", " x"], |t| t.delete_big_word());
+    t.test((0, 0), (0, 0, t.0, ""));
+    t.test((0, 2), (0, 0, &["  foo/bar 🐶!", " x"], "cd"));
+    t.test((0, 4), (0, 0, &["foo/bar 🐶!", " x"], "cd  "));
+    t.test((0, 8), (0, 4, &["cd  bar 🐶!", " x"], "foo/"));
+    t.test((0, 11), (0, 4, &["cd   🐶!", " x"], "foo/bar"));
+    t.test((0, 12), (0, 4, &["cd  🐶!", " x"], "foo/bar "));
+    t.test((0, 14), (0, 12, &["cd  foo/bar ", " x"], "🐶!"));
+    t.test((1, 0), (0, 14, &["cd  foo/bar 🐶! x"], ""));
+    t.test((1, 2), (1, 1, &["cd  foo/bar 🐶!", " "], "x"));
+}
+
+#[test]
+fn test_delete_next_big_word() {
+    let t = DeleteTester(&["cd  foo/bar 🐶!", " x"], |t| t.delete_next_big_word());
+    t.test((0, 0), (0, 0, &["  foo/bar 🐶!", " x"], "cd"));
+    t.test((0, 2), (0, 2, &["cd 🐶!", " x"], "  foo/bar"));
+    t.test((0, 5), (0, 5, &["cd  f 🐶!", " x"], "oo/bar"));
+    t.test((0, 11), (0, 11, &["cd  foo/bar", " x"], " 🐶!"));
+    t.test((0, 13), (0, 13, &["cd  foo/bar 🐶", " x"], "!"));
+    t.test((0, 14), (0, 14, &["cd  foo/bar 🐶! x"], ""));
+    t.test((1, 0), (1, 0, &["cd  foo/bar 🐶!", ""], " x"));
+    t.test((1, 2), (1, 2, t.0, ""));
+}
+
+#[test]
+fn test_replace_char() {
+    for (before, pos, c, after, after_pos) in [
+        (&["abc"][..], (0, 0), 'x', &["xbc"][..], (0, 0)),
+        (&["abc"][..], (0, 2), 'x', &["abx"][..], (0, 2)),
+        (&["あ🐶い"][..], (0, 1), '犬', &["あ犬い"][..], (0, 1)),
+        (
+            &["abc", "d"][..],
+            (0, 1),
+            '\n',
+            &["a", "c", "d"][..],
+            (1, 0),
+        ),
+        (&["abc"][..], (0, 2), '\r', &["ab", ""][..], (1, 0)),
+        // Nothing happens at the end of line
+        (&["abc"][..], (0, 3), 'x', &["abc"][..], (0, 3)),
+        (&[""][..], (0, 0), 'x', &[""][..], (0, 0)),
+    ] {
+        let mut t = TextArea::from(before.iter().cloned());
+        t.move_cursor(CursorMove::Jump(pos.0 as _, pos.1 as _));
+        let modified = t.replace_char(c);
+        let context = (before, pos, c);
+        assert_eq!(modified, before != after, "{:?}", context);
+        assert_eq!(t.lines(), after, "{:?}", context);
+        assert_eq!(t.cursor(), after_pos, "{:?}", context);
+        assert_eq!(t.yank_text(), "", "{:?}", context);
+        assert_undo_redo(pos, before, after, &mut t, context);
+    }
+
+    // The selection is cancelled so that the anchor does not go out of the text
+    let mut t = TextArea::from(["abc"]);
+    t.move_cursor(CursorMove::End);
+    t.start_selection();
+    t.move_cursor(CursorMove::Head);
+    assert!(t.replace_char('\n'));
+    assert_eq!(t.lines(), ["", "bc"]);
+    assert_eq!(t.selection_range(), None);
+}
+
+#[test]
+fn test_transpose_chars() {
+    for (before, pos, after, after_pos) in [
+        (&["ab", "c"][..], (0, 1), &["ba", "c"][..], (0, 2)),
+        (&["abc"][..], (0, 3), &["acb"][..], (0, 3)),
+        (&["あ🐶い"][..], (0, 2), &["あい🐶"][..], (0, 3)),
+        // Nothing happens at the head of line or on a short line
+        (&["ab"][..], (0, 0), &["ab"][..], (0, 0)),
+        (&["a", "b"][..], (1, 1), &["a", "b"][..], (1, 1)),
+        (&[""][..], (0, 0), &[""][..], (0, 0)),
+    ] {
+        let mut t = TextArea::from(before.iter().cloned());
+        t.set_undo_cursor(UndoCursor::Restore);
+        t.move_cursor(CursorMove::Jump(pos.0 as _, pos.1 as _));
+        let modified = t.transpose_chars();
+        assert_eq!(modified, before != after, "{:?}", before);
+        assert_eq!(t.lines(), after, "{:?}", before);
+        assert_eq!(t.cursor(), after_pos, "{:?}", before);
+        assert_undo_redo(pos, before, after, &mut t, before);
+    }
+
+    let mut t = TextArea::from(["ab"]);
+    t.move_cursor(CursorMove::Forward);
+    assert!(t.input(Input {
+        key: Key::Char('t'),
+        ctrl: true,
+        alt: false,
+        shift: false,
+    }));
+    assert_eq!(t.lines(), ["ba"]);
+}
+
+#[test]
+fn test_transpose_lines() {
+    for (before, pos, after, after_pos) in [
+        (&["a", "bc", "d"][..], (1, 1), &["bc", "a", "d"][..], (0, 1)),
+        (&["a", "bc"][..], (1, 2), &["bc", "a"][..], (0, 2)),
+        (&["", "a"][..], (1, 0), &["a", ""][..], (0, 0)),
+        // Nothing happens at the first line
+        (&["a", "b"][..], (0, 1), &["a", "b"][..], (0, 1)),
+    ] {
+        let mut t = TextArea::from(before.iter().cloned());
+        t.set_undo_cursor(UndoCursor::Restore);
+        t.move_cursor(CursorMove::Jump(pos.0 as _, pos.1 as _));
+        let modified = t.transpose_lines();
+        assert_eq!(modified, before != after, "{:?}", before);
+        assert_eq!(t.lines(), after, "{:?}", before);
+        assert_eq!(t.cursor(), after_pos, "{:?}", before);
+        assert_undo_redo(pos, before, after, &mut t, before);
+    }
+}
+
+#[test]
+fn test_transpose_cancels_selection() {
+    let mut t = TextArea::from(["abcde", "x"]);
+    t.move_cursor(CursorMove::Jump(0, 1));
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(1, 1));
+    assert!(t.transpose_lines());
+    assert_eq!(t.lines(), ["x", "abcde"]);
+    assert_eq!(t.selection_range(), None);
+    assert!(!t.cut());
+
+    t.move_cursor(CursorMove::Jump(1, 1));
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(1, 3));
+    assert!(t.transpose_chars());
+    assert_eq!(t.lines(), ["x", "abdce"]);
+    assert_eq!(t.selection_range(), None);
+}
+
+#[test]
+fn test_join_lines() {
+    for (before, pos, count, after, after_pos) in [
+        (&["a", "b"][..], (0, 0), 1, &["a b"][..], (0, 1)),
+        (
+            &["a", "  \tb", "c"][..],
+            (0, 1),
+            1,
+            &["a b", "c"][..],
+            (0, 1),
+        ),
+        (&["a", "b", "c"][..], (0, 0), 2, &["a b c"][..], (0, 3)),
+        (&["a", "b"][..], (0, 0), 10, &["a b"][..], (0, 1)),
+        // No space after whitespace, before ')', or around empty lines
+        (&["a ", "b"][..], (0, 0), 1, &["a b"][..], (0, 2)),
+        (&["(a", ")"][..], (0, 0), 1, &["(a)"][..], (0, 2)),
+        (&["", "  b"][..], (0, 0), 1, &["b"][..], (0, 0)),
+        (&["a", "  ", "b"][..], (0, 0), 2, &["a b"][..], (0, 1)),
+        (&["あ", "🐶"][..], (0, 0), 1, &["あ 🐶"][..], (0, 1)),
+        // Nothing happens at the last line or with zero count
+        (&["a", "b"][..], (1, 0), 1, &["a", "b"][..], (1, 0)),
+        (&["a", "b"][..], (0, 0), 0, &["a", "b"][..], (0, 0)),
+    ] {
+        let mut t = TextArea::from(before.iter().cloned());
+        t.set_undo_cursor(UndoCursor::Restore);
+        t.move_cursor(CursorMove::Jump(pos.0 as _, pos.1 as _));
+        let modified = t.join_lines(count);
+        let context = (before, count);
+        assert_eq!(modified, before != after, "{:?}", context);
+        assert_eq!(t.lines(), after, "{:?}", context);
+        assert_eq!(t.cursor(), after_pos, "{:?}", context);
+        assert_eq!(t.yank_text(), "", "{:?}", context);
+        assert_undo_redo(pos, before, after, &mut t, context);
+    }
+}
+
+#[test]
+fn test_join_lines_cancels_selection() {
+    // The anchor at the last line would be out of the text after the join
+    let mut t = TextArea::from(["a", "b", "c"]);
+    t.move_cursor(CursorMove::Bottom);
+    t.start_line_selection();
+    t.move_cursor(CursorMove::Top);
+    assert!(t.join_lines(1));
+    assert_eq!(t.lines(), ["a b", "c"]);
+    assert!(!t.is_selecting());
+    assert_eq!(t.selection_range(), None);
+}
+
+#[test]
+fn test_indent_selection() {
+    let mut t = TextArea::from(["a", "", "  b", "c"]);
+    t.move_cursor(CursorMove::Jump(0, 1));
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(2, 2));
+    assert!(t.indent_selection());
+    assert_eq!(t.lines(), ["    a", "", "      b", "c"]);
+    assert_eq!(t.selection_range(), Some(((0, 5), (2, 6))));
+    assert_undo_redo_selection(
+        ((0, 1), (2, 2)),
+        (2, 2),
+        &["a", "", "  b", "c"],
+        &["    a", "", "      b", "c"],
+        &mut t,
+        "",
+    );
+
+    // Line-wise selection ends at the head of the next line but does not touch it
+    let mut t = TextArea::from(["a", "b", "c"]);
+    t.start_line_selection();
+    assert!(t.indent_selection());
+    assert_eq!(t.lines(), ["    a", "b", "c"]);
+
+    // Neither does a character-wise selection ending at the head of a line
+    let mut t = TextArea::from(["a", "b", "c"]);
+    t.start_selection();
+    t.move_cursor(CursorMove::Down);
+    assert!(t.indent_selection());
+    assert_eq!(t.lines(), ["    a", "b", "c"]);
+
+    // Hard tab and empty lines
+    let mut t = TextArea::from(["a", ""]);
+    t.set_hard_tab_indent(true);
+    t.set_indent_empty_lines(true);
+    t.select_all();
+    assert!(t.indent_selection());
+    assert_eq!(t.lines(), ["\ta", "\t"]);
+
+    // Cursor line without selection
+    let mut t = TextArea::from(["a", "b"]);
+    t.move_cursor(CursorMove::Down);
+    t.set_tab_length(2);
+    assert!(t.indent_selection());
+    assert_eq!(t.lines(), ["a", "  b"]);
+    assert_eq!(t.cursor(), (1, 2));
+    assert!(!t.is_selecting());
+
+    // Nothing to indent
+    let mut t = TextArea::from([""]);
+    assert!(!t.indent_selection());
+    assert_no_undo_redo(&mut t, "");
+
+    // Tab while selecting text indents the lines
+    let mut t = TextArea::from(["a", "b"]);
+    t.select_all();
+    let tab = |shift| Input {
+        key: Key::Tab,
+        ctrl: false,
+        alt: false,
+        shift,
+    };
+    assert!(t.input(tab(false)));
+    assert_eq!(t.lines(), ["    a", "    b"]);
+    assert!(t.input(tab(true)));
+    assert_eq!(t.lines(), ["a", "b"]);
+    assert!(t.is_selecting());
+}
+
+#[test]
+fn test_dedent_selection() {
+    let mut t = TextArea::from(["      a", "\t\tb", "  c", "d", "\t"]);
+    t.move_cursor(CursorMove::Jump(0, 7));
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(2, 1));
+    assert!(t.dedent_selection());
+    assert_eq!(t.lines(), ["  a", "\tb", "c", "d", "\t"]);
+    // Cursor on the removed whitespace moves to the head of line
+    assert_eq!(t.selection_range(), Some(((0, 3), (2, 0))));
+    assert_undo_redo_selection(
+        ((0, 7), (2, 1)),
+        (2, 1),
+        &["      a", "\t\tb", "  c", "d", "\t"],
+        &["  a", "\tb", "c", "d", "\t"],
+        &mut t,
+        "",
+    );
+
+    // Never dedent past the column 0
+    let mut t = TextArea::from(["a"]);
+    assert!(!t.dedent_selection());
+    assert_no_undo_redo(&mut t, "");
+
+    let mut t = TextArea::from(["   a", "\t"]);
+    t.select_all();
+    assert!(t.dedent_selection());
+    assert_eq!(t.lines(), ["a", ""]);
+    assert!(!t.dedent_selection());
+}
+
+#[test]
+fn test_toggle_comment() {
+    // Comment the selected lines at their common indentation
+    let before = &["  a", "", "    b", "  c"][..];
+    let after = &["  # a", "", "  #   b", "  c"][..];
+    let mut t = TextArea::from(before.iter().cloned());
+    t.set_comment_prefix("# ");
+    t.move_cursor(CursorMove::Jump(0, 3));
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(2, 1));
+    assert!(t.toggle_comment());
+    assert_eq!(t.lines(), after);
+    assert_eq!(t.selection_range(), Some(((0, 5), (2, 1))));
+    assert_undo_redo_selection(((0, 3), (2, 1)), (2, 1), before, after, &mut t, "");
+
+    // Uncomment when all non-blank lines are commented. The prefix without trailing space is also accepted
+    let before = &["  // a", "", "//b", "    //  c"][..];
+    let after = &["  a", "", "b", "     c"][..];
+    let mut t = TextArea::from(before.iter().cloned());
+    t.set_comment_prefix("// ");
+    t.select_all();
+    assert!(t.toggle_comment());
+    assert_eq!(t.lines(), after);
+    assert_eq!(t.selection_range(), Some(((0, 0), (3, 6))));
+    assert_undo_redo_selection(((0, 0), (3, 9)), (3, 9), before, after, &mut t, "");
+
+    // Only the line-wise selected line is commented
+    let mut t = TextArea::from(["a", "b", "c"]);
+    t.set_comment_prefix("//");
+    t.start_line_selection();
+    assert!(t.toggle_comment());
+    assert_eq!(t.lines(), ["//a", "b", "c"]);
+
+    // Lines are commented when some of them are not commented yet
+    let mut t = TextArea::from(["// a", "b"]);
+    t.set_comment_prefix("// ");
+    t.select_all();
+    assert!(t.toggle_comment());
+    assert_eq!(t.lines(), ["// // a", "// b"]);
+
+    // Cursor line without selection
+    let mut t = TextArea::from(["a", "b"]);
+    t.set_comment_prefix("--");
+    t.move_cursor(CursorMove::Jump(1, 1));
+    assert!(t.toggle_comment());
+    assert_eq!(t.lines(), ["a", "--b"]);
+    assert_eq!(t.cursor(), (1, 3));
+    assert!(t.toggle_comment());
+    assert_eq!(t.lines(), ["a", "b"]);
+    assert_eq!(t.cursor(), (1, 1));
+
+    // Nothing happens on blank lines or without prefix
+    for (prefix, lines) in [("# ", &["  ", ""][..]), ("", &["a"][..])] {
+        let mut t = TextArea::from(lines.iter().cloned());
+        t.set_comment_prefix(prefix);
+        t.select_all();
+        assert!(!t.toggle_comment(), "{:?}", lines);
+        assert_eq!(t.lines(), lines);
+        assert_no_undo_redo(&mut t, lines);
+    }
+}
+
+#[test]
+fn test_convert_selection_case() {
+    // Character-wise selection over multiple lines
+    let before = &["abc", "Straße", "xyz"][..];
+    let after = &["aBC", "STRASSE", "Xyz"][..];
+    let mut t = TextArea::from(before.iter().cloned());
+    t.move_cursor(CursorMove::Jump(0, 1));
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(2, 1));
+    assert!(t.convert_selection_case(Case::Upper));
+    assert_eq!(t.lines(), after);
+    assert_eq!(t.selection_range(), Some(((0, 1), (2, 1))));
+    assert_undo_redo_selection(((0, 1), (2, 1)), (2, 1), before, after, &mut t, "");
+
+    // Length change is reflected to the selection on the same line
+    let mut t = TextArea::from(["aßb"]);
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(0, 2));
+    assert!(t.convert_selection_case(Case::Upper));
+    assert_eq!(t.lines(), ["ASSb"]);
+    assert_eq!(t.selection_range(), Some(((0, 0), (0, 3))));
+
+    // Line-wise and block selections
+    let mut t = TextArea::from(["Abc", "dEf", "ghi"]);
+    t.start_line_selection();
+    t.move_cursor(CursorMove::Down);
+    assert!(t.convert_selection_case(Case::Toggle));
+    assert_eq!(t.lines(), ["aBC", "DeF", "ghi"]);
+    t.cancel_selection();
+    t.move_cursor(CursorMove::Jump(0, 1));
+    t.start_block_selection();
+    t.move_cursor(CursorMove::Jump(2, 2));
+    assert!(t.convert_selection_case(Case::Lower));
+    assert_eq!(t.lines(), ["abC", "DeF", "ghi"]);
+
+    // Without selection, the character under the cursor is converted and the cursor moves forward
+    let mut t = TextArea::from(["aß1"]);
+    for (lines, cursor, modified) in [
+        (["Aß1"], (0, 1), true),
+        (["ASS1"], (0, 3), true),
+        (["ASS1"], (0, 4), false),
+        (["ASS1"], (0, 4), false),
+    ] {
+        assert_eq!(t.convert_selection_case(Case::Toggle), modified);
+        assert_eq!(t.lines(), lines);
+        assert_eq!(t.cursor(), cursor);
+    }
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["Aß1"]);
+}
+
+#[test]
+fn test_chunks() {
+    for lines in [
+        &[""][..],
+        &["abc"][..],
+        &["", ""][..],
+        &["abc", "def", ""][..],
+        &["", "あいう", "", "🐶🐱"][..],
+    ] {
+        let t = TextArea::from(lines.iter().copied());
+        let want = lines.join("\n");
+
+        let text: String = t.chunks().collect();
+        assert_eq!(text, want, "{lines:?}");
+        assert_eq!(t.byte_len(), want.len(), "{lines:?}");
+
+        let mut buf = vec![];
+        t.write_to(&mut buf).unwrap();
+        assert_eq!(buf, want.as_bytes(), "{lines:?}");
+    }
+}
+
+#[test]
+fn test_byte_len_after_edits() {
+    let mut t = TextArea::from(["abc", "def"]);
+    assert_eq!(t.byte_len(), 7);
+
+    let len = |t: &TextArea| t.lines().join("\n").len();
+    t.insert_str("あ\nい");
+    assert_eq!(t.byte_len(), len(&t));
+    t.delete_line_by_head();
+    assert_eq!(t.byte_len(), len(&t));
+    t.undo();
+    assert_eq!(t.byte_len(), len(&t));
+    t.undo();
+    assert_eq!(t.byte_len(), 7);
+    t.redo();
+    assert_eq!(t.byte_len(), len(&t));
+
+    t.set_max_histories(0);
+    t.insert_newline();
+    assert_eq!(t.byte_len(), len(&t));
+    t.set_lines(vec!["x".to_string()]);
+    assert_eq!(t.byte_len(), 1);
+    assert_eq!(t.clone().byte_len(), 1);
+}
+
+#[test]
+fn test_list_continuation() {
+    for test in [
+        // (line, col, after)
+        ("- foo", 5, &["- foo", "- "][..]),
+        ("* foo", 5, &["* foo", "* "][..]),
+        ("  - foo", 7, &["  - foo", "  - "][..]),
+        ("\t* foo", 6, &["\t* foo", "\t* "][..]),
+        ("1. foo", 6, &["1. foo", "2. "][..]),
+        ("9) foo", 6, &["9) foo", "10) "][..]),
+        ("  41. foo", 9, &["  41. foo", "  42. "][..]),
+        ("- [ ] foo", 9, &["- [ ] foo", "- [ ] "][..]),
+        ("- [x] foo", 9, &["- [x] foo", "- [ ] "][..]),
+        ("- [X] foo", 9, &["- [X] foo", "- [ ] "][..]),
+        // Split the item at the cursor
+        ("- foobar", 5, &["- foo", "- bar"][..]),
+        ("1. あいう", 4, &["1. あ", "2. いう"][..]),
+        // Newline just after the marker moves the whole content to the next item
+        ("- foo", 2, &["- ", "- foo"][..]),
+        // Empty items end the list
+        ("- ", 2, &[""][..]),
+        ("1.   ", 5, &[""][..]),
+        ("- [x] ", 6, &[""][..]),
+        // Not list items
+        ("foo", 3, &["foo", ""][..]),
+        ("-foo", 4, &["-foo", ""][..]),
+        ("1.foo", 5, &["1.foo", ""][..]),
+        ("1234567890. foo", 15, &["1234567890. foo", ""][..]),
+        // Cursor in the marker
+        ("- foo", 1, &["-", " foo"][..]),
+        ("  - foo", 0, &["", "  - foo"][..]),
+    ] {
+        let (line, col, after) = test;
+        let mut t = TextArea::from([line]);
+        t.set_list_continuation(Some(ListContinuation::default()));
+        t.move_cursor(CursorMove::Jump(0, col));
+        t.insert_newline();
+        assert_eq!(t.lines(), after, "{test:?}");
+        let pos = if after.len() == 1 {
+            (0, 0)
+        } else {
+            (1, after[1].chars().count() - line.chars().skip(col).count())
+        };
+        assert_eq!(t.cursor(), pos, "{test:?}");
+        assert_undo_redo((0, col as _), &[line], after, &mut t, test);
+    }
+}
+
+#[test]
+fn test_list_continuation_outdent() {
+    for test in [
+        // (line, col, after, cursor)
+        ("    - ", 6, "- ", 2),
+        ("      1. ", 9, "  1. ", 5),
+        ("  * ", 4, "* ", 2),
+        ("\t\t- [x] ", 8, "\t- [x] ", 7),
+        // Cursor in the trailing whitespaces of the item
+        ("    -   ", 7, "-   ", 3),
+    ] {
+        let (line, col, after, cursor) = test;
+        let mut t = TextArea::from([line]);
+        t.set_list_continuation(Some(ListContinuation::default()));
+        t.move_cursor(CursorMove::Jump(0, col));
+        t.insert_newline();
+        assert_eq!(t.lines(), [after], "{test:?}");
+        assert_eq!(t.cursor(), (0, cursor), "{test:?}");
+        t.undo();
+        assert_eq!(t.lines(), [line], "{test:?}");
+    }
+
+    // Each newline outdents the empty item by one level until it reaches the top level
+    let mut t = TextArea::from(["- a", "        - "]);
+    t.set_list_continuation(Some(ListContinuation::default()));
+    t.set_tab_length(2);
+    t.move_cursor(CursorMove::Bottom);
+    t.move_cursor(CursorMove::End);
+    for want in ["      - ", "    - ", "  - ", "- ", ""] {
+        t.insert_newline();
+        assert_eq!(t.lines(), ["- a", want]);
+    }
+}
+
+#[test]
+fn test_list_continuation_markers() {
+    let mut t = TextArea::from(["- foo", "* foo", "1. foo", "- [ ] foo"]);
+
+    // Disabled by default
+    t.move_cursor(CursorMove::End);
+    t.insert_newline();
+    assert_eq!(t.lines()[1], "");
+    t.undo();
+
+    t.set_list_continuation(Some(ListContinuation::new([ListMarker::Asterisk])));
+    assert_eq!(
+        t.list_continuation().unwrap().markers(),
+        [ListMarker::Asterisk]
+    );
+    for (row, want) in [(0, ""), (1, "* "), (2, ""), (3, "")] {
+        t.move_cursor(CursorMove::Jump(row, 0));
+        t.move_cursor(CursorMove::End);
+        t.insert_newline();
+        assert_eq!(t.lines()[row + 1], want, "row {row}");
+        t.undo();
+    }
+
+    // Task list items are continued as bullets when checkbox marker is not enabled
+    t.set_list_continuation(Some(ListContinuation::new([ListMarker::Dash])));
+    t.move_cursor(CursorMove::Jump(3, 0));
+    t.move_cursor(CursorMove::End);
+    t.insert_newline();
+    assert_eq!(t.lines()[4], "- ");
+}
+
+#[test]
+fn test_debug_state() {
+    let mut t = TextArea::from(["abc", "def"]);
+    t.move_cursor(CursorMove::Jump(0, 1));
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(1, 2));
+    t.set_mask_char('*');
+    t.set_list_continuation(Some(ListContinuation::default()));
+
+    let state = t.debug_state();
+    let want = "cursor=(1,2) selection=(0,1)-(1,2) viewport=0,0 0x0 lines=2 history=0/0/50 tab=4 hard_tab=false mask=Some('*') lnum=false align=Left list=true syntax=None theme=None";
+    assert!(state.starts_with(want), "{state}");
+    assert!(!state.contains('\n'), "{state}");
+
+    t.cut();
+    t.undo();
+    let state = t.debug_state();
+    assert!(state.contains(" selection=(0,1)-(1,2) "), "{state}");
+    assert!(state.contains(" history=0/1/50 "), "{state}");
+
+    t.redo();
+    let state = t.debug_state();
+    assert!(state.contains(" selection=None "), "{state}");
+}
+
+#[test]
+#[allow(clippy::single_range_in_vec_init)]
+fn test_is_modified() {
+    let mut t = TextArea::from(["abc"]);
+    assert!(!t.is_modified());
+
+    // Cursor moves and yanks are not modifications
+    t.move_cursor(CursorMove::End);
+    t.set_yank_text("xyz");
+    t.start_selection();
+    t.move_cursor(CursorMove::Head);
+    t.copy();
+    assert!(!t.is_modified());
+
+    t.move_cursor(CursorMove::End);
+    t.insert_char('d');
+    assert!(t.is_modified());
+    t.mark_saved();
+    assert!(!t.is_modified());
+
+    t.delete_char();
+    t.delete_char();
+    assert!(t.is_modified());
+    t.undo();
+    assert!(t.is_modified());
+    t.undo();
+    assert!(!t.is_modified());
+    t.undo();
+    assert!(t.is_modified());
+    t.redo();
+    assert!(!t.is_modified());
+    t.redo();
+    assert!(t.is_modified());
+
+    // Undo before the saved state, then a new edit makes the saved state unreachable
+    t.undo();
+    t.undo();
+    t.undo();
+    t.insert_char('e');
+    assert!(t.is_modified());
+    t.undo();
+    assert_eq!(t.lines(), ["abc"]);
+    assert!(t.is_modified());
+
+    // Resetting the history keeps the modified state
+    t.set_max_histories(10);
+    assert!(t.is_modified());
+    t.mark_saved();
+    t.set_max_histories(10);
+    assert!(!t.is_modified());
+
+    // Rejected edit is not a modification
+    t.set_protected_rows(vec![0..1]);
+    assert!(!t.insert_str("x"));
+    assert!(!t.is_modified());
+}
+
+#[test]
+fn test_history_group() {
+    let mut t = TextArea::default();
+    t.begin_history_group();
+    t.insert_str("ab");
+    t.begin_history_group();
+    t.insert_newline();
+    t.end_history_group();
+    // Undo and redo are not available while the group is open
+    assert!(!t.undo());
+    assert!(!t.redo());
+    t.insert_char('c');
+    t.end_history_group();
+    assert_eq!(t.lines(), ["ab", "c"]);
+
+    // Nested groups are flattened into one entry
+    assert!(t.undo());
+    assert_eq!(t.lines(), [""]);
+    assert_eq!(t.cursor(), (0, 0));
+    assert!(!t.undo());
+    assert!(t.redo());
+    assert_eq!(t.lines(), ["ab", "c"]);
+    assert_eq!(t.cursor(), (0, 0));
+
+    // Empty group adds nothing
+    t.begin_history_group();
+    t.move_cursor(CursorMove::Top);
+    t.end_history_group();
+    assert!(!t.redo());
+    assert!(t.undo());
+    assert_eq!(t.lines(), [""]);
+
+    // Unmatched end is ignored
+    t.end_history_group();
+    assert!(t.redo());
+    assert_eq!(t.lines(), ["ab", "c"]);
+}
+
+#[test]
+fn test_with_history_group() {
+    let mut t = TextArea::from(["abc"]);
+    let ret = t.with_history_group(|t| {
+        t.delete_next_char();
+        t.delete_next_char();
+        t.with_history_group(|t| t.delete_next_char())
+    });
+    assert!(ret);
+    assert!(t.is_empty());
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["abc"]);
+    assert!(!t.undo());
+
+    // The group is ended when the closure panics
+    let mut t = TextArea::from(["abc"]);
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        t.with_history_group(|t| {
+            t.delete_next_char();
+            panic!("oops");
+        })
+    }));
+    assert!(result.is_err());
+    assert_eq!(t.lines(), ["bc"]);
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["abc"]);
+}
+
+#[test]
+fn test_history_coalescing() {
+    fn undo_steps(t: &mut TextArea<'_>) -> usize {
+        let mut steps = 0;
+        while t.undo() {
+            steps += 1;
+        }
+        steps
+    }
+
+    fn type_str(t: &mut TextArea<'_>, s: &str) {
+        for c in s.chars() {
+            assert!(t.input(Input {
+                key: Key::Char(c),
+                ..Default::default()
+            }));
+        }
+    }
+
+    for (coalescing, want) in [
+        (Coalescing::PerChar, 11),
+        (Coalescing::PerWord, 2),
+        (Coalescing::TimeWindow(Duration::from_secs(3600)), 1),
+        (Coalescing::TimeWindow(Duration::ZERO), 11),
+        (Coalescing::Manual, 1),
+    ] {
+        let mut t = TextArea::default();
+        t.set_history_coalescing(coalescing);
+        assert_eq!(t.history_coalescing(), coalescing);
+        type_str(&mut t, "hello world");
+        assert_eq!(t.lines(), ["hello world"]);
+        assert_eq!(undo_steps(&mut t), want, "{coalescing:?}");
+        assert_eq!(t.lines(), [""], "{coalescing:?}");
+    }
+    assert_eq!(
+        TextArea::default().history_coalescing(),
+        Coalescing::PerChar
+    );
+
+    // Manual policy groups edits only with the explicit grouping API
+    let mut t = TextArea::default();
+    t.set_history_coalescing(Coalescing::Manual);
+    t.begin_history_group();
+    type_str(&mut t, "hello world");
+    t.end_history_group();
+    assert_eq!(undo_steps(&mut t), 1);
+
+    // Edits before and after an explicit group are separate undo units and moving the cursor does not split them
+    let mut t = TextArea::default();
+    t.set_history_coalescing(Coalescing::Manual);
+    type_str(&mut t, "ab");
+    t.move_cursor(CursorMove::Head);
+    type_str(&mut t, "cd");
+    t.with_history_group(|t| type_str(t, "ef"));
+    type_str(&mut t, "gh");
+    t.move_cursor(CursorMove::End);
+    assert!(t.delete_char());
+    assert_eq!(t.lines(), ["cdefgha"]);
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["cdefab"]);
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["cdab"]);
+    assert!(t.undo());
+    assert!(t.is_empty());
+    assert!(!t.undo());
+
+    // Typed characters are not merged into the explicit group
+    let mut t = TextArea::default();
+    t.set_history_coalescing(Coalescing::PerWord);
+    t.with_history_group(|t| type_str(t, "ab"));
+    type_str(&mut t, "cd");
+    assert_eq!(undo_steps(&mut t), 2);
+
+    // Words are undone one by one and redo restores them at once
+    let mut t = TextArea::default();
+    t.set_history_coalescing(Coalescing::PerWord);
+    t.set_undo_cursor(UndoCursor::Restore);
+    type_str(&mut t, "hello world");
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["hello "]);
+    assert_eq!(t.cursor(), (0, 6));
+    assert!(t.redo());
+    assert_eq!(t.lines(), ["hello world"]);
+    assert_eq!(t.cursor(), (0, 11));
+
+    // Deleting characters is merged separately from inserting them
+    for _ in 0..11 {
+        assert!(t.delete_char());
+    }
+    assert!(t.is_empty());
+    assert_eq!(undo_steps(&mut t), 4);
+
+    // Moving the cursor starts a new undo unit
+    let mut t = TextArea::default();
+    t.set_history_coalescing(Coalescing::TimeWindow(Duration::from_secs(3600)));
+    type_str(&mut t, "ab");
+    t.move_cursor(CursorMove::Head);
+    type_str(&mut t, "cd");
+    assert_eq!(t.lines(), ["cdab"]);
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["ab"]);
+    assert!(t.undo());
+    assert!(t.is_empty());
+
+    // The saved state is kept in its own undo unit
+    let mut t = TextArea::default();
+    t.set_history_coalescing(Coalescing::TimeWindow(Duration::from_secs(3600)));
+    type_str(&mut t, "ab");
+    t.mark_saved();
+    type_str(&mut t, "cd");
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["ab"]);
+    assert!(!t.is_modified());
+
+    // The policy is kept when the history is reset
+    t.set_max_histories(10);
+    assert_eq!(
+        t.history_coalescing(),
+        Coalescing::TimeWindow(Duration::from_secs(3600))
+    );
+}
+
+#[test]
+fn test_last_edit_info() {
+    fn info(t: &TextArea<'_>) -> (std::ops::Range<usize>, isize) {
+        let info = t.last_edit_info().unwrap();
+        (info.rows(), info.lines_delta())
+    }
+
+    let mut t = TextArea::from(["abc", "def", "ghi"]);
+    assert_eq!(t.last_edit_info(), None);
+
+    // Single-line edit
+    t.move_cursor(CursorMove::Jump(1, 1));
+    t.insert_char('x');
+    assert_eq!(info(&t), (1..2, 0));
+    t.delete_char();
+    assert_eq!(info(&t), (1..2, 0));
+
+    // Splitting and joining lines
+    t.insert_newline();
+    assert_eq!(info(&t), (1..3, 1));
+    t.delete_char();
+    assert_eq!(info(&t), (1..2, -1));
+
+    // Multi-line insertion and deletion
+    t.move_cursor(CursorMove::Jump(0, 1));
+    t.insert_str("x\ny\nz");
+    assert_eq!(t.lines(), ["ax", "y", "zbc", "def", "ghi"]);
+    assert_eq!(info(&t), (0..3, 2));
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(4, 1));
+    assert!(t.cut());
+    assert_eq!(t.lines(), ["ax", "y", "zhi"]);
+    assert_eq!(info(&t), (2..3, -2));
+
+    // Undo and redo
+    assert!(t.undo());
+    assert_eq!(info(&t), (2..5, 2));
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["abc", "def", "ghi"]);
+    assert_eq!(info(&t), (0..1, -2));
+    assert!(t.redo());
+    assert_eq!(info(&t), (0..3, 2));
+
+    // Grouped edits report the range covering all of them
+    let mut t = TextArea::from(["abc", "def", "ghi"]);
+    t.with_history_group(|t| {
+        t.move_cursor(CursorMove::Jump(2, 1));
+        t.insert_newline();
+        t.move_cursor(CursorMove::Jump(0, 0));
+        t.insert_char('x');
+    });
+    assert_eq!(t.lines(), ["xabc", "def", "g", "hi"]);
+    assert_eq!(info(&t), (0..4, 1));
+    assert!(t.undo());
+    assert_eq!(info(&t), (0..3, -1));
+    assert!(t.redo());
+    assert_eq!(info(&t), (0..4, 1));
+
+    // Rows between the grouped edits are included even when they are not modified
+    let mut t = TextArea::from(["abc", "def", "ghi", "jkl"]);
+    t.with_history_group(|t| {
+        t.move_cursor(CursorMove::Jump(3, 0));
+        t.delete_char();
+        t.move_cursor(CursorMove::Jump(1, 0));
+        t.insert_char('x');
+    });
+    assert_eq!(t.lines(), ["abc", "xdef", "ghijkl"]);
+    assert_eq!(info(&t), (1..3, -1));
+}
+
+#[test]
+fn test_history_entries() {
+    fn entries(t: &TextArea<'_>) -> Vec<(HistoryEditKind, (usize, usize), String, bool)> {
+        t.history()
+            .map(|e| {
+                (
+                    e.kind(),
+                    e.position(),
+                    e.preview().to_string(),
+                    e.is_undone(),
+                )
+            })
+            .collect()
+    }
+
+    let mut t = TextArea::default();
+    assert_eq!(t.history().count(), 0);
+    t.set_history_coalescing(Coalescing::PerWord);
+    for c in "hello world".chars() {
+        t.insert_char(c);
+    }
+    for _ in 0..5 {
+        t.delete_char();
+    }
+    t.insert_str("a\nb");
+    assert_eq!(
+        entries(&t),
+        [
+            (HistoryEditKind::Insert, (0, 0), "hello ".to_string(), false),
+            (HistoryEditKind::Insert, (0, 6), "world".to_string(), false),
+            (HistoryEditKind::Delete, (0, 6), "world".to_string(), false),
+            (HistoryEditKind::Insert, (0, 6), "a\nb".to_string(), false),
+        ],
+    );
+
+    // Replacing the selection
+    t.select_all();
+    t.insert_char('x');
+    assert_eq!(t.lines(), ["x"]);
+    let last = t.history().last().unwrap();
+    assert_eq!(last.kind(), HistoryEditKind::Replace);
+    assert_eq!(last.position(), (0, 0));
+    assert_eq!(last.preview(), "x");
+
+    // Undone entries
+    assert!(t.undo());
+    assert!(t.undo());
+    let undone: Vec<_> = t.history().map(|e| e.is_undone()).collect();
+    assert_eq!(undone, [false, false, false, true, true]);
+
+    // Long text is truncated
+    let mut t = TextArea::default();
+    let long = "a".repeat(HistoryEntry::PREVIEW_LEN + 1);
+    t.insert_str(&long);
+    let preview = t.history().next().unwrap().preview().to_string();
+    assert_eq!(preview, format!("{}…", &long[1..]));
+}
+
+#[test]
+fn test_max_history_bytes() {
+    let mut t = TextArea::default();
+    t.set_max_history_bytes(10);
+    assert_eq!(t.max_history_bytes(), 10);
+    for s in ["abcd", "efgh", "ijkl"] {
+        t.insert_str(s);
+    }
+    assert_eq!(t.history_memory_usage(), 8);
+
+    // Remaining entries can be undone and redone consistently
+    assert!(t.undo());
+    assert!(t.undo());
+    assert!(!t.undo());
+    assert_eq!(t.lines(), ["abcd"]);
+    assert!(t.redo());
+    assert!(t.redo());
+    assert_eq!(t.lines(), ["abcdefghijkl"]);
+
+    // Edits in a group are counted together
+    t.with_history_group(|t| {
+        t.insert_str("mnop");
+        t.insert_str("qrst");
+    });
+    assert_eq!(t.history_memory_usage(), 8);
+    assert!(t.undo());
+    assert!(!t.undo());
+    assert_eq!(t.lines(), ["abcdefghijkl"]);
+
+    // Entry larger than the budget is kept and only older entries are evicted
+    assert!(t.redo());
+    t.insert_str("uvwxyz01234");
+    assert_eq!(t.history_memory_usage(), 11);
+    let lines = t.lines().to_vec();
+    assert!(t.undo());
+    assert!(!t.undo());
+    assert_eq!(t.lines(), ["abcdefghmnopqrstijkl"]);
+    assert!(t.redo());
+    assert_eq!(t.lines(), lines);
+
+    // The large entry is evicted by the next edit
+    t.insert_str("a");
+    assert_eq!(t.history_memory_usage(), 1);
+    assert!(t.undo());
+    assert!(!t.undo());
+
+    // Lowering the budget evicts entries. Entries to redo are removed when their preceding entries are evicted
+    t.insert_str("abc");
+    t.insert_str("def");
+    assert!(t.undo());
+    assert!(t.undo());
+    t.set_max_history_bytes(5);
+    assert_eq!(t.history_memory_usage(), 0);
+    assert!(!t.redo());
+
+    // The budget is kept when the history is reset
+    t.set_max_histories(10);
+    assert_eq!(t.max_history_bytes(), 5);
+
+    let mut t = TextArea::default();
+    assert_eq!(t.max_history_bytes(), usize::MAX);
+    t.insert_str("a\nb");
+    t.insert_char('あ');
+    t.delete_char();
+    assert_eq!(t.history_memory_usage(), 8);
+}
+
+#[test]
+fn test_undo_redo_selection() {
+    // Inserting text over the selection is undone at once
+    let mut t = TextArea::from(["abc", "def"]);
+    t.move_cursor(CursorMove::Jump(1, 2));
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(0, 1));
+    assert!(t.insert_str("xy"));
+    assert_eq!(t.lines(), ["axyf"]);
+    assert_eq!(t.cursor(), (0, 3));
+    let selection = ((0, 1), (1, 2));
+    assert_undo_redo_selection(selection, (0, 1), &["abc", "def"], &["axyf"], &mut t, "");
+
+    // Typing over the selection
+    let mut t = TextArea::from(["abc"]);
+    t.start_selection();
+    t.move_cursor(CursorMove::End);
+    t.insert_char('x');
+    assert_undo_redo_selection(((0, 0), (0, 3)), (0, 3), &["abc"], &["x"], &mut t, "");
+
+    // Edits without selection do not restore any selection
+    t.insert_char('y');
+    assert!(t.undo());
+    assert!(!t.is_selecting());
+
+    // Block selection is restored with its mode
+    let mut t = TextArea::from(["abc", "def"]);
+    t.move_cursor(CursorMove::Jump(0, 1));
+    t.start_block_selection();
+    t.move_cursor(CursorMove::Jump(1, 2));
+    assert!(t.cut());
+    assert_eq!(t.lines(), ["ac", "df"]);
+    assert!(t.undo());
+    assert_eq!(t.selection_mode(), Some(SelectionMode::Block));
+    assert_eq!(t.selection_range(), Some(((0, 1), (1, 2))));
+    assert_eq!(t.cursor(), (1, 2));
+}
+
+#[test]
+fn test_insert_str_chunked() {
+    let text = "a\nbc\r\n\nd\ne";
+    for chunk_lines in 0..7 {
+        let ctx = format!("chunk_lines={chunk_lines}");
+        let mut t = TextArea::from(["xy"]);
+        t.move_cursor(CursorMove::Jump(0, 1));
+        let mut calls = vec![];
+        let inserted = t.insert_str_chunked(text, chunk_lines, |n, total| {
+            calls.push((n, total));
+            ControlFlow::Continue(())
+        });
+        assert!(inserted, "{ctx}");
+
+        let mut want = TextArea::from(["xy"]);
+        want.move_cursor(CursorMove::Jump(0, 1));
+        want.insert_str(text);
+        assert_eq!(t.lines(), want.lines(), "{ctx}");
+        assert_eq!(t.cursor(), want.cursor(), "{ctx}");
+
+        // Progress is also reported after the last chunk
+        let step = chunk_lines.max(1);
+        let mut want_calls: Vec<_> = (1..)
+            .map(|i| i * step)
+            .take_while(|n| *n < 5)
+            .map(|n| (n, 5))
+            .collect();
+        want_calls.push((5, 5));
+        assert_eq!(calls, want_calls, "{ctx}");
+
+        // Whole insertion is undone at once
+        assert!(t.undo(), "{ctx}");
+        assert_eq!(t.lines(), ["xy"], "{ctx}");
+        assert_eq!(t.cursor(), (0, 1), "{ctx}");
+        assert!(!t.undo(), "{ctx}");
+        assert!(t.redo(), "{ctx}");
+        assert_eq!(t.lines(), want.lines(), "{ctx}");
+    }
+}
+
+#[test]
+fn test_insert_str_chunked_abort() {
+    let mut t = TextArea::from(["abc", "def"]);
+    t.move_cursor(CursorMove::Jump(0, 1));
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(1, 1));
+
+    let mut calls = 0;
+    let inserted = t.insert_str_chunked("x\ny\nz", 1, |_, _| {
+        calls += 1;
+        if calls == 2 {
+            ControlFlow::Break(())
+        } else {
+            ControlFlow::Continue(())
+        }
+    });
+    assert!(!inserted);
+    assert_eq!(calls, 2);
+    assert_eq!(t.lines(), ["abc", "def"]);
+    assert_eq!(t.cursor(), (1, 1));
+    assert_eq!(t.selection_range(), Some(((0, 1), (1, 1))));
+    assert!(!t.undo());
+    assert!(!t.redo());
+
+    // Aborted insertion is reverted even if the edit history is disabled
+    t.set_max_histories(0);
+    t.cancel_selection();
+    t.insert_str_chunked("x\ny", 1, |_, _| ControlFlow::Break(()));
+    assert_eq!(t.lines(), ["abc", "def"]);
+
+    // The insertion can be aborted after the last chunk
+    let inserted = t.insert_str_chunked("x\ny", 2, |n, total| {
+        assert_eq!((n, total), (2, 2));
+        ControlFlow::Break(())
+    });
+    assert!(!inserted);
+    assert_eq!(t.lines(), ["abc", "def"]);
+}
+
+#[test]
+fn test_undo_redo_restore_cursor() {
+    let mut t = TextArea::from(["abc", "def"]);
+    t.set_undo_cursor(UndoCursor::Restore);
+
+    // Joining lines
+    t.move_cursor(CursorMove::Jump(1, 0));
+    assert!(t.delete_newline());
+    assert_eq!(t.cursor(), (0, 3));
+    assert!(t.undo());
+    assert_eq!(t.cursor(), (1, 0));
+    assert!(t.redo());
+    assert_eq!(t.cursor(), (0, 3));
+
+    // Multi-line paste
+    t.set_yank_text("x\ny");
+    t.move_cursor(CursorMove::Jump(0, 1));
+    assert!(t.paste());
+    assert_eq!(t.lines(), ["ax", "ybcdef"]);
+    assert_eq!(t.cursor(), (1, 1));
+    assert!(t.undo());
+    assert_eq!(t.cursor(), (0, 1));
+    assert!(t.redo());
+    assert_eq!(t.cursor(), (1, 1));
+
+    // Multi-line cut
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(0, 1));
+    assert!(t.cut());
+    assert_eq!(t.lines(), ["abcdef"]);
+    assert!(t.undo());
+    assert_eq!(t.cursor(), (0, 1));
+    assert_eq!(t.selection_range(), Some(((0, 1), (1, 1))));
+    assert!(t.redo());
+    assert_eq!(t.cursor(), (0, 1));
+}
+
+#[test]
+fn test_undo_redo_cursor_at_edit_start() {
+    // Multi-line cut with cursor at end of the selection
+    let mut t = TextArea::from(["abc", "def", "ghi"]);
+    t.move_cursor(CursorMove::Jump(0, 1));
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(2, 1));
+    assert!(t.cut());
+    assert_eq!(t.lines(), ["ahi"]);
+    assert_eq!(t.cursor(), (0, 1));
+    for _ in 0..2 {
+        // Undoing the edit made while selecting restores the selection and the cursor
+        assert!(t.undo());
+        assert_eq!(t.lines(), ["abc", "def", "ghi"]);
+        assert_eq!(t.cursor(), (2, 1));
+        assert_eq!(t.selection_range(), Some(((0, 1), (2, 1))));
+        assert!(t.redo());
+        assert_eq!(t.lines(), ["ahi"]);
+        assert_eq!(t.cursor(), (0, 1));
+    }
+
+    // Multi-line cut with cursor at start of the selection
+    let mut t = TextArea::from(["abc", "def", "ghi"]);
+    t.move_cursor(CursorMove::Jump(2, 1));
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(0, 2));
+    assert!(t.cut());
+    assert_eq!(t.lines(), ["abhi"]);
+    for _ in 0..2 {
+        assert!(t.undo());
+        assert_eq!(t.cursor(), (0, 2));
+        assert!(t.redo());
+        assert_eq!(t.cursor(), (0, 2));
+    }
+
+    // Single-line cut
+    let mut t = TextArea::from(["abcdef"]);
+    t.move_cursor(CursorMove::Jump(0, 4));
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(0, 1));
+    assert!(t.cut());
+    assert_eq!(t.lines(), ["aef"]);
+    for _ in 0..2 {
+        assert!(t.undo());
+        assert_eq!(t.lines(), ["abcdef"]);
+        assert_eq!(t.cursor(), (0, 1));
+        assert_eq!(t.selection_range(), Some(((0, 1), (0, 4))));
+        assert!(t.redo());
+        assert_eq!(t.lines(), ["aef"]);
+        assert_eq!(t.cursor(), (0, 1));
+    }
+
+    // Multi-line paste
+    let mut t = TextArea::from(["ab"]);
+    t.move_cursor(CursorMove::Jump(0, 1));
+    t.set_yank_text("x\nyz");
+    assert!(t.paste());
+    assert_eq!(t.lines(), ["ax", "yzb"]);
+    assert_eq!(t.cursor(), (1, 2));
+    for _ in 0..2 {
+        assert!(t.undo());
+        assert_eq!(t.lines(), ["ab"]);
+        assert_eq!(t.cursor(), (0, 1));
+        assert!(t.redo());
+        assert_eq!(t.lines(), ["ax", "yzb"]);
+        assert_eq!(t.cursor(), (0, 1));
+    }
+
+    // Joining lines
+    let mut t = TextArea::from(["ab", "cd"]);
+    t.move_cursor(CursorMove::Jump(1, 0));
+    assert!(t.delete_newline());
+    for _ in 0..2 {
+        assert!(t.undo());
+        assert_eq!(t.lines(), ["ab", "cd"]);
+        assert_eq!(t.cursor(), (0, 2));
+        assert!(t.redo());
+        assert_eq!(t.lines(), ["abcd"]);
+        assert_eq!(t.cursor(), (0, 2));
+    }
+}
+
+#[test]
+#[allow(clippy::single_range_in_vec_init)]
+fn test_protected_rows_reject_edits() {
+    let lines = ["head", "er", "body"];
+    let mut t = TextArea::from(lines);
+    t.set_protected_rows(vec![0..2]);
+
+    // Edits in protected rows are rejected
+    t.move_cursor(CursorMove::Jump(1, 1));
+    assert!(!t.input(Input {
+        key: Key::Char('x'),
+        ..Default::default()
+    }));
+    assert!(!t.insert_str("x"));
+    t.insert_newline();
+    assert!(!t.delete_char());
+    assert!(!t.delete_line_by_end());
+    assert_eq!(t.lines(), lines);
+    assert_eq!(t.cursor(), (1, 1));
+    assert_eq!(t.protected_row_error().unwrap().row(), 1);
+    assert_no_undo_redo(&mut t, "rejected edits");
+
+    // Joining the first editable row with the protected row is rejected
+    t.move_cursor(CursorMove::Jump(2, 0));
+    assert!(!t.delete_char());
+    assert_eq!(t.lines(), lines);
+
+    // Text in protected rows can be copied but not cut
+    t.move_cursor(CursorMove::Jump(1, 0));
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(2, 2));
+    t.copy();
+    assert_eq!(t.yank_text(), "er\nbo");
+    t.set_yank_text("yank");
+    t.move_cursor(CursorMove::Jump(1, 0));
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(2, 2));
+    assert!(!t.cut());
+    assert_eq!(t.lines(), lines);
+    assert_eq!(t.yank_text(), "yank");
+    assert_eq!(t.selection_range(), Some(((1, 0), (2, 2))));
+
+    // Paste into protected rows is rejected
+    t.cancel_selection();
+    t.move_cursor(CursorMove::Top);
+    assert!(!t.paste());
+    assert_eq!(t.lines(), lines);
+
+    // Other rows are editable
+    t.move_cursor(CursorMove::Jump(2, 0));
+    assert!(t.insert_str("x"));
+    assert_eq!(t.lines(), ["head", "er", "xbody"]);
+    assert_eq!(t.protected_row_error(), None);
+
+    // Undo and redo work outside protected rows
+    assert!(t.undo());
+    assert!(t.redo());
+    assert_eq!(t.lines(), ["head", "er", "xbody"]);
+
+    // Removing the protection makes the rows editable
+    t.set_protected_rows(vec![]);
+    t.move_cursor(CursorMove::Top);
+    assert!(t.insert_str("x"));
+    assert_eq!(t.lines(), ["xhead", "er", "xbody"]);
+}
+
+#[test]
+#[allow(clippy::single_range_in_vec_init)]
+fn test_protected_rows_shift_with_edits() {
+    let mut t = TextArea::from(["a", "b", "protected", "c"]);
+    t.set_protected_rows(vec![2..3]);
+
+    // Inserting rows above shifts the protected rows
+    t.move_cursor(CursorMove::Jump(1, 1));
+    t.insert_str("\nx\ny");
+    assert_eq!(t.protected_rows(), [4..5]);
+    assert!(t.is_row_protected(4));
+    assert!(!t.is_row_protected(2));
+
+    // Removing rows above shifts the protected rows back
+    t.move_cursor(CursorMove::Jump(0, 0));
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(3, 0));
+    assert!(t.cut());
+    assert_eq!(t.lines(), ["y", "protected", "c"]);
+    assert_eq!(t.protected_rows(), [1..2]);
+
+    // Edits below the protected rows don't shift them
+    t.move_cursor(CursorMove::Bottom);
+    t.insert_newline();
+    assert_eq!(t.protected_rows(), [1..2]);
+
+    // Undo and redo shift the protected rows as well
+    assert!(t.undo());
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["a", "b", "x", "y", "protected", "c"]);
+    assert_eq!(t.protected_rows(), [4..5]);
+    assert!(t.redo());
+    assert_eq!(t.protected_rows(), [1..2]);
+}
+
+#[test]
+#[allow(clippy::single_range_in_vec_init)]
+fn test_protected_rows_reject_undo_redo() {
+    let mut t = TextArea::from(["abc", "def"]);
+    t.insert_str("x");
+    t.move_cursor(CursorMove::Jump(1, 0));
+    t.insert_str("y");
+    assert!(t.undo());
+
+    // Both undo and redo would modify the protected row
+    t.set_protected_rows(vec![0..2]);
+    assert!(!t.undo());
+    assert_eq!(t.protected_row_error().unwrap().row(), 0);
+    assert!(!t.redo());
+    assert_eq!(t.protected_row_error().unwrap().row(), 1);
+    assert_eq!(t.lines(), ["xabc", "def"]);
+
+    // Undo via key input is also rejected
+    assert!(!t.input(Input {
+        key: Key::Char('u'),
+        ctrl: true,
+        ..Default::default()
+    }));
+    assert_eq!(t.lines(), ["xabc", "def"]);
+    assert!(t.protected_row_error().is_some());
+
+    t.set_protected_rows(vec![1..2]);
+    assert!(!t.redo());
+    assert_eq!(t.protected_row_error().unwrap().row(), 1);
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["abc", "def"]);
+}
+
+#[test]
+fn test_jump_list() {
+    let mut t: TextArea = (0..10).map(|i| i.to_string()).collect();
+
+    // Small cursor moves are not remembered
+    for m in [
+        CursorMove::Down,
+        CursorMove::Forward,
+        CursorMove::Back,
+        CursorMove::Up,
+    ] {
+        t.move_cursor(m);
+    }
+    assert!(t.jumps().is_empty());
+    assert!(!t.jump_back());
+
+    t.move_cursor(CursorMove::Jump(5, 0));
+    t.move_cursor(CursorMove::Bottom);
+    assert_eq!(t.jumps(), [(0, 0), (5, 0)]);
+
+    assert!(t.jump_back());
+    assert_eq!(t.cursor(), (5, 0));
+    assert!(t.jump_back());
+    assert_eq!(t.cursor(), (0, 0));
+    assert!(!t.jump_back());
+    assert!(t.jump_forward());
+    assert_eq!(t.cursor(), (5, 0));
+
+    // New jump while walking the list discards the forward positions
+    t.move_cursor(CursorMove::Jump(2, 0));
+    assert_eq!(t.jumps(), [(0, 0), (5, 0)]);
+    assert!(!t.jump_forward());
+    assert!(t.jump_back());
+    assert_eq!(t.cursor(), (5, 0));
+    assert!(t.jump_forward());
+    assert_eq!(t.cursor(), (2, 0));
+
+    // Stale positions are clamped after edits
+    t.move_cursor(CursorMove::Jump(9, 1));
+    t.move_cursor(CursorMove::Top);
+    t.select_all();
+    t.insert_str("abc");
+    assert_eq!(t.lines(), ["abc"]);
+    assert!(t.jump_back());
+    assert_eq!(t.cursor(), (0, 1));
+
+    // The number of positions is bounded
+    let mut t: TextArea = (0..10).map(|i| i.to_string()).collect();
+    for _ in 0..100 {
+        t.move_cursor(CursorMove::Bottom);
+        t.move_cursor(CursorMove::Top);
+    }
+    assert_eq!(t.jumps().len(), 100);
+}
+
+#[test]
+fn test_insert_str_at() {
+    let mut t = TextArea::from(["fn main() {", "    foo(a)", "}"]);
+    t.move_cursor(CursorMove::Jump(1, 8));
+    t.start_selection();
+    t.move_cursor(CursorMove::Forward);
+
+    // Before the cursor on the same line
+    assert!(t.insert_str_at(1, 4, "bar::"));
+    assert_eq!(t.lines(), ["fn main() {", "    bar::foo(a)", "}"]);
+    assert_eq!(t.cursor(), (1, 14));
+    assert_eq!(t.selection_range(), Some(((1, 13), (1, 14))));
+
+    // Lines above the cursor
+    assert!(t.insert_str_at(0, 0, "use x;\n\n"));
+    assert_eq!(
+        t.lines(),
+        ["use x;", "", "fn main() {", "    bar::foo(a)", "}"]
+    );
+    assert_eq!(t.cursor(), (3, 14));
+    assert_eq!(t.selection_range(), Some(((3, 13), (3, 14))));
+
+    // After the cursor. Out-of-range position is clamped
+    assert!(t.insert_str_at(10, 10, "\n"));
+    assert_eq!(t.lines().len(), 6);
+    assert_eq!(t.cursor(), (3, 14));
+    assert!(!t.insert_str_at(0, 0, ""));
+
+    // Text inserted at the cursor is placed before it
+    let mut t = TextArea::from(["ab"]);
+    t.move_cursor(CursorMove::Forward);
+    assert!(t.insert_str_at(0, 1, "x\ny"));
+    assert_eq!(t.lines(), ["ax", "yb"]);
+    assert_eq!(t.cursor(), (1, 1));
+
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["ab"]);
+    assert_eq!(t.cursor(), (0, 1));
+    assert!(t.redo());
+    assert_eq!(t.lines(), ["ax", "yb"]);
+}
+
+#[test]
+fn test_delete_str_at() {
+    let mut t = TextArea::from(["use x;", "", "fn main() {", "    foo(a,)", "}"]);
+    t.move_cursor(CursorMove::Jump(3, 4));
+    t.start_selection();
+    t.move_cursor(CursorMove::End);
+    t.set_yank_text("yank");
+
+    // After the cursor
+    assert!(t.delete_str_at(3, 9, 1));
+    assert_eq!(t.lines()[3], "    foo(a)");
+    assert_eq!(t.cursor(), (3, 10));
+    assert_eq!(t.selection_range(), Some(((3, 4), (3, 10))));
+
+    // Lines above the cursor
+    assert!(t.delete_str_at(0, 0, 8));
+    assert_eq!(t.lines(), ["fn main() {", "    foo(a)", "}"]);
+    assert_eq!(t.selection_range(), Some(((1, 4), (1, 10))));
+
+    // Range containing the selection anchor. Out-of-range count is clamped
+    assert!(t.delete_str_at(1, 2, 4));
+    assert_eq!(t.lines(), ["fn main() {", "  o(a)", "}"]);
+    assert_eq!(t.selection_range(), Some(((1, 2), (1, 6))));
+    assert!(t.delete_str_at(2, 0, 100));
+    assert_eq!(t.lines(), ["fn main() {", "  o(a)", ""]);
+    assert!(!t.delete_str_at(2, 0, 1));
+    assert!(!t.delete_str_at(0, 0, 0));
+    assert_eq!(t.yank_text(), "yank");
+
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["fn main() {", "  o(a)", "}"]);
+}
+
+#[test]
+fn test_delete_range() {
+    let mut t = TextArea::from(["abc", "def", "ghi"]);
+    t.move_cursor(CursorMove::Bottom);
+    t.move_cursor(CursorMove::End);
+    t.start_selection();
+    t.move_cursor(CursorMove::Back);
+    t.set_yank_text("yank");
+
+    // Reversed positions in the same line
+    assert_eq!(t.delete_range((0, 2), (0, 1)), "b");
+    assert_eq!(t.lines(), ["ac", "def", "ghi"]);
+    assert_eq!(t.cursor(), (0, 1));
+    // The selection anchor is kept on the same text
+    assert_eq!(t.selection_range(), Some(((0, 1), (2, 3))));
+
+    // Multi-line range with columns past the line ends
+    assert_eq!(t.delete_range((0, 1), (1, 10)), "c\ndef");
+    assert_eq!(t.lines(), ["a", "ghi"]);
+    assert_eq!(t.cursor(), (0, 1));
+    assert_eq!(t.selection_range(), Some(((0, 1), (1, 3))));
+
+    // Empty range
+    assert_eq!(t.delete_range((1, 1), (1, 1)), "");
+    assert_eq!(t.cursor(), (1, 1));
+    assert_eq!(t.lines(), ["a", "ghi"]);
+    assert_eq!(t.yank_text(), "yank");
+
+    // Each deletion is one undo entry
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["ac", "def", "ghi"]);
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["abc", "def", "ghi"]);
+    assert!(t.redo());
+    assert_eq!(t.lines(), ["ac", "def", "ghi"]);
+
+    // Range past the end of text
+    let mut t = TextArea::from(["abc", "def"]);
+    assert_eq!(t.delete_range((5, 5), (0, 0)), "abc\ndef");
+    assert_eq!(t.lines(), [""]);
+}
+
+#[test]
+fn test_set_lines() {
+    let mut t = TextArea::from(["abc", "def", "ghi"]);
+    t.set_tab_length(2);
+    t.set_yank_text("yank");
+    t.move_cursor(CursorMove::Bottom);
+    t.move_cursor(CursorMove::End);
+    t.start_selection();
+    t.move_cursor(CursorMove::Back);
+
+    t.set_lines(vec!["x".to_string(), "yz".to_string()]);
+    assert_eq!(t.lines(), ["x", "yz"]);
+    assert_eq!(t.cursor(), (1, 2));
+    assert!(!t.is_selecting());
+    assert_eq!(t.tab_length(), 2);
+    assert_eq!(t.yank_text(), "yank");
+
+    t.set_text("");
+    assert_eq!(t.lines(), [""]);
+    assert_eq!(t.cursor(), (0, 0));
+    t.set_lines(vec![]);
+    assert_eq!(t.lines(), [""]);
+    t.set_text("a\r\nb\n");
+    assert_eq!(t.lines(), ["a", "b", ""]);
+
+    // Each replacement is one undo entry
+    assert!(t.undo());
+    assert_eq!(t.lines(), [""]);
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["x", "yz"]);
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["abc", "def", "ghi"]);
+    assert_eq!(t.cursor(), (2, 2));
+    assert!(t.redo());
+    assert_eq!(t.lines(), ["x", "yz"]);
+}
+
+#[test]
+fn test_duplicate() {
+    // Cursor line
+    let mut t = TextArea::from(["abc", "def"]);
+    t.set_undo_cursor(UndoCursor::Restore);
+    t.set_yank_text("yank");
+    t.move_cursor(CursorMove::Forward);
+    assert!(t.duplicate());
+    assert_eq!(t.lines(), ["abc", "abc", "def"]);
+    assert_eq!(t.cursor(), (1, 1));
+
+    // Last line does not add an empty line
+    t.move_cursor(CursorMove::Bottom);
+    assert!(t.duplicate());
+    assert_eq!(t.lines(), ["abc", "abc", "def", "def"]);
+    assert_eq!(t.cursor(), (3, 1));
+    assert_eq!(t.yank_text(), "yank");
+
+    // One undo entry
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["abc", "abc", "def"]);
+    assert_eq!(t.cursor(), (2, 1));
+
+    // Character-wise selection across lines
+    let mut t = TextArea::from(["abc", "def"]);
+    t.move_cursor(CursorMove::Jump(1, 1));
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(0, 2));
+    assert!(t.duplicate());
+    assert_eq!(t.lines(), ["abc", "dc", "def"]);
+    assert_eq!(t.selection_range(), Some(((0, 2), (1, 1))));
+    assert_eq!(t.cursor(), (0, 2));
+
+    // Line-wise selection
+    let mut t = TextArea::from(["a", "b", "c"]);
+    t.move_cursor(CursorMove::Down);
+    t.start_line_selection();
+    t.move_cursor(CursorMove::Down);
+    assert!(t.duplicate());
+    assert_eq!(t.lines(), ["a", "b", "c", "b", "c"]);
+    assert_eq!(t.cursor(), (2, 0));
+    assert!(t.is_selecting());
+
+    // Block-wise selection
+    let mut t = TextArea::from(["abcd", "e", "fghi"]);
+    t.move_cursor(CursorMove::Forward);
+    t.start_block_selection();
+    t.move_cursor(CursorMove::Jump(2, 3));
+    assert!(t.duplicate());
+    assert_eq!(t.lines(), ["abcbcd", "e", "fghghi"]);
+    assert_eq!(t.cursor(), (2, 3));
+    assert_eq!(t.yank_text(), "");
+}
+
+#[test]
+fn test_move_lines() {
+    let mut t = TextArea::from(["a", "bb", "cc", "d"]);
+    t.move_cursor(CursorMove::Jump(1, 1));
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(2, 2));
+
+    assert!(t.move_lines_down());
+    assert_eq!(t.lines(), ["a", "d", "bb", "cc"]);
+    assert_eq!(t.selection_range(), Some(((2, 1), (3, 2))));
+    assert!(!t.move_lines_down());
+
+    assert!(t.move_lines_up());
+    assert!(t.move_lines_up());
+    assert_eq!(t.lines(), ["bb", "cc", "a", "d"]);
+    assert_eq!(t.selection_range(), Some(((0, 1), (1, 2))));
+    assert!(!t.move_lines_up());
+
+    // Each move is undone one step at a time
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["a", "bb", "cc", "d"]);
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["a", "d", "bb", "cc"]);
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["a", "bb", "cc", "d"]);
+    assert_eq!(t.cursor(), (2, 2));
+
+    // Only the line-wise selected line is moved
+    let mut t = TextArea::from(["a", "b", "c"]);
+    t.start_line_selection();
+    assert!(t.move_lines_down());
+    assert_eq!(t.lines(), ["b", "a", "c"]);
+    assert_eq!(t.selection_range(), Some(((1, 0), (2, 0))));
+    assert!(t.move_lines_up());
+    assert_eq!(t.lines(), ["a", "b", "c"]);
+    assert_eq!(t.selection_range(), Some(((0, 0), (1, 0))));
+
+    // Selection ending at the head of the last line touches the last line
+    let mut t = TextArea::from(["a", "b", "c"]);
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(2, 0));
+    assert!(!t.move_lines_down());
+    assert_eq!(t.lines(), ["a", "b", "c"]);
+    assert_eq!(t.selection_range(), Some(((0, 0), (2, 0))));
+    assert_eq!(t.render_to_string(3, 3), "a  \nb  \nc  ");
+
+    // Cursor line with Alt+Up and Alt+Down
+    let mut t = TextArea::from(["a", "b"]);
+    let alt = |key| Input {
+        key,
+        alt: true,
+        ..Default::default()
+    };
+    assert!(t.input(alt(Key::Down)));
+    assert_eq!(t.lines(), ["b", "a"]);
+    assert_eq!(t.cursor(), (1, 0));
+    assert!(t.input(alt(Key::Up)));
+    assert_eq!(t.lines(), ["a", "b"]);
+    assert_eq!(t.cursor(), (0, 0));
+}
+
+#[test]
+fn test_auto_indent() {
+    let mut t = TextArea::from(["  \tfoo", "bar"]);
+    t.set_auto_indent(true);
+
+    // Only indentation before the cursor is copied
+    t.move_cursor(CursorMove::Forward);
+    t.insert_newline();
+    assert_eq!(t.lines(), [" ", " foo", "bar"]);
+    assert_eq!(t.cursor(), (1, 1));
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["  \tfoo", "bar"]);
+    assert_eq!(t.cursor(), (0, 1));
+
+    t.move_cursor(CursorMove::Jump(0, 4));
+    t.insert_newline();
+    assert_eq!(t.lines(), ["  \tf", "  \too", "bar"]);
+    assert_eq!(t.cursor(), (1, 3));
+
+    // Undo removes the newline and the indentation together
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["  \tfoo", "bar"]);
+    assert!(t.redo());
+    assert_eq!(t.lines(), ["  \tf", "  \too", "bar"]);
+
+    // No indentation
+    t.move_cursor(CursorMove::Jump(2, 1));
+    t.insert_newline();
+    assert_eq!(t.lines(), ["  \tf", "  \too", "b", "ar"]);
+    assert_eq!(t.cursor(), (3, 0));
+
+    // Disabled by default
+    let mut t = TextArea::from(["  foo"]);
+    t.move_cursor(CursorMove::End);
+    t.insert_newline();
+    assert_eq!(t.lines(), ["  foo", ""]);
+}
+
+#[test]
+fn test_auto_pairs() {
+    let mut t = TextArea::default();
+
+    // Inert when disabled
+    for c in "(\"".chars() {
+        t.insert_char(c);
+    }
+    assert_eq!(t.lines(), ["(\""]);
+    assert!(t.delete_char());
+    assert_eq!(t.lines(), ["("]);
+
+    let mut t = TextArea::default();
+    t.set_auto_pairs(true);
+    for c in "f(a, [".chars() {
+        t.input(Input {
+            key: Key::Char(c),
+            ..Default::default()
+        });
+    }
+    assert_eq!(t.lines(), ["f(a, [])"]);
+    assert_eq!(t.cursor(), (0, 6));
+
+    // Closing character steps over the next one
+    t.insert_char(']');
+    t.insert_char(')');
+    assert_eq!(t.lines(), ["f(a, [])"]);
+    assert_eq!(t.cursor(), (0, 8));
+    t.insert_char(')');
+    assert_eq!(t.lines(), ["f(a, []))"]);
+
+    // Inserting a pair is one undo entry
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["f(a, [])"]);
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["f(a, )"]);
+    assert_eq!(t.cursor(), (0, 5));
+
+    // Backspace in an empty pair deletes both
+    t.insert_char('{');
+    assert_eq!(t.lines(), ["f(a, {})"]);
+    assert!(t.delete_char());
+    assert_eq!(t.lines(), ["f(a, )"]);
+    assert_eq!(t.cursor(), (0, 5));
+
+    // Nested pairs are tracked across edits inside and before them
+    t.insert_char('[');
+    t.insert_char('(');
+    t.insert_char('x');
+    t.move_cursor(CursorMove::Head);
+    t.insert_str("let ");
+    t.move_cursor(CursorMove::Jump(0, 12));
+    assert_eq!(t.lines(), ["let f(a, [(x)])"]);
+    assert!(t.delete_char());
+    assert_eq!(t.lines(), ["let f(a, [()])"]);
+    assert!(t.delete_char());
+    assert_eq!(t.lines(), ["let f(a, [])"]);
+    assert!(t.delete_char());
+    assert_eq!(t.lines(), ["let f(a, )"]);
+
+    // Backspace deletes only one character of a pair which was not inserted by auto pairs
+    let mut t = TextArea::from(["()"]);
+    t.set_auto_pairs(true);
+    t.move_cursor(CursorMove::Forward);
+    assert!(t.delete_char());
+    assert_eq!(t.lines(), [")"]);
+
+    // Quotes are not paired after a word character
+    let mut t = TextArea::default();
+    t.set_auto_pairs(true);
+    for c in "don't".chars() {
+        t.insert_char(c);
+    }
+    assert_eq!(t.lines(), ["don't"]);
+    t.insert_char(' ');
+    t.insert_char('"');
+    assert_eq!(t.lines(), ["don't \"\""]);
+    assert_eq!(t.cursor(), (0, 7));
+    t.insert_char('"');
+    assert_eq!(t.lines(), ["don't \"\""]);
+    assert_eq!(t.cursor(), (0, 8));
+}
+
+#[test]
+fn test_sort_lines() {
+    let mut t = TextArea::from(["x", "c", "a", "b", "a", "y"]);
+    // Partially selected lines are sorted as whole lines
+    t.move_cursor(CursorMove::Jump(1, 1));
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(4, 0));
+    assert!(t.sort_lines(SortOrder::Descending, false));
+    assert_eq!(t.lines(), ["x", "c", "b", "a", "a", "y"]);
+    assert_eq!(t.selection_range(), Some(((1, 1), (4, 0))));
+
+    // Removing duplicates shrinks the selection into the sorted lines
+    assert!(t.sort_lines(SortOrder::Ascending, true));
+    assert_eq!(t.lines(), ["x", "a", "b", "c", "y"]);
+    assert_eq!(t.selection_range(), Some(((1, 1), (3, 0))));
+    assert!(!t.sort_lines(SortOrder::Ascending, true));
+
+    // One undo entry
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["x", "c", "b", "a", "a", "y"]);
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["x", "c", "a", "b", "a", "y"]);
+
+    // Only the line-wise selected lines are sorted
+    let mut l = TextArea::from(["c", "b", "a"]);
+    l.start_line_selection();
+    assert!(!l.sort_lines(SortOrder::Ascending, false));
+    l.move_cursor(CursorMove::Down);
+    assert!(l.sort_lines(SortOrder::Ascending, false));
+    assert_eq!(l.lines(), ["b", "c", "a"]);
+
+    // All lines without selection
+    t.cancel_selection();
+    assert!(t.sort_lines(SortOrder::Ascending, true));
+    assert_eq!(t.lines(), ["a", "b", "c", "x", "y"]);
+    assert_eq!(t.cursor(), (4, 0));
+}
+
+#[test]
+fn test_trim_trailing_whitespace() {
+    let mut t = TextArea::from(["a  ", "b", " \t", "c d \t "]);
+    t.move_cursor(CursorMove::Jump(0, 3));
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(3, 2));
+
+    assert_eq!(t.trim_trailing_whitespace(), 3);
+    assert_eq!(t.lines(), ["a", "b", "", "c d"]);
+    // The selection anchor was in the removed whitespaces
+    assert_eq!(t.selection_range(), Some(((0, 1), (3, 2))));
+    assert_eq!(t.trim_trailing_whitespace(), 0);
+
+    // One undo entry
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["a  ", "b", " \t", "c d \t "]);
+    assert!(!t.undo());
+}
+
+#[test]
+fn test_increment_number() {
+    let mut t = TextArea::from(["width: 0x0f;", "depth: 007", "none"]);
+    t.set_undo_cursor(UndoCursor::Restore);
+
+    assert!(t.increment_number(1));
+    assert_eq!(t.lines()[0], "width: 0x10;");
+    assert_eq!(t.cursor(), (0, 10));
+
+    t.move_cursor(CursorMove::Down);
+    t.move_cursor(CursorMove::Head);
+    assert!(t.increment_number(-8));
+    assert_eq!(t.lines()[1], "depth: -001");
+    assert_eq!(t.cursor(), (1, 10));
+    assert!(!t.increment_number(0));
+
+    t.move_cursor(CursorMove::Down);
+    assert!(!t.increment_number(1));
+    assert_eq!(t.cursor(), (2, 4));
+
+    // Each call is one undo entry
+    assert!(t.undo());
+    assert_eq!(t.lines()[1], "depth: 007");
+    assert_eq!(t.cursor(), (1, 0));
+    assert!(t.undo());
+    assert_eq!(t.lines()[0], "width: 0x0f;");
+    assert!(!t.undo());
+}
+
+#[test]
+fn test_insert_str_block() {
+    let mut t = TextArea::from(["abcd", "a", "", "abcd"]);
+    t.move_cursor(CursorMove::Jump(0, 2));
+    t.start_block_selection();
+    t.move_cursor(CursorMove::Jump(3, 3));
+
+    // Short lines are skipped by default
+    assert!(t.insert_str_block("XY\nZ"));
+    assert_eq!(t.lines(), ["abXYcd", "a", "", "abXYcd"]);
+    assert_eq!(t.cursor(), (0, 4));
+    assert!(!t.is_selecting());
+
+    // One undo entry
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["abcd", "a", "", "abcd"]);
+    assert_eq!(t.selection_range(), Some(((0, 2), (3, 3))));
+
+    t.set_block_insert_padding(true);
+    assert!(t.insert_str_block("-"));
+    assert_eq!(t.lines(), ["ab-cd", "a -", "  -", "ab-cd"]);
+    assert_eq!(t.cursor(), (0, 3));
+
+    // Without block selection, the text is inserted at the cursor
+    assert!(t.insert_str_block("!"));
+    assert_eq!(t.lines()[0], "ab-!cd");
+    assert!(!t.insert_str_block(""));
+}
+
+#[test]
+fn test_text_object() {
+    let mut t = TextArea::from(["let s = f(\"foo bar\", (1, 2));", "if x {", "    y", "}"]);
+
+    // ci"
+    t.move_cursor(CursorMove::Jump(0, 13));
+    let (start, end) = t.text_object_range(TextObject::InnerQuote('"')).unwrap();
+    assert_eq!((start, end), ((0, 11), (0, 18)));
+    assert_eq!(t.delete_range(start, end), "foo bar");
+    t.insert_str("baz");
+    assert_eq!(t.lines()[0], "let s = f(\"baz\", (1, 2));");
+
+    // da( selects the outer pair when the cursor is not in the nested pair
+    t.move_cursor(CursorMove::Jump(0, 15));
+    assert!(t.select_text_object(TextObject::APair('(', ')')));
+    assert_eq!(t.selection_range(), Some(((0, 9), (0, 24))));
+    t.cut();
+    assert_eq!(t.lines()[0], "let s = f;");
+    assert_eq!(t.yank_text(), "(\"baz\", (1, 2))");
+
+    // di{ removes the lines between braces
+    t.move_cursor(CursorMove::Jump(3, 0));
+    assert!(t.select_text_object(TextObject::InnerPair('{', '}')));
+    t.cut();
+    assert_eq!(t.lines(), ["let s = f;", "if x {", "", "}"]);
+
+    // diw
+    t.move_cursor(CursorMove::Jump(1, 3));
+    let (start, end) = t.text_object_range(TextObject::InnerWord).unwrap();
+    assert_eq!(t.delete_range(start, end), "x");
+    assert_eq!(t.lines()[1], "if  {");
+
+    // Not found
+    t.move_cursor(CursorMove::Jump(0, 0));
+    assert!(!t.select_text_object(TextObject::InnerPair('[', ']')));
+    assert!(!t.is_selecting());
+    assert_eq!(t.text_object_range(TextObject::AQuote('\'')), None);
+}
+
+#[test]
+fn test_paragraph_text_object() {
+    let mut t = TextArea::from(["a", "b", "", "c", "d", "", "", "e"]);
+
+    // dap
+    t.move_cursor(CursorMove::Jump(4, 0));
+    assert!(t.select_text_object(TextObject::AParagraph));
+    t.cut();
+    assert_eq!(t.lines(), ["a", "b", "", "e"]);
+    assert_eq!(t.yank_text(), "c\nd\n\n\n");
+
+    // dip on the last paragraph removes the line break before it
+    t.move_cursor(CursorMove::Jump(3, 0));
+    let (start, end) = t.text_object_range(TextObject::InnerParagraph).unwrap();
+    assert_eq!(t.delete_range(start, end), "\ne");
+    assert_eq!(t.lines(), ["a", "b", ""]);
+
+    // Blank lines at the end
+    t.move_cursor(CursorMove::Jump(2, 0));
+    let (start, end) = t.text_object_range(TextObject::AParagraph).unwrap();
+    assert_eq!(t.delete_range(start, end), "\n");
+    assert_eq!(t.lines(), ["a", "b"]);
+
+    // The entire text
+    assert!(t.select_text_object(TextObject::AParagraph));
+    t.cut();
+    assert_eq!(t.lines(), [""]);
+}
+
+#[test]
+fn test_repeat_last_edit() {
+    let mut t = TextArea::from(["abc", "def", "g"]);
+    assert!(!t.repeat_last_edit());
+
+    t.set_edit_recording(true);
+    assert!(!t.repeat_last_edit());
+
+    // Typed text in one history group is repeated at once
+    t.begin_history_group();
+    for c in "xy".chars() {
+        t.insert_char(c);
+    }
+    t.insert_newline();
+    t.end_history_group();
+    assert_eq!(t.lines(), ["xy", "abc", "def", "g"]);
+
+    // Cursor moves and undo/redo do not replace the recorded change
+    t.move_cursor(CursorMove::Jump(2, 1));
+    t.undo();
+    t.redo();
+    t.move_cursor(CursorMove::Jump(2, 1));
+    assert!(t.repeat_last_edit());
+    assert_eq!(t.lines(), ["xy", "abc", "dxy", "ef", "g"]);
+    assert_eq!(t.cursor(), (3, 0));
+
+    // Repeating is one undo entry
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["xy", "abc", "def", "g"]);
+
+    // Deleting is clamped to the end of the line
+    t.move_cursor(CursorMove::Jump(1, 0));
+    t.delete_str(3);
+    assert_eq!(t.lines(), ["xy", "", "def", "g"]);
+    t.move_cursor(CursorMove::Jump(3, 0));
+    assert!(t.repeat_last_edit());
+    assert_eq!(t.lines(), ["xy", "", "def", ""]);
+    assert!(!t.repeat_last_edit());
+
+    // Line-wise deletion deletes entire lines from the cursor line
+    let mut t = TextArea::from(["a", "bcd", "e", "fg", "h"]);
+    t.set_edit_recording(true);
+    t.start_line_selection();
+    t.cut();
+    assert_eq!(t.lines(), ["bcd", "e", "fg", "h"]);
+    t.move_cursor(CursorMove::Jump(0, 2));
+    assert!(t.repeat_last_edit());
+    assert_eq!(t.lines(), ["e", "fg", "h"]);
+    t.move_cursor(CursorMove::Jump(2, 0));
+    assert!(t.repeat_last_edit());
+    assert_eq!(t.lines(), ["e", "fg"]);
+    assert_eq!(t.cursor(), (1, 0));
+
+    // Resetting the history keeps the recording enabled
+    t.set_max_histories(10);
+    assert!(t.edit_recording());
+
+    // Disabling the recording forgets the change
+    t.set_edit_recording(false);
+    assert!(!t.repeat_last_edit());
+}
+
+#[test]
+fn test_marks() {
+    let mut t = TextArea::from(["abc", "def", "ghi", "jkl"]);
+    t.move_cursor(CursorMove::Jump(1, 2));
+    t.set_mark('a');
+    t.move_cursor(CursorMove::Jump(3, 1));
+    t.set_mark('b');
+    t.move_cursor(CursorMove::Jump(2, 0));
+    t.set_mark('c');
+
+    // Marks follow inserted lines and characters
+    t.move_cursor(CursorMove::Top);
+    t.insert_str("x\ny");
+    assert_eq!(t.mark('a'), Some((2, 2)));
+    assert_eq!(t.mark('c'), Some((3, 0)));
+    t.move_cursor(CursorMove::Jump(2, 0));
+    t.insert_char('z');
+    assert_eq!(t.mark('a'), Some((2, 3)));
+
+    // Deleting the marked line moves the mark to the next line
+    t.move_cursor(CursorMove::Jump(3, 1));
+    t.start_line_selection();
+    t.cut();
+    assert_eq!(t.lines(), ["x", "yabc", "zdef", "jkl"]);
+    assert_eq!(t.mark('c'), Some((3, 0)));
+    assert_eq!(t.mark('b'), Some((3, 1)));
+
+    // Undo and redo move the marks back and forth
+    t.undo();
+    assert_eq!(t.mark('b'), Some((4, 1)));
+    t.undo();
+    t.undo();
+    assert_eq!(t.lines(), ["abc", "def", "ghi", "jkl"]);
+    assert_eq!(t.mark('a'), Some((1, 2)));
+    assert_eq!(t.mark('b'), Some((3, 1)));
+    t.redo();
+    assert_eq!(t.mark('a'), Some((2, 2)));
+
+    // Jumping clamps the position and is remembered in the jump list
+    t.move_cursor(CursorMove::Jump(0, 0));
+    t.set_mark('d');
+    t.move_cursor(CursorMove::End);
+    t.delete_line_by_head();
+    assert!(t.jump_to_mark('b'));
+    assert_eq!(t.cursor(), (4, 1));
+    assert!(t.jump_back());
+    assert_eq!(t.cursor(), (0, 0));
+
+    let names: Vec<_> = t.marks().map(|(c, _)| c).collect();
+    assert_eq!(names, ['a', 'b', 'c', 'd']);
+    assert_eq!(t.remove_mark('d'), Some((0, 0)));
+    assert!(!t.jump_to_mark('d'));
+
+    // Marks collapsed by deleted text are restored on undo unless they were moved after the deletion
+    let mut t = TextArea::from(["abc", "def", "ghi"]);
+    t.move_cursor(CursorMove::Jump(2, 2));
+    t.set_mark('a');
+    t.move_cursor(CursorMove::Jump(1, 1));
+    t.set_mark('b');
+    t.move_cursor(CursorMove::Jump(0, 3));
+    t.start_selection();
+    t.move_cursor(CursorMove::Bottom);
+    t.move_cursor(CursorMove::End);
+    t.cut();
+    assert_eq!(t.lines(), ["abc"]);
+    assert_eq!(t.mark('a'), Some((0, 3)));
+    t.move_cursor(CursorMove::Jump(0, 1));
+    t.set_mark('b');
+    t.undo();
+    assert_eq!(t.mark('a'), Some((2, 2)));
+    assert_eq!(t.mark('b'), Some((0, 1)));
+    t.redo();
+    assert_eq!(t.mark('a'), Some((0, 3)));
+    t.undo();
+    assert_eq!(t.mark('a'), Some((2, 2)));
+
+    let mut t = TextArea::from(["abc", "def", "ghi"]);
+    t.move_cursor(CursorMove::Jump(2, 2));
+    t.set_mark('a');
+    t.select_all();
+    t.cut();
+    t.undo();
+    assert_eq!(t.mark('a'), Some((2, 2)));
+}
+
+#[test]
+fn test_abbreviations() {
+    let mut t = TextArea::default();
+    t.set_undo_cursor(UndoCursor::Restore);
+    let abbrevs = [("teh", "the"), ("brb", "be right back"), ("sig", "--\nme")];
+    t.set_abbreviations(
+        abbrevs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect(),
+    );
+    assert_eq!(t.abbreviations().len(), 3);
+
+    for c in "teh".chars() {
+        t.insert_char(c);
+    }
+    t.insert_char(' ');
+    assert_eq!(t.lines(), ["the "]);
+    assert_eq!(t.cursor(), (0, 4));
+
+    // One undo restores the typed word
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["teh"]);
+    assert_eq!(t.cursor(), (0, 3));
+
+    // Only exact matches of entire words are expanded
+    for c in "x,tehx brb2 Teh(".chars() {
+        t.insert_char(c);
+    }
+    assert_eq!(t.lines(), ["tehx,tehx brb2 Teh("]);
+
+    // Newline and punctuation are word boundaries
+    t.select_all();
+    t.cut();
+    for c in "brb!sig".chars() {
+        t.insert_char(c);
+    }
+    t.input(Input {
+        key: Key::Enter,
+        ..Default::default()
+    });
+    assert_eq!(t.lines(), ["be right back!--", "me", ""]);
+
+    // Typing over selection does not expand the word before it
+    t.insert_str("tehh");
+    t.start_selection();
+    t.move_cursor(CursorMove::Back);
+    t.insert_char(' ');
+    assert_eq!(t.lines(), ["be right back!--", "me", "teh "]);
+}
+
+#[test]
+fn test_apply_edit() {
+    let mut t = TextArea::from(["aaa", "bbb", "ccc", "ddd"]);
+    t.move_cursor(CursorMove::Jump(2, 1));
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(3, 2));
+
+    // Lines added above the cursor and the selection shift them down
+    assert!(t.apply_edit((0, 3), (0, 3), "\nxxx\nyyy"));
+    assert_eq!(t.lines(), ["aaa", "xxx", "yyy", "bbb", "ccc", "ddd"]);
+    assert_eq!(t.cursor(), (5, 2));
+    assert_eq!(t.selection_range(), Some(((4, 1), (5, 2))));
+
+    // Lines removed above shift them up
+    assert!(t.apply_edit((3, 0), (0, 0), ""));
+    assert_eq!(t.lines(), ["bbb", "ccc", "ddd"]);
+    assert_eq!(t.selection_range(), Some(((1, 1), (2, 2))));
+
+    // Edits after the cursor keep the cursor
+    assert!(t.apply_edit((2, 2), (2, 3), "D"));
+    assert_eq!(t.lines(), ["bbb", "ccc", "ddD"]);
+    assert_eq!(t.cursor(), (2, 2));
+
+    // Edits in the same line before the cursor shift the column
+    assert!(t.apply_edit((2, 0), (2, 1), "XYZ"));
+    assert_eq!(t.lines()[2], "XYZdD");
+    assert_eq!(t.cursor(), (2, 4));
+
+    // Cursor inside the replaced text moves to the start of the edit
+    t.cancel_selection();
+    assert!(t.apply_edit((2, 3), (2, 5), ""));
+    assert_eq!(t.cursor(), (2, 3));
+    assert!(!t.apply_edit((1, 1), (1, 1), ""));
+
+    // Each edit is one undo entry
+    t.undo();
+    assert_eq!(t.lines(), ["bbb", "ccc", "XYZdD"]);
+
+    // Edits in a batch refer to the original text
+    let mut t = TextArea::from(["fn f(a: i32) -> i32 {", "    a", "}"]);
+    t.move_cursor(CursorMove::Jump(1, 5));
+    let edits = vec![
+        ((1, 4), (1, 5), "b".to_string()),
+        ((0, 5), (0, 6), "b".to_string()),
+        ((0, 0), (0, 0), "// doc\n".to_string()),
+        ((0, 0), (0, 0), "// more\n".to_string()),
+    ];
+    assert!(t.apply_edits(edits));
+    assert_eq!(
+        t.lines(),
+        ["// doc", "// more", "fn f(b: i32) -> i32 {", "    b", "}"],
+    );
+    assert_eq!(t.cursor(), (3, 5));
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["fn f(a: i32) -> i32 {", "    a", "}"]);
+    assert!(!t.apply_edits(Vec::<((usize, usize), (usize, usize), &str)>::new()));
+}
+
+#[test]
+fn test_normalize_newlines() {
+    let mixed = "a\r\nb\rc\nd\r\r\ne\r";
+
+    let mut t = TextArea::from(["[]"]);
+    t.move_cursor(CursorMove::Forward);
+    assert!(t.insert_str(mixed));
+    assert_eq!(t.lines(), ["[a", "b", "c", "d", "", "e", "]"]);
+    assert_eq!(t.cursor(), (6, 0));
+    assert!(t.lines().iter().all(|l| !l.contains('\r')));
+
+    // Pasted text is normalized when it is set as yanked text
+    let mut t = TextArea::default();
+    t.set_yank_text(mixed);
+    assert_eq!(t.yank_text(), "a\nb\nc\nd\n\ne\n");
+    assert!(t.paste());
+    assert_eq!(t.lines(), ["a", "b", "c", "d", "", "e", ""]);
+    assert_eq!(t.cursor(), (6, 0));
+
+    t.set_text("x\ry\r\n");
+    assert_eq!(t.lines(), ["x", "y", ""]);
+
+    // Carriage returns are kept when the normalization is disabled
+    let mut t = TextArea::default();
+    t.set_normalize_newlines(false);
+    assert!(t.insert_str(mixed));
+    assert_eq!(t.lines(), ["a\r", "b\rc", "d\r\r", "e\r"]);
+    assert_eq!(t.cursor(), (3, 2));
+    t.set_yank_text("p\r\nq");
+    assert_eq!(t.yank_text(), "p\r\nq");
+}
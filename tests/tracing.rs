@@ -0,0 +1,102 @@
+#![cfg(feature = "tracing")]
+
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+use tui_textarea::{Input, Key, TextArea};
+
+// Subscriber recording the message and the fields of every event on one line
+#[derive(Clone, Default)]
+struct Recorder(Arc<Mutex<Vec<String>>>);
+
+struct Line<'a>(&'a mut String);
+
+impl Visit for Line<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            self.0.insert_str(0, &format!("{:?}", value));
+        } else {
+            self.0.push_str(&format!(" {}={:?}", field.name(), value));
+        }
+    }
+}
+
+impl Subscriber for Recorder {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+    fn new_span(&self, _: &Attributes<'_>) -> Id {
+        Id::from_u64(1)
+    }
+    fn record(&self, _: &Id, _: &Record<'_>) {}
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+    fn event(&self, event: &Event<'_>) {
+        let mut line = String::new();
+        event.record(&mut Line(&mut line));
+        self.0.lock().unwrap().push(line);
+    }
+    fn enter(&self, _: &Id) {}
+    fn exit(&self, _: &Id) {}
+}
+
+impl Recorder {
+    fn take(&self) -> Vec<String> {
+        std::mem::take(&mut *self.0.lock().unwrap())
+    }
+
+    fn find(&self, message: &str) -> Vec<String> {
+        self.take()
+            .into_iter()
+            .filter(|l| l.starts_with(message))
+            .collect()
+    }
+}
+
+fn key(key: Key, shift: bool) -> Input {
+    Input {
+        key,
+        shift,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_trace_history_and_selection() {
+    let recorder = Recorder::default();
+    tracing::subscriber::with_default(recorder.clone(), || {
+        let mut t = TextArea::default();
+        t.input(key(Key::Char('a'), false));
+        let pushed = recorder.find("history entry pushed");
+        assert_eq!(pushed.len(), 1, "{:?}", pushed);
+        assert!(pushed[0].contains("preview: \"a\""), "{:?}", pushed);
+
+        t.input(key(Key::Left, true));
+        let events = recorder.take();
+        assert!(
+            events.iter().any(|l| l.starts_with("move cursor")),
+            "{:?}",
+            events,
+        );
+        let changed: Vec<_> = events
+            .iter()
+            .filter(|l| l.starts_with("selection changed"))
+            .collect();
+        assert_eq!(changed.len(), 1, "{:?}", events);
+        assert!(changed[0].contains("before=None"), "{:?}", changed);
+        assert!(
+            changed[0].contains("after=Some(((0, 1), Char))"),
+            "{:?}",
+            changed
+        );
+
+        t.undo();
+        let undone = recorder.find("history entry undone");
+        assert_eq!(undone.len(), 1, "{:?}", undone);
+        assert!(undone[0].contains("preview: \"a\""), "{:?}", undone);
+
+        t.redo();
+        assert_eq!(recorder.find("history entry redone").len(), 1);
+    });
+}
@@ -0,0 +1,1105 @@
+#![cfg(feature = "ratatui")]
+
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Alignment, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Span;
+use ratatui::widgets::{Block, Paragraph, Widget as _};
+use tui_textarea::{CursorMove, CursorRendering, Scrolling, Sign, TextArea};
+
+const SEL: Color = Color::Blue;
+
+fn bordered_block() -> Block<'static> {
+    Block::bordered().title_top("top").title_bottom("bottom")
+}
+
+fn render(textarea: &TextArea<'_>, width: u16, height: u16) -> Buffer {
+    textarea.render_to_styled(width, height)
+}
+
+#[track_caller]
+fn assert_bg(buf: &Buffer, y: u16, xs: std::ops::Range<u16>, bg: Color) {
+    for x in xs {
+        let cell = &buf[(x, y)];
+        assert_eq!(cell.bg, bg, "bg of cell ({x}, {y}) {:?}", cell.symbol());
+    }
+}
+
+#[test]
+fn test_render_selection_on_first_and_last_rows_with_block() {
+    let mut t = TextArea::from(["abc", "def", "ghi"]);
+    t.set_block(bordered_block());
+    t.set_selection_style(Style::default().bg(SEL));
+    t.move_cursor(CursorMove::Jump(0, 1));
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(2, 2));
+
+    // Borders take rows 0 and 4 and columns 0 and 9, so the text is at rows 1..=3 and columns 1..=8
+    let buf = render(&t, 10, 5);
+    assert_eq!(buf[(1, 0)].symbol(), "t");
+    assert_eq!(buf[(1, 4)].symbol(), "b");
+    assert_eq!(buf[(1, 1)].symbol(), "a");
+    assert_eq!(buf[(1, 3)].symbol(), "g");
+
+    // First visible row: "a" is not selected, "bc" and the newline are
+    assert_bg(&buf, 1, 1..2, Color::Reset);
+    assert_bg(&buf, 1, 2..5, SEL);
+    assert_bg(&buf, 1, 5..9, Color::Reset);
+    assert_bg(&buf, 2, 1..5, SEL);
+    // Last visible row: "gh" is selected and the cursor is on "i"
+    assert_bg(&buf, 3, 1..3, SEL);
+    assert_ne!(buf[(3, 3)].bg, SEL);
+    assert_bg(&buf, 3, 4..9, Color::Reset);
+
+    // Borders never get text styles
+    for x in 0..10 {
+        assert_eq!(buf[(x, 0)].bg, Color::Reset);
+        assert_eq!(buf[(x, 4)].bg, Color::Reset);
+    }
+    for y in 0..5 {
+        assert_eq!(buf[(0, y)].bg, Color::Reset);
+        assert_eq!(buf[(9, y)].bg, Color::Reset);
+    }
+}
+
+#[test]
+fn test_render_selection_on_last_row_after_scroll() {
+    let mut t = TextArea::from(["1", "2", "3", "4", "5", "6"]);
+    t.set_block(bordered_block());
+    t.set_selection_style(Style::default().bg(SEL));
+    t.move_cursor(CursorMove::Jump(3, 0));
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(5, 1));
+
+    // Three inner rows show lines 4..=6 after scrolling to the cursor
+    let buf = render(&t, 6, 5);
+    assert_eq!(buf[(1, 1)].symbol(), "4");
+    assert_eq!(buf[(1, 3)].symbol(), "6");
+    assert_bg(&buf, 1, 1..3, SEL);
+    assert_bg(&buf, 2, 1..3, SEL);
+    assert_bg(&buf, 3, 1..2, SEL);
+    assert_ne!(buf[(2, 3)].bg, SEL);
+    assert_bg(&buf, 4, 0..6, Color::Reset);
+}
+
+#[cfg(feature = "search")]
+#[test]
+fn test_render_search_matches_on_first_and_last_rows_with_block() {
+    const MATCH: Color = Color::Green;
+
+    let mut t = TextArea::from(["xfoox", "yyy", "foo"]);
+    t.set_block(bordered_block());
+    t.set_search_style(Style::default().bg(MATCH));
+    t.set_search_pattern("foo").unwrap();
+    t.move_cursor(CursorMove::Jump(1, 0));
+
+    let buf = render(&t, 8, 5);
+    assert_bg(&buf, 1, 1..2, Color::Reset);
+    assert_bg(&buf, 1, 2..5, MATCH);
+    assert_bg(&buf, 1, 5..7, Color::Reset);
+    assert_bg(&buf, 3, 1..4, MATCH);
+    assert_bg(&buf, 3, 4..7, Color::Reset);
+    assert_bg(&buf, 4, 0..8, Color::Reset);
+}
+
+#[test]
+fn test_to_styled_text_expands_tabs_across_spans() {
+    use ratatui::text::{Line, Span, Text};
+
+    const KEY: Style = Style::new().fg(Color::Red);
+
+    let mut t = TextArea::from(["a\tb\tc", "key:\tvalue", ""]);
+    t.set_tab_length(4);
+    let text = t.to_styled_text(|row, line| {
+        if let Some((key, value)) = line.split_once(':') {
+            Line::from(vec![
+                Span::styled(key, KEY),
+                Span::raw(":"),
+                Span::raw(value.to_string()),
+            ])
+        } else {
+            Line::from(format!("{row}:{line}"))
+        }
+    });
+
+    let have: Vec<Vec<(&str, Style)>> = text
+        .lines
+        .iter()
+        .map(|l| {
+            l.spans
+                .iter()
+                .map(|s| (s.content.as_ref(), s.style))
+                .collect()
+        })
+        .collect();
+    assert_eq!(
+        have,
+        [
+            vec![("0:a b   c", Style::default())],
+            vec![
+                ("key", KEY),
+                (":", Style::default()),
+                ("    value", Style::default()),
+            ],
+            vec![("2:", Style::default())],
+        ],
+    );
+
+    // Columns are aligned with the editor pane
+    let mut preview = Buffer::empty(Rect::new(0, 0, 20, 3));
+    Paragraph::new(text.clone()).render(preview.area, &mut preview);
+    let preview: String = (0..20).map(|x| preview[(x, 1)].symbol()).collect();
+    assert_eq!(
+        t.render_to_string(20, 3).lines().nth(1),
+        Some(preview.as_str())
+    );
+
+    let t = TextArea::from_text(Text::from(vec![
+        Line::from(vec![Span::styled("key", KEY), Span::raw(":\tvalue")]),
+        Line::default(),
+    ]));
+    assert_eq!(t.lines(), ["key:\tvalue", ""]);
+
+    let t = TextArea::from_text(Text::default());
+    assert_eq!(t.lines(), [""]);
+}
+
+#[test]
+fn test_render_inactive_selection() {
+    let mut t = TextArea::from(["abcd"]);
+    t.set_selection_style(Style::default().bg(SEL));
+    t.set_inactive_selection_style(Style::default().bg(Color::DarkGray));
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(0, 2));
+
+    let buf = render(&t, 5, 1);
+    assert_bg(&buf, 0, 0..2, SEL);
+
+    t.set_focused(false);
+    let buf = render(&t, 5, 1);
+    assert_bg(&buf, 0, 0..2, Color::DarkGray);
+    assert_bg(&buf, 0, 3..5, Color::Reset);
+}
+
+#[test]
+fn test_render_unfocused_cursor() {
+    let mut t = TextArea::from(["abcd"]);
+    t.set_selection_style(Style::default().bg(SEL));
+    t.set_cursor_line_style(Style::default());
+    t.start_selection();
+    t.move_cursor(CursorMove::End);
+
+    // The cursor at the end of the line is rendered only while focused
+    assert_eq!(t.render_to_string(6, 1), "abcd  ");
+    let buf = render(&t, 6, 1);
+    assert!(buf[(4, 0)].modifier.contains(Modifier::REVERSED));
+
+    t.set_focused(false);
+    let buf = render(&t, 6, 1);
+    assert_bg(&buf, 0, 0..4, Color::DarkGray);
+    for x in 0..6 {
+        assert!(!buf[(x, 0)].modifier.contains(Modifier::REVERSED));
+    }
+
+    t.set_unfocused_cursor_style(Style::default().bg(Color::Red));
+    let buf = render(&t, 6, 1);
+    assert_bg(&buf, 0, 0..4, Color::DarkGray);
+    assert_bg(&buf, 0, 4..5, Color::Red);
+
+    t.set_focused(true);
+    let buf = render(&t, 6, 1);
+    assert!(buf[(4, 0)].modifier.contains(Modifier::REVERSED));
+    assert_bg(&buf, 0, 0..4, SEL);
+}
+
+#[test]
+fn test_render_cursor_line_fill() {
+    const LINE: Color = Color::DarkGray;
+    let mut t = TextArea::from(["ab", "cdefgh", "i"]);
+    t.set_cursor_line_style(Style::default().bg(LINE));
+    t.set_line_number_style(Style::default().fg(Color::Yellow));
+    t.move_cursor(CursorMove::Down);
+
+    let buf = render(&t, 8, 3);
+    assert_bg(&buf, 1, 0..3, Color::Reset);
+    assert_bg(&buf, 1, 4..8, LINE);
+
+    t.set_cursor_line_fill(true);
+    let buf = render(&t, 8, 3);
+    assert_bg(&buf, 0, 0..8, Color::Reset);
+    assert_bg(&buf, 1, 0..8, LINE);
+    assert_bg(&buf, 2, 0..8, Color::Reset);
+    // The line number style is merged
+    assert_eq!(buf[(1, 1)].fg, Color::Yellow);
+
+    // All display rows of the wrapped cursor line are filled
+    t.set_wrap(true);
+    let rows = [" 1 ab   ", " 2 cdefg", "   h    ", " 3 i    "];
+    assert_eq!(t.render_to_string(8, 4), rows.join("\n"));
+    let buf = render(&t, 8, 4);
+    assert_bg(&buf, 0, 0..8, Color::Reset);
+    assert_bg(&buf, 1, 0..8, LINE);
+    assert_bg(&buf, 2, 0..8, LINE);
+    assert_bg(&buf, 3, 0..8, Color::Reset);
+}
+
+#[test]
+fn test_render_block_selection() {
+    let mut t = TextArea::from(["abcde", "fg", "hijkl"]);
+    t.set_selection_style(Style::default().bg(SEL));
+    t.move_cursor(CursorMove::Jump(0, 1));
+    t.start_block_selection();
+    t.move_cursor(CursorMove::Jump(2, 3));
+
+    // Only the rectangle columns are highlighted on each row
+    let buf = render(&t, 6, 3);
+    assert_bg(&buf, 0, 0..1, Color::Reset);
+    assert_bg(&buf, 0, 1..3, SEL);
+    assert_bg(&buf, 0, 3..6, Color::Reset);
+    assert_bg(&buf, 1, 0..1, Color::Reset);
+    assert_bg(&buf, 1, 1..2, SEL);
+    assert_bg(&buf, 1, 2..6, Color::Reset);
+    assert_bg(&buf, 2, 0..1, Color::Reset);
+    assert_bg(&buf, 2, 1..3, SEL);
+}
+
+#[test]
+#[allow(clippy::single_range_in_vec_init)]
+fn test_render_protected_rows() {
+    let mut t = TextArea::from(["head", "body"]);
+    t.set_protected_rows(vec![0..1]);
+    t.set_protected_row_style(Style::default().bg(Color::DarkGray));
+    t.move_cursor(CursorMove::Down);
+
+    let buf = render(&t, 6, 2);
+    assert_bg(&buf, 0, 0..4, Color::DarkGray);
+    assert_bg(&buf, 1, 1..4, Color::Reset);
+}
+
+#[cfg(feature = "search")]
+#[test]
+fn test_render_current_search_match() {
+    let mut t = TextArea::from(["ab ab", "ab"]);
+    t.set_search_style(Style::default().bg(Color::Blue));
+    t.set_current_search_match_style(Style::default().bg(Color::Yellow));
+    t.set_search_pattern("ab").unwrap();
+
+    // No current match until the cursor jumps to a match
+    let buf = render(&t, 6, 2);
+    assert_bg(&buf, 0, 3..5, Color::Blue);
+    assert_bg(&buf, 1, 0..2, Color::Blue);
+
+    assert!(t.search_forward(false));
+    let buf = render(&t, 6, 2);
+    // The first character of the current match is under the cursor
+    assert_bg(&buf, 0, 4..5, Color::Yellow);
+    assert_bg(&buf, 1, 0..2, Color::Blue);
+
+    // The current match follows the search
+    assert!(t.search_forward(false));
+    let buf = render(&t, 6, 2);
+    assert_bg(&buf, 0, 3..5, Color::Blue);
+    assert_bg(&buf, 1, 1..2, Color::Yellow);
+    assert!(t.search_back(false));
+    let buf = render(&t, 6, 2);
+    assert_bg(&buf, 0, 4..5, Color::Yellow);
+    assert_bg(&buf, 1, 0..2, Color::Blue);
+
+    // Changing the pattern clears the current match
+    t.set_search_pattern("a").unwrap();
+    let buf = render(&t, 6, 2);
+    assert_bg(&buf, 1, 0..1, Color::Blue);
+    assert_eq!(t.current_search_match(), None);
+}
+
+#[test]
+fn test_render_after_editing_above_viewport() {
+    let mut t: TextArea = (0..10).map(|i| i.to_string()).collect();
+    t.move_cursor(CursorMove::Bottom);
+    render(&t, 5, 3);
+
+    t.insert_str_at(0, 0, "a\nb\n");
+    assert_eq!(t.cursor(), (11, 0));
+    let buf = render(&t, 5, 3);
+    assert_eq!(buf[(0, 0)].symbol(), "7");
+    assert_eq!(buf[(0, 2)].symbol(), "9");
+
+    t.delete_str_at(0, 0, 4);
+    assert_eq!(t.cursor(), (9, 0));
+    let buf = render(&t, 5, 3);
+    assert_eq!(buf[(0, 0)].symbol(), "7");
+    assert_eq!(buf[(0, 2)].symbol(), "9");
+}
+
+#[test]
+fn test_render_trailing_whitespace() {
+    let mut t = TextArea::from(["a  ", "b"]);
+    t.set_cursor_line_style(Style::default());
+    t.set_trailing_whitespace_style(Style::default().bg(Color::Red));
+    t.move_cursor(CursorMove::Down);
+
+    let buf = render(&t, 5, 2);
+    assert_bg(&buf, 0, 0..1, Color::Reset);
+    assert_bg(&buf, 0, 1..3, Color::Red);
+    assert_bg(&buf, 0, 3..5, Color::Reset);
+
+    t.remove_trailing_whitespace_style();
+    let buf = render(&t, 5, 2);
+    assert_bg(&buf, 0, 0..5, Color::Reset);
+}
+
+#[test]
+fn test_render_trailing_whitespace_at_cursor() {
+    let mut t = TextArea::from(["a\t", "b \t ", "c"]);
+    t.set_cursor_line_style(Style::default());
+    t.set_trailing_whitespace_style(Style::default().bg(Color::Red));
+    t.move_cursor(CursorMove::Jump(1, 4));
+
+    // The tab is rendered until the next tab stop
+    let buf = render(&t, 8, 3);
+    assert_bg(&buf, 0, 1..4, Color::Red);
+    assert_bg(&buf, 0, 4..8, Color::Reset);
+    // Whitespaces being typed at the cursor are not highlighted
+    assert_bg(&buf, 1, 1..8, Color::Reset);
+
+    // Whitespaces before the cursor in the middle of the line are highlighted
+    t.move_cursor(CursorMove::Head);
+    let buf = render(&t, 8, 3);
+    assert_bg(&buf, 1, 1..5, Color::Red);
+
+    t.move_cursor(CursorMove::End);
+    t.set_trailing_whitespace_at_cursor(true);
+    let buf = render(&t, 8, 3);
+    assert_bg(&buf, 1, 1..5, Color::Red);
+}
+
+#[test]
+fn test_render_control_chars() {
+    let mut t = TextArea::from(["a\x1bb\u{9b}c", "abcdefgh"]);
+    t.set_cursor_line_style(Style::default());
+    t.set_control_char_style(Style::default().fg(Color::Red));
+
+    assert_eq!(t.render_to_string(10, 2), "a^[b<9b>c \nabcdefgh  ");
+    let buf = render(&t, 10, 2);
+    assert_eq!(buf[(1, 0)].fg, Color::Red);
+    assert_eq!(buf[(2, 0)].fg, Color::Red);
+    assert_eq!(buf[(3, 0)].fg, Color::Reset);
+    assert_eq!(buf[(4, 0)].fg, Color::Red);
+
+    // The control character is still a single character of the text
+    t.move_cursor(CursorMove::Forward);
+    t.move_cursor(CursorMove::Forward);
+    assert_eq!(t.cursor(), (0, 2));
+    let buf = render(&t, 10, 2);
+    assert!(buf[(3, 0)].modifier.contains(Modifier::REVERSED));
+
+    // Moving the cursor vertically keeps the display column
+    t.move_cursor(CursorMove::Down);
+    assert_eq!(t.cursor(), (1, 3));
+    t.move_cursor(CursorMove::Jump(1, 5));
+    t.move_cursor(CursorMove::Up);
+    assert_eq!(t.cursor(), (0, 3));
+
+    t.delete_char();
+    assert_eq!(t.lines(), ["a\x1b\u{9b}c", "abcdefgh"]);
+}
+
+#[test]
+fn test_render_matching_bracket() {
+    const MATCH: Color = Color::Yellow;
+    const ERROR: Color = Color::Red;
+    let mut t = TextArea::from(["f(a, {", "  b", "})"]);
+    t.set_cursor_line_style(Style::default());
+    t.set_matching_bracket_style(Style::default().bg(MATCH));
+    t.set_unmatched_bracket_style(Style::default().bg(ERROR));
+
+    // The cursor is not on a bracket
+    let buf = render(&t, 8, 3);
+    assert_bg(&buf, 0, 1..8, Color::Reset);
+
+    // The cursor is on a bracket matching across lines
+    t.move_cursor(CursorMove::Jump(0, 5));
+    let buf = render(&t, 8, 3);
+    assert_bg(&buf, 2, 0..1, MATCH);
+    assert_bg(&buf, 2, 1..2, Color::Reset);
+
+    // The bracket just before the cursor
+    t.move_cursor(CursorMove::Jump(2, 2));
+    let buf = render(&t, 8, 3);
+    assert_bg(&buf, 0, 1..2, MATCH);
+    assert_bg(&buf, 2, 1..2, MATCH);
+    assert_bg(&buf, 0, 5..6, Color::Reset);
+
+    // The matching bracket is too far from the cursor line
+    t.set_bracket_match_distance(1);
+    let buf = render(&t, 8, 3);
+    assert_bg(&buf, 0, 1..2, Color::Reset);
+    assert_bg(&buf, 2, 1..2, ERROR);
+    t.set_bracket_match_distance(2);
+
+    // The highlight follows the modified buffer
+    t.move_cursor(CursorMove::Jump(0, 2));
+    t.delete_char();
+    assert_eq!(t.lines()[0], "fa, {");
+    t.move_cursor(CursorMove::Jump(2, 2));
+    let buf = render(&t, 8, 3);
+    assert_bg(&buf, 2, 1..2, ERROR);
+
+    t.remove_matching_bracket_style();
+    let buf = render(&t, 8, 3);
+    assert_bg(&buf, 2, 1..2, Color::Reset);
+}
+
+#[test]
+fn test_render_hardware_cursor() {
+    let mut t = TextArea::from(["abc", "def", "ghi", "jkl"]);
+    t.set_cursor_rendering(CursorRendering::Hardware);
+    t.set_block(Block::bordered());
+    t.set_line_number_style(Style::default());
+    t.move_cursor(CursorMove::Jump(1, 3));
+
+    let area = Rect::new(2, 1, 10, 4);
+    let mut buf = Buffer::empty(area);
+    t.render(area, &mut buf);
+    for y in area.top()..area.bottom() {
+        for x in area.left()..area.right() {
+            assert!(!buf[(x, y)].modifier.contains(Modifier::REVERSED));
+        }
+    }
+    // Inside the border, on the right of the line number
+    assert_eq!(t.screen_cursor_position(area), Some((9, 3)));
+
+    // The cursor is out of the viewport until the next rendering
+    t.move_cursor(CursorMove::Bottom);
+    assert_eq!(t.screen_cursor_position(area), None);
+
+    // Display columns are used for tabs and wide characters, and wrapped rows are counted. `b` is wrapped to the
+    // next row
+    let mut t = TextArea::from(["あ\tb", "c"]);
+    t.set_wrap(true);
+    t.move_cursor(CursorMove::Jump(0, 2));
+    let area = Rect::new(0, 0, 5, 4);
+    let mut buf = Buffer::empty(area);
+    t.render(area, &mut buf);
+    assert_eq!(t.screen_cursor_position(area), Some((0, 1)));
+    t.move_cursor(CursorMove::End);
+    t.render(area, &mut buf);
+    assert_eq!(t.screen_cursor_position(area), Some((1, 1)));
+    t.move_cursor(CursorMove::Down);
+    t.render(area, &mut buf);
+    assert_eq!(t.screen_cursor_position(area), Some((1, 2)));
+
+    // Aligned lines
+    let mut t = TextArea::from(["abcd"]);
+    t.set_alignment(Alignment::Right);
+    t.move_cursor(CursorMove::Forward);
+    let area = Rect::new(0, 0, 8, 1);
+    let mut buf = Buffer::empty(area);
+    t.render(area, &mut buf);
+    assert_eq!(buf[(5, 0)].symbol(), "b");
+    assert_eq!(t.screen_cursor_position(area), Some((5, 0)));
+}
+
+#[test]
+fn test_render_ghost_and_virtual_text() {
+    const GHOST: Color = Color::DarkGray;
+    let mut t = TextArea::from(["ab", "cd"]);
+    t.set_cursor_line_style(Style::default());
+    t.move_cursor(CursorMove::Forward);
+    t.set_ghost_text(Some(("xyz".to_string(), Style::default().fg(GHOST))));
+    t.set_virtual_text(1, Some((" <- here".to_string(), Style::default())));
+
+    // The ghost text is rendered at the cursor and truncated at the edge
+    assert_eq!(t.render_to_string(8, 2), "axyzb   \ncd <- he");
+    let buf = render(&t, 8, 2);
+    assert_eq!(buf[(1, 0)].fg, GHOST);
+    assert!(buf[(4, 0)].modifier.contains(Modifier::REVERSED));
+    assert_eq!(t.screen_cursor_position(buf.area), Some((4, 0)));
+
+    // The hardware cursor is put on the character at the cursor after the ghost text as well
+    t.set_cursor_rendering(CursorRendering::Hardware);
+    assert_eq!(t.render_to_string(8, 2), "axyzb   \ncd <- he");
+    let buf = render(&t, 8, 2);
+    assert!(!buf[(4, 0)].modifier.contains(Modifier::REVERSED));
+    assert_eq!(t.screen_cursor_position(buf.area), Some((4, 0)));
+    t.set_cursor_rendering(CursorRendering::Cell);
+
+    // Same in a wrapped line
+    t.set_wrap(true);
+    assert_eq!(t.render_to_string(8, 2), "axyzb   \ncd <- he");
+    let buf = render(&t, 8, 2);
+    assert_eq!(t.screen_cursor_position(buf.area), Some((4, 0)));
+    t.set_wrap(false);
+
+    // At the end of the line the ghost text follows the cursor
+    t.move_cursor(CursorMove::End);
+    assert_eq!(t.render_to_string(8, 2), "ab xyz  \ncd <- he");
+    let buf = render(&t, 8, 2);
+    assert!(buf[(2, 0)].modifier.contains(Modifier::REVERSED));
+
+    // Virtual texts do not take display columns of wrapped lines
+    t.set_wrap(true);
+    t.set_virtual_text(1, Some(("!".to_string(), Style::default())));
+    t.set_ghost_text(None);
+    assert_eq!(t.render_to_string(3, 2), "ab \ncd!");
+
+    // They are not a part of the text and removed on editing
+    t.insert_char('c');
+    assert_eq!(t.lines(), ["abc", "cd"]);
+    assert_eq!(t.virtual_text(1), None);
+    t.set_ghost_text(Some(("xyz".to_string(), Style::default())));
+    t.undo();
+    assert_eq!(t.ghost_text(), None);
+}
+
+#[test]
+fn test_styled_lines() {
+    let mut t = TextArea::from(["abc", "def", "ghi"]);
+    t.set_line_number_style(Style::default().fg(Color::Yellow));
+    t.set_selection_style(Style::default().bg(SEL));
+    t.move_cursor(CursorMove::Jump(1, 1));
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(2, 1));
+
+    // The styled lines are painted as they are
+    let buf = render(&t, 8, 3);
+    let lines = t.styled_lines(0..3);
+    assert_eq!(lines.len(), 3);
+    for (y, line) in lines.iter().enumerate() {
+        let mut x = 0;
+        for span in &line.spans {
+            for c in span.content.chars() {
+                let cell = &buf[(x, y as u16)];
+                assert_eq!(cell.symbol(), c.to_string(), "({}, {})", x, y);
+                assert_eq!(Some(cell.fg), span.style.fg.or(Some(Color::Reset)));
+                assert_eq!(Some(cell.bg), span.style.bg.or(Some(Color::Reset)));
+                assert_eq!(cell.modifier, span.style.add_modifier);
+                x += 1;
+            }
+        }
+    }
+
+    assert_eq!(t.styled_lines(2..10).len(), 1);
+    assert!(t.styled_lines(5..10).is_empty());
+}
+
+#[test]
+fn test_render_wrap() {
+    let mut t = TextArea::from(["hello world foo", "bar"]);
+    t.set_wrap(true);
+    t.set_line_number_style(Style::default());
+    t.set_selection_style(Style::default().bg(SEL));
+    t.move_cursor(CursorMove::Jump(0, 3));
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(0, 8));
+
+    // The line number only appears on the first display row of the line
+    let rows = [
+        " 1 hello ",
+        "   world ",
+        "   foo   ",
+        " 2 bar   ",
+        "         ",
+    ];
+    assert_eq!(t.render_to_string(9, 5), rows.join("\n"));
+    let buf = render(&t, 9, 5);
+    // The selection continues across display rows
+    assert_bg(&buf, 0, 6..9, SEL);
+    assert_bg(&buf, 1, 3..5, SEL);
+    assert_ne!(buf[(5, 1)].bg, SEL); // The cursor
+    assert_bg(&buf, 2, 3..9, Color::Reset);
+}
+
+#[test]
+fn test_render_wrap_scroll() {
+    let mut t = TextArea::from(["aaaa bbbb cccc dddd", "eeee"]);
+    t.set_wrap(true);
+
+    // Scrolling is done by display rows so that the cursor at the end of the long line is visible
+    t.move_cursor(CursorMove::End);
+    assert_eq!(t.render_to_string(5, 2), "cccc \ndddd ");
+
+    t.move_cursor(CursorMove::Down);
+    assert_eq!(t.render_to_string(5, 2), "dddd \neeee ");
+
+    // Vertical moves go by display rows
+    t.move_cursor(CursorMove::Up);
+    assert_eq!(t.cursor(), (0, 19));
+    t.move_cursor(CursorMove::Up);
+    assert_eq!(t.cursor(), (0, 14));
+    assert_eq!(t.render_to_string(5, 2), "cccc \ndddd ");
+}
+
+#[test]
+fn test_scroll_wrapped_lines() {
+    let mut t = TextArea::from(["aaaa bbbb cccc dddd", "eeee"]);
+    t.set_wrap(true);
+    assert_eq!(t.render_to_string(5, 2), "aaaa \nbbbb ");
+
+    // The viewport is scrolled by display rows and the cursor is moved into it
+    t.scroll(Scrolling::PageDown);
+    assert_eq!(t.cursor(), (0, 10));
+    assert_eq!(t.render_to_string(5, 2), "cccc \ndddd ");
+    t.scroll((1, 0));
+    assert_eq!(t.cursor(), (0, 15));
+    assert_eq!(t.render_to_string(5, 2), "dddd \neeee ");
+    t.scroll((-3, 0));
+    assert_eq!(t.cursor(), (0, 5));
+    assert_eq!(t.render_to_string(5, 2), "aaaa \nbbbb ");
+
+    // The viewport stops at the last display row
+    t.scroll((10, 0));
+    assert_eq!(t.cursor(), (1, 0));
+    assert_eq!(t.viewport_offset(), (1, 0));
+    assert_eq!(t.render_to_string(5, 2), "eeee \n     ");
+
+    // A single long line is scrolled within the line
+    let mut t = TextArea::from([(0..10)
+        .map(|i| i.to_string().repeat(10))
+        .collect::<String>()]);
+    t.set_wrap(true);
+    t.render_to_string(10, 3);
+    t.scroll(Scrolling::PageDown);
+    assert_eq!(t.cursor(), (0, 30));
+    assert_eq!(
+        t.render_to_string(10, 3),
+        "3333333333\n4444444444\n5555555555"
+    );
+    t.scroll((2, 0));
+    assert_eq!(t.cursor(), (0, 50));
+    assert_eq!(
+        t.render_to_string(10, 3),
+        "5555555555\n6666666666\n7777777777"
+    );
+}
+
+#[test]
+fn test_render_wrap_indicator() {
+    let mut t = TextArea::from(["  ab cd ef gh"]);
+    t.set_wrap(true);
+    t.set_wrap_hanging_indent(true);
+    t.set_wrap_indicator(Some((
+        "> ".to_string(),
+        Style::default().fg(Color::DarkGray),
+    )));
+    t.set_selection_style(Style::default().bg(SEL));
+    t.set_cursor_line_style(Style::default());
+    t.move_cursor(CursorMove::Jump(0, 3));
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(0, 9));
+
+    // Continuation rows start with the indent and the indicator, which are not selected
+    assert_eq!(t.render_to_string(8, 3), "  ab cd \n  > ef  \n  > gh  ");
+    let buf = render(&t, 8, 3);
+    assert_eq!(buf[(2, 1)].fg, Color::DarkGray);
+    assert_bg(&buf, 1, 0..4, Color::Reset);
+    assert_bg(&buf, 1, 4..5, SEL);
+    assert_eq!(t.screen_cursor_position(buf.area), Some((5, 1)));
+
+    // The cursor moves by display columns including the prefix
+    t.cancel_selection();
+    t.move_cursor(CursorMove::Up);
+    assert_eq!(t.cursor(), (0, 5));
+    t.move_cursor(CursorMove::Head);
+    t.move_cursor(CursorMove::Down);
+    assert_eq!(t.cursor(), (0, 8));
+}
+
+#[test]
+fn test_scroll_state() {
+    let mut t = TextArea::from(["aaaa bbbb cccc", "dddd", "eeee", "ffff"]);
+    t.set_block(bordered_block());
+    assert_eq!(t.scroll_state(), (0, 0, 4));
+
+    t.move_cursor(CursorMove::Bottom);
+    render(&t, 7, 4);
+    assert_eq!(t.scroll_state(), (2, 2, 4));
+
+    // Rows are counted in display rows when lines are wrapped
+    t.set_wrap(true);
+    t.move_cursor(CursorMove::Top);
+    t.move_cursor(CursorMove::End);
+    render(&t, 7, 4);
+    assert_eq!(t.scroll_state(), (2, 2, 6));
+}
+
+#[test]
+fn test_viewport_offset() {
+    let mut t = TextArea::from(["aaaa bbbb", "cccc", "dddd", "eeee"]);
+    t.move_cursor(CursorMove::Jump(3, 2));
+    t.set_viewport_offset(2, 2);
+    assert_eq!(t.render_to_string(3, 2), "dd \nee ");
+    assert_eq!(t.viewport_offset(), (2, 2));
+    assert_eq!(t.cursor(), (3, 2));
+
+    // The row is clamped to the last line and the cursor follows the viewport
+    t.set_viewport_offset(100, 0);
+    assert_eq!(t.viewport_offset(), (3, 0));
+    assert_eq!(t.render_to_string(3, 2), "eee\n   ");
+}
+
+#[test]
+fn test_render_beyond_u16_rows() {
+    let mut t: TextArea = (0..70000).map(|i| i.to_string()).collect();
+    t.render_to_string(6, 5);
+    t.move_cursor(CursorMove::Jump(69999, 0));
+    assert_eq!(
+        t.render_to_string(6, 5),
+        "69995 \n69996 \n69997 \n69998 \n69999 ",
+    );
+    assert_eq!(t.viewport_offset(), (69995, 0));
+    assert_eq!(
+        t.screen_cursor_position(Rect::new(0, 0, 6, 5)),
+        Some((0, 4))
+    );
+
+    t.set_viewport_offset(66000, 0);
+    assert_eq!(t.cursor(), (66004, 0));
+    assert_eq!(t.render_to_string(6, 5).lines().next(), Some("66000 "));
+}
+
+#[test]
+fn test_scroll_cursor_alignment() {
+    let mut t: TextArea = (0..20).map(|i| i.to_string()).collect();
+    t.render_to_string(4, 5);
+    t.move_cursor(CursorMove::Jump(10, 0));
+    for (scrolling, top) in [
+        (Scrolling::CursorTop, 10),
+        (Scrolling::CursorCenter, 8),
+        (Scrolling::CursorBottom, 6),
+    ] {
+        t.scroll(scrolling);
+        t.render_to_string(4, 5);
+        assert_eq!(t.viewport_offset(), (top, 0), "{:?}", scrolling);
+        assert_eq!(t.cursor(), (10, 0), "{:?}", scrolling);
+    }
+
+    // The viewport stops at the first line
+    t.move_cursor(CursorMove::Jump(1, 0));
+    t.scroll(Scrolling::CursorBottom);
+    t.render_to_string(4, 5);
+    assert_eq!(t.viewport_offset(), (0, 0));
+
+    // The viewport stops at the last line
+    t.move_cursor(CursorMove::Jump(18, 0));
+    for scrolling in [Scrolling::CursorTop, Scrolling::CursorCenter] {
+        t.scroll(scrolling);
+        t.render_to_string(4, 5);
+        assert_eq!(t.viewport_offset(), (15, 0), "{:?}", scrolling);
+    }
+
+    // Display rows of wrapped lines are counted
+    let mut t = TextArea::from(["a", "bbbbbb", "c", "d"]);
+    t.set_wrap(true);
+    t.move_cursor(CursorMove::Jump(1, 5));
+    t.render_to_string(4, 3);
+    for (scrolling, want) in [
+        (Scrolling::CursorTop, "bb  \nc   \nd   "),
+        (Scrolling::CursorCenter, "bbbb\nbb  \nc   "),
+        (Scrolling::CursorBottom, "a   \nbbbb\nbb  "),
+    ] {
+        t.scroll(scrolling);
+        assert_eq!(t.render_to_string(4, 3), want, "{:?}", scrolling);
+    }
+    t.move_cursor(CursorMove::Jump(2, 0));
+    t.scroll(Scrolling::CursorTop);
+    assert_eq!(t.render_to_string(4, 3), "bb  \nc   \nd   ");
+}
+
+#[test]
+fn test_scroll_margin() {
+    let mut t: TextArea = (0..30).map(|i| i.to_string()).collect();
+    t.set_scroll_margin(3);
+    t.render_to_string(4, 10);
+
+    // The top row trails the cursor by the margin while moving down
+    for row in 1..30usize {
+        t.move_cursor(CursorMove::Down);
+        t.render_to_string(4, 10);
+        let top = (row + 3 + 1).saturating_sub(10).min(20);
+        assert_eq!(t.viewport_offset(), (top, 0), "row={}", row);
+    }
+
+    // And leads the cursor by the margin while moving up
+    for row in (0..29usize).rev() {
+        t.move_cursor(CursorMove::Up);
+        t.render_to_string(4, 10);
+        let top = row.saturating_sub(3).min(20);
+        assert_eq!(t.viewport_offset(), (top, 0), "row={}", row);
+    }
+
+    // Scrolling keeps the cursor off the edges of the viewport
+    t.scroll(Scrolling::PageDown);
+    assert_eq!(t.cursor(), (13, 0));
+    t.render_to_string(4, 10);
+    assert_eq!(t.viewport_offset(), (10, 0));
+
+    // Jumps put the cursor inside the margin
+    t.move_cursor(CursorMove::Jump(25, 0));
+    t.render_to_string(4, 10);
+    assert_eq!(t.viewport_offset(), (19, 0));
+
+    // The cursor is kept at the middle of a viewport too short for the margin
+    t.render_to_string(4, 5);
+    assert_eq!(t.viewport_offset(), (23, 0));
+
+    // Rows beyond the range of u16 do not overflow
+    let mut t: TextArea = (0..65538).map(|i| i.to_string()).collect();
+    t.set_scroll_margin(3);
+    t.render_to_string(6, 10);
+    t.move_cursor(CursorMove::Bottom);
+    let rendered = t.render_to_string(6, 10);
+    assert_eq!(rendered.lines().last(), Some("65537 "));
+
+    // The margin is counted in display rows of wrapped lines
+    let mut t = TextArea::from(["a", "bbbbbb", "c", "d", "e"]);
+    t.set_wrap(true);
+    t.set_scroll_margin(1);
+    t.move_cursor(CursorMove::Jump(2, 0));
+    assert_eq!(t.render_to_string(4, 3), "bb  \nc   \nd   ");
+}
+
+#[test]
+fn test_render_wide_chars() {
+    // Display columns: 'a' at 0, 'あ' at 1-2, 'い' at 3-4, '😀' at 5-6 and 'b' at 7
+    let mut t = TextArea::from(["aあい😀b"]);
+    t.set_selection_style(Style::default().bg(SEL));
+    t.set_cursor_line_style(Style::default());
+    let reversed = |buf: &Buffer| -> Vec<u16> {
+        (0..buf.area.width)
+            .filter(|&x| buf[(x, 0)].modifier.contains(Modifier::REVERSED))
+            .collect()
+    };
+
+    // The cursor cell is at the display column of the character
+    t.move_cursor(CursorMove::Forward);
+    t.move_cursor(CursorMove::Forward);
+    assert_eq!(t.cursor(), (0, 2));
+    let buf = render(&t, 10, 1);
+    assert_eq!(t.render_to_string(10, 1), "aあい😀b  ");
+    assert_eq!(reversed(&buf), [3]);
+    assert_eq!(t.screen_cursor_position(buf.area), Some((3, 0)));
+
+    // Selection is highlighted at the display columns of the selected characters
+    t.move_cursor(CursorMove::Back);
+    t.start_selection();
+    t.move_cursor(CursorMove::Forward);
+    t.move_cursor(CursorMove::Forward);
+    let buf = render(&t, 10, 1);
+    assert_eq!(reversed(&buf), [5]);
+    assert_bg(&buf, 0, 0..1, Color::Reset);
+    assert_bg(&buf, 0, 1..2, SEL);
+    assert_bg(&buf, 0, 3..4, SEL);
+    t.cancel_selection();
+
+    // Both cells of the character under the cursor are scrolled into the viewport
+    let mut t = TextArea::from(["aあい😀b"]);
+    t.move_cursor(CursorMove::Jump(0, 2));
+    assert_eq!(t.render_to_string(4, 1), "あい");
+    assert_eq!(t.viewport_offset(), (0, 1));
+    t.move_cursor(CursorMove::End);
+    assert_eq!(t.render_to_string(4, 1), "😀b ");
+    assert_eq!(t.viewport_offset(), (0, 5));
+    assert_eq!(reversed(&render(&t, 4, 1)), [3]);
+
+    // A character split by the left edge is rendered as a space
+    t.move_cursor(CursorMove::Back);
+    t.set_viewport_offset(0, 4);
+    assert_eq!(t.render_to_string(4, 1), " 😀b");
+    assert_eq!(reversed(&render(&t, 4, 1)), [3]);
+
+    // Scrolling horizontally moves the cursor to the first character fully in the viewport
+    t.move_cursor(CursorMove::Head);
+    t.render_to_string(4, 1);
+    t.scroll((0, 4));
+    assert_eq!(t.cursor(), (0, 3));
+    assert_eq!(t.render_to_string(4, 1), " 😀b");
+}
+
+#[test]
+fn test_render_hard_tabs() {
+    // Tabs used for alignment like Go sources or Makefiles
+    let mut t = TextArea::from(["\tfoo\t= 1", "\tbarbaz\t= 2", "x:\ty"]);
+    t.set_tab_length(8);
+    t.set_hard_tab_indent(true);
+    t.set_selection_style(Style::default().bg(SEL));
+    t.set_cursor_line_style(Style::default());
+    t.set_color_column(Some((16, Style::default().bg(Color::Red))));
+    t.set_cursor_rendering(CursorRendering::Hardware);
+
+    // Tabs advance to the next tab stop so that the columns after them line up
+    let area = Rect::new(0, 0, 20, 3);
+    assert_eq!(
+        t.render_to_string(20, 3),
+        "        foo     = 1 \n        barbaz  = 2 \nx:      y           ",
+    );
+
+    // The cursor is put at the display column after the tabs and keeps it while moving vertically
+    t.move_cursor(CursorMove::Jump(0, 5));
+    render(&t, 20, 3);
+    assert_eq!(t.screen_cursor_position(area), Some((16, 0)));
+    t.move_cursor(CursorMove::Down);
+    assert_eq!(t.cursor(), (1, 8));
+    render(&t, 20, 3);
+    assert_eq!(t.screen_cursor_position(area), Some((16, 1)));
+
+    // Selection covers all cells of the expanded tab
+    t.move_cursor(CursorMove::Jump(0, 4));
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(0, 6));
+    let buf = render(&t, 20, 3);
+    assert_bg(&buf, 0, 8..11, Color::Reset);
+    assert_bg(&buf, 0, 11..17, SEL);
+
+    // The color column is at the same display column as the text after the tabs
+    assert_bg(&buf, 2, 16..17, Color::Red);
+    assert_bg(&buf, 2, 8..9, Color::Reset);
+
+    // A masked tab is as wide as the mask character
+    let mut t = TextArea::from(["a\tb"]);
+    t.set_mask_char('*');
+    t.set_cursor_rendering(CursorRendering::Hardware);
+    t.move_cursor(CursorMove::End);
+    assert_eq!(t.render_to_string(6, 1), "***   ");
+    assert_eq!(
+        t.screen_cursor_position(Rect::new(0, 0, 6, 1)),
+        Some((3, 0))
+    );
+}
+
+#[test]
+fn test_render_overflow_indicators() {
+    let mut t = TextArea::from(["abcdefghij", "abc", "あいうえお"]);
+    t.set_overflow_indicators(Some('<'), Some('>'));
+    t.set_overflow_indicator_style(Style::default().fg(Color::Red));
+
+    // The half of the double-width character under the indicator is cleared
+    assert_eq!(t.render_to_string(6, 3), "abcde>\nabc   \nあい >");
+    let buf = render(&t, 6, 3);
+    assert_eq!(buf[(5, 0)].fg, Color::Red);
+
+    // The indicator does not cover the cursor at the edge
+    t.move_cursor(CursorMove::Jump(0, 5));
+    assert_eq!(t.render_to_string(6, 3), "abcdef\nabc   \nあい >");
+
+    // Lines scrolled out on the left get the left indicator. The double-width character split by the left edge is
+    // replaced with the indicator
+    t.move_cursor(CursorMove::End);
+    assert_eq!(t.render_to_string(6, 3), "<ghij \n<     \n<えお ");
+}
+
+#[test]
+fn test_render_line_number_formatter() {
+    let mut t = TextArea::from((1..=10).map(|i| format!("line{}", i)));
+    t.set_line_number_style(Style::default());
+    t.set_line_number_formatter(|ctx| {
+        if ctx.cursor_row {
+            Span::styled(
+                format!("{:>w$}", ctx.row + 1, w = ctx.width),
+                Style::default().fg(Color::Red),
+            )
+        } else if ctx.row % 2 == 0 {
+            Span::raw("-")
+        } else {
+            Span::raw("too long for the column")
+        }
+    });
+
+    // Spans of any width are fitted to the width of the column so the text is aligned
+    let rows = ["   1line1 ", "too line2 ", "-   line3 "];
+    assert_eq!(t.render_to_string(10, 3), rows.join("\n"));
+    let buf = render(&t, 10, 3);
+    assert_eq!(buf[(3, 0)].fg, Color::Red);
+
+    t.remove_line_number_formatter();
+    let rows = ["  1 line1 ", "  2 line2 ", "  3 line3 "];
+    assert_eq!(t.render_to_string(10, 3), rows.join("\n"));
+}
+
+#[test]
+fn test_render_signs() {
+    let mut t = TextArea::from(["aaa", "bbb", "ccc"]);
+    t.move_cursor(CursorMove::Bottom);
+
+    // No column is reserved without signs
+    assert_eq!(t.render_to_string(8, 3), "aaa     \nbbb     \nccc     ");
+
+    t.set_sign(1, Some(Sign::new("●", Style::default().fg(Color::Red))));
+    assert_eq!(t.render_to_string(8, 3), "  aaa   \n● bbb   \n  ccc   ");
+    let buf = render(&t, 8, 3);
+    assert_eq!(buf[(0, 1)].fg, Color::Red);
+
+    // Signs are put on the left of line numbers
+    t.set_line_number_style(Style::default());
+    t.set_sign(2, Some(Sign::new("too long", Style::default())));
+    let rows = ["   1 aaa", "●  2 bbb", "to 3 ccc"];
+    assert_eq!(t.render_to_string(8, 3), rows.join("\n"));
+
+    // Signs follow the lines when a line is inserted above
+    t.move_cursor(CursorMove::Top);
+    t.insert_str("x\n");
+    let rows = ["   1 x  ", "   2 aaa", "●  3 bbb", "to 4 ccc"];
+    assert_eq!(t.render_to_string(8, 4), rows.join("\n"));
+
+    // The column can be shown without any sign
+    t.clear_signs();
+    t.remove_line_number();
+    t.set_sign_column(true);
+    let rows = ["  x     ", "  aaa   ", "  bbb   ", "  ccc   "];
+    assert_eq!(t.render_to_string(8, 4), rows.join("\n"));
+}
+
+#[test]
+fn test_render_color_columns() {
+    const COL: Color = Color::DarkGray;
+    let mut t = TextArea::from(["abcdef", "ab"]);
+    t.set_line_number_style(Style::default());
+    t.set_selection_style(Style::default().bg(SEL));
+    t.set_color_columns(&[(1, Style::default().bg(COL)), (4, Style::default().bg(COL))]);
+    t.move_cursor(CursorMove::Jump(0, 3));
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(0, 5));
+
+    // The columns are counted after the line numbers and painted under the text styles
+    let buf = render(&t, 10, 3);
+    for y in 0..3 {
+        assert_bg(&buf, y, 0..3, Color::Reset);
+        assert_bg(&buf, y, 4..5, COL);
+    }
+    assert_bg(&buf, 0, 6..8, SEL);
+    assert_bg(&buf, 1, 7..8, COL);
+    assert_bg(&buf, 2, 7..8, COL);
+
+    t.set_color_column(None);
+    let buf = render(&t, 10, 3);
+    assert_bg(&buf, 2, 0..10, Color::Reset);
+}
+
+#[test]
+fn test_render_end_of_buffer() {
+    let mut t = TextArea::from(["abc", ""]);
+    t.set_cursor_line_style(Style::default());
+    t.set_line_number_style(Style::default());
+    t.set_end_of_buffer_char(Some(('~', Style::default().fg(Color::Blue))));
+
+    // The character is rendered on the right of the gutter which is kept blank
+    let rows = [" 1 abc", " 2    ", "   ~  ", "   ~  "];
+    assert_eq!(t.render_to_string(6, 4), rows.join("\n"));
+    let buf = render(&t, 6, 4);
+    assert_eq!(buf[(3, 2)].fg, Color::Blue);
+
+    // It disappears as lines are added
+    t.move_cursor(CursorMove::Bottom);
+    t.insert_newline();
+    let rows = [" 1 abc", " 2    ", " 3    ", "   ~  "];
+    assert_eq!(t.render_to_string(6, 4), rows.join("\n"));
+    t.insert_newline();
+    let rows = [" 1 abc", " 2    ", " 3    ", " 4    "];
+    assert_eq!(t.render_to_string(6, 4), rows.join("\n"));
+
+    // Rows beyond the end of wrapped lines
+    let mut t = TextArea::from(["abcdefgh"]);
+    t.set_wrap(true);
+    t.set_end_of_buffer_char(Some(('~', Style::default())));
+    assert_eq!(t.render_to_string(5, 3), "abcde\nfgh  \n~    ");
+}